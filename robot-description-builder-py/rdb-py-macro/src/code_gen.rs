@@ -118,7 +118,7 @@ fn gen_set_soft_lower<'a>(
 	});
 
 	quote! {
-		pub fn set_soft_lower_limit(mut self, soft_lower_limit: f32) -> Result<Self, #err_type> {
+		pub fn set_soft_lower_limit(mut self, soft_lower_limit: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -137,7 +137,7 @@ fn gen_get_soft_lower<'a>(
 	});
 
 	quote! {
-		pub fn soft_lower_limit(&self) -> Option<f32> {
+		pub fn soft_lower_limit(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -157,7 +157,7 @@ fn gen_set_soft_upper<'a>(
 	});
 
 	quote! {
-		pub fn set_soft_upper_limit(mut self, soft_upper_limit: f32) -> Result<Self, #err_type> {
+		pub fn set_soft_upper_limit(mut self, soft_upper_limit: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -176,7 +176,7 @@ fn gen_get_soft_upper<'a>(
 	});
 
 	quote! {
-		pub fn soft_upper_limit(&self) -> Option<f32> {
+		pub fn soft_upper_limit(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -194,7 +194,7 @@ fn gen_set_k_position<'a>(
 	);
 
 	quote! {
-		pub fn set_k_position(mut self, k_position: f32) -> Result<Self, #err_type> {
+		pub fn set_k_position(mut self, k_position: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -210,7 +210,7 @@ fn gen_get_k_position<'a>(
 		.map(|(variant, _)| quote! { Self::#variant(value) => value.k_position(), });
 
 	quote! {
-		pub fn k_position(&self) -> Option<f32> {
+		pub fn k_position(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -229,7 +229,7 @@ fn gen_set_k_velocity(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 	});
 
 	quote! {
-		pub fn set_k_velocity(mut self, k_velocity: f32) -> Self {
+		pub fn set_k_velocity(mut self, k_velocity: ::robot_description_builder::Float) -> Self {
 			match self {
 				#(#variants)*
 			}
@@ -244,7 +244,7 @@ fn gen_get_k_velocity<'a>(
 		.map(|(variant, _)| quote! { Self::#variant(value) => Some(value.k_velocity()), });
 
 	quote! {
-		pub fn get_k_velocity(&self) -> Option<f32> {
+		pub fn get_k_velocity(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -267,7 +267,7 @@ fn gen_with_safety_controller(
 		});
 
 	quote! {
-		pub fn with_safety_controller(mut self, k_velocity: f32) -> Result<Self, #err_type> {
+		pub fn with_safety_controller(mut self, k_velocity: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -324,7 +324,7 @@ fn gen_set_mimic_offset<'a>(
 	});
 
 	quote! {
-		pub fn set_mimic_offset(mut self, offset: f32) -> Result<Self, #err_type> {
+		pub fn set_mimic_offset(mut self, offset: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -343,7 +343,7 @@ fn gen_get_mimic_offset<'a>(
 	});
 
 	quote! {
-		pub fn mimic_offset(&self) -> Option<f32> {
+		pub fn mimic_offset(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -363,7 +363,7 @@ fn gen_set_mimic_multiplier<'a>(
 	});
 
 	quote! {
-		pub fn set_mimic_multiplier(mut self, multiplier: f32) -> Result<Self, #err_type> {
+		pub fn set_mimic_multiplier(mut self, multiplier: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -382,7 +382,7 @@ fn gen_get_mimic_multiplier<'a>(
 	});
 
 	quote! {
-		pub fn mimic_multiplier(&self) -> Option<f32> {
+		pub fn mimic_multiplier(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -518,7 +518,7 @@ fn gen_set_upper_limit<'a>(
 	});
 
 	quote! {
-		pub fn set_upper_limit(mut self, upper_limit: f32) -> Result<Self, #err_type> {
+		pub fn set_upper_limit(mut self, upper_limit: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -535,7 +535,7 @@ fn gen_get_upper_limit<'a>(
 		.map(|(variant, _)| quote! { Self::#variant(value) => value.upper_limit(), });
 
 	quote! {
-		pub fn upper_limit(&self) -> Option<f32> {
+		pub fn upper_limit(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -556,7 +556,7 @@ fn gen_set_lower_limit<'a>(
 	});
 
 	quote! {
-		pub fn set_lower_limit(mut self, lower_limit: f32) -> Result<Self, #err_type> {
+		pub fn set_lower_limit(mut self, lower_limit: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -573,7 +573,7 @@ fn gen_get_lower_limit<'a>(
 		.map(|(variant, _)| quote! { Self::#variant(value) => value.lower_limit(), });
 
 	quote! {
-		pub fn lower_limit(&self) -> Option<f32> {
+		pub fn lower_limit(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -591,7 +591,7 @@ fn gen_with_limit(variants_data: &[(Ident, TokenStream)], err_type: &Ident) -> T
 		);
 
 	quote! {
-		pub fn with_limit(mut self, effort: f32, velocity: f32) -> Result<Self, #err_type> {
+		pub fn with_limit(mut self, effort: ::robot_description_builder::Float, velocity: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -609,7 +609,7 @@ fn gen_set_velocity<'a>(
 	);
 
 	quote! {
-		pub fn set_velocity(mut self, velocity: f32) -> Result<Self, #err_type> {
+		pub fn set_velocity(mut self, velocity: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -625,7 +625,7 @@ fn gen_get_velocity<'a>(
 		.map(|(variant, _)| quote! { Self::#variant(value) => Some(value.velocity()), });
 
 	quote! {
-		pub fn velocity(&self) -> Option<f32> {
+		pub fn velocity(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -645,7 +645,7 @@ fn gen_set_effort<'a>(
 	});
 
 	quote! {
-		pub fn set_effort(mut self, effort: f32) -> Result<Self, #err_type> {
+		pub fn set_effort(mut self, effort: ::robot_description_builder::Float) -> Result<Self, #err_type> {
 			match self {
 				#(#variants)*
 				err => Err(err.into()),
@@ -661,7 +661,7 @@ fn gen_get_effort<'a>(
 		.map(|(variant, _)| quote! { Self::#variant(value) => Some(value.effort()), });
 
 	quote! {
-		pub fn effort(&self) -> Option<f32> {
+		pub fn effort(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -699,7 +699,7 @@ fn gen_get_friction(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 		});
 
 	quote! {
-		pub fn friction(&self) -> Option<f32> {
+		pub fn friction(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -721,7 +721,7 @@ fn gen_set_friction(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 		}
 	});
 	quote! {
-		pub fn set_friction(mut self, friction: f32) -> Self {
+		pub fn set_friction(mut self, friction: ::robot_description_builder::Float) -> Self {
 			match self {
 				#(#variants)*
 			}
@@ -740,7 +740,7 @@ fn gen_get_damping(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 		});
 
 	quote! {
-		pub fn damping(&self) -> Option<f32> {
+		pub fn damping(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -762,7 +762,7 @@ fn gen_set_damping(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 		}
 	});
 	quote! {
-		pub fn set_damping(mut self, damping: f32) -> Self {
+		pub fn set_damping(mut self, damping: ::robot_description_builder::Float) -> Self {
 			match self {
 				#(#variants)*
 			}
@@ -804,7 +804,7 @@ fn gen_set_rising_calibration(variants_data: &[(Ident, TokenStream)]) -> TokenSt
 
 	quote! {
 		/// Sets the rising calibration value. `NoCalibration` will be upgraded.
-		pub fn set_rising_calibration(mut self, rising: f32) -> Self {
+		pub fn set_rising_calibration(mut self, rising: ::robot_description_builder::Float) -> Self {
 			match self {
 				#(#variants)*
 			}
@@ -823,7 +823,7 @@ fn gen_get_rising(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 		});
 
 	quote! {
-		pub fn rising_calibration(&self) -> Option<f32> {
+		pub fn rising_calibration(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -848,7 +848,7 @@ fn gen_set_falling_calibration(variants_data: &[(Ident, TokenStream)]) -> TokenS
 
 	quote! {
 		/// Sets the falling calibration value. `NoCalibration` will be upgraded.
-		pub fn set_falling_calibration(mut self, falling: f32) -> Self {
+		pub fn set_falling_calibration(mut self, falling: ::robot_description_builder::Float) -> Self {
 			match self {
 				#(#variants)*
 			}
@@ -867,7 +867,7 @@ fn gen_get_falling(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 		});
 
 	quote! {
-		pub fn falling_calibration(&self) -> Option<f32> {
+		pub fn falling_calibration(&self) -> Option<::robot_description_builder::Float> {
 			match self {
 				#(#variants)*
 				_ => None,
@@ -900,7 +900,7 @@ fn gen_get_axis(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 		});
 
 	quote! {
-		pub fn axis(&self) -> Option<(f32, f32, f32)> {
+		pub fn axis(&self) -> Option<(::robot_description_builder::Float, ::robot_description_builder::Float, ::robot_description_builder::Float)> {
 			match self {
 				#(#variants)*
 				_ => None
@@ -917,7 +917,7 @@ fn gen_with_axis(variants_data: &[(Ident, TokenStream)]) -> TokenStream {
 	});
 
 	quote! {
-		pub fn with_axis(mut self, axis: (f32, f32, f32)) -> Self {
+		pub fn with_axis(mut self, axis: (::robot_description_builder::Float, ::robot_description_builder::Float, ::robot_description_builder::Float)) -> Self {
 			match self {
 				#(#variants)*
 			}
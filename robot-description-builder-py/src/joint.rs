@@ -4,7 +4,7 @@ mod smartjointbuilder;
 use std::sync::{Arc, RwLock, Weak};
 
 use pyo3::{exceptions::PyReferenceError, prelude::*, types::PyWeakrefProxy};
-use robot_description_builder::{joint_data, Chained, Joint, JointBuilder, JointType};
+use robot_description_builder::{joint_data, Chained, Float, Joint, JointBuilder, JointType};
 
 use crate::{
 	exceptions::RebuildBranchError,
@@ -28,10 +28,10 @@ pub(super) fn init_module(_py: Python<'_>, module: &Bound<'_, PyModule>) -> PyRe
 
 #[derive(FromPyObject)]
 pub(in crate::joint) struct PyLimit {
-	lower: Option<f32>,
-	upper: Option<f32>,
-	effort: f32,
-	velocity: f32,
+	lower: Option<Float>,
+	upper: Option<Float>,
+	effort: Float,
+	velocity: Float,
 }
 
 impl From<PyLimit> for joint_data::LimitData {
@@ -163,7 +163,7 @@ impl PyJoint {
 	}
 
 	#[getter]
-	fn get_axis(&self) -> PyResult<Option<(f32, f32, f32)>> {
+	fn get_axis(&self) -> PyResult<Option<(Float, Float, Float)>> {
 		Ok(self.try_internal()?.py_read()?.axis())
 	}
 
@@ -4,7 +4,7 @@ use pyo3::{
 	sync::GILOnceCell,
 	types::{PyDict, PyType},
 };
-use robot_description_builder::JointBuilder;
+use robot_description_builder::{Float, JointBuilder};
 
 use crate::{
 	link::PyLinkBuilder,
@@ -121,13 +121,13 @@ impl PyJointBuilderBase {
 	}
 
 	#[getter]
-	pub fn get_axis(&self) -> Option<(f32, f32, f32)> {
+	pub fn get_axis(&self) -> Option<(Float, Float, Float)> {
 		self.builder.axis()
 	}
 
 	// TODO: BETTER TYPE (falling, rising)
 	#[getter]
-	pub fn get_calibration(&self) -> Option<(Option<f32>, Option<f32>)> {
+	pub fn get_calibration(&self) -> Option<(Option<Float>, Option<Float>)> {
 		let data = self.builder.calibration();
 
 		match data.contains_some() {
@@ -138,7 +138,7 @@ impl PyJointBuilderBase {
 
 	// TODO: BETTER TYPE (friction, damping)
 	#[getter]
-	pub fn get_dynamics(&self) -> Option<(Option<f32>, Option<f32>)> {
+	pub fn get_dynamics(&self) -> Option<(Option<Float>, Option<Float>)> {
 		let data = self.builder.dynamics();
 
 		match data.contains_some() {
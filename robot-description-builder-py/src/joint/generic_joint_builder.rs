@@ -1,5 +1,5 @@
 use pyo3::{exceptions::PyTypeError, intern, prelude::*, types::PyDict};
-use robot_description_builder::{prelude::GroupIDChanger, JointBuilder};
+use robot_description_builder::{prelude::GroupIDChanger, Float, JointBuilder};
 
 use crate::identifier::GroupIDError;
 
@@ -94,12 +94,12 @@ impl PyJointBuilder {
 	// TEMP implementation
 	//
 	// TODO: Something
-	fn add_origin_offset(mut slf: PyRefMut<'_, Self>, x: f32, y: f32, z: f32) {
+	fn add_origin_offset(mut slf: PyRefMut<'_, Self>, x: Float, y: Float, z: Float) {
 		slf.as_mut().builder = slf.as_ref().builder.clone().add_origin_offset((x, y, z));
 	}
 
 	#[setter]
-	fn set_axis(mut slf: PyRefMut<'_, Self>, axis: Option<(f32, f32, f32)>) {
+	fn set_axis(mut slf: PyRefMut<'_, Self>, axis: Option<(Float, Float, Float)>) {
 		match (axis, slf.as_ref().builder.axis().is_some()) {
 			(Some(axis), _) => slf.as_mut().builder.with_axis(axis),
 			(None, true) => {
@@ -4,7 +4,10 @@ mod mesh_geometry;
 mod sphere_geometry;
 
 use pyo3::{basic::CompareOp, exceptions::PyNotImplementedError, prelude::*};
-use robot_description_builder::link_data::geometry::{GeometryInterface, GeometryShapeContainer};
+use robot_description_builder::{
+	link_data::geometry::{GeometryInterface, GeometryShapeContainer},
+	Float,
+};
 
 pub use box_geometry::PyBoxGeometry;
 pub use cylinder_geometry::PyCylinderGeometry;
@@ -37,15 +40,15 @@ pub struct PyGeometryBase {
 
 #[pymethods]
 impl PyGeometryBase {
-	fn volume(&self) -> f32 {
+	fn volume(&self) -> Float {
 		self.inner.volume()
 	}
 
-	fn surface_area(&self) -> f32 {
+	fn surface_area(&self) -> Float {
 		self.inner.surface_area()
 	}
 
-	fn bounding_box(&self) -> (f32, f32, f32) {
+	fn bounding_box(&self) -> (Float, Float, Float) {
 		self.inner.bounding_box()
 	}
 
@@ -1,6 +1,9 @@
 use pyo3::{basic::CompareOp, prelude::*};
 
-use robot_description_builder::link_data::geometry::{BoxGeometry, GeometryInterface};
+use robot_description_builder::{
+	link_data::geometry::{BoxGeometry, GeometryInterface},
+	Float,
+};
 
 use super::PyGeometryBase;
 
@@ -11,7 +14,7 @@ pub struct PyBoxGeometry {
 }
 
 impl PyBoxGeometry {
-	fn new(side0: f32, side1: f32, side2: f32) -> (PyBoxGeometry, PyGeometryBase) {
+	fn new(side0: Float, side1: Float, side2: Float) -> (PyBoxGeometry, PyGeometryBase) {
 		let geometry = BoxGeometry::new(side0, side1, side2);
 		let base = PyGeometryBase::new(&geometry);
 		(PyBoxGeometry { inner: geometry }, base)
@@ -23,7 +26,7 @@ impl PyBoxGeometry {
 	/// TODO: Names of arguments might be incorrect/Require explanation
 	#[new]
 	#[pyo3(signature = (width, length, height))]
-	fn py_new(width: f32, length: f32, height: f32) -> (PyBoxGeometry, PyGeometryBase) {
+	fn py_new(width: Float, length: Float, height: Float) -> (PyBoxGeometry, PyGeometryBase) {
 		Self::new(width, length, height)
 	}
 
@@ -47,12 +50,12 @@ impl PyBoxGeometry {
 	}
 
 	#[getter]
-	fn get_size(&self) -> (f32, f32, f32) {
+	fn get_size(&self) -> (Float, Float, Float) {
 		(self.inner.side1, self.inner.side2, self.inner.side3)
 	}
 
 	#[setter]
-	fn set_size(mut slf: PyRefMut<'_, Self>, size: (f32, f32, f32)) {
+	fn set_size(mut slf: PyRefMut<'_, Self>, size: (Float, Float, Float)) {
 		slf.inner.side1 = size.0;
 		slf.inner.side2 = size.1;
 		slf.inner.side3 = size.2;
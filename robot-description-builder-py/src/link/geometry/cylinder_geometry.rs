@@ -1,6 +1,9 @@
 use pyo3::{basic::CompareOp, prelude::*};
 
-use robot_description_builder::link_data::geometry::{CylinderGeometry, GeometryInterface};
+use robot_description_builder::{
+	link_data::geometry::{CylinderGeometry, GeometryInterface},
+	Float,
+};
 
 use super::PyGeometryBase;
 
@@ -11,7 +14,7 @@ pub struct PyCylinderGeometry {
 }
 
 impl PyCylinderGeometry {
-	fn new(radius: f32, length: f32) -> (PyCylinderGeometry, PyGeometryBase) {
+	fn new(radius: Float, length: Float) -> (PyCylinderGeometry, PyGeometryBase) {
 		let geometry = CylinderGeometry::new(radius, length);
 		let base = PyGeometryBase::new(&geometry);
 		(PyCylinderGeometry { inner: geometry }, base)
@@ -22,7 +25,7 @@ impl PyCylinderGeometry {
 impl PyCylinderGeometry {
 	#[new]
 	#[pyo3(signature = (radius, length))]
-	fn py_new(radius: f32, length: f32) -> (PyCylinderGeometry, PyGeometryBase) {
+	fn py_new(radius: Float, length: Float) -> (PyCylinderGeometry, PyGeometryBase) {
 		Self::new(radius, length)
 	}
 
@@ -47,17 +50,17 @@ impl PyCylinderGeometry {
 
 	/// TODO: Maybe change to dict? or remove
 	#[getter]
-	fn get_size(&self) -> (f32, f32) {
+	fn get_size(&self) -> (Float, Float) {
 		(self.inner.radius, self.inner.length)
 	}
 
 	#[getter]
-	fn get_radius(&self) -> f32 {
+	fn get_radius(&self) -> Float {
 		self.inner.radius
 	}
 
 	#[setter]
-	fn set_radius(mut slf: PyRefMut<'_, Self>, radius: f32) {
+	fn set_radius(mut slf: PyRefMut<'_, Self>, radius: Float) {
 		slf.inner.radius = radius;
 
 		let data = slf.inner.boxed_clone();
@@ -67,12 +70,12 @@ impl PyCylinderGeometry {
 	}
 
 	#[getter]
-	fn get_length(&self) -> f32 {
+	fn get_length(&self) -> Float {
 		self.inner.length
 	}
 
 	#[setter]
-	fn set_length(mut slf: PyRefMut<'_, Self>, length: f32) {
+	fn set_length(mut slf: PyRefMut<'_, Self>, length: Float) {
 		slf.inner.length = length;
 
 		let data = slf.inner.boxed_clone();
@@ -82,7 +85,7 @@ impl PyCylinderGeometry {
 	}
 
 	// #[setter]
-	// fn set_size(mut self_: PyRefMut<'_, Self>, size: (f32, f32, f32)) {
+	// fn set_size(mut self_: PyRefMut<'_, Self>, size: (Float, Float, Float)) {
 	//     self_.inner.side1 = size.0;
 	//     self_.inner.side2 = size.1;
 	//     self_.inner.side3 = size.2;
@@ -1,6 +1,9 @@
 use pyo3::{basic::CompareOp, prelude::*};
 
-use robot_description_builder::link_data::geometry::{GeometryInterface, MeshGeometry};
+use robot_description_builder::{
+	link_data::geometry::{GeometryInterface, MeshGeometry},
+	Float,
+};
 
 use super::PyGeometryBase;
 
@@ -13,8 +16,8 @@ pub struct PyMeshGeometry {
 impl PyMeshGeometry {
 	fn new(
 		path: String,
-		bounding_box: (f32, f32, f32),
-		scale: Option<(f32, f32, f32)>,
+		bounding_box: (Float, Float, Float),
+		scale: Option<(Float, Float, Float)>,
 	) -> (PyMeshGeometry, PyGeometryBase) {
 		let geometry = MeshGeometry::new(path, bounding_box, scale);
 		let base = PyGeometryBase::new(&geometry);
@@ -29,8 +32,8 @@ impl PyMeshGeometry {
 	#[pyo3(signature = (path, bounding_box, scale=None))]
 	fn py_new(
 		path: String,
-		bounding_box: (f32, f32, f32),
-		scale: Option<(f32, f32, f32)>,
+		bounding_box: (Float, Float, Float),
+		scale: Option<(Float, Float, Float)>,
 	) -> (PyMeshGeometry, PyGeometryBase) {
 		Self::new(path, bounding_box, scale)
 	}
@@ -70,12 +73,12 @@ impl PyMeshGeometry {
 	}
 
 	#[getter]
-	fn get_bounding_box(&self) -> (f32, f32, f32) {
+	fn get_bounding_box(&self) -> (Float, Float, Float) {
 		self.inner.bounding_box
 	}
 
 	#[setter]
-	fn set_bounding_box(mut slf: PyRefMut<'_, Self>, bounding_box: (f32, f32, f32)) {
+	fn set_bounding_box(mut slf: PyRefMut<'_, Self>, bounding_box: (Float, Float, Float)) {
 		slf.inner.bounding_box.0 = bounding_box.0;
 		slf.inner.bounding_box.1 = bounding_box.1;
 		slf.inner.bounding_box.2 = bounding_box.2;
@@ -87,12 +90,12 @@ impl PyMeshGeometry {
 	}
 
 	#[getter]
-	fn get_scale(&self) -> (f32, f32, f32) {
+	fn get_scale(&self) -> (Float, Float, Float) {
 		self.inner.scale
 	}
 
 	#[setter]
-	fn set_scale(mut slf: PyRefMut<'_, Self>, scale: (f32, f32, f32)) {
+	fn set_scale(mut slf: PyRefMut<'_, Self>, scale: (Float, Float, Float)) {
 		slf.inner.scale.0 = scale.0;
 		slf.inner.scale.1 = scale.1;
 		slf.inner.scale.2 = scale.2;
@@ -1,6 +1,9 @@
 use pyo3::{basic::CompareOp, prelude::*};
 
-use robot_description_builder::link_data::geometry::{GeometryInterface, SphereGeometry};
+use robot_description_builder::{
+	link_data::geometry::{GeometryInterface, SphereGeometry},
+	Float,
+};
 
 use super::PyGeometryBase;
 
@@ -11,7 +14,7 @@ pub struct PySphereGeometry {
 }
 
 impl PySphereGeometry {
-	fn new(radius: f32) -> (PySphereGeometry, PyGeometryBase) {
+	fn new(radius: Float) -> (PySphereGeometry, PyGeometryBase) {
 		let geometry = SphereGeometry::new(radius);
 		let base = PyGeometryBase::new(&geometry);
 		(PySphereGeometry { inner: geometry }, base)
@@ -22,7 +25,7 @@ impl PySphereGeometry {
 impl PySphereGeometry {
 	#[new]
 	#[pyo3(signature = (radius))]
-	fn py_new(radius: f32) -> (PySphereGeometry, PyGeometryBase) {
+	fn py_new(radius: Float) -> (PySphereGeometry, PyGeometryBase) {
 		// TODO: Maybe add kwargs and args checking
 		// I do not think it is necessary yet.
 		Self::new(radius)
@@ -45,12 +48,12 @@ impl PySphereGeometry {
 	}
 
 	#[getter]
-	fn get_radius(&self) -> f32 {
+	fn get_radius(&self) -> Float {
 		self.inner.radius
 	}
 
 	#[setter]
-	fn set_radius(mut slf: PyRefMut<'_, Self>, radius: f32) {
+	fn set_radius(mut slf: PyRefMut<'_, Self>, radius: Float) {
 		slf.inner.radius = radius;
 
 		let data = slf.inner.boxed_clone();
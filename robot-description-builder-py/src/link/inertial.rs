@@ -1,5 +1,5 @@
 use pyo3::prelude::*;
-use robot_description_builder::link_data::Inertial;
+use robot_description_builder::{link_data::Inertial, Float};
 
 use crate::transform::PyTransform;
 
@@ -21,13 +21,13 @@ pub(super) fn init_module(_py: Python<'_>, module: &Bound<'_, PyModule>) -> PyRe
 )] // Is set_all ok, since we would need to send the data back to the tree which is weird, immutability is also an option, set_all)]
 pub struct PyInertial {
 	pub transform: Option<PyTransform>,
-	pub mass: f32,
-	pub ixx: f32, // Not the nicesest way of doing this.
-	pub ixy: f32,
-	pub ixz: f32,
-	pub iyy: f32,
-	pub iyz: f32,
-	pub izz: f32,
+	pub mass: Float,
+	pub ixx: Float, // Not the nicesest way of doing this.
+	pub ixy: Float,
+	pub ixz: Float,
+	pub iyy: Float,
+	pub iyz: Float,
+	pub izz: Float,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -36,13 +36,13 @@ impl PyInertial {
 	#[new]
 	#[pyo3(signature = (mass, ixx, iyy, izz, ixy=0., ixz=0., iyz=0., transform=None))]
 	fn py_new(
-		mass: f32,
-		ixx: f32,
-		iyy: f32,
-		izz: f32,
-		ixy: f32,
-		ixz: f32,
-		iyz: f32,
+		mass: Float,
+		ixx: Float,
+		iyy: Float,
+		izz: Float,
+		ixy: Float,
+		ixz: Float,
+		iyz: Float,
 		transform: Option<PyTransform>,
 	) -> Self {
 		Self {
@@ -5,6 +5,7 @@ use robot_description_builder::{
 		Material, MaterialDescriptor,
 	},
 	prelude::GroupIDChanger,
+	Float,
 };
 
 use crate::{
@@ -35,7 +36,7 @@ fn repr_material_data(data: &MaterialData) -> String {
 #[derive(Debug, Clone, FromPyObject)]
 enum PyMaterialData {
 	#[pyo3(annotation = "Color")]
-	Color(f32, f32, f32, f32),
+	Color(Float, Float, Float, Float),
 	#[pyo3(annotation = "TexturePath")]
 	TexturePath { path: String },
 }
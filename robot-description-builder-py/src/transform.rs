@@ -1,5 +1,5 @@
 use pyo3::{basic::CompareOp, prelude::*};
-use robot_description_builder::{MirrorAxis, Transform};
+use robot_description_builder::{Float, MirrorAxis, Transform};
 
 const NONE_STR: &str = "None";
 
@@ -18,12 +18,12 @@ pub(super) fn init_module(_py: Python<'_>, module: &Bound<'_, PyModule>) -> PyRe
 )]
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
 pub struct PyTransform {
-	x: Option<f32>,
-	y: Option<f32>,
-	z: Option<f32>,
-	roll: Option<f32>,
-	pitch: Option<f32>,
-	yaw: Option<f32>,
+	x: Option<Float>,
+	y: Option<Float>,
+	z: Option<Float>,
+	roll: Option<Float>,
+	pitch: Option<Float>,
+	yaw: Option<Float>,
 }
 
 #[pymethods]
@@ -31,12 +31,12 @@ impl PyTransform {
 	#[new]
 	#[pyo3(signature = (x=None, y=None, z=None, roll=None, pitch=None, yaw=None))]
 	fn py_new(
-		x: Option<f32>,
-		y: Option<f32>,
-		z: Option<f32>,
-		roll: Option<f32>,
-		pitch: Option<f32>,
-		yaw: Option<f32>,
+		x: Option<Float>,
+		y: Option<Float>,
+		z: Option<Float>,
+		roll: Option<Float>,
+		pitch: Option<Float>,
+		yaw: Option<Float>,
 	) -> Self {
 		Self {
 			x,
@@ -111,7 +111,7 @@ impl PyTransform {
 
 impl From<PyTransform> for Transform {
 	fn from(value: PyTransform) -> Self {
-		let translation: Option<(f32, f32, f32)> = match (value.x, value.y, value.z) {
+		let translation: Option<(Float, Float, Float)> = match (value.x, value.y, value.z) {
 			(None, None, None) => None,
 			(x, y, z) => Some((
 				x.unwrap_or_default(),
@@ -120,7 +120,7 @@ impl From<PyTransform> for Transform {
 			)),
 		};
 
-		let rotation: Option<(f32, f32, f32)> = match (value.roll, value.pitch, value.yaw) {
+		let rotation: Option<(Float, Float, Float)> = match (value.roll, value.pitch, value.yaw) {
 			(None, None, None) => None,
 			(r, p, y) => Some((
 				r.unwrap_or_default(),
@@ -1,11 +1,14 @@
 use crate::{joint::PyJoint, utils::TryIntoRefPyAny};
 use pyo3::{intern, prelude::*, PyTypeInfo};
-use robot_description_builder::transmission::{TransmissionActuator, TransmissionJointBuilder};
+use robot_description_builder::{
+	transmission::{TransmissionActuator, TransmissionJointBuilder},
+	Float,
+};
 
 use super::PyTransmissionHardwareInterface;
 
 #[derive(Debug, Clone, FromPyObject)]
-pub(super) struct PyTransmissionActuator(String, Option<f32>);
+pub(super) struct PyTransmissionActuator(String, Option<Float>);
 
 unsafe impl PyNativeType for PyTransmissionActuator {
 	type AsRefSource = Self;
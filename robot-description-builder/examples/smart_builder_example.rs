@@ -1,5 +1,5 @@
 use robot_description_builder::{
-	link_data::geometry::BoxGeometry, linkbuilding::VisualBuilder, KinematicInterface, Link,
+	link_data::geometry::BoxGeometry, linkbuilding::VisualBuilder, Float, KinematicInterface, Link,
 	SmartJointBuilder, Transform,
 };
 
@@ -8,7 +8,7 @@ fn main() {
 		.fixed()
 		.add_dynamic_transform(|data| {
 			let bounding_box = &data.main_geometry.bounding_box();
-			let extend = |x: f32, bound: f32| {
+			let extend = |x: Float, bound: Float| {
 				if x == 0. {
 					0.
 				} else {
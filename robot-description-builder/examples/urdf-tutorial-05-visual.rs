@@ -1,7 +1,7 @@
 /// If an argument is supplied no meshes are used.
 ///
 /// This has been done to prevent allow for the use of an online viewer and the usage of WSL without setting some OpenGL settings.
-use std::f32::consts::FRAC_PI_2;
+use robot_description_builder::float::consts::FRAC_PI_2;
 
 use robot_description_builder::{
 	link_data::{geometry::*, Visual},
@@ -1,11 +1,10 @@
 /// If an argument is supplied no meshes are used.
 ///
 /// This has been done to prevent allow for the use of an online viewer and the usage of WSL without setting some OpenGL settings.
-use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_2};
-
 use robot_description_builder as rdb;
 
 use rdb::{
+	float::consts::{FRAC_1_SQRT_2, FRAC_PI_2},
 	link_data::{geometry::*, Collision, Inertial, Visual},
 	material::MaterialDescriptor,
 	prelude::*,
@@ -13,7 +12,7 @@ use rdb::{
 		to_urdf::{to_urdf, URDFConfig},
 		xml_writer_to_string, XMLMode,
 	},
-	Link, MirrorAxis, Robot, SmartJointBuilder, Transform,
+	Float, Link, MirrorAxis, Robot, SmartJointBuilder, Transform,
 };
 
 fn to_urdf_string(robot: &Robot) -> String {
@@ -29,7 +28,7 @@ fn to_urdf_string(robot: &Robot) -> String {
 	)
 }
 
-fn get_arg(args: &Vec<String>, index: usize, default: f32) -> f32 {
+fn get_arg(args: &Vec<String>, index: usize, default: Float) -> Float {
 	args.get(index)
 		.map(|s| s.parse().unwrap_or(default))
 		.unwrap_or(default)
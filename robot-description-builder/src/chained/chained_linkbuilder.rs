@@ -75,7 +75,7 @@ where
 
 #[cfg(test)]
 mod tests {
-	use std::f32::consts::FRAC_PI_2;
+	use crate::float::consts::FRAC_PI_2;
 
 	use test_log::test;
 
@@ -5,16 +5,25 @@ use std::{
 
 use crate::{
 	cluster_objects::kinematic_data_errors::AddTransmissionError,
+	collision::{link_collision, self_collisions, CollisionCheckConfig, LinkCollision},
+	dynamics::{
+		inverse_dynamics, subtree_mass_properties, JointEfforts, MassMatrix, MassProperties,
+	},
 	joint::{Joint, JointBuilder},
+	kinematics::{
+		link_poses, solve_inverse_kinematics, IKConfig, IKSolution, Jacobian, JointState,
+		KinematicChain, KinematicsError,
+	},
 	link::{builder::LinkBuilder, Link},
 	material::{data::MaterialData, Material},
+	transform::Transform,
 	transmission::{
 		transmission_builder_state::{WithActuator, WithJoints},
 		Transmission, TransmissionBuilder,
 	},
 	utils::{ArcLock, ArcRW, WeakLock},
 	yank_errors::{YankJointError, YankLinkError},
-	Chained,
+	Chained, Float,
 };
 
 pub mod kinematic_data_errors;
@@ -192,5 +201,372 @@ pub trait KinematicInterface: Sized {
 		builder.unwrap() // FIXME: Is unwrap ok here? NO
 	}
 
+	/// Calculates the pose of every `Link` relative to the root `Link`, with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// The poses are indexed by the name of the `Link`.
+	/// The motion of every `Joint` is applied after its `transform`, along or around its axis.
+	/// Mimicking `Joint`s follow the `Joint` they mimic.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{kinematics::JointState, prelude::*, Link, SmartJointBuilder, Transform};
+	/// let robot = Link::builder("base").build_tree().to_robot("arm");
+	///
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(
+	///         SmartJointBuilder::new_prismatic("lift")
+	///             .with_axis((0., 0., 1.))
+	///             .with_limit(100., 0.5)
+	///             .add_transform(Transform::new_translation(1., 0., 0.)),
+	///         Link::builder("carriage"),
+	///     )
+	///     .unwrap();
+	///
+	/// let poses = robot.forward_kinematics(&JointState::new().with_position("lift", 0.25));
+	///
+	/// assert_eq!(poses["carriage"].translation, Some((1., 0., 0.25)));
+	/// ```
+	fn forward_kinematics(&self, joint_state: &JointState) -> HashMap<String, Transform> {
+		link_poses(self, joint_state)
+			.into_iter()
+			.map(|(name, pose)| (name, Transform::from_isometry(&pose)))
+			.collect()
+	}
+
+	/// Calculates the pose of the `Link` with the specified name relative to the root `Link`, with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// Returns `None` if the `Link` does not exist. See [`forward_kinematics`](KinematicInterface::forward_kinematics) for more details.
+	fn link_pose(&self, name: &str, joint_state: &JointState) -> Option<Transform> {
+		link_poses(self, joint_state)
+			.get(name)
+			.map(Transform::from_isometry)
+	}
+
+	/// Gets the [`KinematicChain`] from the `Link` named `base_link` to the `Link` named `tip_link`.
+	///
+	/// The chain goes up from the base `Link` to the closest common ancestor of both `Link`s and then down to the tip `Link`.
+	/// Returns an error if one of the `Link`s does not exist.
+	fn get_chain(
+		&self,
+		base_link: &str,
+		tip_link: &str,
+	) -> Result<KinematicChain, KinematicsError> {
+		KinematicChain::new(self, base_link, tip_link)
+	}
+
+	/// Calculates the geometric [`Jacobian`] of the chain from the `Link` named `base_link` to the `Link` named `tip_link`, with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// The `Jacobian` is expressed in the frame of the base `Link`.
+	/// Returns an error if one of the `Link`s does not exist or if the tip `Link` is not a descendant of the base `Link`.
+	fn jacobian(
+		&self,
+		base_link: &str,
+		tip_link: &str,
+		joint_state: &JointState,
+	) -> Result<Jacobian, KinematicsError> {
+		Jacobian::compute(self, base_link, tip_link, joint_state)
+	}
+
+	/// Finds the positions of the `Joint`s in the chain from the `Link` named `base_link` to the `Link` named `tip_link`,
+	/// which place the tip `Link` at the `target` pose relative to the base `Link`.
+	///
+	/// The positions are found with the damped least squares method, starting from the `initial_state`.
+	/// Revolute and prismatic `Joint`s are kept within their `LimitData` and the positions of continuous `Joint`s wrap around to stay in `[-π, π)`.
+	/// Whether the target was reached and the remaining error are reported in the [`IKSolution`].
+	///
+	/// Returns an error if one of the `Link`s does not exist or if the tip `Link` is not a descendant of the base `Link`.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{
+	/// #     kinematics::{IKConfig, JointState},
+	/// #     prelude::*,
+	/// #     Link, SmartJointBuilder, Transform,
+	/// # };
+	/// let robot = Link::builder("base").build_tree().to_robot("lift");
+	///
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(
+	///         SmartJointBuilder::new_prismatic("lift")
+	///             .with_axis((0., 0., 1.))
+	///             .with_limit(100., 0.5)
+	///             .set_lower_limit(0.)
+	///             .set_upper_limit(1.),
+	///         Link::builder("carriage"),
+	///     )
+	///     .unwrap();
+	///
+	/// let solution = robot
+	///     .inverse_kinematics(
+	///         "base",
+	///         "carriage",
+	///         &Transform::new_translation(0., 0., 0.5),
+	///         &JointState::new(),
+	///         IKConfig::default(),
+	///     )
+	///     .unwrap();
+	/// assert!(solution.converged());
+	///
+	/// // The lift can not reach beyond its upper limit.
+	/// let solution = robot
+	///     .inverse_kinematics(
+	///         "base",
+	///         "carriage",
+	///         &Transform::new_translation(0., 0., 2.),
+	///         &JointState::new(),
+	///         IKConfig::default(),
+	///     )
+	///     .unwrap();
+	/// assert!(!solution.converged());
+	/// assert_eq!(solution.joint_state().position("lift"), Some(1.));
+	/// assert_eq!(solution.position_error(), 1.);
+	/// ```
+	fn inverse_kinematics(
+		&self,
+		base_link: &str,
+		tip_link: &str,
+		target: &Transform,
+		initial_state: &JointState,
+		config: IKConfig,
+	) -> Result<IKSolution, KinematicsError> {
+		solve_inverse_kinematics(self, base_link, tip_link, target, initial_state, &config)
+	}
+
+	/// Calculates the total mass of all `Link`s in the kinematic tree.
+	///
+	/// `Link`s without an [`Inertial`](crate::link_data::Inertial) are massless.
+	fn total_mass(&self) -> Float {
+		self.get_links()
+			.read()
+			.unwrap()
+			.values()
+			.filter_map(WeakLock::upgrade)
+			.filter_map(|link| {
+				link.read()
+					.unwrap()
+					.inertial()
+					.map(|inertial| inertial.mass)
+			})
+			.sum()
+	}
+
+	/// Calculates the center of mass of the kinematic tree relative to the root `Link`, with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// Returns `None` if the kinematic tree has no mass.
+	fn center_of_mass(&self, joint_state: &JointState) -> Option<(Float, Float, Float)> {
+		let root_name = self.get_root_link().read().unwrap().name().clone();
+
+		// This unwrap is Ok, since the root `Link` is always in the tree.
+		let mass_properties =
+			subtree_mass_properties(self, &root_name, &root_name, joint_state).unwrap();
+		(mass_properties.mass() > 0.).then(|| mass_properties.center_of_mass())
+	}
+
+	/// Calculates the combined [`MassProperties`] of the `Link` named `subtree_root` and all its descendants,
+	/// expressed in the frame of the `Link` named `frame`, with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// The [`Inertial`](crate::link_data::Inertial) of every `Link` is rotated by its `transform` and the pose of the `Link`,
+	/// after which they are combined about their common center of mass with the parallel axis theorem.
+	/// The inertia about the origin of the `frame` is found with [`MassProperties::inertia_about`].
+	///
+	/// Returns an error if one of the `Link`s does not exist.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{kinematics::JointState, link_data::Inertial, prelude::*, Link, SmartJointBuilder, Transform};
+	/// let robot = Link::builder("base")
+	///     .add_intertial(Inertial {
+	///         mass: 3.,
+	///         ..Default::default()
+	///     })
+	///     .build_tree()
+	///     .to_robot("lift");
+	///
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(
+	///         SmartJointBuilder::new_prismatic("lift")
+	///             .with_axis((0., 0., 1.))
+	///             .with_limit(100., 0.5),
+	///         Link::builder("carriage").add_intertial(Inertial {
+	///             mass: 1.,
+	///             ..Default::default()
+	///         }),
+	///     )
+	///     .unwrap();
+	///
+	/// let joint_state = JointState::new().with_position("lift", 2.);
+	/// let mass_properties = robot.mass_properties("base", "base", &joint_state).unwrap();
+	///
+	/// assert_eq!(mass_properties.mass(), robot.total_mass());
+	/// assert_eq!(mass_properties.center_of_mass(), (0., 0., 0.5));
+	/// assert_eq!(robot.center_of_mass(&joint_state), Some((0., 0., 0.5)));
+	/// ```
+	fn mass_properties(
+		&self,
+		subtree_root: &str,
+		frame: &str,
+		joint_state: &JointState,
+	) -> Result<MassProperties, KinematicsError> {
+		subtree_mass_properties(self, subtree_root, frame, joint_state)
+	}
+
+	/// Calculates the efforts of the `Joint`s, which are required to move the kinematic tree with the specified `velocities` and `accelerations`,
+	/// from the positions in the `joint_state`.
+	///
+	/// The efforts are calculated with the recursive Newton-Euler algorithm, from the [`Inertial`](crate::link_data::Inertial)s of the `Link`s.
+	/// The `velocities` and the `accelerations` of the single degree of freedom `Joint`s are specified as the positions of a `JointState`,
+	/// in radians per second (squared) or meters per second (squared). Missing values are zero.
+	/// Mimicking `Joint`s follow the position, velocity and acceleration of the `Joint` they mimic, scaled by their multiplier,
+	/// and get their own effort.
+	///
+	/// The root `Link` is fixed in the world and [`Planar`](crate::JointType::Planar) and [`Floating`](crate::JointType::Floating) `Joint`s
+	/// are held at their position in the `joint_state`.
+	/// The `gravity` is the acceleration in meters per second squared in the frame of the root `Link`,
+	/// which is [`STANDARD_GRAVITY`](crate::dynamics::STANDARD_GRAVITY) on earth if the z-axis points up.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{dynamics::STANDARD_GRAVITY, kinematics::JointState, link_data::Inertial, prelude::*, Link, SmartJointBuilder, Transform};
+	/// let robot = Link::builder("base").build_tree().to_robot("lift");
+	///
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(
+	///         SmartJointBuilder::new_prismatic("lift")
+	///             .with_axis((0., 0., 1.))
+	///             .with_limit(100., 0.5),
+	///         Link::builder("carriage").add_intertial(Inertial {
+	///             mass: 2.,
+	///             ..Default::default()
+	///         }),
+	///     )
+	///     .unwrap();
+	///
+	/// let no_motion = JointState::new();
+	/// let accelerations = JointState::new().with_position("lift", 1.);
+	/// let efforts = robot.inverse_dynamics(&no_motion, &no_motion, &accelerations, (0., 0., -9.));
+	///
+	/// assert_eq!(efforts.get("lift"), Some(20.));
+	/// // The lift can hold the carriage.
+	/// assert!(efforts.exceeding_limits(&robot).is_empty());
+	/// ```
+	fn inverse_dynamics(
+		&self,
+		joint_state: &JointState,
+		velocities: &JointState,
+		accelerations: &JointState,
+		gravity: (Float, Float, Float),
+	) -> JointEfforts {
+		inverse_dynamics(self, joint_state, velocities, accelerations, gravity)
+	}
+
+	/// Calculates the efforts of the `Joint`s, which are required to hold the kinematic tree still against the `gravity`,
+	/// with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// These efforts can be compared to the `effort` of the [`LimitData`](crate::joint_data::LimitData) of the `Joint`s
+	/// with [`JointEfforts::exceeding_limits`]. See [`KinematicInterface::inverse_dynamics`] for more details.
+	fn gravity_compensation(
+		&self,
+		joint_state: &JointState,
+		gravity: (Float, Float, Float),
+	) -> JointEfforts {
+		let no_motion = JointState::new();
+		inverse_dynamics(self, joint_state, &no_motion, &no_motion, gravity)
+	}
+
+	/// Calculates the joint space [`MassMatrix`] of the kinematic tree, with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// The matrix is calculated with the composite rigid body algorithm, from the [`Inertial`](crate::link_data::Inertial)s of the `Link`s.
+	/// Together with [`KinematicInterface::inverse_dynamics`], the efforts of the `Joint`s are `M(q) * q̈` plus the efforts without accelerations.
+	/// See [`MassMatrix`] for the treatment of mimicking `Joint`s.
+	fn mass_matrix(&self, joint_state: &JointState) -> MassMatrix {
+		MassMatrix::compute(self, joint_state)
+	}
+
+	/// Finds the pairs of `Link`s whose `Collision` geometries intersect, with the `Joint`s at the positions in the `joint_state`.
+	///
+	/// Boxes, spheres and cylinders are checked exactly and meshes are approximated by their bounding box.
+	/// The geometries are inflated by the `padding` of the `config`, so pairs which are closer than twice the `padding` are reported as well.
+	/// Adjacent `Link`s and the pairs of the `disabled` [`CollisionMatrix`](crate::collision::CollisionMatrix) are skipped, see [`CollisionCheckConfig`].
+	/// The collisions are ordered by the names of the `Link`s.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{
+	/// #     collision::CollisionCheckConfig,
+	/// #     float::consts::FRAC_PI_2,
+	/// #     kinematics::JointState,
+	/// #     link_data::{geometry::*, Collision},
+	/// #     prelude::*,
+	/// #     JointBuilder, JointType, Link, SmartJointBuilder, Transform,
+	/// # };
+	/// let robot = Link::builder("base")
+	///     .add_collider(Collision::builder(BoxGeometry::new(1., 1., 0.2)))
+	///     .build_tree()
+	///     .to_robot("robot");
+	///
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(
+	///         SmartJointBuilder::new_revolute("shoulder")
+	///             .with_axis((0., 1., 0.))
+	///             .with_limit(10., 1.)
+	///             .add_transform(Transform::new_translation(0., 0., 0.5)),
+	///         Link::builder("arm").add_collider(
+	///             Collision::builder(SphereGeometry::new(0.1))
+	///                 .transformed(Transform::new_translation(0.5, 0., 0.)),
+	///         ),
+	///     )
+	///     .unwrap();
+	///
+	/// let config = CollisionCheckConfig {
+	///     skip_adjacent: false,
+	///     ..Default::default()
+	/// };
+	/// assert!(robot.self_collisions(&JointState::new(), &config).is_empty());
+	///
+	/// // Turning the shoulder down puts the arm in the middle of the base.
+	/// let joint_state = JointState::new().with_position("shoulder", FRAC_PI_2);
+	/// let collisions = robot.self_collisions(&joint_state, &config);
+	/// assert_eq!(collisions.len(), 1);
+	/// assert_eq!((collisions[0].link1.as_str(), collisions[0].link2.as_str()), ("arm", "base"));
+	/// assert!((collisions[0].depth - 0.2).abs() < 1e-4);
+	/// ```
+	fn self_collisions(
+		&self,
+		joint_state: &JointState,
+		config: &CollisionCheckConfig,
+	) -> Vec<LinkCollision> {
+		self_collisions(self, joint_state, config)
+	}
+
+	/// Checks if the `Collision` geometries of the `Link`s named `link1` and `link2` intersect,
+	/// with the `Joint`s at the positions in the `joint_state` and the geometries inflated by the `padding`.
+	///
+	/// Returns the [`LinkCollision`] with the penetration depth if they intersect, or an error if one of the `Link`s does not exist.
+	/// See [`KinematicInterface::self_collisions`] for more details.
+	fn link_collision(
+		&self,
+		link1: &str,
+		link2: &str,
+		joint_state: &JointState,
+		padding: Float,
+	) -> Result<Option<LinkCollision>, KinematicsError> {
+		link_collision(self, link1, link2, joint_state, padding)
+	}
+
 	// TODO: or a rebuild?
 }
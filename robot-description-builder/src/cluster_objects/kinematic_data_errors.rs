@@ -89,6 +89,9 @@ pub enum AddJointError {
 		"The new Joint could not be added since its name '{0}' is already in use by another Joint"
 	)]
 	Conflict(String),
+	/// An Error, which occurs when a new `Joint` mimics a `Joint` that is not registered in the tree.
+	#[error("The new Joint '{joint}' mimics the Joint '{mimicked}', which is not in the tree")]
+	UnknownMimic { joint: String, mimicked: String },
 }
 
 impl PartialEq for AddJointError {
@@ -97,6 +100,16 @@ impl PartialEq for AddJointError {
 			(Self::ReadNewJoint(l0), Self::ReadNewJoint(r0)) => l0.get_ref() == r0.get_ref(),
 			(Self::ReadIndex(l0), Self::ReadIndex(r0)) => l0.get_ref() == r0.get_ref(),
 			(Self::Conflict(l0), Self::Conflict(r0)) => l0 == r0,
+			(
+				Self::UnknownMimic {
+					joint: l_joint,
+					mimicked: l_mimicked,
+				},
+				Self::UnknownMimic {
+					joint: r_joint,
+					mimicked: r_mimicked,
+				},
+			) => l_joint == r_joint && l_mimicked == r_mimicked,
 			_ => false,
 		}
 	}
@@ -5,8 +5,15 @@ use std::{
 
 use itertools::Itertools;
 
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::{SDFConfig, ToSDF};
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::{ToURDF, URDFConfig, URDFMaterialMode, URDFMaterialReferences};
+#[cfg(feature = "mjcf")]
+use crate::{
+	identifiers::GroupID,
+	to_rdf::to_mjcf::{MJCFConfig, ToMJCF},
+};
 use crate::{
 	joint::Joint,
 	link::{builder::BuildLink, Link},
@@ -58,6 +65,13 @@ impl KinematicDataTree {
 
 			//FIXME: This unwrap is not Ok, the Link could contain conflicting materials
 			data.try_add_link(&data.root_link).unwrap();
+			data.root_link
+				.read()
+				.unwrap()
+				.joints()
+				.iter()
+				.try_for_each(|joint| data.resolve_pending_mimics(joint))
+				.expect("The mimicked Joints of a tree should be a part of that tree");
 		}
 		data
 	}
@@ -209,6 +223,30 @@ impl KinematicDataTree {
 		}
 	}
 
+	/// Resolves the mimic data of the `Joint`s in the newly registered chain starting at `joint`.
+	///
+	/// This has to happen after the chain is registered, since the mimicked `Joint` can be a part of the same chain.
+	pub(crate) fn resolve_pending_mimics(
+		&self,
+		joint: &ArcLock<Joint>,
+	) -> Result<(), AttachChainError> {
+		let child_link = {
+			let mut joint = joint.write().unwrap();
+			let joint_index = self.joints.mread().map_err(AddJointError::ReadIndex)?;
+			joint.resolve_pending_mimic(&joint_index)?;
+			joint.child_link()
+		};
+
+		child_link
+			.mread()
+			.map_err(AddLinkError::ReadNewLink)?
+			.joints()
+			.iter()
+			.try_for_each(|joint| self.resolve_pending_mimics(joint))?;
+
+		Ok(())
+	}
+
 	/// Cleans up orphaned/broken `Joint` entries from the `joints` HashMap.
 	pub(crate) fn purge_joints(&self) {
 		/* In the future the lock could be saved by overwriting with a newly generated index,
@@ -387,7 +425,7 @@ impl ToURDF for KinematicDataTree {
 					},
 				)
 			})
-			.process_results(|iter| iter.collect())?;
+			.process_results(|iter| iter.collect::<()>())?;
 
 		self.root_link
 			.read()
@@ -414,7 +452,7 @@ impl ToURDF for KinematicDataTree {
 			.unwrap() // FIXME: Is unwrap ok here?
 			.values()
 			.map(|transmission| transmission.read().unwrap().to_urdf(writer, urdf_config)) // FIXME: Is unwrap ok here?
-			.process_results(|iter| iter.collect())?;
+			.process_results(|iter| iter.collect::<()>())?;
 
 		Ok(())
 	}
@@ -433,6 +471,105 @@ impl PartialEq for KinematicDataTree {
 	}
 }
 
+/// SDFormat has no global materials and no transmissions, so only the `Link`s and `Joint`s are written.
+#[cfg(feature = "sdf")]
+impl ToSDF for KinematicDataTree {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		self.root_link.read().unwrap().to_sdf(writer, sdf_config)
+	}
+}
+
+/// The `Link`s and `Joint`s are written in the `<worldbody>`.
+/// The meshes are written as `<asset>`s and the mimics as `<equality>` constraints, both ordered by the name of their `Link` or `Joint`.
+#[cfg(feature = "mjcf")]
+impl ToMJCF for KinematicDataTree {
+	fn to_mjcf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		mjcf_config: &MJCFConfig,
+	) -> Result<(), quick_xml::Error> {
+		// MJCF uses degrees by default.
+		writer
+			.create_element("compiler")
+			.with_attribute(("angle", "radian"))
+			.write_empty()?;
+
+		if let Some(armature) = mjcf_config.armature {
+			writer.create_element("default").write_inner_content(
+				|writer| -> quick_xml::Result<()> {
+					writer
+						.create_element("joint")
+						.with_attribute(("armature", armature.to_string().as_str()))
+						.write_empty()?;
+					Ok(())
+				},
+			)?;
+		}
+
+		let meshes = self
+			.links
+			.read()
+			.unwrap()
+			.iter()
+			.sorted_by_key(|(name, _)| *name)
+			.filter_map(|(_, link)| link.upgrade())
+			.flat_map(|link| link.read().unwrap().mjcf_meshes())
+			.collect_vec();
+
+		if !meshes.is_empty() {
+			writer
+				.create_element("asset")
+				.write_inner_content(|writer| {
+					meshes
+						.iter()
+						.map(|(name, mesh)| mesh.write_mjcf_asset(writer, name))
+						.process_results(|iter| iter.collect::<()>())
+				})?;
+		}
+
+		writer
+			.create_element("worldbody")
+			.write_inner_content(|writer| {
+				self.root_link.read().unwrap().to_mjcf(writer, mjcf_config)
+			})?;
+
+		let mimicking_joints = self
+			.joints
+			.read()
+			.unwrap()
+			.iter()
+			.sorted_by_key(|(name, _)| *name)
+			.filter_map(|(_, joint)| joint.upgrade())
+			.filter(|joint| joint.read().unwrap().mjcf_mimic().is_some())
+			.collect_vec();
+
+		if !mimicking_joints.is_empty() {
+			writer
+				.create_element("equality")
+				.write_inner_content(|writer| {
+					mimicking_joints
+						.iter()
+						.map(|joint| {
+							let joint = joint.read().unwrap();
+							match joint.mjcf_mimic() {
+								Some(mimic) => {
+									mimic.write_mjcf_equality(writer, &joint.name().display())
+								}
+								None => Ok(()),
+							}
+						})
+						.process_results(|iter| iter.collect::<()>())
+				})?;
+		}
+
+		Ok(())
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use itertools::Itertools;
@@ -882,14 +1019,14 @@ mod tests {
 				.try_attach_child(
 					SmartJointBuilder::new_fixed("Leg_[L1]_j1").add_transform(Transform::new(
 						(0., 3., 0.),
-						(0., 0., std::f32::consts::FRAC_PI_2),
+						(0., 0., crate::float::consts::FRAC_PI_2),
 					)),
 					Link::builder("Leg_[L1]_l2")
 						.add_visual(
 							Visual::builder(geom_leg_l2.clone())
 								.transformed(Transform::new(
 									(0., 5., 0.),
-									(std::f32::consts::FRAC_PI_2, 0., 0.),
+									(crate::float::consts::FRAC_PI_2, 0., 0.),
 								))
 								.named("Leg_[L1]_l2_vis_1")
 								.materialized(material_l2.clone()),
@@ -898,7 +1035,7 @@ mod tests {
 							Collision::builder(geom_leg_l2.clone())
 								.transformed(Transform::new(
 									(0., 5., 0.),
-									(std::f32::consts::FRAC_PI_2, 0., 0.),
+									(crate::float::consts::FRAC_PI_2, 0., 0.),
 								))
 								.named("Leg_[L1]_l2_col_1"),
 						),
@@ -949,9 +1086,9 @@ mod tests {
 		</geometry>
 	</collision>
 </link>"#,
-					std::f32::consts::FRAC_PI_2,
-					std::f32::consts::FRAC_PI_2,
-					std::f32::consts::FRAC_PI_2,
+					crate::float::consts::FRAC_PI_2,
+					crate::float::consts::FRAC_PI_2,
+					crate::float::consts::FRAC_PI_2,
 				),
 				&URDFConfig {
 					xml_mode: XMLMode::Indent('\t', 1),
@@ -1002,9 +1139,9 @@ mod tests {
 		</geometry>
 	</collision>
 </link>"#,
-					std::f32::consts::FRAC_PI_2,
-					std::f32::consts::FRAC_PI_2,
-					std::f32::consts::FRAC_PI_2,
+					crate::float::consts::FRAC_PI_2,
+					crate::float::consts::FRAC_PI_2,
+					crate::float::consts::FRAC_PI_2,
 				),
 				&URDFConfig {
 					xml_mode: XMLMode::Indent('\t', 1),
@@ -1,8 +1,12 @@
 use std::{
-	collections::HashMap,
+	collections::{BTreeMap, HashMap},
 	sync::{Arc, PoisonError, RwLockWriteGuard},
 };
 
+#[cfg(feature = "mjcf")]
+use crate::to_rdf::to_mjcf::{MJCFConfig, ToMJCF};
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::{SDFConfig, ToSDF};
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::{ToURDF, URDFConfig};
 #[cfg(feature = "xml")]
@@ -17,6 +21,7 @@ use crate::{
 	joint::Joint,
 	link::Link,
 	material::{data::MaterialData, Material},
+	simplify::Frame,
 	transmission::{
 		transmission_builder_state::{WithActuator, WithJoints},
 		Transmission, TransmissionBuilder,
@@ -29,6 +34,8 @@ pub struct Robot {
 	/// The name of the robot.
 	name: String,
 	data: Arc<KinematicDataTree>,
+	/// The frames, which are attached to the `Link`s of the robot, indexed by their name.
+	frames: BTreeMap<String, Frame>,
 }
 
 impl Robot {
@@ -36,6 +43,7 @@ impl Robot {
 		Self {
 			name: name.into(),
 			data,
+			frames: BTreeMap::new(),
 		}
 	}
 
@@ -53,6 +61,18 @@ impl Robot {
 	pub fn name(&self) -> &String {
 		&self.name
 	}
+
+	/// Gets the [`Frame`]s, which are attached to the `Link`s of the `Robot`, indexed by their name.
+	///
+	/// The frames are written as `<frame>` elements in SDFormat, from [`SDFVersion::V1_7`](crate::to_rdf::to_sdf::SDFVersion::V1_7) onwards.
+	/// They are ignored by the other formats.
+	pub fn frames(&self) -> &BTreeMap<String, Frame> {
+		&self.frames
+	}
+
+	pub(crate) fn set_frames(&mut self, frames: BTreeMap<String, Frame>) {
+		self.frames = frames;
+	}
 }
 
 impl KinematicInterface for Robot {
@@ -164,3 +184,82 @@ impl ToURDF for Robot {
 		Ok(())
 	}
 }
+
+#[cfg(feature = "mjcf")]
+impl ToMJCF for Robot {
+	fn to_mjcf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		mjcf_config: &MJCFConfig,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("mujoco")
+			.with_attribute(Attribute {
+				key: QName(b"model"),
+				value: self.name.display().as_bytes().into(),
+			})
+			.write_inner_content(|writer| self.data.to_mjcf(writer, mjcf_config))?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl ToSDF for Robot {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("sdf")
+			.with_attribute(Attribute {
+				key: QName(b"version"),
+				value: sdf_config.sdf_version.as_str().as_bytes().into(),
+			})
+			.write_inner_content(|writer| -> quick_xml::Result<()> {
+				writer
+					.create_element("model")
+					.with_attribute(Attribute {
+						key: QName(b"name"),
+						value: self.name.display().as_bytes().into(),
+					})
+					.write_inner_content(|writer| -> quick_xml::Result<()> {
+						self.data.to_sdf(writer, sdf_config)?;
+
+						if !sdf_config.sdf_version.supports_relative_to() {
+							#[cfg(any(feature = "logging", test))]
+							if !self.frames.is_empty() {
+								log::warn!(
+									"SDFormat {} does not support <frame> elements, the {} frames of '{}' are dropped",
+									sdf_config.sdf_version.as_str(),
+									self.frames.len(),
+									self.name.display()
+								);
+							}
+						} else {
+							for (name, frame) in self.frames.iter() {
+								writer
+									.create_element("frame")
+									.with_attribute(Attribute {
+										key: QName(b"name"),
+										value: name.display().as_bytes().into(),
+									})
+									.with_attribute(Attribute {
+										key: QName(b"attached_to"),
+										value: frame.attached_to.display().as_bytes().into(),
+									})
+									.write_inner_content(|writer| {
+										frame.transform.write_sdf_pose(
+											writer,
+											Some(&frame.attached_to.display()),
+										)
+									})?;
+							}
+						}
+						Ok(())
+					})?;
+				Ok(())
+			})?;
+		Ok(())
+	}
+}
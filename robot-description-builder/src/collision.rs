@@ -0,0 +1,896 @@
+//! The analysis of the collisions between the `Link`s of a kinematic tree.
+//!
+//! The [`CollisionMatrix`] contains the pairs of `Link`s for which collision checking can be disabled, together with the reason why.
+//! It can be [computed](CollisionMatrix::compute) from the `Collision` geometries of a kinematic tree,
+//! in the same way as the [MoveIt Setup Assistant](https://moveit.picknik.ai/main/doc/examples/setup_assistant/setup_assistant_tutorial.html) does.
+//! Meshes are approximated by their bounding box.
+//!
+//! The `Link`s which collide in a configuration are found with [`KinematicInterface::self_collisions`]
+//! and a single pair of `Link`s can be checked with [`KinematicInterface::link_collision`].
+//! Boxes, spheres and cylinders are checked exactly, together with the penetration depth of the collisions.
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{float::consts::PI, Float};
+
+use nalgebra::Isometry3;
+
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::JointType,
+	kinematics::{link_poses, JointState, KinematicsError},
+	link::{link_data::LinkParent, Link},
+	utils::ArcLock,
+};
+
+mod shape;
+
+use shape::{Primitive, Shape};
+
+/// The reason why the collisions between two `Link`s are disabled.
+///
+/// These are the reasons used by the MoveIt Setup Assistant.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DisabledCollisionReason {
+	/// The `Link`s are directly connected by a `Joint`.
+	Adjacent,
+	/// The `Link`s are in collision in the default configuration.
+	Default,
+	/// The `Link`s can never collide.
+	Never,
+	/// The `Link`s are always in collision.
+	Always,
+	/// The collisions are disabled by the user. This is the default.
+	#[default]
+	User,
+}
+
+impl std::fmt::Display for DisabledCollisionReason {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Adjacent => write!(f, "Adjacent"),
+			Self::Default => write!(f, "Default"),
+			Self::Never => write!(f, "Never"),
+			Self::Always => write!(f, "Always"),
+			Self::User => write!(f, "User"),
+		}
+	}
+}
+
+/// A Configuration for the computation of a [`CollisionMatrix`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CollisionMatrixConfig {
+	/// The number of random configurations, which are checked to find the pairs of `Link`s that never collide.
+	///
+	/// More samples make it less likely that a pair which rarely collides is marked as [`Never`](DisabledCollisionReason::Never).
+	pub samples: usize,
+	/// The seed of the random configurations, the same seed always results in the same `CollisionMatrix`.
+	pub seed: u64,
+	/// The distance in meters by which all `Collision` geometries are inflated.
+	pub padding: Float,
+}
+
+impl Default for CollisionMatrixConfig {
+	fn default() -> Self {
+		Self {
+			samples: 10_000,
+			seed: 0,
+			padding: 0.,
+		}
+	}
+}
+
+/// A Configuration for the collision checking of [`KinematicInterface::self_collisions`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct CollisionCheckConfig {
+	/// The distance in meters by which all `Collision` geometries are inflated.
+	pub padding: Float,
+	/// Skips the pairs of adjacent `Link`s, which are connected by a `Joint`. This is enabled by default.
+	///
+	/// Like in [`CollisionMatrix::compute`], `Link`s without `Collision` geometries are skipped, so their parent is adjacent to their children.
+	pub skip_adjacent: bool,
+	/// The pairs of `Link`s which are not checked, for example a computed [`CollisionMatrix`].
+	pub disabled: CollisionMatrix,
+}
+
+impl Default for CollisionCheckConfig {
+	fn default() -> Self {
+		Self {
+			padding: 0.,
+			skip_adjacent: true,
+			disabled: CollisionMatrix::new(),
+		}
+	}
+}
+
+/// A collision between the `Collision` geometries of two `Link`s.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LinkCollision {
+	/// The name of the first `Link`, which is the first in alphabetical order.
+	pub link1: String,
+	/// The name of the second `Link`.
+	pub link2: String,
+	/// The penetration depth in meters of the deepest intersecting pair of geometries, including the padding.
+	///
+	/// This is the shortest distance over which the geometries have to move apart to separate them.
+	/// Touching geometries have a depth of zero.
+	pub depth: Float,
+}
+
+/// A matrix of the pairs of `Link`s for which collision checking is disabled, with the reason per pair.
+///
+/// The `Link`s are referred to by name and the order of the `Link`s in a pair does not matter.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     collision::{CollisionMatrix, CollisionMatrixConfig, DisabledCollisionReason},
+/// #     link_data::{geometry::*, Collision},
+/// #     prelude::*,
+/// #     JointBuilder, JointType, Link, Transform,
+/// # };
+/// let robot = Link::builder("base")
+///     .add_collider(Collision::builder(BoxGeometry::new(1., 1., 0.2)))
+///     .build_tree()
+///     .to_robot("robot");
+///
+/// for (name, y) in [("left", 0.3), ("right", -0.3)] {
+///     robot
+///         .get_root_link()
+///         .write()
+///         .unwrap()
+///         .try_attach_child(
+///             JointBuilder::new(format!("{name}_joint"), JointType::Fixed)
+///                 .add_origin_offset((0., y, 0.5)),
+///             Link::builder(format!("{name}_arm"))
+///                 .add_collider(Collision::builder(SphereGeometry::new(0.1))),
+///         )
+///         .unwrap();
+/// }
+///
+/// let collision_matrix = CollisionMatrix::compute(&robot, CollisionMatrixConfig::default());
+///
+/// assert_eq!(
+///     collision_matrix.reason("base", "left_arm"),
+///     Some(DisabledCollisionReason::Adjacent)
+/// );
+/// assert_eq!(
+///     collision_matrix.reason("right_arm", "left_arm"),
+///     Some(DisabledCollisionReason::Never)
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CollisionMatrix {
+	pairs: BTreeMap<(String, String), DisabledCollisionReason>,
+}
+
+/// The `Collision` geometries of a `Link`, with their poses relative to the `Link`.
+type LocalShapes = Vec<(Isometry3<Float>, Primitive)>;
+
+/// The lower and upper limit of a `Joint`.
+type JointRange = (Float, Float);
+
+/// Orders the names of a pair of `Link`s, since the order does not matter.
+fn pair_key(link1: &str, link2: &str) -> (String, String) {
+	match link1 <= link2 {
+		true => (link1.to_string(), link2.to_string()),
+		false => (link2.to_string(), link1.to_string()),
+	}
+}
+
+impl CollisionMatrix {
+	/// Creates a new empty `CollisionMatrix`.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Computes the `CollisionMatrix` of the `tree` from the `Collision` geometries of its `Link`s.
+	///
+	/// The pairs of `Link`s are classified in the following order:
+	/// 1. [`Adjacent`](DisabledCollisionReason::Adjacent): The `Link`s are connected by a `Joint`.
+	///    `Link`s without `Collision` geometries are skipped, so their parent is adjacent to their children.
+	/// 2. [`Always`](DisabledCollisionReason::Always): The `Link`s collide in the default configuration
+	///    and in all of the random configurations. This requires at least one sample.
+	/// 3. [`Default`](DisabledCollisionReason::Default): The `Link`s collide in the default configuration,
+	///    where all `Joint`s are at zero (or the limit closest to zero).
+	/// 4. [`Never`](DisabledCollisionReason::Never): The `Link`s did not collide in any of the random configurations.
+	///    The positions of the `Joint`s are sampled within their `LimitData`.
+	///    Continuous `Joint`s and revolute `Joint`s without limits are sampled over a full rotation.
+	///    Prismatic `Joint`s without limits and multi degree of freedom `Joint`s are kept at zero.
+	///
+	/// Pairs which do not fit any of these reasons are not in the `CollisionMatrix`, since they need to be checked.
+	/// Only `Link`s with `Collision` geometries are considered for the last three reasons.
+	pub fn compute(tree: &impl KinematicInterface, config: CollisionMatrixConfig) -> Self {
+		let mut collision_matrix = Self::new();
+
+		let links = sorted_links(tree);
+		let link_shapes = link_shapes(&links);
+
+		// Adjacent links
+		for (link1, link2) in adjacent_pairs(&links, &link_shapes) {
+			collision_matrix.insert(&link1, &link2, DisabledCollisionReason::Adjacent);
+		}
+
+		let joints: Vec<(String, JointType, Option<JointRange>)> = {
+			let joints = tree.get_joints();
+			let joints = joints.read().unwrap();
+			let mut joints: Vec<_> = joints
+				.values()
+				.filter_map(|joint| joint.upgrade())
+				.map(|joint| {
+					let joint = joint.read().unwrap();
+					(
+						joint.name().clone(),
+						joint.joint_type(),
+						joint
+							.limit()
+							.and_then(|limit| limit.lower.zip(limit.upper))
+							.filter(|(lower, upper)| lower <= upper),
+					)
+				})
+				.collect();
+			joints.sort_by(|a, b| a.0.cmp(&b.0));
+			joints
+		};
+
+		let colliding_pairs = |joint_state: &JointState| {
+			let poses = link_poses(tree, joint_state);
+			let world_shapes: Vec<Vec<Shape>> = link_shapes
+				.iter()
+				.map(|(name, shapes)| world_shapes(shapes, &poses[name], config.padding))
+				.collect();
+
+			let mut pairs = Vec::new();
+			for (index1, shapes1) in world_shapes.iter().enumerate() {
+				for (index2, shapes2) in world_shapes.iter().enumerate().skip(index1 + 1) {
+					if shapes1
+						.iter()
+						.any(|shape1| shapes2.iter().any(|shape2| shape1.intersects(shape2)))
+					{
+						pairs.push((index1, index2));
+					}
+				}
+			}
+			pairs
+		};
+
+		let default_joint_state = joints
+			.iter()
+			.map(|(name, _, limits)| {
+				let position = limits.map_or(0., |(lower, upper)| Float::clamp(0., lower, upper));
+				(name.clone(), position)
+			})
+			.collect();
+		let default_pairs = colliding_pairs(&default_joint_state);
+
+		// The number of random configurations in which each pair of links collides
+		let mut random = SplitMix64(config.seed);
+		let mut collisions = vec![vec![0; link_shapes.len()]; link_shapes.len()];
+		for _ in 0..config.samples {
+			let joint_state = joints
+				.iter()
+				.map(|(name, joint_type, limits)| {
+					let position = match (joint_type, limits) {
+						(JointType::Revolute | JointType::Prismatic, Some((lower, upper))) => {
+							lower + (upper - lower) * random.next_float()
+						}
+						(JointType::Revolute | JointType::Continuous, _) => {
+							-PI + 2. * PI * random.next_float()
+						}
+						_ => 0.,
+					};
+					(name.clone(), position)
+				})
+				.collect();
+
+			for (index1, index2) in colliding_pairs(&joint_state) {
+				collisions[index1][index2] += 1;
+			}
+		}
+
+		// Links colliding in the default configuration, which are always or by default in collision
+		for (index1, index2) in default_pairs {
+			let (link1, link2) = (&link_shapes[index1].0, &link_shapes[index2].0);
+			if collision_matrix.reason(link1, link2).is_none() {
+				let reason =
+					match config.samples > 0 && collisions[index1][index2] == config.samples {
+						true => DisabledCollisionReason::Always,
+						false => DisabledCollisionReason::Default,
+					};
+				collision_matrix.insert(link1, link2, reason);
+			}
+		}
+
+		// Links which never collide
+		for (index1, (link1, _)) in link_shapes.iter().enumerate() {
+			for (index2, (link2, _)) in link_shapes.iter().enumerate().skip(index1 + 1) {
+				if collisions[index1][index2] == 0
+					&& collision_matrix.reason(link1, link2).is_none()
+				{
+					collision_matrix.insert(link1, link2, DisabledCollisionReason::Never);
+				}
+			}
+		}
+
+		collision_matrix
+	}
+
+	/// Inserts the pair of `Link`s with the specified names, with the `reason` why their collisions are disabled.
+	///
+	/// If the pair was already present, the previous reason is returned.
+	pub fn insert(
+		&mut self,
+		link1: &str,
+		link2: &str,
+		reason: DisabledCollisionReason,
+	) -> Option<DisabledCollisionReason> {
+		self.pairs.insert(pair_key(link1, link2), reason)
+	}
+
+	/// Removes the pair of `Link`s with the specified names, which enables their collisions.
+	///
+	/// If the pair was present, its reason is returned.
+	pub fn remove(&mut self, link1: &str, link2: &str) -> Option<DisabledCollisionReason> {
+		self.pairs.remove(&pair_key(link1, link2))
+	}
+
+	/// Gets the reason why the collisions between the `Link`s with the specified names are disabled.
+	///
+	/// If the collisions are not disabled `None` is returned.
+	pub fn reason(&self, link1: &str, link2: &str) -> Option<DisabledCollisionReason> {
+		self.pairs.get(&pair_key(link1, link2)).copied()
+	}
+
+	/// Checks if the collisions between the `Link`s with the specified names are disabled.
+	pub fn is_disabled(&self, link1: &str, link2: &str) -> bool {
+		self.pairs.contains_key(&pair_key(link1, link2))
+	}
+
+	/// Gets an iterator over the pairs of `Link` names and their reasons, ordered by name.
+	pub fn iter(&self) -> impl Iterator<Item = (&String, &String, DisabledCollisionReason)> {
+		self.pairs
+			.iter()
+			.map(|((link1, link2), reason)| (link1, link2, *reason))
+	}
+
+	/// Gets the number of pairs in the `CollisionMatrix`.
+	pub fn len(&self) -> usize {
+		self.pairs.len()
+	}
+
+	/// Checks if the `CollisionMatrix` is empty.
+	pub fn is_empty(&self) -> bool {
+		self.pairs.is_empty()
+	}
+}
+
+/// Gets the `Link`s of the `tree`, ordered by name.
+fn sorted_links(tree: &impl KinematicInterface) -> Vec<ArcLock<Link>> {
+	let links = tree.get_links();
+	let links = links.read().unwrap();
+	let mut links: Vec<_> = links.values().filter_map(|link| link.upgrade()).collect();
+	links.sort_by_key(|link| link.read().unwrap().name().clone());
+	links
+}
+
+/// Gets the `Collision` geometries of the `link`, with their poses relative to the `link`.
+fn local_shapes(link: &Link) -> LocalShapes {
+	link.colliders()
+		.iter()
+		.map(|collision| {
+			(
+				collision
+					.transform()
+					.map(|transform| transform.to_isometry())
+					.unwrap_or_else(Isometry3::identity),
+				Primitive::from_geometry(&collision.geometry().shape_container()),
+			)
+		})
+		.collect()
+}
+
+/// Gets the names and the `Collision` geometries of the `links` which have `Collision` geometries.
+fn link_shapes(links: &[ArcLock<Link>]) -> Vec<(String, LocalShapes)> {
+	links
+		.iter()
+		.map(|link| {
+			let link = link.read().unwrap();
+			(link.name().clone(), local_shapes(&link))
+		})
+		.filter(|(_, shapes)| !shapes.is_empty())
+		.collect()
+}
+
+/// Places the `shapes` of a `Link` with the specified `pose` in the world, inflated by the `padding`.
+fn world_shapes(shapes: &LocalShapes, pose: &Isometry3<Float>, padding: Float) -> Vec<Shape> {
+	shapes
+		.iter()
+		.map(|(local_pose, primitive)| Shape {
+			primitive: *primitive,
+			pose: (pose * local_pose).cast(),
+			padding: padding.into(),
+		})
+		.collect()
+}
+
+/// Gets the pairs of adjacent `Link`s, which are connected by a `Joint`.
+///
+/// `Link`s without `Collision` geometries in the `link_shapes` are skipped, so their parent is adjacent to their children.
+fn adjacent_pairs(
+	links: &[ArcLock<Link>],
+	link_shapes: &[(String, LocalShapes)],
+) -> Vec<(String, String)> {
+	let has_geometry = |name: &String| link_shapes.iter().any(|(link, _)| link == name);
+
+	let mut pairs = Vec::new();
+	for link in links.iter() {
+		let link = link.read().unwrap();
+		let mut ancestor = parent_link(&link);
+		let mut is_direct_parent = true;
+
+		while let Some(parent) = ancestor {
+			let parent = parent.read().unwrap();
+			if is_direct_parent || has_geometry(parent.name()) {
+				pairs.push(pair_key(link.name(), parent.name()));
+			}
+			if has_geometry(parent.name()) || !has_geometry(link.name()) {
+				break;
+			}
+
+			ancestor = parent_link(&parent);
+			is_direct_parent = false;
+		}
+	}
+
+	pairs
+}
+
+/// Gets the penetration depth of the deepest intersecting pair of shapes, if any of the shapes intersect.
+fn deepest_penetration(shapes1: &[Shape], shapes2: &[Shape]) -> Option<Float> {
+	shapes1
+		.iter()
+		.flat_map(|shape1| {
+			shapes2
+				.iter()
+				.filter_map(|shape2| shape1.penetration_depth(shape2))
+		})
+		.max_by(f64::total_cmp)
+		.map(|depth| depth as Float)
+}
+
+/// Finds the pairs of `Link`s of the `tree` whose `Collision` geometries intersect, with the `Joint`s at the positions in the `joint_state`.
+pub(crate) fn self_collisions(
+	tree: &impl KinematicInterface,
+	joint_state: &JointState,
+	config: &CollisionCheckConfig,
+) -> Vec<LinkCollision> {
+	let links = sorted_links(tree);
+	let link_shapes = link_shapes(&links);
+	let adjacent_pairs = match config.skip_adjacent {
+		true => adjacent_pairs(&links, &link_shapes),
+		false => Vec::new(),
+	};
+
+	let poses = link_poses(tree, joint_state);
+	let world_shapes: Vec<Vec<Shape>> = link_shapes
+		.iter()
+		.map(|(name, shapes)| world_shapes(shapes, &poses[name], config.padding))
+		.collect();
+
+	let mut collisions = Vec::new();
+	for (index1, (link1, _)) in link_shapes.iter().enumerate() {
+		for (index2, (link2, _)) in link_shapes.iter().enumerate().skip(index1 + 1) {
+			if config.disabled.is_disabled(link1, link2)
+				|| adjacent_pairs.contains(&pair_key(link1, link2))
+			{
+				continue;
+			}
+
+			if let Some(depth) = deepest_penetration(&world_shapes[index1], &world_shapes[index2]) {
+				collisions.push(LinkCollision {
+					link1: link1.clone(),
+					link2: link2.clone(),
+					depth,
+				});
+			}
+		}
+	}
+
+	collisions
+}
+
+/// Checks if the `Collision` geometries of the `Link`s named `link1` and `link2` of the `tree` intersect,
+/// with the `Joint`s at the positions in the `joint_state`.
+pub(crate) fn link_collision(
+	tree: &impl KinematicInterface,
+	link1: &str,
+	link2: &str,
+	joint_state: &JointState,
+	padding: Float,
+) -> Result<Option<LinkCollision>, KinematicsError> {
+	let poses = link_poses(tree, joint_state);
+	let shapes = |name: &str| {
+		let link = tree
+			.get_link(name)
+			.ok_or_else(|| KinematicsError::InvalidLink(name.to_string()))?;
+		let shapes = local_shapes(&link.read().unwrap());
+		Ok(world_shapes(&shapes, &poses[name], padding))
+	};
+	let (shapes1, shapes2) = (shapes(link1)?, shapes(link2)?);
+	let (link1, link2) = pair_key(link1, link2);
+
+	Ok(
+		deepest_penetration(&shapes1, &shapes2).map(|depth| LinkCollision {
+			link1,
+			link2,
+			depth,
+		}),
+	)
+}
+
+/// Gets the parent `Link` of the `link`, if it is not the root.
+fn parent_link(link: &Link) -> Option<ArcLock<Link>> {
+	match link.parent() {
+		LinkParent::Joint(joint) => Some(joint.upgrade()?.read().unwrap().parent_link()),
+		LinkParent::KinematicTree(_) => None,
+	}
+	.map(|link| Arc::clone(&link))
+}
+
+/// A small and fast pseudorandom number generator, to generate reproducible configurations.
+///
+/// See [SplitMix64](https://prng.di.unimi.it/splitmix64.c).
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+	fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+
+	/// Gets a random number in the range `[0, 1)`.
+	fn next_float(&mut self) -> Float {
+		(self.next_u64() >> 40) as Float / (1u64 << 24) as Float
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		CollisionCheckConfig, CollisionMatrix, CollisionMatrixConfig, DisabledCollisionReason,
+		LinkCollision,
+	};
+	use crate::{
+		float::consts::FRAC_PI_2,
+		joint_data::LimitData,
+		kinematics::{JointState, KinematicsError},
+		link_data::{geometry::*, Collision},
+		prelude::*,
+		Float, JointBuilder, JointType, Link, Robot, SmartJointBuilder,
+	};
+	use test_log::test;
+
+	/// A base with an arm rotating within the `limit`, which can hit a post on the base.
+	fn robot(limit: Float) -> Robot {
+		let robot = Link::builder("base")
+			.add_collider(Collision::builder(BoxGeometry::new(0.2, 0.2, 0.2)))
+			.build_tree()
+			.to_robot("robot");
+		let base = robot.get_root_link();
+
+		base.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_revolute("shoulder")
+					.with_axis((0., 0., 1.))
+					.with_limit(10., 1.)
+					.set_lower_limit(-limit)
+					.set_upper_limit(limit)
+					.add_transform(crate::Transform::new_translation(0., 0., 0.2)),
+				// The link frame is not in the geometry, so the arm is connected through a link without geometry.
+				Link::builder("shoulder_link"),
+			)
+			.unwrap();
+		robot
+			.get_link("shoulder_link")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("arm_joint", JointType::Fixed),
+				Link::builder("arm").add_collider(
+					Collision::builder(BoxGeometry::new(1., 0.1, 0.1))
+						.transformed(crate::Transform::new_translation(0.6, 0., 0.)),
+				),
+			)
+			.unwrap();
+		// A post, which the arm can only hit when it turns to the far left.
+		base.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("post_joint", JointType::Fixed).add_origin_offset((0., 0.7, 0.2)),
+				Link::builder("post")
+					.add_collider(Collision::builder(CylinderGeometry::new(0.1, 0.4))),
+			)
+			.unwrap();
+		// A lamp, which is always in the way of the arm.
+		base.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("lamp_joint", JointType::Fixed).add_origin_offset((0.5, 0., 0.2)),
+				Link::builder("lamp").add_collider(Collision::builder(SphereGeometry::new(0.1))),
+			)
+			.unwrap();
+		// A foot, which is out of reach.
+		base.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("foot_joint", JointType::Fixed).add_origin_offset((0., 0., -1.)),
+				Link::builder("foot").add_collider(Collision::builder(SphereGeometry::new(0.1))),
+			)
+			.unwrap();
+
+		robot
+	}
+
+	#[test]
+	fn compute() {
+		let collision_matrix = CollisionMatrix::compute(
+			&robot(1.5),
+			CollisionMatrixConfig {
+				samples: 500,
+				..Default::default()
+			},
+		);
+
+		assert_eq!(
+			collision_matrix.reason("base", "shoulder_link"),
+			Some(DisabledCollisionReason::Adjacent)
+		);
+		assert_eq!(
+			collision_matrix.reason("arm", "base"),
+			Some(DisabledCollisionReason::Adjacent)
+		);
+		// The arm only hits the lamp in part of the configurations.
+		assert_eq!(
+			collision_matrix.reason("lamp", "arm"),
+			Some(DisabledCollisionReason::Default)
+		);
+		assert_eq!(
+			collision_matrix.reason("foot", "arm"),
+			Some(DisabledCollisionReason::Never)
+		);
+		assert_eq!(
+			collision_matrix.reason("post", "lamp"),
+			Some(DisabledCollisionReason::Never)
+		);
+		// The arm can hit the post, so it needs to be checked.
+		assert_eq!(collision_matrix.reason("arm", "post"), None);
+
+		assert_eq!(
+			collision_matrix,
+			CollisionMatrix::compute(
+				&robot(1.5),
+				CollisionMatrixConfig {
+					samples: 500,
+					..Default::default()
+				},
+			)
+		);
+	}
+
+	#[test]
+	fn limits() {
+		let robot = robot(0.5);
+		assert_eq!(
+			robot.get_joint("shoulder").unwrap().read().unwrap().limit(),
+			Some(&LimitData {
+				lower: Some(-0.5),
+				upper: Some(0.5),
+				effort: 10.,
+				velocity: 1.
+			})
+		);
+
+		// The arm can not turn far enough to hit the post.
+		let collision_matrix = CollisionMatrix::compute(
+			&robot,
+			CollisionMatrixConfig {
+				samples: 500,
+				..Default::default()
+			},
+		);
+		assert_eq!(
+			collision_matrix.reason("arm", "post"),
+			Some(DisabledCollisionReason::Never)
+		);
+	}
+
+	#[test]
+	fn padding() {
+		let padded = CollisionMatrix::compute(
+			&robot(0.5),
+			CollisionMatrixConfig {
+				samples: 500,
+				padding: 0.35,
+				..Default::default()
+			},
+		);
+
+		// The padded lamp and post touch.
+		assert_eq!(
+			padded.reason("lamp", "post"),
+			Some(DisabledCollisionReason::Always)
+		);
+		assert_eq!(
+			padded.reason("foot", "arm"),
+			Some(DisabledCollisionReason::Never)
+		);
+	}
+
+	#[test]
+	fn always() {
+		let config = CollisionMatrixConfig {
+			samples: 500,
+			padding: 0.35,
+			..Default::default()
+		};
+
+		// The lamp and post are fixed to the base, so they touch in every configuration.
+		let collision_matrix = CollisionMatrix::compute(&robot(0.5), config);
+		assert_eq!(
+			collision_matrix.reason("lamp", "post"),
+			Some(DisabledCollisionReason::Always)
+		);
+
+		// Without samples, pairs can not be classified as always colliding.
+		let collision_matrix = CollisionMatrix::compute(
+			&robot(0.5),
+			CollisionMatrixConfig {
+				samples: 0,
+				..config
+			},
+		);
+		assert_eq!(
+			collision_matrix.reason("lamp", "post"),
+			Some(DisabledCollisionReason::Default)
+		);
+	}
+
+	/// Asserts that there is a single collision between `link1` and `link2` with the `depth`.
+	fn assert_collision(collisions: &[LinkCollision], link1: &str, link2: &str, depth: Float) {
+		assert_eq!(collisions.len(), 1, "{collisions:?}");
+		assert_eq!(collisions[0].link1, link1);
+		assert_eq!(collisions[0].link2, link2);
+		assert!(
+			(collisions[0].depth - depth).abs() < 1e-4,
+			"{} != {depth}",
+			collisions[0].depth
+		);
+	}
+
+	#[test]
+	fn self_collisions() {
+		let robot = robot(2.);
+		let config = CollisionCheckConfig::default();
+
+		// The lamp is half inside the arm.
+		assert_collision(
+			&robot.self_collisions(&JointState::new(), &config),
+			"arm",
+			"lamp",
+			0.15,
+		);
+		assert_collision(
+			&robot.self_collisions(
+				&JointState::new().with_position("shoulder", FRAC_PI_2),
+				&config,
+			),
+			"arm",
+			"post",
+			0.15,
+		);
+		assert!(robot
+			.self_collisions(
+				&JointState::new().with_position("shoulder", -FRAC_PI_2),
+				&config
+			)
+			.is_empty());
+
+		// Disabled pairs are skipped.
+		let mut disabled = CollisionMatrix::new();
+		disabled.insert("lamp", "arm", DisabledCollisionReason::Default);
+		assert!(robot
+			.self_collisions(
+				&JointState::new(),
+				&CollisionCheckConfig {
+					disabled,
+					..Default::default()
+				}
+			)
+			.is_empty());
+	}
+
+	#[test]
+	fn self_collisions_padding() {
+		let robot = robot(2.);
+		let joint_state = JointState::new().with_position("shoulder", -FRAC_PI_2);
+
+		// The padded arm and base overlap by 0.01, but they are adjacent.
+		let config = CollisionCheckConfig {
+			padding: 0.03,
+			..Default::default()
+		};
+		assert!(robot.self_collisions(&joint_state, &config).is_empty());
+
+		let config = CollisionCheckConfig {
+			skip_adjacent: false,
+			..config
+		};
+		assert_collision(
+			&robot.self_collisions(&joint_state, &config),
+			"arm",
+			"base",
+			0.01,
+		);
+	}
+
+	#[test]
+	fn link_collision() {
+		let robot = robot(2.);
+
+		assert_eq!(
+			robot
+				.link_collision("lamp", "arm", &JointState::new(), 0.)
+				.unwrap()
+				.map(|collision| (collision.link1, collision.link2)),
+			Some(("arm".to_string(), "lamp".to_string()))
+		);
+		assert_eq!(
+			robot.link_collision("foot", "arm", &JointState::new(), 0.),
+			Ok(None)
+		);
+		// The padded foot and base overlap by 0.2.
+		let collision = robot
+			.link_collision("foot", "base", &JointState::new(), 0.5)
+			.unwrap()
+			.unwrap();
+		assert_eq!(collision.link1, "base");
+		assert!((collision.depth - 0.2).abs() < 1e-4, "{}", collision.depth);
+		assert_eq!(
+			robot.link_collision("missing", "arm", &JointState::new(), 0.),
+			Err(KinematicsError::InvalidLink("missing".to_string()))
+		);
+	}
+
+	#[test]
+	fn matrix() {
+		let mut collision_matrix = CollisionMatrix::new();
+		assert!(collision_matrix.is_empty());
+
+		assert_eq!(
+			collision_matrix.insert("b", "a", DisabledCollisionReason::User),
+			None
+		);
+		assert_eq!(
+			collision_matrix.insert("a", "b", DisabledCollisionReason::Never),
+			Some(DisabledCollisionReason::User)
+		);
+		assert!(collision_matrix.is_disabled("b", "a"));
+		assert_eq!(
+			collision_matrix.iter().collect::<Vec<_>>(),
+			vec![(
+				&"a".to_string(),
+				&"b".to_string(),
+				DisabledCollisionReason::Never
+			)]
+		);
+		assert_eq!(
+			collision_matrix.remove("b", "a"),
+			Some(DisabledCollisionReason::Never)
+		);
+		assert_eq!(collision_matrix.len(), 0);
+	}
+}
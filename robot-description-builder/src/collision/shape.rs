@@ -0,0 +1,534 @@
+//! Convex collision shapes, the [GJK](https://en.wikipedia.org/wiki/Gilbert%E2%80%93Johnson%E2%80%93Keerthi_distance_algorithm) intersection test
+//! and the EPA penetration depth.
+use nalgebra::{Isometry3, Point3, Vector3};
+
+use crate::link::link_data::geometry::GeometryShapeContainer;
+
+/// The maximum number of GJK iterations, after which the shapes are considered to be intersecting.
+const MAX_ITERATIONS: usize = 64;
+
+/// The maximum number of EPA iterations, after which the best estimate of the penetration depth is used.
+const MAX_EPA_ITERATIONS: usize = 256;
+
+/// The accuracy in meters of the penetration depth.
+const DEPTH_TOLERANCE: f64 = 1e-6;
+
+/// The primitive form of a [`Shape`], in its local frame.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum Primitive {
+	Sphere {
+		radius: f64,
+	},
+	/// A box with the specified half sizes.
+	Box {
+		half_extents: Vector3<f64>,
+	},
+	/// A cylinder along the local z-axis.
+	Cylinder {
+		radius: f64,
+		half_length: f64,
+	},
+}
+
+impl Primitive {
+	/// Gets the primitive for the `geometry`. Meshes are represented by their bounding box.
+	pub fn from_geometry(geometry: &GeometryShapeContainer) -> Self {
+		match geometry {
+			GeometryShapeContainer::Sphere(sphere) => Self::Sphere {
+				radius: sphere.radius.into(),
+			},
+			GeometryShapeContainer::Cylinder(cylinder) => Self::Cylinder {
+				radius: cylinder.radius.into(),
+				half_length: f64::from(cylinder.length) / 2.,
+			},
+			GeometryShapeContainer::Box(box_geometry) => Self::Box {
+				half_extents: Vector3::new(
+					box_geometry.side1.into(),
+					box_geometry.side2.into(),
+					box_geometry.side3.into(),
+				) / 2.,
+			},
+			GeometryShapeContainer::Mesh(mesh) => {
+				let (x, y, z) = mesh.bounding_box;
+				Self::Box {
+					half_extents: Vector3::new(x.into(), y.into(), z.into()) / 2.,
+				}
+			}
+		}
+	}
+
+	/// Gets the radius of the smallest sphere around the local origin, which contains the primitive.
+	fn bounding_radius(&self) -> f64 {
+		match self {
+			Self::Sphere { radius } => *radius,
+			Self::Box { half_extents } => half_extents.norm(),
+			Self::Cylinder {
+				radius,
+				half_length,
+			} => radius.hypot(*half_length),
+		}
+	}
+
+	/// Gets the point of the primitive furthest in the `direction`, in the local frame.
+	fn support(&self, direction: &Vector3<f64>) -> Vector3<f64> {
+		match self {
+			Self::Sphere { radius } => direction
+				.try_normalize(f64::EPSILON)
+				.map(|direction| direction * *radius)
+				.unwrap_or_else(Vector3::zeros),
+			Self::Box { half_extents } => half_extents
+				.zip_map(direction, |half_extent, direction| {
+					half_extent.copysign(direction)
+				}),
+			Self::Cylinder {
+				radius,
+				half_length,
+			} => {
+				let radial = Vector3::new(direction.x, direction.y, 0.)
+					.try_normalize(f64::EPSILON)
+					.map(|radial| radial * *radius)
+					.unwrap_or_else(Vector3::zeros);
+				radial + Vector3::z() * half_length.copysign(direction.z)
+			}
+		}
+	}
+}
+
+/// A convex collision shape placed in the world.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct Shape {
+	pub primitive: Primitive,
+	pub pose: Isometry3<f64>,
+	/// The distance by which the shape is inflated.
+	pub padding: f64,
+}
+
+impl Shape {
+	fn center(&self) -> Point3<f64> {
+		self.pose.translation.vector.into()
+	}
+
+	/// Gets the point of the shape furthest in the `direction`, in the world frame.
+	fn support(&self, direction: &Vector3<f64>) -> Vector3<f64> {
+		let local_direction = self.pose.rotation.inverse_transform_vector(direction);
+		let padding = direction
+			.try_normalize(f64::EPSILON)
+			.map(|direction| direction * self.padding)
+			.unwrap_or_else(Vector3::zeros);
+
+		self.pose
+			.transform_point(&self.primitive.support(&local_direction).into())
+			.coords + padding
+	}
+
+	/// Checks if the bounding spheres of the shapes overlap, which is a cheap necessary condition for an intersection.
+	fn bounding_spheres_intersect(&self, other: &Self) -> bool {
+		(self.center() - other.center()).norm()
+			<= self.primitive.bounding_radius()
+				+ self.padding
+				+ other.primitive.bounding_radius()
+				+ other.padding
+	}
+
+	/// Checks if the shapes intersect, touching shapes are considered to be intersecting.
+	pub fn intersects(&self, other: &Self) -> bool {
+		self.intersection_simplex(other).is_some()
+	}
+
+	/// Gets the penetration depth of the shapes, which is the shortest distance over which one of the shapes has to move to separate them.
+	///
+	/// Returns `None` if the shapes do not intersect. Touching shapes have a depth of zero.
+	pub fn penetration_depth(&self, other: &Self) -> Option<f64> {
+		if let (
+			Primitive::Sphere { radius },
+			Primitive::Sphere {
+				radius: other_radius,
+			},
+		) = (self.primitive, other.primitive)
+		{
+			let depth = radius + self.padding + other_radius + other.padding
+				- (self.center() - other.center()).norm();
+			return (depth >= 0.).then_some(depth);
+		}
+
+		let simplex = self.intersection_simplex(other)?;
+		Some(self.expanding_polytope(other, simplex))
+	}
+
+	/// Gets the support point of the Minkowski difference of both shapes.
+	fn minkowski_support(&self, other: &Self, direction: &Vector3<f64>) -> Vector3<f64> {
+		self.support(direction) - other.support(&-direction)
+	}
+
+	/// Finds a simplex of the Minkowski difference of both shapes which contains the origin, with the GJK algorithm.
+	///
+	/// Returns `None` if the shapes do not intersect.
+	fn intersection_simplex(&self, other: &Self) -> Option<Vec<Vector3<f64>>> {
+		if !self.bounding_spheres_intersect(other) {
+			return None;
+		}
+
+		let initial_direction = (self.center() - other.center())
+			.try_normalize(f64::EPSILON)
+			.unwrap_or_else(Vector3::x);
+		let first = self.minkowski_support(other, &initial_direction);
+
+		// The simplex, with the newest point first.
+		let mut simplex = vec![first];
+		let mut direction = -first;
+
+		for _ in 0..MAX_ITERATIONS {
+			if direction.norm_squared() <= f64::EPSILON * f64::EPSILON {
+				// The origin lies on the boundary of the simplex.
+				return Some(simplex);
+			}
+
+			let point = self.minkowski_support(other, &direction);
+			if point.dot(&direction) < 0. {
+				return None;
+			}
+
+			simplex.insert(0, point);
+			if next_simplex(&mut simplex, &mut direction) {
+				return Some(simplex);
+			}
+		}
+
+		Some(simplex)
+	}
+
+	/// Calculates the penetration depth from the `simplex` of the GJK algorithm, with the expanding polytope algorithm (EPA).
+	///
+	/// The polytope is expanded towards the boundary of the Minkowski difference, until the face closest to the origin is on the boundary.
+	fn expanding_polytope(&self, other: &Self, simplex: Vec<Vector3<f64>>) -> f64 {
+		let Some(mut vertices) = self.complete_tetrahedron(other, simplex) else {
+			// The Minkowski difference is flat, so the shapes only touch.
+			return 0.;
+		};
+		// A point inside the polytope, to orient the faces outwards.
+		let inside = vertices.iter().sum::<Vector3<f64>>() / 4.;
+
+		let face = |vertices: &[Vector3<f64>], indices: [usize; 3]| {
+			let [a, b, c] = indices.map(|index| vertices[index]);
+			match (b - a).cross(&(c - a)).try_normalize(f64::EPSILON) {
+				Some(normal) if normal.dot(&(a - inside)) < 0. => {
+					(indices, -normal, -normal.dot(&a))
+				}
+				Some(normal) => (indices, normal, normal.dot(&a)),
+				// A degenerate face is never the closest face.
+				None => (indices, Vector3::zeros(), f64::INFINITY),
+			}
+		};
+
+		let mut faces: Vec<_> = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+			.into_iter()
+			.map(|indices| face(&vertices, indices))
+			.collect();
+
+		// The closest face is a lower bound of the depth and the support distance in any direction is an upper bound.
+		let mut depth = f64::INFINITY;
+		for _ in 0..MAX_EPA_ITERATIONS {
+			let Some((_, normal, distance)) =
+				faces.iter().min_by(|a, b| a.2.total_cmp(&b.2)).copied()
+			else {
+				break;
+			};
+
+			let point = self.minkowski_support(other, &normal);
+			depth = depth.min(point.dot(&normal));
+			if depth - distance < DEPTH_TOLERANCE {
+				break;
+			}
+
+			// Remove the faces which can see the new point and close the hole with new faces to the new point.
+			let mut horizon: Vec<[usize; 2]> = Vec::new();
+			faces.retain(|(indices, normal, distance)| {
+				let visible = normal.dot(&point) - distance > 0.;
+				if visible {
+					for edge in [
+						[indices[0], indices[1]],
+						[indices[1], indices[2]],
+						[indices[2], indices[0]],
+					] {
+						match horizon
+							.iter()
+							.position(|other| *other == edge || *other == [edge[1], edge[0]])
+						{
+							Some(index) => {
+								horizon.swap_remove(index);
+							}
+							None => horizon.push(edge),
+						}
+					}
+				}
+				!visible
+			});
+
+			vertices.push(point);
+			let new_vertex = vertices.len() - 1;
+			faces.extend(
+				horizon
+					.into_iter()
+					.map(|[a, b]| face(&vertices, [a, b, new_vertex])),
+			);
+		}
+
+		depth.max(0.)
+	}
+
+	/// Completes the `simplex` of the GJK algorithm to a tetrahedron, when the origin was found on a lower dimensional simplex.
+	///
+	/// Returns `None` if the Minkowski difference is too flat to contain a tetrahedron.
+	fn complete_tetrahedron(
+		&self,
+		other: &Self,
+		mut simplex: Vec<Vector3<f64>>,
+	) -> Option<Vec<Vector3<f64>>> {
+		let axes = [Vector3::x(), Vector3::y(), Vector3::z()];
+		let is_new = |simplex: &[Vector3<f64>], point: &Vector3<f64>| match simplex.len() {
+			1 => (point - simplex[0]).norm() > DEPTH_TOLERANCE,
+			2 => {
+				(simplex[1] - simplex[0])
+					.cross(&(point - simplex[0]))
+					.norm() > DEPTH_TOLERANCE * DEPTH_TOLERANCE
+			}
+			_ => {
+				(simplex[1] - simplex[0])
+					.cross(&(simplex[2] - simplex[0]))
+					.dot(&(point - simplex[0]))
+					.abs() > DEPTH_TOLERANCE * DEPTH_TOLERANCE * DEPTH_TOLERANCE
+			}
+		};
+
+		while simplex.len() < 4 {
+			let directions: Vec<Vector3<f64>> = match simplex.len() {
+				1 | 2 => axes.iter().flat_map(|axis| [*axis, -axis]).collect(),
+				_ => {
+					let normal = (simplex[1] - simplex[0]).cross(&(simplex[2] - simplex[0]));
+					vec![normal, -normal]
+				}
+			};
+
+			let point = directions
+				.iter()
+				.map(|direction| self.minkowski_support(other, direction))
+				.find(|point| is_new(&simplex, point))?;
+			simplex.push(point);
+		}
+
+		Some(simplex)
+	}
+}
+
+fn same_direction(direction: &Vector3<f64>, ao: &Vector3<f64>) -> bool {
+	direction.dot(ao) > 0.
+}
+
+/// Reduces the `simplex` to the feature closest to the origin and updates the search `direction`.
+///
+/// Returns `true` if the simplex contains the origin.
+fn next_simplex(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+	match simplex.len() {
+		2 => line(simplex, direction),
+		3 => triangle(simplex, direction),
+		_ => tetrahedron(simplex, direction),
+	}
+}
+
+fn line(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+	let (a, b) = (simplex[0], simplex[1]);
+	let (ab, ao) = (b - a, -a);
+
+	if same_direction(&ab, &ao) {
+		*direction = ab.cross(&ao).cross(&ab);
+	} else {
+		*simplex = vec![a];
+		*direction = ao;
+	}
+
+	false
+}
+
+fn triangle(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+	let (a, b, c) = (simplex[0], simplex[1], simplex[2]);
+	let (ab, ac, ao) = (b - a, c - a, -a);
+	let abc = ab.cross(&ac);
+
+	if same_direction(&abc.cross(&ac), &ao) {
+		if same_direction(&ac, &ao) {
+			*simplex = vec![a, c];
+			*direction = ac.cross(&ao).cross(&ac);
+			false
+		} else {
+			*simplex = vec![a, b];
+			line(simplex, direction)
+		}
+	} else if same_direction(&ab.cross(&abc), &ao) {
+		*simplex = vec![a, b];
+		line(simplex, direction)
+	} else if same_direction(&abc, &ao) {
+		*direction = abc;
+		false
+	} else {
+		*simplex = vec![a, c, b];
+		*direction = -abc;
+		false
+	}
+}
+
+fn tetrahedron(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+	let (a, b, c, d) = (simplex[0], simplex[1], simplex[2], simplex[3]);
+	let (ab, ac, ad, ao) = (b - a, c - a, d - a, -a);
+
+	if same_direction(&ab.cross(&ac), &ao) {
+		*simplex = vec![a, b, c];
+		triangle(simplex, direction)
+	} else if same_direction(&ac.cross(&ad), &ao) {
+		*simplex = vec![a, c, d];
+		triangle(simplex, direction)
+	} else if same_direction(&ad.cross(&ab), &ao) {
+		*simplex = vec![a, d, b];
+		triangle(simplex, direction)
+	} else {
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use nalgebra::{Isometry3, Vector3};
+	use std::f64::consts::FRAC_PI_4;
+	use test_log::test;
+
+	use super::{Primitive, Shape};
+
+	fn shape(primitive: Primitive, x: f64, y: f64, z: f64) -> Shape {
+		Shape {
+			primitive,
+			pose: Isometry3::translation(x, y, z),
+			padding: 0.,
+		}
+	}
+
+	const SPHERE: Primitive = Primitive::Sphere { radius: 0.5 };
+	const CUBE: Primitive = Primitive::Box {
+		half_extents: Vector3::new(0.5, 0.5, 0.5),
+	};
+	const CYLINDER: Primitive = Primitive::Cylinder {
+		radius: 0.5,
+		half_length: 1.,
+	};
+
+	#[test]
+	fn spheres() {
+		assert!(shape(SPHERE, 0., 0., 0.).intersects(&shape(SPHERE, 0.9, 0., 0.)));
+		assert!(!shape(SPHERE, 0., 0., 0.).intersects(&shape(SPHERE, 1.1, 0., 0.)));
+		assert!(!shape(SPHERE, 0., 0., 0.).intersects(&shape(SPHERE, 0.75, 0.75, 0.)));
+	}
+
+	#[test]
+	fn boxes() {
+		assert!(shape(CUBE, 0., 0., 0.).intersects(&shape(CUBE, 0.9, 0.9, 0.9)));
+		assert!(!shape(CUBE, 0., 0., 0.).intersects(&shape(CUBE, 1.1, 0., 0.)));
+
+		// A sphere near the corner of a cube.
+		assert!(!shape(CUBE, 0., 0., 0.).intersects(&shape(SPHERE, 0.8, 0.8, 0.8)));
+		assert!(shape(CUBE, 0., 0., 0.).intersects(&shape(SPHERE, 0.7, 0.7, 0.)));
+
+		// A rotated cube reaches further along its diagonal.
+		let rotated = Shape {
+			pose: Isometry3::new(Vector3::new(1.15, 0., 0.), Vector3::z() * FRAC_PI_4),
+			..shape(CUBE, 0., 0., 0.)
+		};
+		assert!(shape(CUBE, 0., 0., 0.).intersects(&rotated));
+		assert!(!shape(CUBE, -0.1, 0., 0.).intersects(&rotated));
+	}
+
+	#[test]
+	fn cylinders() {
+		assert!(shape(CYLINDER, 0., 0., 0.).intersects(&shape(SPHERE, 0., 0., 1.4)));
+		assert!(!shape(CYLINDER, 0., 0., 0.).intersects(&shape(SPHERE, 0., 0., 1.6)));
+		// The rim of a cylinder is round, unlike its bounding box.
+		assert!(!shape(CYLINDER, 0., 0., 0.).intersects(&shape(SPHERE, 0.75, 0.75, 0.)));
+		assert!(shape(CYLINDER, 0., 0., 0.).intersects(&shape(CYLINDER, 0.9, 0., 0.)));
+	}
+
+	/// Checks if the penetration depth is equal to `expected` within a small tolerance.
+	fn assert_depth(shape1: Shape, shape2: Shape, expected: Option<f64>) {
+		let depth = shape1.penetration_depth(&shape2);
+		assert!(
+			match (depth, expected) {
+				(Some(depth), Some(expected)) => (depth - expected).abs() < 1e-4,
+				_ => depth == expected,
+			},
+			"{depth:?} is not equal to {expected:?}"
+		);
+	}
+
+	#[test]
+	fn penetration_depth() {
+		assert_depth(
+			shape(SPHERE, 0., 0., 0.),
+			shape(SPHERE, 0.9, 0., 0.),
+			Some(0.1),
+		);
+		assert_depth(shape(SPHERE, 0., 0., 0.), shape(SPHERE, 1.1, 0., 0.), None);
+
+		assert_depth(
+			shape(CUBE, 0., 0., 0.),
+			shape(CUBE, 0.8, 0.1, 0.),
+			Some(0.2),
+		);
+		assert_depth(
+			shape(CUBE, 0., 0., 0.),
+			shape(CUBE, 0.9, 0.9, 0.9),
+			Some(0.1),
+		);
+		assert_depth(shape(CUBE, 0., 0., 0.), shape(CUBE, 1.1, 0., 0.), None);
+		// Concentric shapes are separated along the shortest direction.
+		assert_depth(shape(CUBE, 0., 0., 0.), shape(CUBE, 0., 0., 0.), Some(1.));
+		assert_depth(
+			shape(CYLINDER, 0., 0., 0.),
+			shape(SPHERE, 0., 0., 0.),
+			Some(1.),
+		);
+
+		assert_depth(
+			shape(CUBE, 0., 0., 0.),
+			shape(SPHERE, 0.8, 0., 0.),
+			Some(0.2),
+		);
+		assert_depth(
+			shape(CYLINDER, 0., 0., 0.),
+			shape(SPHERE, 0., 0., 1.4),
+			Some(0.1),
+		);
+		assert_depth(
+			shape(CYLINDER, 0., 0., 0.),
+			shape(CYLINDER, 0.9, 0., 0.),
+			Some(0.1),
+		);
+		assert_depth(
+			shape(CYLINDER, 0., 0., 0.),
+			shape(CUBE, 0., 0.7, 0.),
+			Some(0.3),
+		);
+
+		// Touching shapes
+		assert_depth(shape(CUBE, 0., 0., 0.), shape(CUBE, 1., 0., 0.), Some(0.));
+	}
+
+	#[test]
+	fn padding() {
+		let padded = Shape {
+			padding: 0.2,
+			..shape(SPHERE, 0., 0., 0.)
+		};
+		assert!(padded.intersects(&shape(CUBE, 1.1, 0., 0.)));
+		assert!(!padded.intersects(&shape(CUBE, 1.3, 0., 0.)));
+
+		assert_depth(padded, shape(CUBE, 1.1, 0., 0.), Some(0.1));
+		assert_depth(padded, shape(SPHERE, 1.1, 0., 0.), Some(0.1));
+		assert_depth(padded, shape(SPHERE, 1.3, 0., 0.), None);
+	}
+}
@@ -0,0 +1,16 @@
+//! The mass properties and the dynamics of a kinematic tree.
+//!
+//! The [`Inertial`](crate::link_data::Inertial)s of a group of `Link`s are combined into their [`MassProperties`],
+//! which can be calculated for any subtree with [`KinematicInterface::mass_properties`](crate::KinematicInterface::mass_properties).
+//! The [`JointEfforts`] required to move the `Joint`s are calculated with [`KinematicInterface::inverse_dynamics`](crate::KinematicInterface::inverse_dynamics)
+//! and the efforts required to hold a configuration against gravity with [`KinematicInterface::gravity_compensation`](crate::KinematicInterface::gravity_compensation).
+//! The joint space [`MassMatrix`] is calculated with [`KinematicInterface::mass_matrix`](crate::KinematicInterface::mass_matrix).
+mod inverse_dynamics;
+mod mass_matrix;
+mod mass_properties;
+
+pub(crate) use inverse_dynamics::inverse_dynamics;
+pub use inverse_dynamics::{JointEfforts, STANDARD_GRAVITY};
+pub use mass_matrix::MassMatrix;
+pub(crate) use mass_properties::subtree_mass_properties;
+pub use mass_properties::MassProperties;
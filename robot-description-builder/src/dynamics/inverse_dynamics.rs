@@ -0,0 +1,428 @@
+//! The inverse dynamics of a kinematic tree, with the recursive Newton-Euler algorithm.
+use nalgebra::{Isometry3, Vector3};
+
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::{Joint, JointType},
+	kinematics::{joint_axis, joint_motion, JointState},
+	link::Link,
+	transform::Transform,
+	Float,
+};
+
+/// The standard gravity of the earth in meters per second squared, pointing down along the z-axis.
+pub const STANDARD_GRAVITY: (Float, Float, Float) = (0., 0., -9.80665);
+
+/// The efforts of the `Joint`s of a kinematic tree, as calculated by [`KinematicInterface::inverse_dynamics`].
+///
+/// The effort of a [`Revolute`](JointType::Revolute) or [`Continuous`](JointType::Continuous) `Joint` is a torque in newton meters around its axis
+/// and the effort of a [`Prismatic`](JointType::Prismatic) `Joint` is a force in newtons along its axis.
+/// Other `Joint`s do not have an effort.
+///
+/// The efforts are ordered depth first from the root `Link`.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct JointEfforts {
+	efforts: Vec<(String, Float)>,
+}
+
+impl JointEfforts {
+	/// Gets the effort of the `Joint` with the specified name, if it has one.
+	pub fn get(&self, joint_name: &str) -> Option<Float> {
+		self.efforts
+			.iter()
+			.find(|(name, _)| name == joint_name)
+			.map(|(_, effort)| *effort)
+	}
+
+	/// Gets an iterator over the names of the `Joint`s and their efforts.
+	pub fn iter(&self) -> impl Iterator<Item = (&String, Float)> {
+		self.efforts.iter().map(|(name, effort)| (name, *effort))
+	}
+
+	/// Gets the `Joint`s whose effort exceeds the `effort` of their [`LimitData`](crate::joint_data::LimitData) in the `tree`,
+	/// with the magnitude of their effort.
+	///
+	/// `Joint`s without `LimitData` are not checked.
+	pub fn exceeding_limits(&self, tree: &impl KinematicInterface) -> Vec<(String, Float)> {
+		self.efforts
+			.iter()
+			.filter(|(name, effort)| {
+				tree.get_joint(name)
+					.and_then(|joint| joint.read().unwrap().limit().map(|limit| limit.effort))
+					.is_some_and(|limit| effort.abs() > limit)
+			})
+			.map(|(name, effort)| (name.clone(), effort.abs()))
+			.collect()
+	}
+}
+
+/// The motion of a `Link`, expressed in its own frame.
+#[derive(Debug, Clone, Copy)]
+struct LinkMotion {
+	angular_velocity: Vector3<Float>,
+	angular_acceleration: Vector3<Float>,
+	/// The linear acceleration of the origin of the `Link`, including the opposite of gravity.
+	linear_acceleration: Vector3<Float>,
+}
+
+/// Gets the velocity or acceleration of the single degree of freedom `joint` from the `rates`.
+///
+/// The rate of a mimicking `Joint` follows the mimicked `Joint`, scaled by its multiplier. Missing rates default to zero.
+fn joint_rate(joint: &Joint, rates: &JointState) -> Float {
+	match joint
+		.mimic()
+		.and_then(|mimic| Some((mimic.joint.upgrade()?, mimic.multiplier)))
+	{
+		Some((mimicked_joint, multiplier)) => {
+			let mimicked_rate = joint_rate(&mimicked_joint.read().unwrap(), rates);
+			multiplier.unwrap_or(1.) * mimicked_rate
+		}
+		None => rates.position(joint.name()).unwrap_or_default(),
+	}
+}
+
+/// Calculates the efforts of the `Joint`s of the `tree` with the recursive Newton-Euler algorithm.
+pub(crate) fn inverse_dynamics(
+	tree: &impl KinematicInterface,
+	joint_state: &JointState,
+	velocities: &JointState,
+	accelerations: &JointState,
+	gravity: (Float, Float, Float),
+) -> JointEfforts {
+	/// Calculates the force and the moment which the parent `Joint` exerts on the `link` to move it and its descendants,
+	/// in the frame of the `link`, and records the efforts of the `Joint`s of its descendants.
+	fn add_link(
+		link: &Link,
+		motion: LinkMotion,
+		states: [&JointState; 3],
+		efforts: &mut Vec<(String, Float)>,
+	) -> (Vector3<Float>, Vector3<Float>) {
+		let [joint_state, velocities, accelerations] = states;
+		let angular_velocity = motion.angular_velocity;
+		let angular_acceleration = motion.angular_acceleration;
+
+		let (mut force, mut moment) = match link.inertial() {
+			Some(inertial) => {
+				let pose = inertial
+					.transform
+					.map(Transform::to_isometry)
+					.unwrap_or_else(Isometry3::identity);
+				let center = pose.translation.vector;
+				let rotation = pose.rotation.to_rotation_matrix();
+				let inertia = rotation * inertial.inertia_matrix() * rotation.transpose();
+
+				let force = (motion.linear_acceleration
+					+ angular_acceleration.cross(&center)
+					+ angular_velocity.cross(&angular_velocity.cross(&center)))
+					* inertial.mass;
+
+				(
+					force,
+					inertia * angular_acceleration
+						+ angular_velocity.cross(&(inertia * angular_velocity))
+						+ center.cross(&force),
+				)
+			}
+			None => (Vector3::zeros(), Vector3::zeros()),
+		};
+
+		for joint in link.joints() {
+			let joint = joint.read().unwrap();
+			let pose = joint.transform().to_isometry() * joint_motion(&joint, joint_state);
+			let offset = pose.translation.vector;
+			// The motion of the `Joint` does not change its axis, so it is the same in the frame of the child `Link`.
+			let axis = joint_axis(&joint).into_inner();
+
+			let to_child = pose.rotation.inverse();
+			let mut child_motion = LinkMotion {
+				angular_velocity: to_child * angular_velocity,
+				angular_acceleration: to_child * angular_acceleration,
+				linear_acceleration: to_child
+					* (motion.linear_acceleration
+						+ angular_acceleration.cross(&offset)
+						+ angular_velocity.cross(&angular_velocity.cross(&offset))),
+			};
+
+			let effort_index = match joint.joint_type() {
+				JointType::Revolute | JointType::Continuous => {
+					let velocity = axis * joint_rate(&joint, velocities);
+					child_motion.angular_acceleration +=
+						child_motion.angular_velocity.cross(&velocity)
+							+ axis * joint_rate(&joint, accelerations);
+					child_motion.angular_velocity += velocity;
+					Some(efforts.len())
+				}
+				JointType::Prismatic => {
+					let velocity = axis * joint_rate(&joint, velocities);
+					child_motion.linear_acceleration +=
+						child_motion.angular_velocity.cross(&velocity) * 2.
+							+ axis * joint_rate(&joint, accelerations);
+					Some(efforts.len())
+				}
+				// Planar and Floating `Joint`s are held at their position in the `joint_state`.
+				JointType::Fixed | JointType::Planar | JointType::Floating => None,
+			};
+			if effort_index.is_some() {
+				efforts.push((joint.name().clone(), 0.));
+			}
+
+			let (child_force, child_moment) = add_link(
+				&joint.child_link().read().unwrap(),
+				child_motion,
+				states,
+				efforts,
+			);
+
+			if let Some(index) = effort_index {
+				efforts[index].1 = match joint.joint_type() {
+					JointType::Prismatic => child_force.dot(&axis),
+					_ => child_moment.dot(&axis),
+				};
+			}
+
+			let child_force = pose.rotation * child_force;
+			force += child_force;
+			moment += pose.rotation * child_moment + offset.cross(&child_force);
+		}
+
+		(force, moment)
+	}
+
+	let (x, y, z) = gravity;
+	let mut efforts = Vec::new();
+	add_link(
+		&tree.get_root_link().read().unwrap(),
+		LinkMotion {
+			angular_velocity: Vector3::zeros(),
+			angular_acceleration: Vector3::zeros(),
+			// Accelerating the root upwards is equivalent to gravity pulling everything down.
+			linear_acceleration: -Vector3::new(x, y, z),
+		},
+		[joint_state, velocities, accelerations],
+		&mut efforts,
+	);
+
+	JointEfforts { efforts }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::STANDARD_GRAVITY;
+	use crate::{
+		float::consts::FRAC_PI_2, kinematics::JointState, link_data::Inertial, prelude::*, Float,
+		JointBuilder, JointType, Link, Robot, SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	const G: Float = 9.80665;
+
+	/// Checks if the values are equal within a small tolerance.
+	fn assert_effort(effort: Option<Float>, expected: Float) {
+		let effort = effort.unwrap();
+		assert!(
+			(effort - expected).abs() < 1e-4,
+			"{effort} is not equal to {expected}"
+		);
+	}
+
+	fn point_mass(mass: Float, center: (Float, Float, Float)) -> Inertial {
+		Inertial {
+			transform: Some(Transform::new_translation(center.0, center.1, center.2)),
+			mass,
+			..Default::default()
+		}
+	}
+
+	/// A pendulum of 2 kg at one meter from a shoulder around the y-axis,
+	/// with a fixed hand of 1 kg at its end, which holds two mimicking fingers of 0.5 kg.
+	fn robot() -> Robot {
+		let robot = Link::builder("base").build_tree().to_robot("pendulum");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_revolute("shoulder")
+					.with_axis((0., 1., 0.))
+					.with_limit(20., 1.),
+				Link::builder("arm").add_intertial(point_mass(2., (1., 0., 0.))),
+			)
+			.unwrap();
+		robot
+			.get_link("arm")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("wrist", JointType::Fixed).add_origin_offset((1., 0., 0.)),
+				Link::builder("hand").add_intertial(point_mass(1., (0., 0., 0.))),
+			)
+			.unwrap();
+		for (side, direction) in [("left", 1.), ("right", -1.)] {
+			let finger_joint = SmartJointBuilder::new_prismatic(format!("{side}_finger_joint"))
+				.with_axis((0., direction, 0.))
+				.with_limit(10., 0.1);
+
+			robot
+				.get_link("hand")
+				.unwrap()
+				.write()
+				.unwrap()
+				.try_attach_child(
+					match side {
+						"right" => JointBuilder::from(
+							finger_joint
+								.with_mimic("left_finger_joint")
+								.set_mimic_multiplier(1.),
+						),
+						_ => finger_joint.into(),
+					},
+					Link::builder(format!("{side}_finger"))
+						.add_intertial(point_mass(0.5, (0., 0., 0.))),
+				)
+				.unwrap();
+		}
+
+		robot
+	}
+
+	#[test]
+	fn gravity_compensation() {
+		let robot = robot();
+
+		let efforts = robot.gravity_compensation(&JointState::new(), STANDARD_GRAVITY);
+		assert_eq!(
+			efforts
+				.iter()
+				.map(|(name, _)| name.as_str())
+				.collect::<Vec<_>>(),
+			["shoulder", "left_finger_joint", "right_finger_joint"]
+		);
+		// The shoulder holds the arm, the hand and the fingers horizontally, against the rotation towards -z.
+		assert_effort(efforts.get("shoulder"), -4. * G);
+		assert_effort(efforts.get("left_finger_joint"), 0.);
+		assert_eq!(efforts.get("wrist"), None);
+
+		// Hanging down, gravity does not cause a torque around the shoulder.
+		let hanging = JointState::new().with_position("shoulder", FRAC_PI_2);
+		let efforts = robot.gravity_compensation(&hanging, STANDARD_GRAVITY);
+		assert_effort(efforts.get("shoulder"), 0.);
+
+		// With gravity along the fingers, they have to be pushed up.
+		let efforts = robot.gravity_compensation(&JointState::new(), (0., -G, 0.));
+		assert_effort(efforts.get("shoulder"), 0.);
+		assert_effort(efforts.get("left_finger_joint"), 0.5 * G);
+		assert_effort(efforts.get("right_finger_joint"), -0.5 * G);
+	}
+
+	#[test]
+	fn inverse_dynamics() {
+		let robot = robot();
+		let no_motion = JointState::new();
+
+		// Without gravity, accelerating the shoulder requires the moment of inertia around it: 4 kg * 1².
+		let accelerations = JointState::new().with_position("shoulder", 2.);
+		let efforts = robot.inverse_dynamics(&no_motion, &no_motion, &accelerations, (0., 0., 0.));
+		assert_effort(efforts.get("shoulder"), 8.);
+
+		// A constant velocity does not require a torque around the shoulder,
+		// and the centripetal acceleration is perpendicular to the fingers.
+		let velocities = JointState::new().with_position("shoulder", 2.);
+		let efforts = robot.inverse_dynamics(&no_motion, &velocities, &no_motion, (0., 0., 0.));
+		assert_effort(efforts.get("shoulder"), 0.);
+		assert_effort(efforts.get("left_finger_joint"), 0.);
+
+		// The right finger mimics the left finger, so it accelerates in the opposite direction along the same axis.
+		let accelerations = JointState::new().with_position("left_finger_joint", 2.);
+		let efforts = robot.inverse_dynamics(&no_motion, &no_motion, &accelerations, (0., 0., 0.));
+		assert_effort(efforts.get("left_finger_joint"), 1.);
+		assert_effort(efforts.get("right_finger_joint"), 1.);
+		assert_effort(efforts.get("shoulder"), 0.);
+
+		// The fingers move along the axis of the shoulder, so turning the shoulder does not cause a Coriolis force.
+		let velocities = JointState::new()
+			.with_position("shoulder", 1.)
+			.with_position("left_finger_joint", 1.);
+		let efforts = robot.inverse_dynamics(&no_motion, &velocities, &no_motion, (0., 0., 0.));
+		assert_effort(efforts.get("shoulder"), 0.);
+	}
+
+	#[test]
+	fn rotated_joint() {
+		// A turntable on its side, with its axis along the -y-axis, which holds a plate with its center of mass off the axis.
+		let robot = Link::builder("base").build_tree().to_robot("turntable");
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_continuous("turntable")
+					.with_axis((0., 0., 1.))
+					.add_transform(Transform::new((0., 0., 1.), (FRAC_PI_2, 0., 0.))),
+				Link::builder("plate").add_intertial(Inertial {
+					transform: Some(Transform::new_translation(0.5, 0., 0.)),
+					mass: 4.,
+					ixx: 1.,
+					iyy: 2.,
+					izz: 3.,
+					..Default::default()
+				}),
+			)
+			.unwrap();
+
+		// The moment of inertia around the axis is 3 + 4 kg * 0.5².
+		let no_motion = JointState::new();
+		let accelerations = JointState::new().with_position("turntable", 2.);
+		assert_effort(
+			robot
+				.inverse_dynamics(&no_motion, &no_motion, &accelerations, (0., 0., 0.))
+				.get("turntable"),
+			8.,
+		);
+
+		assert_effort(
+			robot
+				.gravity_compensation(&no_motion, STANDARD_GRAVITY)
+				.get("turntable"),
+			2. * G,
+		);
+		// Turned upwards, the center of mass is above the axis.
+		let turned = JointState::new().with_position("turntable", FRAC_PI_2);
+		assert_effort(
+			robot
+				.gravity_compensation(&turned, STANDARD_GRAVITY)
+				.get("turntable"),
+			0.,
+		);
+		// The gravity and the acceleration add up.
+		assert_effort(
+			robot
+				.inverse_dynamics(&no_motion, &no_motion, &accelerations, STANDARD_GRAVITY)
+				.get("turntable"),
+			8. + 2. * G,
+		);
+	}
+
+	#[test]
+	fn exceeding_limits() {
+		let robot = robot();
+
+		assert_eq!(
+			robot
+				.gravity_compensation(&JointState::new(), STANDARD_GRAVITY)
+				.exceeding_limits(&robot)
+				.into_iter()
+				.map(|(name, _)| name)
+				.collect::<Vec<_>>(),
+			["shoulder"]
+		);
+		assert!(robot
+			.gravity_compensation(
+				&JointState::new().with_position("shoulder", 1.3),
+				STANDARD_GRAVITY
+			)
+			.exceeding_limits(&robot)
+			.is_empty());
+	}
+}
@@ -0,0 +1,444 @@
+//! The joint space [`MassMatrix`] of a kinematic tree, with the composite rigid body algorithm.
+use std::collections::HashMap;
+
+use nalgebra::{DMatrix, Isometry3, Vector3};
+
+use super::MassProperties;
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::JointType,
+	kinematics::{independent_joint, joint_axis, link_poses, JointState},
+	link::Link,
+	Float,
+};
+
+/// The joint space inertia matrix `M(q)` of a kinematic tree, which maps the accelerations of the `Joint`s to their efforts.
+///
+/// Every row and column corresponds to a `Joint`, in the order of [`joints`](MassMatrix::joints).
+/// Only [`Revolute`](JointType::Revolute), [`Continuous`](JointType::Continuous) and [`Prismatic`](JointType::Prismatic) `Joint`s have a column.
+/// Mimicking `Joint`s are added to the column of the `Joint` they mimic, scaled by their multiplier,
+/// so the matrix contains the inertia as felt by the independent `Joint`s.
+/// [`Planar`](JointType::Planar) and [`Floating`](JointType::Floating) `Joint`s are treated as [`Fixed`](JointType::Fixed) `Joint`s.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{kinematics::JointState, link_data::Inertial, prelude::*, Link, SmartJointBuilder, Transform};
+/// let robot = Link::builder("base").build_tree().to_robot("turntable");
+///
+/// robot
+///     .get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(
+///         SmartJointBuilder::new_continuous("turntable").with_axis((0., 0., 1.)),
+///         Link::builder("plate").add_intertial(Inertial {
+///             transform: Some(Transform::new_translation(1., 0., 0.)),
+///             mass: 2.,
+///             izz: 0.5,
+///             ..Default::default()
+///         }),
+///     )
+///     .unwrap();
+///
+/// let mass_matrix = robot.mass_matrix(&JointState::new());
+///
+/// assert_eq!(mass_matrix.joints(), &["turntable".to_string()]);
+/// // The inertia of the plate around the axis of the turntable is 0.5 + 2 kg * 1².
+/// assert_eq!(mass_matrix.inertia("turntable"), Some(2.5));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct MassMatrix {
+	joints: Vec<String>,
+	matrix: DMatrix<Float>,
+}
+
+/// A degree of freedom of the kinematic tree, with its motion expressed at the origin of the root `Link`.
+struct DegreeOfFreedom {
+	/// The name of the independent `Joint` and the factor by which this `Joint` follows it.
+	joint: (String, Float),
+	/// The angular velocity of the child `Link` for a unit velocity of the `Joint`.
+	angular: Vector3<Float>,
+	/// The linear velocity of the child `Link` at the origin of the root `Link` for a unit velocity of the `Joint`.
+	linear: Vector3<Float>,
+	/// The `MassProperties` of the child `Link` and all its descendants.
+	composite: MassProperties,
+	/// The index of the closest degree of freedom between the `Joint` and the root `Link`.
+	parent: Option<usize>,
+}
+
+impl DegreeOfFreedom {
+	/// Gets the linear and angular momentum about the origin of the root `Link`, of the `composite` body moving with the unit velocity of the `Joint`.
+	fn momentum(&self) -> (Vector3<Float>, Vector3<Float>) {
+		let (x, y, z) = self.composite.center_of_mass();
+		let center = Vector3::new(x, y, z);
+
+		let linear = (self.linear + self.angular.cross(&center)) * self.composite.mass();
+		(
+			linear,
+			self.composite.inertia() * self.angular + center.cross(&linear),
+		)
+	}
+}
+
+impl MassMatrix {
+	/// Computes the `MassMatrix` of the `tree` with the `Joint`s at the positions in the `joint_state`.
+	pub(crate) fn compute(tree: &impl KinematicInterface, joint_state: &JointState) -> Self {
+		/// Adds the degrees of freedom of the descendants of the `link`
+		/// and gets the `MassProperties` of the `link` and all its descendants.
+		fn add_link(
+			link: &Link,
+			parent: Option<usize>,
+			poses: &HashMap<String, Isometry3<Float>>,
+			degrees_of_freedom: &mut Vec<DegreeOfFreedom>,
+		) -> MassProperties {
+			let mut composite = link
+				.inertial()
+				.map(|inertial| MassProperties::from_inertial(inertial, &poses[link.name()]))
+				.unwrap_or_default();
+
+			for joint_lock in link.joints() {
+				let joint = joint_lock.read().unwrap();
+				let child_link = joint.child_link();
+				let child_link = child_link.read().unwrap();
+
+				// The motion of the `Joint` does not change its axis, so the pose of the child `Link` can be used.
+				let child_pose = poses[child_link.name()];
+				let axis = child_pose.rotation * joint_axis(&joint).into_inner();
+
+				let (angular, linear) = match joint.joint_type() {
+					JointType::Revolute | JointType::Continuous => {
+						(axis, child_pose.translation.vector.cross(&axis))
+					}
+					JointType::Prismatic => (Vector3::zeros(), axis),
+					JointType::Fixed | JointType::Planar | JointType::Floating => {
+						composite += add_link(&child_link, parent, poses, degrees_of_freedom);
+						continue;
+					}
+				};
+				drop(joint);
+
+				let index = degrees_of_freedom.len();
+				degrees_of_freedom.push(DegreeOfFreedom {
+					joint: independent_joint(joint_lock),
+					angular,
+					linear,
+					composite: MassProperties::default(),
+					parent,
+				});
+
+				let child_composite = add_link(&child_link, Some(index), poses, degrees_of_freedom);
+				degrees_of_freedom[index].composite = child_composite;
+				composite += child_composite;
+			}
+
+			composite
+		}
+
+		let poses = link_poses(tree, joint_state);
+		let mut degrees_of_freedom = Vec::new();
+		add_link(
+			&tree.get_root_link().read().unwrap(),
+			None,
+			&poses,
+			&mut degrees_of_freedom,
+		);
+
+		let mut joints: Vec<String> = Vec::new();
+		let indices: Vec<usize> = degrees_of_freedom
+			.iter()
+			.map(
+				|dof| match joints.iter().position(|joint| *joint == dof.joint.0) {
+					Some(index) => index,
+					None => {
+						joints.push(dof.joint.0.clone());
+						joints.len() - 1
+					}
+				},
+			)
+			.collect();
+
+		let mut matrix = DMatrix::zeros(joints.len(), joints.len());
+		for (i, dof) in degrees_of_freedom.iter().enumerate() {
+			// Only the degrees of freedom between this one and the root move the composite body of this one.
+			let (linear_momentum, angular_momentum) = dof.momentum();
+
+			let mut ancestor = Some(i);
+			while let Some(j) = ancestor {
+				let other = &degrees_of_freedom[j];
+				let value = (other.angular.dot(&angular_momentum)
+					+ other.linear.dot(&linear_momentum))
+					* dof.joint.1 * other.joint.1;
+
+				matrix[(indices[i], indices[j])] += value;
+				if i != j {
+					matrix[(indices[j], indices[i])] += value;
+				}
+
+				ancestor = other.parent;
+			}
+		}
+
+		Self { joints, matrix }
+	}
+
+	/// Gets the names of the `Joint`s corresponding to the rows and the columns of the `MassMatrix`.
+	pub fn joints(&self) -> &[String] {
+		&self.joints
+	}
+
+	/// Gets the symmetric NxN matrix of the `MassMatrix`.
+	pub fn matrix(&self) -> &DMatrix<Float> {
+		&self.matrix
+	}
+
+	/// Gets the inertia felt by the `Joint` with the specified name, when all other `Joint`s are locked.
+	///
+	/// This is the diagonal element of the `Joint`, in kilogram square meters for rotating `Joint`s and in kilograms for prismatic `Joint`s.
+	/// The inertia reflected to a motor behind a reduction is this value divided by the square of the reduction.
+	pub fn inertia(&self, joint_name: &str) -> Option<Float> {
+		self.joints
+			.iter()
+			.position(|joint| joint == joint_name)
+			.map(|index| self.matrix[(index, index)])
+	}
+
+	/// Calculates the kinetic energy in joules of the kinematic tree, moving with the specified `velocities`.
+	///
+	/// The `velocities` are specified as the positions of a `JointState`, like for [`KinematicInterface::inverse_dynamics`].
+	pub fn kinetic_energy(&self, velocities: &JointState) -> Float {
+		let velocities = DMatrix::from_iterator(
+			self.joints.len(),
+			1,
+			self.joints
+				.iter()
+				.map(|joint| velocities.position(joint).unwrap_or_default()),
+		);
+
+		(velocities.transpose() * &self.matrix * &velocities)[(0, 0)] / 2.
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use nalgebra::DMatrix;
+
+	use crate::{
+		dynamics::JointEfforts, float::consts::FRAC_PI_2, kinematics::JointState,
+		link_data::Inertial, prelude::*, Float, JointBuilder, JointType, Link, Robot,
+		SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	/// Checks if the matrices are equal within a small tolerance.
+	fn assert_matrix(matrix: &DMatrix<Float>, expected: &DMatrix<Float>) {
+		assert!(
+			(matrix - expected).amax() < 1e-4,
+			"{matrix} is not equal to {expected}"
+		);
+	}
+
+	fn point_mass(mass: Float, center: (Float, Float, Float)) -> Inertial {
+		Inertial {
+			transform: Some(Transform::new_translation(center.0, center.1, center.2)),
+			mass,
+			..Default::default()
+		}
+	}
+
+	/// A planar arm with a point mass of 2 kg at one meter from the shoulder
+	/// and a point mass of 1 kg at half a meter from the elbow.
+	fn planar_arm() -> Robot {
+		let robot = Link::builder("base").build_tree().to_robot("planar_arm");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_continuous("shoulder").with_axis((0., 0., 1.)),
+				Link::builder("upper_arm").add_intertial(point_mass(2., (1., 0., 0.))),
+			)
+			.unwrap();
+		robot
+			.get_link("upper_arm")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_continuous("elbow")
+					.with_axis((0., 0., 1.))
+					.add_transform(Transform::new_translation(1., 0., 0.)),
+				Link::builder("forearm").add_intertial(point_mass(1., (0.5, 0., 0.))),
+			)
+			.unwrap();
+
+		robot
+	}
+
+	/// A lift with a rotated wrist, which holds a gripper with two mimicking fingers, all with full inertia tensors.
+	fn gripper() -> Robot {
+		let inertial = |mass, center: (Float, Float, Float)| Inertial {
+			transform: Some(Transform::new(center, (0.1, 0.2, 0.3))),
+			mass,
+			ixx: 0.3,
+			ixy: 0.01,
+			iyy: 0.2,
+			iyz: -0.02,
+			izz: 0.1,
+			..Default::default()
+		};
+		let robot = Link::builder("base").build_tree().to_robot("gripper");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_prismatic("lift")
+					.with_axis((0., 0., 1.))
+					.with_limit(100., 1.),
+				Link::builder("column").add_intertial(inertial(3., (0., 0.1, 0.5))),
+			)
+			.unwrap();
+		robot
+			.get_link("column")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("mount", JointType::Fixed)
+					.add_origin_offset((0.2, 0., 1.))
+					.add_origin_rotation((0., FRAC_PI_2, 0.)),
+				Link::builder("mount").add_intertial(inertial(0.5, (0., 0., 0.1))),
+			)
+			.unwrap();
+		robot
+			.get_link("mount")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_revolute("wrist")
+					.with_axis((1., 0., 0.))
+					.with_limit(10., 1.)
+					.add_transform(Transform::new((0., 0., 0.3), (0.4, 0., 0.))),
+				Link::builder("hand").add_intertial(inertial(1., (0.1, 0.05, 0.2))),
+			)
+			.unwrap();
+		for (side, direction) in [("left", 1.), ("right", -1.)] {
+			let finger_joint = SmartJointBuilder::new_prismatic(format!("{side}_finger_joint"))
+				.with_axis((0., direction, 0.))
+				.with_limit(10., 0.1)
+				.add_transform(Transform::new_translation(0.1, 0., 0.3));
+
+			robot
+				.get_link("hand")
+				.unwrap()
+				.write()
+				.unwrap()
+				.try_attach_child(
+					match side {
+						"right" => JointBuilder::from(
+							finger_joint
+								.with_mimic("left_finger_joint")
+								.set_mimic_multiplier(1.),
+						),
+						_ => finger_joint.into(),
+					},
+					Link::builder(format!("{side}_finger"))
+						.add_intertial(inertial(0.2, (0., 0.02, 0.05))),
+				)
+				.unwrap();
+		}
+
+		robot
+	}
+
+	#[test]
+	fn planar_arm_mass_matrix() {
+		let robot = planar_arm();
+
+		let mass_matrix = robot.mass_matrix(&JointState::new());
+		assert_eq!(mass_matrix.joints(), &["shoulder", "elbow"]);
+		assert_matrix(
+			mass_matrix.matrix(),
+			&DMatrix::from_row_slice(2, 2, &[4.25, 0.75, 0.75, 0.25]),
+		);
+
+		let mass_matrix = robot.mass_matrix(&JointState::new().with_position("elbow", FRAC_PI_2));
+		assert_matrix(
+			mass_matrix.matrix(),
+			&DMatrix::from_row_slice(2, 2, &[3.25, 0.25, 0.25, 0.25]),
+		);
+		assert!((mass_matrix.inertia("elbow").unwrap() - 0.25).abs() < 1e-5);
+		assert_eq!(mass_matrix.inertia("base"), None);
+
+		// The forearm moves at 2 * 1.5 m/s with a stretched arm.
+		let velocities = JointState::new().with_position("shoulder", 2.);
+		let kinetic_energy = robot
+			.mass_matrix(&JointState::new())
+			.kinetic_energy(&velocities);
+		assert!((kinetic_energy - (2. * 2. * 2. + 1. * 3. * 3.) / 2.).abs() < 1e-4);
+	}
+
+	#[test]
+	fn consistent_with_inverse_dynamics() {
+		let robot = gripper();
+		let joint_state = JointState::new()
+			.with_position("lift", 0.3)
+			.with_position("wrist", 0.7)
+			.with_position("left_finger_joint", 0.02);
+		let no_motion = JointState::new();
+
+		let mass_matrix = robot.mass_matrix(&joint_state);
+		assert_eq!(
+			mass_matrix.joints(),
+			&["lift", "wrist", "left_finger_joint"]
+		);
+		assert_eq!(mass_matrix.matrix(), &mass_matrix.matrix().transpose());
+
+		// The efforts of the independent joints, with the effort of the mimicking finger added to the finger it mimics.
+		let independent_efforts = |efforts: JointEfforts| {
+			[
+				efforts.get("lift").unwrap(),
+				efforts.get("wrist").unwrap(),
+				efforts.get("left_finger_joint").unwrap()
+					+ efforts.get("right_finger_joint").unwrap(),
+			]
+		};
+
+		for (column, joint) in mass_matrix.joints().iter().enumerate() {
+			let accelerations = JointState::new().with_position(joint, 1.);
+			let efforts = independent_efforts(robot.inverse_dynamics(
+				&joint_state,
+				&no_motion,
+				&accelerations,
+				(0., 0., 0.),
+			));
+
+			for (row, effort) in efforts.into_iter().enumerate() {
+				assert!(
+					(mass_matrix.matrix()[(row, column)] - effort).abs() < 1e-4,
+					"{} is not equal to {effort} at ({row}, {column})",
+					mass_matrix.matrix()[(row, column)]
+				);
+			}
+		}
+
+		// The whole gripper moves with the lift.
+		assert!((mass_matrix.inertia("lift").unwrap() - robot.total_mass()).abs() < 1e-4);
+	}
+
+	#[test]
+	fn without_joints() {
+		let robot = Link::builder("base")
+			.add_intertial(point_mass(1., (0., 0., 0.)))
+			.build_tree()
+			.to_robot("statue");
+
+		let mass_matrix = robot.mass_matrix(&JointState::new());
+		assert!(mass_matrix.joints().is_empty());
+		assert_eq!(mass_matrix.matrix().shape(), (0, 0));
+		assert_eq!(mass_matrix.kinetic_energy(&JointState::new()), 0.);
+	}
+}
@@ -0,0 +1,399 @@
+//! The combined mass, center of mass and inertia of a group of `Link`s.
+use std::{
+	collections::HashMap,
+	iter::Sum,
+	ops::{Add, AddAssign},
+};
+
+use nalgebra::{Isometry3, Matrix3, Vector3};
+
+use crate::{
+	cluster_objects::KinematicInterface,
+	kinematics::{link_poses, JointState, KinematicsError},
+	link::Link,
+	link_data::Inertial,
+	transform::Transform,
+	Float,
+};
+
+/// The mass, the center of mass and the inertia of a group of `Link`s, expressed in a reference frame.
+///
+/// The inertia tensor is taken about the center of mass, along the axes of the reference frame.
+/// `MassProperties` in the same reference frame are combined with `+`, which applies the parallel axis theorem.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{dynamics::MassProperties, link_data::Inertial};
+/// # use nalgebra::{Isometry3, Matrix3};
+/// let weight = Inertial {
+///     mass: 1.,
+///     ..Default::default()
+/// };
+///
+/// let combined = MassProperties::from_inertial(&weight, &Isometry3::translation(1., 0., 0.))
+///     + MassProperties::from_inertial(&weight, &Isometry3::translation(-1., 0., 0.));
+///
+/// assert_eq!(combined.mass(), 2.);
+/// assert_eq!(combined.center_of_mass(), (0., 0., 0.));
+/// assert_eq!(combined.inertia(), Matrix3::from_diagonal(&[0., 2., 2.].into()));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct MassProperties {
+	mass: Float,
+	center_of_mass: Vector3<Float>,
+	inertia: Matrix3<Float>,
+}
+
+impl MassProperties {
+	/// Creates the `MassProperties` of an `Inertial`, which belongs to a `Link` with the specified `pose` in the reference frame.
+	///
+	/// The inertia tensor is rotated by the `transform` of the `Inertial` and the `pose`.
+	pub fn from_inertial(inertial: &Inertial, pose: &Isometry3<Float>) -> Self {
+		let pose = pose
+			* inertial
+				.transform
+				.map(Transform::to_isometry)
+				.unwrap_or_else(Isometry3::identity);
+		let rotation = pose.rotation.to_rotation_matrix();
+
+		Self {
+			mass: inertial.mass,
+			center_of_mass: pose.translation.vector,
+			inertia: rotation * inertial.inertia_matrix() * rotation.transpose(),
+		}
+	}
+
+	/// Gets the total mass.
+	pub fn mass(&self) -> Float {
+		self.mass
+	}
+
+	/// Gets the center of mass in the reference frame.
+	///
+	/// This is the origin of the reference frame if the mass is zero.
+	pub fn center_of_mass(&self) -> (Float, Float, Float) {
+		(
+			self.center_of_mass.x,
+			self.center_of_mass.y,
+			self.center_of_mass.z,
+		)
+	}
+
+	/// Gets the inertia tensor about the center of mass, along the axes of the reference frame.
+	pub fn inertia(&self) -> Matrix3<Float> {
+		self.inertia
+	}
+
+	/// Gets the inertia tensor about the specified `point` in the reference frame, along the axes of the reference frame.
+	///
+	/// The tensor is shifted from the center of mass with the parallel axis theorem.
+	/// The inertia about the origin of the reference frame is found with the `point` `(0., 0., 0.)`.
+	pub fn inertia_about(&self, point: (Float, Float, Float)) -> Matrix3<Float> {
+		let (x, y, z) = point;
+
+		self.inertia + parallel_axis_shift(self.mass, self.center_of_mass - Vector3::new(x, y, z))
+	}
+
+	/// Converts the `MassProperties` to an `Inertial` with its origin at the center of mass, for a `Link` at the origin of the reference frame.
+	pub fn to_inertial(&self) -> Inertial {
+		let (x, y, z) = self.center_of_mass();
+
+		Inertial::from_inertia_matrix(
+			(self.center_of_mass != Vector3::zeros()).then(|| Transform::new_translation(x, y, z)),
+			self.mass,
+			&self.inertia,
+		)
+	}
+}
+
+/// Gets the inertia tensor of a point mass at the `offset` from the reference point.
+fn parallel_axis_shift(mass: Float, offset: Vector3<Float>) -> Matrix3<Float> {
+	(Matrix3::identity() * offset.norm_squared() - offset * offset.transpose()) * mass
+}
+
+impl Add for MassProperties {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		let mass = self.mass + rhs.mass;
+		let center_of_mass = match mass > 0. {
+			true => (self.center_of_mass * self.mass + rhs.center_of_mass * rhs.mass) / mass,
+			false => Vector3::zeros(),
+		};
+
+		Self {
+			mass,
+			center_of_mass,
+			inertia: self.inertia
+				+ parallel_axis_shift(self.mass, self.center_of_mass - center_of_mass)
+				+ rhs.inertia
+				+ parallel_axis_shift(rhs.mass, rhs.center_of_mass - center_of_mass),
+		}
+	}
+}
+
+impl AddAssign for MassProperties {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+
+impl Sum for MassProperties {
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+		iter.fold(Self::default(), Add::add)
+	}
+}
+
+/// Calculates the `MassProperties` of the `Link` named `subtree_root` and all its descendants,
+/// in the frame of the `Link` named `frame`, with the `Joint`s at the positions in the `joint_state`.
+pub(crate) fn subtree_mass_properties(
+	tree: &impl KinematicInterface,
+	subtree_root: &str,
+	frame: &str,
+	joint_state: &JointState,
+) -> Result<MassProperties, KinematicsError> {
+	fn add_link(
+		link: &Link,
+		poses: &HashMap<String, Isometry3<Float>>,
+		frame_pose: &Isometry3<Float>,
+		mass_properties: &mut MassProperties,
+	) {
+		if let Some(inertial) = link.inertial() {
+			*mass_properties +=
+				MassProperties::from_inertial(inertial, &(frame_pose * poses[link.name()]));
+		}
+
+		for joint in link.joints() {
+			add_link(
+				&joint.read().unwrap().child_link().read().unwrap(),
+				poses,
+				frame_pose,
+				mass_properties,
+			);
+		}
+	}
+
+	let root = tree
+		.get_link(subtree_root)
+		.ok_or_else(|| KinematicsError::InvalidLink(subtree_root.to_owned()))?;
+
+	let poses = link_poses(tree, joint_state);
+	let frame_pose = poses
+		.get(frame)
+		.ok_or_else(|| KinematicsError::InvalidLink(frame.to_owned()))?
+		.inverse();
+
+	let mut mass_properties = MassProperties::default();
+	add_link(
+		&root.read().unwrap(),
+		&poses,
+		&frame_pose,
+		&mut mass_properties,
+	);
+
+	Ok(mass_properties)
+}
+
+#[cfg(test)]
+mod tests {
+	use nalgebra::{Isometry3, Matrix3};
+
+	use super::MassProperties;
+	use crate::{
+		float::consts::FRAC_PI_2,
+		kinematics::{JointState, KinematicsError},
+		link_data::Inertial,
+		prelude::*,
+		Float, JointBuilder, JointType, Link, Robot, SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	/// Checks if the matrices are equal within a small tolerance.
+	fn assert_matrix(matrix: Matrix3<Float>, expected: Matrix3<Float>) {
+		assert!(
+			(matrix - expected).amax() < 1e-5,
+			"{matrix} is not equal to {expected}"
+		);
+	}
+
+	/// Checks if the points are equal within a small tolerance.
+	fn assert_point(point: (Float, Float, Float), expected: (Float, Float, Float)) {
+		assert!(
+			(point.0 - expected.0).abs() < 1e-5
+				&& (point.1 - expected.1).abs() < 1e-5
+				&& (point.2 - expected.2).abs() < 1e-5,
+			"{point:?} is not equal to {expected:?}"
+		);
+	}
+
+	fn inertial(mass: Float, transform: Option<Transform>) -> Inertial {
+		Inertial {
+			transform,
+			mass,
+			ixx: 1.,
+			iyy: 2.,
+			izz: 3.,
+			..Default::default()
+		}
+	}
+
+	/// A base of 4 kg with an arm of 1 kg, which has a hand of 1 kg at its end.
+	fn robot() -> Robot {
+		let robot = Link::builder("base")
+			.add_intertial(inertial(4., None))
+			.build_tree()
+			.to_robot("arm");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_continuous("shoulder")
+					.with_axis((0., 0., 1.))
+					.add_transform(Transform::new_translation(0., 0., 1.)),
+				Link::builder("arm")
+					.add_intertial(inertial(1., Some(Transform::new_translation(0.5, 0., 0.)))),
+			)
+			.unwrap();
+		robot
+			.get_link("arm")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("wrist", JointType::Fixed).add_origin_offset((1., 0., 0.)),
+				Link::builder("hand").add_intertial(inertial(1., None)),
+			)
+			.unwrap();
+
+		robot
+	}
+
+	#[test]
+	fn from_inertial() {
+		let mass_properties = MassProperties::from_inertial(
+			&inertial(2., Some(Transform::new((0., 1., 0.), (0., 0., FRAC_PI_2)))),
+			&Isometry3::translation(1., 0., 0.),
+		);
+
+		assert_eq!(mass_properties.mass(), 2.);
+		assert_point(mass_properties.center_of_mass(), (1., 1., 0.));
+		// The x-axis and the y-axis of the `Inertial` are swapped by the rotation.
+		assert_matrix(
+			mass_properties.inertia(),
+			Matrix3::from_diagonal(&[2., 1., 3.].into()),
+		);
+		assert_matrix(
+			mass_properties.inertia_about((0., 0., 0.)),
+			Matrix3::new(4., -2., 0., -2., 3., 0., 0., 0., 7.),
+		);
+	}
+
+	#[test]
+	fn add() {
+		let left =
+			MassProperties::from_inertial(&inertial(1., None), &Isometry3::translation(0., 1., 0.));
+		let right = MassProperties::from_inertial(
+			&inertial(3., None),
+			&Isometry3::translation(0., -1., 0.),
+		);
+
+		let combined = left + right;
+		assert_eq!(combined.mass(), 4.);
+		assert_point(combined.center_of_mass(), (0., -0.5, 0.));
+		// The parts are 1.5 m and 0.5 m from the center of mass, which adds 1 × 1.5² + 3 × 0.5² = 3 kg m² around the x-axis and the z-axis.
+		assert_matrix(
+			combined.inertia(),
+			Matrix3::from_diagonal(&[2. + 3., 4., 6. + 3.].into()),
+		);
+
+		assert_eq!([left, right].into_iter().sum::<MassProperties>(), combined);
+		assert_eq!(MassProperties::default() + left, left);
+	}
+
+	#[test]
+	fn to_inertial() {
+		let mass_properties =
+			MassProperties::from_inertial(&inertial(2., None), &Isometry3::translation(1., 2., 3.));
+
+		assert_eq!(
+			mass_properties.to_inertial(),
+			inertial(2., Some(Transform::new_translation(1., 2., 3.)))
+		);
+		assert_eq!(
+			MassProperties::from_inertial(&inertial(2., None), &Isometry3::identity())
+				.to_inertial(),
+			inertial(2., None)
+		);
+	}
+
+	#[test]
+	fn total_mass() {
+		assert_eq!(robot().total_mass(), 6.);
+		assert_eq!(Link::builder("empty").build_tree().total_mass(), 0.);
+	}
+
+	#[test]
+	fn center_of_mass() {
+		let robot = robot();
+
+		assert_point(
+			robot.center_of_mass(&JointState::new()).unwrap(),
+			(1.5 / 6., 0., 2. / 6.),
+		);
+		assert_point(
+			robot
+				.center_of_mass(&JointState::new().with_position("shoulder", FRAC_PI_2))
+				.unwrap(),
+			(0., 1.5 / 6., 2. / 6.),
+		);
+
+		assert_eq!(
+			Link::builder("empty")
+				.build_tree()
+				.center_of_mass(&JointState::new()),
+			None
+		);
+	}
+
+	#[test]
+	fn subtree() {
+		let robot = robot();
+		let joint_state = JointState::new().with_position("shoulder", FRAC_PI_2);
+
+		let arm = robot.mass_properties("arm", "arm", &joint_state).unwrap();
+		assert_eq!(arm.mass(), 2.);
+		assert_point(arm.center_of_mass(), (0.75, 0., 0.));
+		// Both parts are 0.25 m from the center of mass.
+		assert_matrix(
+			arm.inertia(),
+			Matrix3::from_diagonal(&[2., 4. + 0.125, 6. + 0.125].into()),
+		);
+
+		// In the frame of the base, the arm is rotated by the shoulder.
+		let arm = robot.mass_properties("arm", "base", &joint_state).unwrap();
+		assert_point(arm.center_of_mass(), (0., 0.75, 1.));
+		assert_matrix(
+			arm.inertia(),
+			Matrix3::from_diagonal(&[4. + 0.125, 2., 6. + 0.125].into()),
+		);
+
+		assert_eq!(
+			robot
+				.mass_properties("base", "base", &joint_state)
+				.unwrap()
+				.mass(),
+			robot.total_mass()
+		);
+
+		assert_eq!(
+			robot.mass_properties("claw", "base", &joint_state),
+			Err(KinematicsError::InvalidLink("claw".into()))
+		);
+		assert_eq!(
+			robot.mass_properties("arm", "claw", &joint_state),
+			Err(KinematicsError::InvalidLink("claw".into()))
+		);
+	}
+}
@@ -0,0 +1,36 @@
+//! The floating point type, which is used for all numeric data of the descriptions.
+//!
+//! By default this is [`f32`], with the `f64` feature it becomes [`f64`].
+
+/// The precision selected by the `f64` feature.
+#[cfg(not(feature = "f64"))]
+type Precision = f32;
+#[cfg(feature = "f64")]
+type Precision = f64;
+
+/// The floating point type used for all numeric data, like [`Transform`](crate::Transform)s, inertia, limits and geometry sizes.
+///
+/// This is [`f32`] by default and [`f64`] when the `f64` feature is enabled.
+/// Double precision prevents the rounding noise of single precision in exported descriptions,
+/// like `0.1` becoming `0.100000001` when read back as double precision, and the accumulation of errors in long chains.
+pub type Float = Precision;
+
+/// The mathematical constants of [`Float`].
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts;
+/// The mathematical constants of [`Float`].
+#[cfg(feature = "f64")]
+pub use std::f64::consts;
+
+/// Converts a value, which was calculated with double precision, to a [`Float`].
+///
+/// With double precision the value is kept as is.
+/// With single precision the value is rounded to 15 significant digits before the cast,
+/// so the rounding noise of the calculation can not tip the cast to a neighbouring [`f32`].
+#[cfg(any(feature = "sdf", feature = "mjcf"))]
+pub(crate) fn from_calculated(value: f64) -> Float {
+	#[cfg(not(feature = "f64"))]
+	return format!("{value:.14e}").parse::<f64>().unwrap_or(value) as Float;
+	#[cfg(feature = "f64")]
+	return value;
+}
@@ -0,0 +1,172 @@
+//! The infrastructure to read a `Robot` from a robot description format.
+//!
+//! This is the counterpart of [`to_rdf`](crate::to_rdf). Every format has its own module and feature:
+//! - [`from_urdf`] reads the Universal Robot Description Format (`urdf` feature).
+//! - [`from_sdf`] reads the `<model>` of a SDFormat description (`sdf` feature).
+//! - [`from_mjcf`] reads MuJoCo models (`mjcf` feature).
+//! - [`from_xacro`] expands xacro descriptions and reads them as URDF (`xacro` feature).
+//!
+//! Each reader can build a [`Robot`](crate::cluster_objects::Robot) or a [`Chained<LinkBuilder>`](crate::chained::Chained),
+//! which can be attached to an existing tree.
+//! Errors report the line of the offending element in the description, malformed XML is reported as a [`XMLReadError`].
+//!
+//! The names in a description are read as they are written,
+//! so the [`GroupID`](crate::identifiers::GroupID) delimiters in them are escaped.
+
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use std::collections::HashMap;
+
+#[cfg(any(feature = "sdf", feature = "mjcf"))]
+use nalgebra::{Isometry3, Translation3, UnitQuaternion};
+#[cfg(feature = "xml")]
+use thiserror::Error;
+
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::{joint::JointBuilder, link::builder::LinkBuilder};
+#[cfg(any(feature = "sdf", feature = "mjcf"))]
+use crate::{transform::Transform, Float};
+
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+pub(crate) mod xml_element;
+
+#[cfg(feature = "mjcf")]
+pub mod from_mjcf;
+#[cfg(feature = "sdf")]
+pub mod from_sdf;
+#[cfg(feature = "urdf")]
+pub mod from_urdf;
+#[cfg(feature = "xacro")]
+pub mod from_xacro;
+
+/// An error, which occurs when the XML of a robot description could not be read.
+///
+/// It contains the `line` at which the problem was found and the underlying [`quick_xml::Error`].
+#[cfg(feature = "xml")]
+#[derive(Debug, Error)]
+#[error("Malformed XML at line {line}: {source}")]
+pub struct XMLReadError {
+	pub(crate) line: usize,
+	#[source]
+	pub(crate) source: quick_xml::Error,
+}
+
+#[cfg(feature = "xml")]
+impl XMLReadError {
+	/// Gets the line number (starting at 1) at which the XML could not be read.
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Gets a reference to the underlying [`quick_xml::Error`].
+	pub fn xml_error(&self) -> &quick_xml::Error {
+		&self.source
+	}
+}
+
+/// A `JointBuilder` with the names of the `Link`s it connects.
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+pub(crate) struct JointDescription {
+	pub builder: JointBuilder,
+	pub parent: String,
+	pub child: String,
+	pub line: usize,
+}
+
+/// Finds the first `JointDescription` which mimics itself, directly or through a cycle of mimicking joints.
+///
+/// Mimics of joints which are not in `joints` end the search, they should be reported separately.
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+pub(crate) fn find_mimic_cycle(joints: &[JointDescription]) -> Option<&JointDescription> {
+	let mimicked_name = |name: &String| {
+		joints
+			.iter()
+			.find(|other| other.builder.name() == name)
+			.and_then(|other| other.builder.mimic())
+			.map(|mimic| &mimic.joint_name)
+	};
+
+	joints.iter().find(|joint| {
+		let mut current = joint.builder.name();
+		// A cycle can not be longer than the number of joints.
+		for _ in 0..joints.len() {
+			match mimicked_name(current) {
+				Some(mimicked_joint) if mimicked_joint == joint.builder.name() => return true,
+				Some(mimicked_joint) => current = mimicked_joint,
+				None => return false,
+			}
+		}
+		false
+	})
+}
+
+/// Takes the `LinkBuilder` with the specified `name` and attaches all its (recursive) child joints to it.
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+pub(crate) fn assemble_link(
+	name: &str,
+	links: &mut HashMap<String, (Option<LinkBuilder>, usize)>,
+	joints: &mut Vec<Option<JointDescription>>,
+) -> LinkBuilder {
+	// Every link has at most one parent, so it is only taken once.
+	let mut link_builder = links
+		.get_mut(name)
+		.and_then(|(link_builder, _)| link_builder.take())
+		.expect("Links should only be taken once");
+
+	let child_joints: Vec<JointDescription> = joints
+		.iter_mut()
+		.filter(|joint| matches!(joint, Some(joint) if joint.parent == name))
+		.filter_map(Option::take)
+		.collect();
+
+	for mut joint in child_joints {
+		joint.builder.child = Some(assemble_link(&joint.child, links, joints));
+		link_builder.joints.push(joint.builder);
+	}
+
+	link_builder
+}
+
+/// Rounds values, which are numerically indistinguishable from zero, to zero.
+///
+/// Since the poses are stored with the precision of [`Float`], anything below [`Float::EPSILON`] is considered noise.
+/// When [`Float`] is [`f64`], the rounding errors of the calculations are a few times larger than the precision, so the margin is increased.
+#[cfg(any(feature = "sdf", feature = "mjcf"))]
+pub(crate) fn remove_noise(value: f64) -> Float {
+	match value.abs() < f64::from(Float::EPSILON).max(4. * f64::EPSILON) {
+		true => 0.,
+		false => crate::float::from_calculated(value),
+	}
+}
+
+/// Converts the `transform` to an [`Isometry3`].
+///
+/// The poses are resolved with double precision, to prevent the accumulation of rounding errors.
+#[cfg(any(feature = "sdf", feature = "mjcf"))]
+pub(crate) fn to_isometry(transform: &Transform) -> Isometry3<f64> {
+	let (x, y, z) = transform.translation.unwrap_or_default();
+	let (roll, pitch, yaw) = transform.rotation.unwrap_or_default();
+
+	Isometry3::from_parts(
+		Translation3::new(x.into(), y.into(), z.into()),
+		UnitQuaternion::from_euler_angles(roll.into(), pitch.into(), yaw.into()),
+	)
+}
+
+/// Converts the `isometry` to a `Transform`, with the numerical noise removed.
+///
+/// An identity `isometry` is converted to `None`.
+#[cfg(any(feature = "sdf", feature = "mjcf"))]
+pub(crate) fn to_transform(isometry: &Isometry3<f64>) -> Option<Transform> {
+	let clean = |(x, y, z): (f64, f64, f64)| {
+		Some([x, y, z].map(remove_noise))
+			.filter(|values| values.iter().any(|value| *value != 0.))
+			.map(|[x, y, z]| (x, y, z))
+	};
+
+	let translation = isometry.translation.vector;
+	Some(Transform {
+		translation: clean((translation.x, translation.y, translation.z)),
+		rotation: clean(isometry.rotation.euler_angles()),
+	})
+	.filter(Transform::contains_some)
+}
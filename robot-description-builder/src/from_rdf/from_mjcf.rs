@@ -0,0 +1,1909 @@
+//! The infrastructure to read a `Robot` from the MuJoCo XML format (MJCF).
+//!
+//! A `<mujoco>` model can be read as a [`Robot`] with [`from_mjcf`], or as a [`Chained<LinkBuilder>`] with [`chain_from_mjcf`].
+//! Both return the [`MJCFWarning`]s for everything in the description, which could not be represented.
+//!
+//! This is the counterpart of [`to_mjcf`](crate::to_rdf::to_mjcf).
+//!
+//! # Bodies
+//! Every `<body>` is read as a `Link`, which is connected to the `Link` of its parent body by its joints:
+//! - A body without joints is connected by a [`Fixed`](crate::JointType::Fixed) joint named `{parent}_to_{body}`.
+//! - `hinge` joints are read as [`Revolute`](crate::JointType::Revolute) joints when they are limited,
+//!   and as [`Continuous`](crate::JointType::Continuous) joints otherwise.
+//! - `slide` joints are read as [`Prismatic`](crate::JointType::Prismatic) joints.
+//! - `free` joints (and `<freejoint>`) are read as [`Floating`](crate::JointType::Floating) joints.
+//! - `ball` joints are read as three [`Continuous`](crate::JointType::Continuous) joints
+//!   around the X, Y and Z axes, named `{joint}_x`, `{joint}_y` and `{joint}_z`.
+//!
+//! A body with multiple (single-DOF) joints is expanded into a chain of joints.
+//! Every joint, except the last one, gets a generated massless link named `{joint}_link` as its child.
+//! The `Link` of a body shares the frame of its last joint, so when that joint has a `pos`,
+//! the geoms, inertial and child bodies of the body are expressed relative to the joint instead.
+//!
+//! Unnamed bodies are named `{parent}_body_{index}` and unnamed joints are named `{body}_joint_{index}`.
+//!
+//! # Root
+//! When the `<worldbody>` contains a single body without joints and no geoms, that body is the root `Link`.
+//! Its pose is not kept, since it places the model in the world.
+//! Otherwise the root is a `Link` named `world`, which contains the geoms of the `<worldbody>`.
+//!
+//! # Geoms
+//! A `<geom>` which collides with other geoms (`contype` or `conaffinity` not `0`) is read as a `Collision`.
+//! A `<geom>` which is visible by default (`group` below `3`) is read as a `Visual`.
+//! The `rgba` of a geom or its `<material>` is used as the color of the `Visual`.
+//!
+//! # Limitations
+//! The following is reported as a [`MJCFWarning`]:
+//! - Elements which can not be represented (e.g. `<actuator>`, `<sensor>`, `<site>` or `<include>`) are ignored.
+//! - Attributes which can not be represented (e.g. `armature`, `friction` or `solref`) are ignored.
+//! - Geoms with an unsupported type (e.g. `plane` or `capsule`) are ignored.
+//! - `ball` joints are approximated by three continuous joints, their range is not kept.
+//! - Joint equality constraints which are not linear, or do not connect two `hinge` or `slide` joints, are ignored.
+//! - Only the first joint equality constraint of a joint is read as its mimic, the others are ignored.
+//! - The `velocity` limit of limited joints, and their `effort` limit without `actuatorfrcrange`, are set to [`UNKNOWN_LIMIT`].
+//!
+//! The bounding box of a [`MeshGeometry`](crate::link_data::geometry::MeshGeometry) can not be determined from the description and is set to `(0, 0, 0)`.
+//! The inertia of bodies without an `<inertial>` is not inferred from their geoms.
+use std::collections::HashMap;
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+use thiserror::Error;
+
+use super::{
+	assemble_link, find_mimic_cycle, to_isometry, to_transform, xml_element::XMLElement,
+	JointDescription, XMLReadError,
+};
+use crate::{
+	chained::Chained,
+	cluster_objects::Robot,
+	identifiers::{escape_group_id_delimiters, GroupID},
+	joint::{joint_data::MimicBuilderData, JointBuilder, JointType},
+	link::builder::{CollisionBuilder, LinkBuilder, VisualBuilder},
+	link_data::{geometry::GeometryInterface, Inertial},
+	material::MaterialDescriptor,
+	transform::Transform,
+	Float,
+};
+
+/// The name of the default class, which applies when no other class is specified.
+const MAIN_CLASS: &str = "main";
+/// The name of the root `Link`, when the `<worldbody>` is read as a `Link`.
+const WORLD_LINK: &str = "world";
+/// The name MuJoCo uses for a model without a `model` attribute.
+const DEFAULT_MODEL_NAME: &str = "MuJoCo Model";
+/// The lowest `group` of geoms, which are hidden by default.
+const HIDDEN_GEOM_GROUP: i32 = 3;
+
+/// The attributes of a `<body>`, which are read.
+const BODY_ATTRIBUTES: &[&str] = &[
+	"name",
+	"childclass",
+	"pos",
+	"quat",
+	"axisangle",
+	"euler",
+	"xyaxes",
+	"zaxis",
+];
+/// The attributes of a `<joint>`, which are read.
+const JOINT_ATTRIBUTES: &[&str] = &[
+	"name",
+	"class",
+	"type",
+	"group",
+	"pos",
+	"axis",
+	"range",
+	"limited",
+	"damping",
+	"frictionloss",
+	"actuatorfrcrange",
+];
+/// The attributes of a `<geom>`, which are read.
+const GEOM_ATTRIBUTES: &[&str] = &[
+	"name",
+	"class",
+	"type",
+	"group",
+	"contype",
+	"conaffinity",
+	"density",
+	"size",
+	"fromto",
+	"pos",
+	"quat",
+	"axisangle",
+	"euler",
+	"xyaxes",
+	"zaxis",
+	"mesh",
+	"material",
+	"rgba",
+];
+
+/// An error, which can occur while reading a MJCF description.
+///
+/// Most variants contain the `line` of the offending element to make it easy to find the problem.
+#[derive(Debug, Error)]
+pub enum FromMJCFError {
+	/// The description is not valid XML.
+	#[error(transparent)]
+	XML(#[from] XMLReadError),
+	/// The root element of the description is not a `<mujoco>` element.
+	#[error("Expected the root element to be <mujoco>, found <{element}> at line {line}")]
+	NotMJCF { element: String, line: usize },
+	/// A required attribute is missing.
+	#[error("<{element}> at line {line} is missing the required attribute '{attribute}'")]
+	MissingAttribute {
+		element: String,
+		attribute: String,
+		line: usize,
+	},
+	/// The value of an attribute could not be interpreted.
+	#[error("<{element}> at line {line} has an invalid value \"{value}\" for the attribute '{attribute}'")]
+	InvalidValue {
+		element: String,
+		attribute: String,
+		value: String,
+		line: usize,
+	},
+	/// The orientation of an element is specified in multiple ways.
+	#[error("<{element}> at line {line} specifies its orientation more than once")]
+	MultipleOrientations { element: String, line: usize },
+	/// Multiple bodies, joints, classes or assets have the same name.
+	#[error("The name '{name}' at line {line} is already in use")]
+	DuplicateName { name: String, line: usize },
+	/// An element refers to a default class which does not exist.
+	#[error("<{element}> at line {line} refers to the unknown class '{class}'")]
+	UnknownClass {
+		element: String,
+		class: String,
+		line: usize,
+	},
+	/// A `<geom>` refers to a `<mesh>` or `<material>` asset which does not exist.
+	#[error("<{element}> at line {line} refers to the unknown asset '{asset}'")]
+	UnknownAsset {
+		element: String,
+		asset: String,
+		line: usize,
+	},
+	/// An equality constraint refers to a joint which does not exist.
+	#[error("The equality constraint at line {line} refers to the unknown joint '{joint}'")]
+	UnknownJoint { joint: String, line: usize },
+	/// An equality constraint makes a joint mimic itself, directly or through a cycle of mimicking joints.
+	#[error("The equality constraint at line {line} makes the joint '{joint}' mimic itself through a cycle of mimics")]
+	MimicCycle { joint: String, line: usize },
+}
+
+/// A construct of a MJCF description, which can not be represented and is therefore not kept.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+#[non_exhaustive]
+pub enum MJCFWarning {
+	/// The element is not supported and is ignored.
+	#[error("<{element}> at line {line} can not be represented and is ignored")]
+	UnsupportedElement { element: String, line: usize },
+	/// The attribute is not supported and is ignored.
+	#[error("The attribute '{attribute}' of <{element}> at line {line} can not be represented and is ignored")]
+	UnsupportedAttribute {
+		element: String,
+		attribute: String,
+		line: usize,
+	},
+	/// The geom type is not supported, the `<geom>` is ignored.
+	#[error("The geom type '{geom_type}' at line {line} is not supported, the geom is ignored")]
+	UnsupportedGeometry { geom_type: String, line: usize },
+	/// The `ball` joint is read as three continuous joints.
+	#[error("The ball joint '{joint}' at line {line} is read as three continuous joints, its range is not kept")]
+	ApproximatedJoint { joint: String, line: usize },
+	/// The joint equality constraint can not be represented as a mimic.
+	///
+	/// This is also the case for every equality constraint after the first one of the same `joint1`.
+	#[error("The equality constraint of the joint '{joint}' at line {line} can not be represented as a mimic and is ignored")]
+	UnsupportedMimic { joint: String, line: usize },
+	/// The `velocity` or `effort` limit of the limited joint is not specified and is set to [`UNKNOWN_LIMIT`].
+	#[error("The {limit} limit of the joint '{joint}' at line {line} is not specified and is set to the maximum")]
+	UnknownLimit {
+		joint: String,
+		limit: &'static str,
+		line: usize,
+	},
+}
+
+/// The `velocity` and `effort` of the limit of a limited joint, when MJCF does not specify them.
+///
+/// MJCF has no velocity limits and only specifies an effort limit with `actuatorfrcrange`,
+/// so these are unbounded by default. This is represented by the largest [`Float`](crate::Float).
+pub const UNKNOWN_LIMIT: crate::Float = crate::Float::MAX;
+
+/// The state which is shared while reading the elements of a MJCF description.
+#[derive(Debug)]
+pub(crate) struct FromMJCFContext {
+	/// The warnings reported so far.
+	pub warnings: Vec<MJCFWarning>,
+	/// Whether angles are specified in degrees, which is the MuJoCo default.
+	pub degrees: bool,
+	/// The sequence of the rotation axes of `euler` orientations.
+	///
+	/// Lower case axes rotate with the frame, upper case axes are fixed.
+	pub eulerseq: [char; 3],
+	/// Whether joints with a `range` are limited when `limited` is not specified.
+	pub autolimits: bool,
+	/// The directory mesh files are relative to.
+	pub meshdir: Option<String>,
+	/// The attributes of every element type, by the name of their default class.
+	defaults: HashMap<String, HashMap<String, Vec<(String, String)>>>,
+	/// The `<mesh>` assets with their defaults applied, by name.
+	pub meshes: HashMap<String, XMLElement>,
+	/// The `<material>` assets with their defaults applied, by name.
+	pub materials: HashMap<String, XMLElement>,
+}
+
+impl Default for FromMJCFContext {
+	fn default() -> Self {
+		Self {
+			warnings: Vec::new(),
+			degrees: true,
+			eulerseq: ['x', 'y', 'z'],
+			autolimits: true,
+			meshdir: None,
+			defaults: HashMap::from([(String::from(MAIN_CLASS), HashMap::new())]),
+			meshes: HashMap::new(),
+			materials: HashMap::new(),
+		}
+	}
+}
+
+impl FromMJCFContext {
+	/// Reports the `warning`.
+	pub fn warn(&mut self, warning: MJCFWarning) {
+		#[cfg(any(feature = "logging", test))]
+		log::warn!("{warning}");
+
+		self.warnings.push(warning);
+	}
+
+	/// Reports all child elements of the `element`, which are not in the `supported` list.
+	pub fn warn_unsupported(&mut self, element: &XMLElement, supported: &[&str]) {
+		for child in element
+			.children
+			.iter()
+			.filter(|child| !supported.contains(&child.name.as_str()))
+		{
+			self.warn(MJCFWarning::UnsupportedElement {
+				element: child.name.clone(),
+				line: child.line,
+			});
+		}
+	}
+
+	/// Reports all attributes of the `element`, which are not in the `supported` list.
+	pub fn warn_unsupported_attributes(&mut self, element: &XMLElement, supported: &[&str]) {
+		for (attribute, _) in element
+			.attributes
+			.iter()
+			.filter(|(attribute, _)| !supported.contains(&attribute.as_str()))
+		{
+			self.warn(MJCFWarning::UnsupportedAttribute {
+				element: element.name.clone(),
+				attribute: attribute.clone(),
+				line: element.line,
+			});
+		}
+	}
+
+	/// Converts the `angle` to radians, according to the `angle` setting of the `<compiler>`.
+	pub fn angle(&self, angle: Float) -> f64 {
+		match self.degrees {
+			true => f64::from(angle).to_radians(),
+			false => angle.into(),
+		}
+	}
+
+	/// Checks that the default `class` exists.
+	///
+	/// The `element` is used for error reporting.
+	fn check_class(&self, element: &XMLElement, class: &str) -> Result<(), FromMJCFError> {
+		match self.defaults.contains_key(class) {
+			true => Ok(()),
+			false => Err(FromMJCFError::UnknownClass {
+				element: element.name.clone(),
+				class: class.to_string(),
+				line: element.line,
+			}),
+		}
+	}
+
+	/// Gets a copy of the `element` with the attributes of its default class added.
+	///
+	/// The class is the `class` attribute of the `element`, or the `childclass` of the enclosing bodies otherwise.
+	pub fn resolve(
+		&self,
+		element: &XMLElement,
+		childclass: &str,
+	) -> Result<XMLElement, FromMJCFError> {
+		let class = element.attribute("class").unwrap_or(childclass);
+		self.check_class(element, class)?;
+
+		let mut resolved = element.clone();
+		if let Some(defaults) = self.defaults[class].get(&element.name) {
+			for (key, value) in defaults {
+				if resolved.attribute(key).is_none() {
+					resolved.attributes.push((key.clone(), value.clone()));
+				}
+			}
+		}
+
+		Ok(resolved)
+	}
+
+	/// Reads the settings of a `<compiler>` element.
+	fn read_compiler(&mut self, element: &XMLElement) -> Result<(), FromMJCFError> {
+		self.warn_unsupported_attributes(
+			element,
+			&["angle", "eulerseq", "autolimits", "meshdir", "assetdir"],
+		);
+
+		if let Some(angle) = element.attribute("angle") {
+			self.degrees = match angle {
+				"degree" => true,
+				"radian" => false,
+				_ => return Err(invalid_value(element, "angle")),
+			};
+		}
+
+		if let Some(eulerseq) = element.attribute("eulerseq") {
+			let axes: Vec<char> = eulerseq.chars().collect();
+			self.eulerseq = match axes.as_slice() {
+				[first, second, third] if axes.iter().all(|axis| "xyzXYZ".contains(*axis)) => {
+					[*first, *second, *third]
+				}
+				_ => return Err(invalid_value(element, "eulerseq")),
+			};
+		}
+
+		if let Some(autolimits) = optional_bool(element, "autolimits")? {
+			self.autolimits = autolimits;
+		}
+
+		// The `meshdir` takes precedence over the `assetdir`.
+		if let Some(directory) = element
+			.attribute("meshdir")
+			.or_else(|| element.attribute("assetdir"))
+		{
+			self.meshdir = Some(directory.trim_end_matches('/').to_string());
+		}
+
+		Ok(())
+	}
+
+	/// Reads a `<default>` element and its nested classes.
+	///
+	/// The `parent` is the name of the enclosing class, which is `None` for the top level `<default>`.
+	fn read_defaults(
+		&mut self,
+		element: &XMLElement,
+		parent: Option<&str>,
+	) -> Result<(), FromMJCFError> {
+		let class = match parent {
+			Some(_) => required_attribute(element, "class")?,
+			None => element.attribute("class").unwrap_or(MAIN_CLASS),
+		};
+
+		if parent.is_some() && self.defaults.contains_key(class) {
+			return Err(FromMJCFError::DuplicateName {
+				name: class.to_string(),
+				line: element.line,
+			});
+		}
+
+		let mut defaults = parent
+			.map(|parent| self.defaults[parent].clone())
+			.unwrap_or_default();
+
+		for child in element
+			.children
+			.iter()
+			.filter(|child| child.name != "default")
+		{
+			match child.name.as_str() {
+				"joint" => self.warn_unsupported_attributes(child, JOINT_ATTRIBUTES),
+				"geom" => self.warn_unsupported_attributes(child, GEOM_ATTRIBUTES),
+				_ => (),
+			}
+
+			let attributes = defaults.entry(child.name.clone()).or_default();
+			for (key, value) in child.attributes.iter() {
+				match attributes.iter_mut().find(|(other, _)| other == key) {
+					Some((_, old_value)) => *old_value = value.clone(),
+					None => attributes.push((key.clone(), value.clone())),
+				}
+			}
+		}
+
+		self.defaults.insert(class.to_string(), defaults);
+
+		for child in element.children_named("default") {
+			self.read_defaults(child, Some(class))?;
+		}
+
+		Ok(())
+	}
+
+	/// Reads the `<mesh>` and `<material>` elements of an `<asset>` element.
+	fn read_assets(&mut self, element: &XMLElement) -> Result<(), FromMJCFError> {
+		self.warn_unsupported(element, &["mesh", "material"]);
+
+		for mesh in element.children_named("mesh") {
+			self.warn_unsupported_attributes(mesh, &["name", "class", "file", "scale"]);
+			let mesh = self.resolve(mesh, MAIN_CLASS)?;
+
+			// A mesh without a name is named after its file.
+			let name = match (mesh.attribute("name"), mesh.attribute("file")) {
+				(Some(name), _) => name.to_string(),
+				(None, Some(file)) => std::path::Path::new(file)
+					.file_stem()
+					.map(|stem| stem.to_string_lossy().into_owned())
+					.unwrap_or_default(),
+				(None, None) => required_attribute(&mesh, "name")?.to_string(),
+			};
+
+			if self.meshes.contains_key(&name) {
+				return Err(FromMJCFError::DuplicateName {
+					name,
+					line: mesh.line,
+				});
+			}
+			self.meshes.insert(name, mesh);
+		}
+
+		for material in element.children_named("material") {
+			self.warn_unsupported_attributes(material, &["name", "class", "rgba"]);
+			let material = self.resolve(material, MAIN_CLASS)?;
+			let name = required_attribute(&material, "name")?.to_string();
+
+			if self.materials.contains_key(&name) {
+				return Err(FromMJCFError::DuplicateName {
+					name,
+					line: material.line,
+				});
+			}
+			self.materials.insert(name, material);
+		}
+
+		Ok(())
+	}
+}
+
+/// A trait to allow parts of a `Robot` to be read from the MJCF.
+///
+/// This is the counterpart of [`ToMJCF`](crate::to_rdf::to_mjcf::ToMJCF).
+/// The `element` passed to [`from_mjcf`](FromMJCF::from_mjcf) already has the attributes of its default class applied.
+pub(crate) trait FromMJCF: Sized {
+	/// Reads the element from its MJCF representation.
+	fn from_mjcf(
+		element: &XMLElement,
+		mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError>;
+}
+
+/// Gets the value of the required `attribute` of the `element`.
+pub(crate) fn required_attribute<'a>(
+	element: &'a XMLElement,
+	attribute: &str,
+) -> Result<&'a str, FromMJCFError> {
+	element
+		.attribute(attribute)
+		.ok_or_else(|| FromMJCFError::MissingAttribute {
+			element: element.name.clone(),
+			attribute: attribute.to_string(),
+			line: element.line,
+		})
+}
+
+/// Creates a [`FromMJCFError::InvalidValue`] for the `attribute` of the `element`.
+pub(crate) fn invalid_value(element: &XMLElement, attribute: &str) -> FromMJCFError {
+	FromMJCFError::InvalidValue {
+		element: element.name.clone(),
+		attribute: attribute.to_string(),
+		value: element.attribute(attribute).unwrap_or_default().to_string(),
+		line: element.line,
+	}
+}
+
+/// Parses the number in the optional `attribute` of the `element`.
+pub(crate) fn optional_float(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Option<Float>, FromMJCFError> {
+	element
+		.attribute(attribute)
+		.map(|value| {
+			value
+				.trim()
+				.parse()
+				.map_err(|_| invalid_value(element, attribute))
+		})
+		.transpose()
+}
+
+/// Parses the whitespace separated list of numbers in the optional `attribute` of the `element`.
+///
+/// The list needs to contain at least `N` numbers, any further numbers are ignored.
+/// This is how MuJoCo treats the `size` of geoms, which always allows for 3 numbers.
+pub(crate) fn optional_leading_float_array<const N: usize>(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Option<[Float; N]>, FromMJCFError> {
+	let Some(value) = element.attribute(attribute) else {
+		return Ok(None);
+	};
+
+	let mut result = [0.; N];
+	let mut parts = value.split_whitespace();
+	for number in result.iter_mut() {
+		*number = parts
+			.next()
+			.and_then(|part| part.parse().ok())
+			.ok_or_else(|| invalid_value(element, attribute))?;
+	}
+
+	match parts.all(|part| part.parse::<Float>().is_ok()) {
+		true => Ok(Some(result)),
+		false => Err(invalid_value(element, attribute)),
+	}
+}
+
+/// Parses the whitespace separated list of exactly `N` numbers in the optional `attribute` of the `element`.
+pub(crate) fn optional_float_array<const N: usize>(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Option<[Float; N]>, FromMJCFError> {
+	match element
+		.attribute(attribute)
+		.map(|value| value.split_whitespace().count())
+	{
+		Some(count) if count != N => Err(invalid_value(element, attribute)),
+		_ => optional_leading_float_array(element, attribute),
+	}
+}
+
+/// Parses the required list of at least `N` numbers of the `attribute` of the `element`.
+///
+/// See [`optional_leading_float_array`].
+pub(crate) fn required_leading_float_array<const N: usize>(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<[Float; N], FromMJCFError> {
+	optional_leading_float_array(element, attribute)?.ok_or_else(|| {
+		FromMJCFError::MissingAttribute {
+			element: element.name.clone(),
+			attribute: attribute.to_string(),
+			line: element.line,
+		}
+	})
+}
+
+/// Parses the optional boolean `attribute` of the `element`.
+pub(crate) fn optional_bool(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Option<bool>, FromMJCFError> {
+	element
+		.attribute(attribute)
+		.map(|value| match value {
+			"true" => Ok(true),
+			"false" => Ok(false),
+			_ => Err(invalid_value(element, attribute)),
+		})
+		.transpose()
+}
+
+/// Converts the optional `vector` to a [`Vector3`], which is zero when it is not specified.
+fn to_vector3(vector: Option<[Float; 3]>) -> Vector3<f64> {
+	let [x, y, z] = vector.unwrap_or_default();
+	Vector3::new(x.into(), y.into(), z.into())
+}
+
+/// Expresses the `transform` relative to a frame, which is translated by the `offset`.
+fn shifted(transform: &Transform, offset: &Vector3<f64>) -> Option<Transform> {
+	to_transform(&(Translation3::from(-offset) * to_isometry(transform)))
+}
+
+/// Gets the transform of a cylinder specified by its `fromto` attribute.
+///
+/// The cylinder is placed at the center of the segment, with its Z-axis along the segment.
+fn fromto_transform([x1, y1, z1, x2, y2, z2]: [Float; 6]) -> Transform {
+	let from = Vector3::new(x1.into(), y1.into(), z1.into());
+	let to = Vector3::new(x2.into(), y2.into(), z2.into());
+
+	// A segment along the negative Z-axis is flipped around the X-axis.
+	let rotation =
+		UnitQuaternion::rotation_between(&Vector3::z(), &(to - from)).unwrap_or_else(|| {
+			UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f64::consts::PI)
+		});
+
+	to_transform(&Isometry3::from_parts(
+		Translation3::from((from + to) / 2.),
+		rotation,
+	))
+	.unwrap_or_default()
+}
+
+/// A linear joint equality constraint, which makes `joint1` mimic `joint2`.
+struct JointEquality {
+	joint1: String,
+	joint2: String,
+	multiplier: Float,
+	offset: Float,
+	line: usize,
+}
+
+/// The links and joints read from the bodies of the description.
+#[derive(Default)]
+struct BodyContent {
+	links: HashMap<String, (Option<LinkBuilder>, usize)>,
+	joints: Vec<JointDescription>,
+	/// The names of the joints which are not read as a single `hinge` or `slide` joint.
+	other_joints: Vec<String>,
+}
+
+impl BodyContent {
+	/// Adds the `link_builder`, read from the element at `line`.
+	fn add_link(&mut self, link_builder: LinkBuilder, line: usize) -> Result<(), FromMJCFError> {
+		if self.links.contains_key(link_builder.name()) {
+			return Err(FromMJCFError::DuplicateName {
+				name: link_builder.name().display(),
+				line,
+			});
+		}
+
+		self.links
+			.insert(link_builder.name().clone(), (Some(link_builder), line));
+		Ok(())
+	}
+
+	/// Adds the `joint`, after checking that its name is not in use.
+	fn add_joint(&mut self, joint: JointDescription) -> Result<(), FromMJCFError> {
+		if self
+			.joints
+			.iter()
+			.any(|other| other.builder.name() == joint.builder.name())
+		{
+			return Err(FromMJCFError::DuplicateName {
+				name: joint.builder.name().display(),
+				line: joint.line,
+			});
+		}
+
+		self.joints.push(joint);
+		Ok(())
+	}
+}
+
+/// Gets the `<joint>` or `<freejoint>` of a body with the defaults of the `childclass` applied.
+///
+/// An unnamed joint gets the `default_name`.
+fn resolve_joint(
+	element: &XMLElement,
+	childclass: &str,
+	default_name: String,
+	mjcf_context: &mut FromMJCFContext,
+) -> Result<XMLElement, FromMJCFError> {
+	let mut joint = match element.name.as_str() {
+		"freejoint" => {
+			mjcf_context.warn_unsupported_attributes(element, &["name", "group"]);
+			let mut joint = element.clone();
+			joint
+				.attributes
+				.push((String::from("type"), String::from("free")));
+			joint
+		}
+		_ => {
+			mjcf_context.warn_unsupported_attributes(element, JOINT_ATTRIBUTES);
+			mjcf_context.resolve(element, childclass)?
+		}
+	};
+
+	if joint.attribute("name").is_none() {
+		joint.attributes.push((String::from("name"), default_name));
+	}
+
+	Ok(joint)
+}
+
+/// Reads the `<geom>` as a `Visual` and/or a `Collision`.
+///
+/// The `offset` is the position of the frame of the `Link` in the frame of the body.
+fn read_geom(
+	geom: &XMLElement,
+	childclass: &str,
+	offset: &Vector3<f64>,
+	link_builder: &mut LinkBuilder,
+	mjcf_context: &mut FromMJCFContext,
+) -> Result<(), FromMJCFError> {
+	mjcf_context.warn_unsupported_attributes(geom, GEOM_ATTRIBUTES);
+	let geom = mjcf_context.resolve(geom, childclass)?;
+
+	let fromto = optional_float_array(&geom, "fromto")?;
+	let geom_type = geom.attribute("type").unwrap_or("sphere");
+	if fromto.is_some() && geom_type != "cylinder" {
+		// Only the cylinders are specified by `fromto`, when supported.
+		mjcf_context.warn(MJCFWarning::UnsupportedGeometry {
+			geom_type: geom_type.to_string(),
+			line: geom.line,
+		});
+		return Ok(());
+	}
+
+	let Some(geometry) =
+		Option::<Box<dyn GeometryInterface + Sync + Send>>::from_mjcf(&geom, mjcf_context)?
+	else {
+		return Ok(());
+	};
+
+	let transform = match fromto {
+		Some(fromto) => fromto_transform(fromto),
+		None => Transform::from_mjcf(&geom, mjcf_context)?,
+	};
+	let transform = shifted(&transform, offset);
+	let name = geom.attribute("name").map(escape_group_id_delimiters);
+
+	let collides = optional_float(&geom, "contype")?.unwrap_or(1.) != 0.
+		|| optional_float(&geom, "conaffinity")?.unwrap_or(1.) != 0.;
+	let visible = geom
+		.attribute("group")
+		.map(|group| group.trim().parse::<i32>())
+		.transpose()
+		.map_err(|_| invalid_value(&geom, "group"))?
+		.unwrap_or_default()
+		< HIDDEN_GEOM_GROUP;
+
+	if collides {
+		link_builder.colliders.push(CollisionBuilder::new_full(
+			name.clone(),
+			transform,
+			geometry.boxed_clone(),
+		));
+	}
+
+	if visible {
+		link_builder.visuals.push(VisualBuilder::new_full(
+			name,
+			transform,
+			geometry,
+			Option::<MaterialDescriptor>::from_mjcf(&geom, mjcf_context)?,
+		));
+	}
+
+	Ok(())
+}
+
+/// Reads the `body` and its child bodies.
+///
+/// The `parent` contains the (MJCF) name of the parent body, and the offset of the frame of its `Link` in the frame of the body.
+/// It is `None` for the root body, which is not connected by a joint.
+/// The `index` of the body among the bodies of its parent is used to name unnamed bodies.
+fn read_body(
+	body: &XMLElement,
+	parent: Option<(&str, &Vector3<f64>)>,
+	childclass: &str,
+	index: usize,
+	content: &mut BodyContent,
+	mjcf_context: &mut FromMJCFContext,
+) -> Result<(), FromMJCFError> {
+	mjcf_context.warn_unsupported_attributes(body, BODY_ATTRIBUTES);
+	mjcf_context.warn_unsupported(body, &["inertial", "joint", "freejoint", "geom", "body"]);
+
+	let childclass = body.attribute("childclass").unwrap_or(childclass);
+	mjcf_context.check_class(body, childclass)?;
+
+	let body_name = match body.attribute("name") {
+		Some(name) => name.to_string(),
+		None => format!(
+			"{}_body_{index}",
+			parent.map_or(WORLD_LINK, |(parent, _)| parent)
+		),
+	};
+	let link_name = escape_group_id_delimiters(&body_name);
+	if content.links.contains_key(&link_name) {
+		return Err(FromMJCFError::DuplicateName {
+			name: body_name,
+			line: body.line,
+		});
+	}
+
+	// The pose of the body relative to the frame of its parent body.
+	let body_pose = to_isometry(&Transform::from_mjcf(body, mjcf_context)?);
+
+	// The single-DOF joints of the body, with their position in the frame of the body.
+	let mut chain: Vec<(JointBuilder, Vector3<f64>, usize)> = Vec::new();
+	for (joint_index, element) in body
+		.children
+		.iter()
+		.filter(|child| child.name == "joint" || child.name == "freejoint")
+		.enumerate()
+	{
+		let joint = resolve_joint(
+			element,
+			childclass,
+			format!("{body_name}_joint_{joint_index}"),
+			mjcf_context,
+		)?;
+		let position = to_vector3(optional_float_array(&joint, "pos")?);
+
+		match joint.attribute("type").unwrap_or("hinge") {
+			"ball" => {
+				let name = required_attribute(&joint, "name")?;
+				mjcf_context.warn(MJCFWarning::ApproximatedJoint {
+					joint: name.to_string(),
+					line: joint.line,
+				});
+
+				let dynamics = FromMJCF::from_mjcf(&joint, mjcf_context)?;
+				for (suffix, axis) in [
+					("x", (1., 0., 0.)),
+					("y", (0., 1., 0.)),
+					("z", (0., 0., 1.)),
+				] {
+					let mut joint_builder = JointBuilder::new(
+						escape_group_id_delimiters(&format!("{name}_{suffix}")),
+						JointType::Continuous,
+					);
+					joint_builder.with_axis(axis);
+					joint_builder.with_dynamics_data(dynamics);
+
+					content.other_joints.push(joint_builder.name().clone());
+					chain.push((joint_builder, position, joint.line));
+				}
+			}
+			"free" => {
+				let joint_builder = JointBuilder::from_mjcf(&joint, mjcf_context)?;
+				content.other_joints.push(joint_builder.name().clone());
+				// The position of a free joint has no effect.
+				chain.push((joint_builder, Vector3::zeros(), joint.line));
+			}
+			_ => chain.push((
+				JointBuilder::from_mjcf(&joint, mjcf_context)?,
+				position,
+				joint.line,
+			)),
+		}
+	}
+
+	// The `Link` shares the frame of its last joint.
+	let offset = chain
+		.last()
+		.map_or_else(Vector3::zeros, |(_, position, _)| *position);
+
+	if let Some((parent_name, parent_offset)) = parent {
+		if chain.is_empty() {
+			chain.push((
+				JointBuilder::new(
+					escape_group_id_delimiters(&format!("{parent_name}_to_{body_name}")),
+					JointType::Fixed,
+				),
+				Vector3::zeros(),
+				body.line,
+			));
+		}
+
+		// The pose of the body relative to the frame of the `Link` of its parent.
+		let body_pose = Translation3::from(-parent_offset) * body_pose;
+
+		let mut parent_link = escape_group_id_delimiters(parent_name);
+		let mut previous_position = Vector3::zeros();
+		let last_index = chain.len() - 1;
+		for (chain_index, (mut joint_builder, position, line)) in chain.drain(..).enumerate() {
+			let transform = match chain_index {
+				0 => body_pose * Translation3::from(position),
+				_ => Isometry3::from(Translation3::from(position - previous_position)),
+			};
+			if let Some(transform) = to_transform(&transform) {
+				joint_builder.set_transform_simple(transform);
+			}
+			previous_position = position;
+
+			let child_link = match chain_index == last_index {
+				true => link_name.clone(),
+				false => {
+					let dummy_link = LinkBuilder::new(format!("{}_link", joint_builder.name()));
+					let dummy_name = dummy_link.name().clone();
+					content.add_link(dummy_link, line)?;
+					dummy_name
+				}
+			};
+
+			content.add_joint(JointDescription {
+				builder: joint_builder,
+				parent: parent_link,
+				child: child_link.clone(),
+				line,
+			})?;
+			parent_link = child_link;
+		}
+	}
+
+	let mut link_builder = LinkBuilder::new(link_name);
+
+	if let Some(inertial) = body.child("inertial") {
+		let mut inertial = Inertial::from_mjcf(inertial, mjcf_context)?;
+		inertial.transform = shifted(&inertial.transform.unwrap_or_default(), &offset);
+		link_builder.intertial = Some(inertial);
+	}
+
+	for geom in body.children_named("geom") {
+		read_geom(geom, childclass, &offset, &mut link_builder, mjcf_context)?;
+	}
+
+	content.add_link(link_builder, body.line)?;
+
+	for (child_index, child) in body.children_named("body").enumerate() {
+		read_body(
+			child,
+			Some((&body_name, &offset)),
+			childclass,
+			child_index,
+			content,
+			mjcf_context,
+		)?;
+	}
+
+	Ok(())
+}
+
+/// Reads the joint equality constraints of an `<equality>` element.
+fn read_equality(
+	element: &XMLElement,
+	mjcf_context: &mut FromMJCFContext,
+) -> Result<Vec<JointEquality>, FromMJCFError> {
+	mjcf_context.warn_unsupported(element, &["joint"]);
+
+	let mut equalities = Vec::new();
+	for joint in element.children_named("joint") {
+		mjcf_context
+			.warn_unsupported_attributes(joint, &["name", "class", "joint1", "joint2", "polycoef"]);
+		let joint = mjcf_context.resolve(joint, MAIN_CLASS)?;
+		let joint1 = required_attribute(&joint, "joint1")?;
+
+		let polycoef = joint
+			.attribute("polycoef")
+			.map(|polycoef| {
+				polycoef
+					.split_whitespace()
+					.map(str::parse::<Float>)
+					.collect::<Result<Vec<_>, _>>()
+					.map_err(|_| invalid_value(&joint, "polycoef"))
+			})
+			.transpose()?
+			.unwrap_or_else(|| vec![0., 1.]);
+
+		match (joint.attribute("joint2"), polycoef.as_slice()) {
+			(Some(joint2), [offset, multiplier, rest @ ..])
+				if rest.iter().all(|coefficient| *coefficient == 0.) =>
+			{
+				equalities.push(JointEquality {
+					joint1: escape_group_id_delimiters(joint1),
+					joint2: escape_group_id_delimiters(joint2),
+					multiplier: *multiplier,
+					offset: *offset,
+					line: joint.line,
+				})
+			}
+			_ => mjcf_context.warn(MJCFWarning::UnsupportedMimic {
+				joint: joint1.to_string(),
+				line: joint.line,
+			}),
+		}
+	}
+
+	Ok(equalities)
+}
+
+/// The intermediate result of reading a MJCF description.
+struct MJCFDescription {
+	name: String,
+	root: LinkBuilder,
+	warnings: Vec<MJCFWarning>,
+}
+
+/// Reads the [`Robot`] described by the MJCF description `mjcf`.
+///
+/// Returns the `Robot` with the [`MJCFWarning`]s for everything which could not be represented.
+/// See the [module documentation](self) for how the description is interpreted.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{from_rdf::from_mjcf::from_mjcf, JointType, KinematicInterface};
+/// let description = r#"<mujoco model="my-robot">
+///   <default>
+///     <joint damping="0.1"/>
+///   </default>
+///   <worldbody>
+///     <body name="base">
+///       <geom type="box" size="0.5 0.5 0.1"/>
+///       <body name="arm" pos="0 0 0.5">
+///         <joint name="base_to_arm" axis="0 0 1" range="-90 90"/>
+///         <site name="tool"/>
+///       </body>
+///     </body>
+///   </worldbody>
+/// </mujoco>"#;
+///
+/// let (robot, warnings) = from_mjcf(description).unwrap();
+///
+/// assert_eq!(robot.name(), "my-robot");
+/// let joint = robot.get_joint("base_to_arm").unwrap();
+/// assert_eq!(joint.read().unwrap().joint_type(), JointType::Revolute);
+/// assert_eq!(
+///     warnings.first().map(ToString::to_string).as_deref(),
+///     Some("<site> at line 10 can not be represented and is ignored")
+/// );
+/// ```
+pub fn from_mjcf(mjcf: &str) -> Result<(Robot, Vec<MJCFWarning>), FromMJCFError> {
+	let description = read_mjcf(mjcf)?;
+	Ok((
+		description.root.build_tree().to_robot(description.name),
+		description.warnings,
+	))
+}
+
+/// Reads the MJCF description `mjcf` as a [`Chained<LinkBuilder>`], starting at the root link.
+///
+/// This can be used to edit, mirror or attach a described part (e.g. a gripper) to another tree.
+/// The name of the model is not kept, since a [`Chained<LinkBuilder>`] can not contain it.
+///
+/// See [`from_mjcf`] for more information.
+pub fn chain_from_mjcf(
+	mjcf: &str,
+) -> Result<(Chained<LinkBuilder>, Vec<MJCFWarning>), FromMJCFError> {
+	let description = read_mjcf(mjcf)?;
+	Ok((Chained(description.root), description.warnings))
+}
+
+/// Reads the MJCF description and assembles the tree of builders.
+fn read_mjcf(mjcf: &str) -> Result<MJCFDescription, FromMJCFError> {
+	let root = XMLElement::parse(mjcf)?;
+
+	if root.name != "mujoco" {
+		return Err(FromMJCFError::NotMJCF {
+			element: root.name,
+			line: root.line,
+		});
+	}
+
+	let mut mjcf_context = FromMJCFContext::default();
+	mjcf_context.warn_unsupported_attributes(&root, &["model"]);
+	mjcf_context.warn_unsupported(
+		&root,
+		&["compiler", "default", "asset", "worldbody", "equality"],
+	);
+	let name = escape_group_id_delimiters(root.attribute("model").unwrap_or(DEFAULT_MODEL_NAME));
+
+	for compiler in root.children_named("compiler") {
+		mjcf_context.read_compiler(compiler)?;
+	}
+
+	for default in root.children_named("default") {
+		mjcf_context.read_defaults(default, None)?;
+	}
+
+	for asset in root.children_named("asset") {
+		mjcf_context.read_assets(asset)?;
+	}
+
+	let mut equalities = Vec::new();
+	for equality in root.children_named("equality") {
+		equalities.append(&mut read_equality(equality, &mut mjcf_context)?);
+	}
+
+	let empty_worldbody = XMLElement::default();
+	let worldbody = root.child("worldbody").unwrap_or(&empty_worldbody);
+	mjcf_context.warn_unsupported(worldbody, &["geom", "body"]);
+
+	let mut content = BodyContent::default();
+	let bodies: Vec<&XMLElement> = worldbody.children_named("body").collect();
+	let root_name = match bodies.as_slice() {
+		// A single welded body is the root of the model.
+		[body]
+			if worldbody.child("geom").is_none()
+				&& body.child("joint").is_none()
+				&& body.child("freejoint").is_none() =>
+		{
+			read_body(body, None, MAIN_CLASS, 0, &mut content, &mut mjcf_context)?;
+			escape_group_id_delimiters(
+				&body
+					.attribute("name")
+					.map_or_else(|| format!("{WORLD_LINK}_body_0"), str::to_string),
+			)
+		}
+		_ => {
+			let mut world = LinkBuilder::new(WORLD_LINK);
+			for geom in worldbody.children_named("geom") {
+				read_geom(
+					geom,
+					MAIN_CLASS,
+					&Vector3::zeros(),
+					&mut world,
+					&mut mjcf_context,
+				)?;
+			}
+			content.add_link(world, worldbody.line)?;
+
+			for (index, body) in bodies.into_iter().enumerate() {
+				read_body(
+					body,
+					Some((WORLD_LINK, &Vector3::zeros())),
+					MAIN_CLASS,
+					index,
+					&mut content,
+					&mut mjcf_context,
+				)?;
+			}
+			String::from(WORLD_LINK)
+		}
+	};
+
+	// The line of the equality constraint which is read as the mimic of each joint.
+	let mut mimic_lines = HashMap::new();
+	for equality in equalities {
+		let is_known = |joint: &String| {
+			content.other_joints.contains(joint)
+				|| content
+					.joints
+					.iter()
+					.any(|other| other.builder.name() == joint)
+		};
+
+		if let Some(joint) = [&equality.joint1, &equality.joint2]
+			.into_iter()
+			.find(|joint| !is_known(joint))
+		{
+			return Err(FromMJCFError::UnknownJoint {
+				joint: joint.display(),
+				line: equality.line,
+			});
+		}
+
+		let joint1 = content.joints.iter_mut().find(|joint| {
+			joint.builder.name() == &equality.joint1
+				&& matches!(
+					joint.builder.joint_type(),
+					JointType::Revolute | JointType::Continuous | JointType::Prismatic
+				)
+		});
+
+		match joint1 {
+			Some(joint1)
+				if joint1.builder.mimic().is_none()
+					&& !content.other_joints.contains(&equality.joint1)
+					&& !content.other_joints.contains(&equality.joint2) =>
+			{
+				joint1.builder.with_mimic_data(MimicBuilderData {
+					joint_name: equality.joint2,
+					multiplier: Some(equality.multiplier),
+					offset: Some(equality.offset),
+				});
+				mimic_lines.insert(equality.joint1, equality.line);
+			}
+			_ => mjcf_context.warn(MJCFWarning::UnsupportedMimic {
+				joint: equality.joint1.display(),
+				line: equality.line,
+			}),
+		}
+	}
+
+	if let Some(joint) = find_mimic_cycle(&content.joints) {
+		return Err(FromMJCFError::MimicCycle {
+			joint: joint.builder.name().display(),
+			line: mimic_lines[joint.builder.name()],
+		});
+	}
+
+	let mut joints = content.joints.into_iter().map(Some).collect();
+	let root = assemble_link(&root_name, &mut content.links, &mut joints);
+
+	Ok(MJCFDescription {
+		name,
+		root,
+		warnings: mjcf_context.warnings,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::float::consts::FRAC_PI_2;
+
+	use super::{chain_from_mjcf, from_mjcf, FromMJCFError, MJCFWarning, UNKNOWN_LIMIT};
+	use crate::{
+		cluster_objects::KinematicInterface,
+		joint::{joint_data::MimicBuilderData, JointBuilder, JointType},
+		link::builder::LinkBuilder,
+		link_data::geometry::{
+			BoxGeometry, CylinderGeometry, GeometryInterface, MeshGeometry, SphereGeometry,
+		},
+		material::MaterialDescriptor,
+		transform::Transform,
+		Robot,
+	};
+	use test_log::test;
+
+	/// Wraps the `worldbody` content in a MJCF document, preceded by the `header` elements.
+	fn mjcf(header: &str, worldbody: &str) -> String {
+		format!(r#"<mujoco model="test">{header}<worldbody>{worldbody}</worldbody></mujoco>"#)
+	}
+
+	/// Gets the rebuilt `JointBuilder` of the joint named `name`.
+	fn joint(robot: &Robot, name: &str) -> JointBuilder {
+		robot.get_joint(name).unwrap().read().unwrap().rebuild()
+	}
+
+	/// Gets the rebuilt `LinkBuilder` of the link named `name`.
+	fn link(robot: &Robot, name: &str) -> LinkBuilder {
+		robot.get_link(name).unwrap().read().unwrap().rebuild()
+	}
+
+	fn geometry(
+		geometry: impl Into<Box<dyn GeometryInterface + Sync + Send>>,
+	) -> Box<dyn GeometryInterface + Sync + Send> {
+		geometry.into()
+	}
+
+	#[test]
+	fn defaults() {
+		let (robot, warnings) = from_mjcf(&mjcf(
+			r#"<default>
+				<joint damping="0.1" axis="0 1 0"/>
+				<geom type="box" size="0.1 0.2 0.3" contype="0" conaffinity="0"/>
+				<default class="arm">
+					<joint range="-90 90" damping="0.5"/>
+					<default class="finger">
+						<geom type="sphere" size="0.05"/>
+					</default>
+				</default>
+			</default>"#,
+			r#"<body name="base">
+				<geom/>
+				<body name="arm" childclass="arm" pos="0 0 1">
+					<joint name="shoulder"/>
+					<geom class="finger"/>
+					<body name="hand">
+						<joint name="wrist" class="main"/>
+					</body>
+				</body>
+			</body>"#,
+		))
+		.unwrap();
+
+		assert!(warnings
+			.iter()
+			.all(|warning| matches!(warning, MJCFWarning::UnknownLimit { .. })));
+		assert_eq!(robot.get_root_link().read().unwrap().name(), "base");
+
+		let base = link(&robot, "base");
+		assert!(base.colliders().is_empty());
+		assert_eq!(
+			base.visuals()[0].geometry(),
+			&geometry(BoxGeometry::new(0.2, 0.4, 0.6))
+		);
+
+		let arm = link(&robot, "arm");
+		assert!(arm.colliders().is_empty());
+		assert_eq!(
+			arm.visuals()[0].geometry(),
+			&geometry(SphereGeometry::new(0.05))
+		);
+
+		let shoulder = joint(&robot, "shoulder");
+		assert_eq!(shoulder.joint_type(), &JointType::Revolute);
+		assert_eq!(shoulder.axis(), Some((0., 1., 0.)));
+		assert_eq!(shoulder.dynamics().damping, Some(0.5));
+		let limit = shoulder.limit().unwrap();
+		assert_eq!(
+			(limit.lower, limit.upper),
+			(Some(-FRAC_PI_2), Some(FRAC_PI_2))
+		);
+		assert_eq!(
+			shoulder.transform(),
+			Some(&Transform::new_translation(0., 0., 1.))
+		);
+
+		let wrist = joint(&robot, "wrist");
+		assert_eq!(wrist.joint_type(), &JointType::Continuous);
+		assert_eq!(wrist.dynamics().damping, Some(0.1));
+		assert!(wrist.transform().is_none());
+	}
+
+	#[test]
+	fn multiple_joints() {
+		let (robot, warnings) = from_mjcf(&mjcf(
+			r#"<compiler angle="radian"/>"#,
+			r#"<body name="base">
+				<body name="hip" pos="0 0 1">
+					<joint name="hip_x" axis="1 0 0" pos="0 0 0.1" range="-1 1"/>
+					<joint name="hip_y" axis="0 1 0" pos="0 0 0.2"/>
+					<inertial pos="0 0 0.2" mass="2" diaginertia="1 2 3"/>
+					<geom size="0.1" pos="0 0 0.5"/>
+					<body pos="0 0 1"/>
+				</body>
+			</body>"#,
+		))
+		.unwrap();
+
+		assert!(warnings
+			.iter()
+			.all(|warning| matches!(warning, MJCFWarning::UnknownLimit { .. })));
+
+		let hip_x = robot.get_joint("hip_x").unwrap();
+		assert_eq!(
+			hip_x.read().unwrap().parent_link().read().unwrap().name(),
+			"base"
+		);
+		assert_eq!(
+			hip_x.read().unwrap().child_link().read().unwrap().name(),
+			"hip_x_link"
+		);
+		assert_eq!(
+			joint(&robot, "hip_x").transform(),
+			Some(&Transform::new_translation(0., 0., 1.1))
+		);
+		assert_eq!(joint(&robot, "hip_x").joint_type(), &JointType::Revolute);
+
+		let hip_y = robot.get_joint("hip_y").unwrap();
+		assert_eq!(
+			hip_y.read().unwrap().parent_link().read().unwrap().name(),
+			"hip_x_link"
+		);
+		assert_eq!(
+			hip_y.read().unwrap().child_link().read().unwrap().name(),
+			"hip"
+		);
+		assert_eq!(
+			joint(&robot, "hip_y").transform(),
+			Some(&Transform::new_translation(0., 0., 0.1))
+		);
+
+		// The content of the body is expressed relative to the last joint.
+		let hip = link(&robot, "hip");
+		assert_eq!(
+			hip.colliders()[0].transform(),
+			Some(&Transform::new_translation(0., 0., 0.3))
+		);
+		assert_eq!(hip.inertial().unwrap().transform, None);
+		assert_eq!(hip.inertial().unwrap().iyy, 2.);
+
+		assert_eq!(
+			joint(&robot, "hip_to_hip_body_0").transform(),
+			Some(&Transform::new_translation(0., 0., 0.8))
+		);
+		assert_eq!(
+			joint(&robot, "hip_to_hip_body_0").joint_type(),
+			&JointType::Fixed
+		);
+	}
+
+	#[test]
+	fn orientations() {
+		let (robot, _) = from_mjcf(&mjcf(
+			r#"<compiler eulerseq="XYZ"/>"#,
+			r#"<body name="base">
+				<body name="euler" euler="0 0 90"/>
+				<body name="axisangle" axisangle="0 0 1 90"/>
+				<body name="xyaxes" xyaxes="0 1 0 -1 0 0"/>
+				<body name="quat" quat="0.70710678 0 0 0.70710678"/>
+				<body name="zaxis" zaxis="1 0 0"/>
+			</body>"#,
+		))
+		.unwrap();
+
+		for body in ["euler", "axisangle", "xyaxes", "quat"] {
+			let rotation = joint(&robot, &format!("base_to_{body}"))
+				.transform()
+				.unwrap()
+				.rotation
+				.unwrap();
+			assert_eq!(rotation.0, 0.);
+			assert_eq!(rotation.1, 0.);
+			assert!(
+				(rotation.2 - FRAC_PI_2).abs() < 1e-6,
+				"{body}: {rotation:?}"
+			);
+		}
+
+		let rotation = joint(&robot, "base_to_zaxis")
+			.transform()
+			.unwrap()
+			.rotation
+			.unwrap();
+		assert!((rotation.1 - FRAC_PI_2).abs() < 1e-3, "{rotation:?}");
+
+		// Multiple orientations are not allowed.
+		assert!(matches!(
+			from_mjcf(&mjcf("", r#"<body quat="1 0 0 0" euler="0 0 0"/>"#)),
+			Err(FromMJCFError::MultipleOrientations { line: 1, .. })
+		));
+	}
+
+	#[test]
+	fn assets() {
+		let (robot, warnings) = from_mjcf(&mjcf(
+			r#"<compiler meshdir="meshes/"/>
+			<asset>
+				<mesh file="base.stl" scale="2 2 2"/>
+				<material name="blue" rgba="0 0 1 1"/>
+				<texture name="grid" type="2d" builtin="checker"/>
+			</asset>"#,
+			r#"<body name="base">
+				<geom type="mesh" mesh="base" material="blue" contype="0" conaffinity="0"/>
+				<geom type="cylinder" size="0.1" fromto="0 0 0 0 0 -2" group="3" rgba="1 0 0 1"/>
+			</body>"#,
+		))
+		.unwrap();
+
+		assert_eq!(
+			warnings,
+			vec![MJCFWarning::UnsupportedElement {
+				element: String::from("texture"),
+				line: 5
+			}]
+		);
+
+		let base = link(&robot, "base");
+		assert_eq!(
+			base.visuals()[0].geometry(),
+			&geometry(MeshGeometry::new(
+				"meshes/base.stl",
+				(0., 0., 0.),
+				Some((2., 2., 2.))
+			))
+		);
+		assert_eq!(
+			base.visuals()[0].material(),
+			Some(&MaterialDescriptor::new_color(0., 0., 1., 1.).named("blue"))
+		);
+
+		// The hidden cylinder is only a collision.
+		assert_eq!(base.visuals().len(), 1);
+		assert_eq!(
+			base.colliders()[0].geometry(),
+			&geometry(CylinderGeometry::new(0.1, 2.))
+		);
+		let transform = base.colliders()[0].transform().unwrap();
+		assert_eq!(transform.translation, Some((0., 0., -1.)));
+		assert!((transform.rotation.unwrap().0.abs() - crate::float::consts::PI).abs() < 1e-6);
+
+		assert!(matches!(
+			from_mjcf(&mjcf("", r#"<body><geom type="mesh" mesh="missing"/></body>"#)),
+			Err(FromMJCFError::UnknownAsset { asset, .. }) if asset == "missing"
+		));
+	}
+
+	#[test]
+	fn unknown_limits() {
+		let (robot, warnings) = from_mjcf(&mjcf(
+			"",
+			r#"<body name="base">
+				<body name="arm"><joint name="shoulder" range="-90 90"/></body>
+				<body name="leg"><joint name="hip" range="-90 90" actuatorfrcrange="-5 10"/></body>
+			</body>"#,
+		))
+		.unwrap();
+
+		assert_eq!(
+			warnings,
+			vec![
+				MJCFWarning::UnknownLimit {
+					joint: String::from("shoulder"),
+					limit: "velocity",
+					line: 2
+				},
+				MJCFWarning::UnknownLimit {
+					joint: String::from("shoulder"),
+					limit: "effort",
+					line: 2
+				},
+				MJCFWarning::UnknownLimit {
+					joint: String::from("hip"),
+					limit: "velocity",
+					line: 3
+				},
+			]
+		);
+
+		let shoulder = *joint(&robot, "shoulder").limit().unwrap();
+		assert_eq!(
+			(shoulder.effort, shoulder.velocity),
+			(UNKNOWN_LIMIT, UNKNOWN_LIMIT)
+		);
+		let hip = *joint(&robot, "hip").limit().unwrap();
+		assert_eq!((hip.effort, hip.velocity), (10., UNKNOWN_LIMIT));
+	}
+
+	#[test]
+	fn mimic() {
+		let description = |polycoef: &str, joint2: &str| {
+			format!(
+				r#"<mujoco>
+					<worldbody>
+						<body name="base">
+							<body name="left"><joint name="left" type="slide"/></body>
+							<body name="right"><joint name="right" type="slide"/></body>
+						</body>
+					</worldbody>
+					<equality>
+						<joint joint1="right" joint2="{joint2}" polycoef="{polycoef}"/>
+					</equality>
+				</mujoco>"#
+			)
+		};
+
+		let (robot, warnings) = from_mjcf(&description("0.5 2 0 0 0", "left")).unwrap();
+		assert!(warnings.is_empty());
+		assert_eq!(robot.name(), "MuJoCo Model");
+		assert_eq!(
+			joint(&robot, "right").mimic(),
+			Some(&MimicBuilderData {
+				joint_name: String::from("left"),
+				multiplier: Some(2.),
+				offset: Some(0.5),
+			})
+		);
+
+		let (robot, warnings) = from_mjcf(&description("0 1 1 0 0", "left")).unwrap();
+		assert_eq!(
+			warnings,
+			vec![MJCFWarning::UnsupportedMimic {
+				joint: String::from("right"),
+				line: 9
+			}]
+		);
+		assert!(joint(&robot, "right").mimic().is_none());
+
+		assert!(matches!(
+			from_mjcf(&description("0 1 0 0 0", "middle")),
+			Err(FromMJCFError::UnknownJoint { joint, line: 9 }) if joint == "middle"
+		));
+
+		assert!(matches!(
+			from_mjcf(&description("0 1 0 0 0", "right")),
+			Err(FromMJCFError::MimicCycle { joint, line: 9 }) if joint == "right"
+		));
+	}
+
+	#[test]
+	fn multiple_mimics() {
+		let description = |equalities: &str| {
+			format!(
+				r#"<mujoco>
+					<worldbody>
+						<body name="base">
+							<body name="left"><joint name="left" type="slide"/></body>
+							<body name="right"><joint name="right" type="slide"/></body>
+							<body name="middle"><joint name="middle" type="slide"/></body>
+						</body>
+					</worldbody>
+					<equality>{equalities}
+					</equality>
+				</mujoco>"#
+			)
+		};
+
+		// Only the first equality constraint of a joint is read as its mimic.
+		let (robot, warnings) = from_mjcf(&description(
+			r#"
+						<joint joint1="right" joint2="left"/>
+						<joint joint1="right" joint2="middle"/>"#,
+		))
+		.unwrap();
+		assert_eq!(
+			warnings,
+			vec![MJCFWarning::UnsupportedMimic {
+				joint: String::from("right"),
+				line: 11
+			}]
+		);
+		assert_eq!(joint(&robot, "right").mimic().unwrap().joint_name, "left");
+
+		assert!(matches!(
+			from_mjcf(&description(
+				r#"
+						<joint joint1="left" joint2="middle"/>
+						<joint joint1="middle" joint2="right"/>
+						<joint joint1="right" joint2="left"/>"#,
+			)),
+			Err(FromMJCFError::MimicCycle { joint, line: 10 }) if joint == "left"
+		));
+	}
+
+	#[test]
+	fn warnings() {
+		let (robot, warnings) = from_mjcf(
+			r#"<mujoco model="warnings">
+				<option timestep="0.001"/>
+				<default>
+					<joint armature="0.1"/>
+				</default>
+				<worldbody>
+					<geom type="plane" size="1 1 0.1"/>
+					<body name="torso" pos="0 0 1">
+						<freejoint/>
+						<site name="imu"/>
+						<geom size="0.1" friction="1 0.1 0.1"/>
+						<body name="head">
+							<joint name="neck" type="ball"/>
+						</body>
+					</body>
+				</worldbody>
+				<actuator/>
+			</mujoco>"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			warnings,
+			vec![
+				MJCFWarning::UnsupportedElement {
+					element: String::from("option"),
+					line: 2
+				},
+				MJCFWarning::UnsupportedElement {
+					element: String::from("actuator"),
+					line: 17
+				},
+				MJCFWarning::UnsupportedAttribute {
+					element: String::from("joint"),
+					attribute: String::from("armature"),
+					line: 4
+				},
+				MJCFWarning::UnsupportedGeometry {
+					geom_type: String::from("plane"),
+					line: 7
+				},
+				MJCFWarning::UnsupportedElement {
+					element: String::from("site"),
+					line: 10
+				},
+				MJCFWarning::UnsupportedAttribute {
+					element: String::from("geom"),
+					attribute: String::from("friction"),
+					line: 11
+				},
+				MJCFWarning::ApproximatedJoint {
+					joint: String::from("neck"),
+					line: 13
+				},
+			]
+		);
+
+		// The world is the root, since the torso has a joint.
+		assert_eq!(robot.get_root_link().read().unwrap().name(), "world");
+		assert_eq!(
+			joint(&robot, "torso_joint_0").joint_type(),
+			&JointType::Floating
+		);
+		assert_eq!(
+			joint(&robot, "torso_joint_0").transform(),
+			Some(&Transform::new_translation(0., 0., 1.))
+		);
+
+		for (axis, child) in [("x", "neck_x_link"), ("y", "neck_y_link"), ("z", "head")] {
+			let neck = robot.get_joint(&format!("neck_{axis}")).unwrap();
+			assert_eq!(neck.read().unwrap().joint_type(), JointType::Continuous);
+			assert_eq!(
+				neck.read().unwrap().child_link().read().unwrap().name(),
+				child
+			);
+		}
+	}
+
+	#[test]
+	fn errors() {
+		assert!(matches!(
+			from_mjcf("<robot/>"),
+			Err(FromMJCFError::NotMJCF { element, line: 1 }) if element == "robot"
+		));
+		assert!(matches!(
+			from_mjcf(&mjcf("", r#"<body childclass="missing"/>"#)),
+			Err(FromMJCFError::UnknownClass { class, .. }) if class == "missing"
+		));
+		assert!(matches!(
+			from_mjcf(&mjcf("", r#"<body name="a"/><body name="a"/>"#)),
+			Err(FromMJCFError::DuplicateName { name, .. }) if name == "a"
+		));
+		assert!(matches!(
+			from_mjcf(&mjcf("", r#"<body><geom type="box" size="1 1"/></body>"#)),
+			Err(FromMJCFError::InvalidValue { attribute, .. }) if attribute == "size"
+		));
+	}
+
+	#[test]
+	fn chain() {
+		let (chain, _) = chain_from_mjcf(&mjcf(
+			"",
+			r#"<body name="palm">
+				<body name="finger" pos="0 0.1 0">
+					<joint name="finger_joint" type="slide" axis="0 1 0"/>
+				</body>
+			</body>"#,
+		))
+		.unwrap();
+
+		assert_eq!(chain.name(), "palm");
+		assert_eq!(chain.joints()[0].name(), "finger_joint");
+		assert_eq!(chain.joints()[0].joint_type(), &JointType::Prismatic);
+		assert!(chain.joints()[0].limit().is_none());
+	}
+
+	#[cfg(feature = "urdf")]
+	mod urdf {
+		use super::{chain_from_mjcf, from_mjcf, test, JointType, MJCFWarning};
+		use crate::{
+			cluster_objects::KinematicInterface,
+			from_rdf::from_urdf::from_urdf,
+			to_rdf::{
+				to_mjcf::{to_mjcf, MJCFConfig},
+				to_urdf::{to_urdf, URDFConfig},
+			},
+			transform::MirrorAxis,
+		};
+
+		const DESCRIPTION: &str = r#"<robot name="arm">
+  <link name="base">
+    <visual><geometry><box size="1 1 0.2"/></geometry></visual>
+    <collision><geometry><box size="1 1 0.2"/></geometry></collision>
+  </link>
+  <joint name="shoulder" type="revolute">
+    <origin xyz="0 0.2 0.2" rpy="0 0 1.5707964"/>
+    <parent link="base"/>
+    <child link="upper_arm"/>
+    <axis xyz="0 1 0"/>
+    <limit lower="-1" upper="1" effort="10" velocity="1"/>
+    <dynamics damping="0.5" friction="0.1"/>
+  </joint>
+  <link name="upper_arm">
+    <inertial>
+      <origin xyz="0 0 0.5"/>
+      <mass value="1"/>
+      <inertia ixx="0.1" ixy="0" ixz="0" iyy="0.1" iyz="0" izz="0.01"/>
+    </inertial>
+    <visual><geometry><cylinder radius="0.05" length="1"/></geometry></visual>
+  </link>
+  <joint name="elbow" type="continuous">
+    <origin xyz="0 0 1"/>
+    <parent link="upper_arm"/>
+    <child link="forearm"/>
+    <axis xyz="0 1 0"/>
+    <mimic joint="shoulder" multiplier="2" offset="0.5"/>
+  </joint>
+  <link name="forearm">
+    <collision><geometry><sphere radius="0.1"/></geometry></collision>
+  </link>
+</robot>"#;
+
+		#[test]
+		fn round_trip() {
+			let robot = from_urdf(DESCRIPTION).unwrap();
+			let mjcf = to_mjcf(&robot, MJCFConfig::default()).unwrap();
+			let (read_robot, warnings) =
+				from_mjcf(&String::from_utf8(mjcf.into_inner().into_inner()).unwrap()).unwrap();
+
+			assert_eq!(
+				warnings,
+				vec![MJCFWarning::UnknownLimit {
+					joint: String::from("shoulder"),
+					limit: "velocity",
+					line: 1
+				}]
+			);
+			assert_eq!(read_robot.name(), "arm");
+
+			for name in ["shoulder", "elbow"] {
+				let original = robot.get_joint(name).unwrap().read().unwrap().rebuild();
+				let read = read_robot
+					.get_joint(name)
+					.unwrap()
+					.read()
+					.unwrap()
+					.rebuild();
+
+				assert_eq!(read.joint_type(), original.joint_type());
+				assert_eq!(read.axis(), original.axis());
+				assert_eq!(read.dynamics(), original.dynamics());
+				assert_eq!(read.mimic(), original.mimic());
+
+				let (original, read) = (original.transform().unwrap(), read.transform().unwrap());
+				for (original, read) in [
+					(original.translation, read.translation),
+					(original.rotation, read.rotation),
+				] {
+					let (original, read) = (original.unwrap_or_default(), read.unwrap_or_default());
+					assert!(
+						(original.0 - read.0).abs() < 1e-6
+							&& (original.1 - read.1).abs() < 1e-6
+							&& (original.2 - read.2).abs() < 1e-6,
+						"{name}: {original:?} != {read:?}"
+					);
+				}
+			}
+
+			let limit = read_robot
+				.get_joint("shoulder")
+				.unwrap()
+				.read()
+				.unwrap()
+				.rebuild()
+				.limit()
+				.copied()
+				.unwrap();
+			assert_eq!((limit.lower, limit.upper), (Some(-1.), Some(1.)));
+			assert_eq!(limit.effort, 10.);
+
+			for (name, visuals, colliders) in
+				[("base", 1, 1), ("upper_arm", 1, 0), ("forearm", 0, 1)]
+			{
+				let link = read_robot.get_link(name).unwrap();
+				assert_eq!(link.read().unwrap().visuals().len(), visuals, "{name}");
+				assert_eq!(link.read().unwrap().colliders().len(), colliders, "{name}");
+			}
+			assert_eq!(
+				read_robot
+					.get_link("upper_arm")
+					.unwrap()
+					.read()
+					.unwrap()
+					.inertial()
+					.copied(),
+				robot
+					.get_link("upper_arm")
+					.unwrap()
+					.read()
+					.unwrap()
+					.inertial()
+					.copied()
+			);
+		}
+
+		#[test]
+		fn mirror_to_urdf() {
+			let (chain, _) = chain_from_mjcf(
+				r#"<mujoco model="leg">
+  <compiler angle="radian"/>
+  <worldbody>
+    <body name="hip">
+      <body name="thigh" pos="0 0.1 0">
+        <joint name="hip_pitch" axis="0 1 0" range="-1 1"/>
+        <joint name="hip_roll" axis="1 0 0"/>
+        <geom type="box" size="0.05 0.05 0.2" pos="0 0 -0.2"/>
+      </body>
+    </body>
+  </worldbody>
+</mujoco>"#,
+			)
+			.unwrap();
+
+			let mirrored = chain.mirror(MirrorAxis::Y);
+			let robot = mirrored.0.build_tree().to_robot("mirrored_leg");
+
+			let hip_pitch = robot.get_joint("hip_pitch").unwrap();
+			assert_eq!(hip_pitch.read().unwrap().joint_type(), JointType::Revolute);
+			assert_eq!(
+				hip_pitch.read().unwrap().transform().translation,
+				Some((0., -0.1, 0.))
+			);
+			assert_eq!(
+				hip_pitch
+					.read()
+					.unwrap()
+					.child_link()
+					.read()
+					.unwrap()
+					.name(),
+				"hip_pitch_link"
+			);
+
+			let urdf = to_urdf(&robot, URDFConfig::default()).unwrap();
+			let urdf = String::from_utf8(urdf.into_inner().into_inner()).unwrap();
+			assert!(urdf.contains(r#"<joint name="hip_roll" type="continuous">"#));
+			assert!(urdf.contains(r#"<link name="hip_pitch_link""#));
+		}
+	}
+}
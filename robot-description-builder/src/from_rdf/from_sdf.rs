@@ -0,0 +1,1251 @@
+//! The infrastructure to read a `Robot` from the SDFormat (SDF).
+//!
+//! A `<model>` can be read as a [`Robot`] with [`from_sdf`], or as a [`Chained<LinkBuilder>`] with [`chain_from_sdf`].
+//! Both return the [`SDFWarning`]s for everything in the description, which could not be represented.
+//!
+//! # Poses
+//! SDFormat describes the pose of every element relative to a frame of choice (`relative_to`).
+//! All poses are resolved and converted to the parent-relative [`Transform`]s used by this crate:
+//! - The transform of a `Joint` is relative to the frame of its parent `Link`.
+//! - A `Link` shares the frame of its parent `Joint`, just like in URDF.
+//!   When the SDFormat frames of a link and its parent joint differ, the poses of the visuals,
+//!   collisions and inertial of the link are expressed in the frame of the joint instead.
+//! - The joint axis is expressed in the frame of the `Joint`.
+//!
+//! # Nested models
+//! Nested `<model>` elements are read as a part of the same tree.
+//! The names of their links and joints get the scope of their model as a [`GroupID`](crate::identifiers::GroupID),
+//! e.g. the link `palm` of the nested model `gripper` becomes `[[gripper]]::palm`.
+//! This displays as the scoped SDFormat name `gripper::palm`, and allows for changing the prefix of the subtree with
+//! [`change_group_id`](crate::identifiers::GroupIDChanger::change_group_id).
+//!
+//! # Limitations
+//! The following is reported as a [`SDFWarning`]:
+//! - Elements which can not be represented (e.g. `<sensor>`, `<plugin>`, `<surface>` or `<include>`) are ignored.
+//! - `ball`, `universal`, `revolute2`, `screw` and `gearbox` joints are read as [`Fixed`](crate::JointType::Fixed) joints.
+//! - Visuals and collisions with an unsupported geometry (e.g. `<plane>` or `<capsule>`) are ignored.
+//! - Materials without a color or an albedo map (e.g. only a `<script>`) are ignored.
+//! - Joints with `world` as their parent are ignored.
+//! - Explicit `<frame>` elements are used to resolve the poses, but are not kept.
+//!
+//! The pose of the top level `<model>` is not kept, since it places the model in the world.
+//! The bounding box of a [`MeshGeometry`](crate::link_data::geometry::MeshGeometry) can not be determined from the description and is set to `(0, 0, 0)`.
+use std::collections::HashMap;
+
+use nalgebra::{Isometry3, Vector3};
+use thiserror::Error;
+
+use super::{
+	assemble_link, find_mimic_cycle, remove_noise, to_isometry, to_transform,
+	xml_element::XMLElement, JointDescription, XMLReadError,
+};
+use crate::{
+	chained::Chained,
+	cluster_objects::Robot,
+	identifiers::{
+		escape_group_id_delimiters, GroupID, DELIMITER_CLOSE_GROUPID, DELIMITER_OPEN_GROUPID,
+	},
+	joint::JointBuilder,
+	link::builder::LinkBuilder,
+	transform::Transform,
+	Float,
+};
+
+/// The name of the frame of the world.
+const WORLD_FRAME: &str = "world";
+/// The name of the frame of the enclosing model.
+const MODEL_FRAME: &str = "__model__";
+/// The delimiter between the scopes of a SDFormat name.
+const SCOPE_DELIMITER: &str = "::";
+
+/// An error, which can occur while reading a SDFormat description.
+///
+/// Most variants contain the `line` of the offending element to make it easy to find the problem.
+#[derive(Debug, Error)]
+pub enum FromSDFError {
+	/// The description is not valid XML.
+	#[error(transparent)]
+	XML(#[from] XMLReadError),
+	/// The root element of the description is not a `<sdf>` element.
+	#[error("Expected the root element to be <sdf>, found <{element}> at line {line}")]
+	NotSDF { element: String, line: usize },
+	/// The description does not contain a `<model>`.
+	#[error("<sdf> at line {line} does not contain a <model>")]
+	MissingModel { line: usize },
+	/// The description contains multiple top level `<model>` elements.
+	#[error("<sdf> at line {line} contains multiple models, only a single model can be read")]
+	MultipleModels { line: usize },
+	/// A required attribute is missing.
+	#[error("<{element}> at line {line} is missing the required attribute '{attribute}'")]
+	MissingAttribute {
+		element: String,
+		attribute: String,
+		line: usize,
+	},
+	/// A required child element is missing.
+	#[error("<{element}> at line {line} is missing the required element <{child}>")]
+	MissingElement {
+		element: String,
+		child: String,
+		line: usize,
+	},
+	/// The value of an attribute could not be interpreted.
+	#[error("<{element}> at line {line} has an invalid value \"{value}\" for the attribute '{attribute}'")]
+	InvalidValue {
+		element: String,
+		attribute: String,
+		value: String,
+		line: usize,
+	},
+	/// The text content of an element could not be interpreted.
+	#[error("<{element}> at line {line} has invalid content \"{content}\"")]
+	InvalidContent {
+		element: String,
+		content: String,
+		line: usize,
+	},
+	/// Multiple elements in the same scope have the same name.
+	#[error("The name '{name}' at line {line} is already in use")]
+	DuplicateName { name: String, line: usize },
+	/// A pose, axis or frame refers to a frame which does not exist.
+	#[error("<{element}> at line {line} refers to the unknown frame '{frame}'")]
+	UnknownFrame {
+		element: String,
+		frame: String,
+		line: usize,
+	},
+	/// The pose of a frame depends on itself.
+	#[error("The pose of the frame '{frame}' at line {line} depends on itself")]
+	CyclicFrame { frame: String, line: usize },
+	/// A `<joint>` refers to a `<link>` which does not exist.
+	#[error("The joint '{joint}' at line {line} refers to the unknown link '{link}'")]
+	UnknownLink {
+		joint: String,
+		link: String,
+		line: usize,
+	},
+	/// A `<link>` is the child of multiple `<joint>` elements.
+	#[error("The link '{link}' is the child of multiple joints, the joint at line {line} is the second parent")]
+	MultipleParents { link: String, line: usize },
+	/// A `<joint>` mimics a `<joint>` which does not exist.
+	#[error("The joint '{joint}' at line {line} mimics the unknown joint '{mimicked_joint}'")]
+	UnknownMimicJoint {
+		joint: String,
+		mimicked_joint: String,
+		line: usize,
+	},
+	/// A `<joint>` mimics itself, directly or through a cycle of mimicking `<joint>`s.
+	#[error("The joint '{joint}' at line {line} mimics itself through a cycle of mimics")]
+	MimicCycle { joint: String, line: usize },
+	/// The description does not contain a root `<link>`.
+	///
+	/// This happens when the description does not contain any links, or every link has a parent.
+	#[error("The description has no root link")]
+	NoRootLink,
+	/// The description contains multiple `<link>` elements without a parent.
+	///
+	/// This also happens when a nested `<model>` is not connected to the rest of the model by a `<joint>`.
+	#[error("The description has multiple root links: {0:?}")]
+	MultipleRootLinks(Vec<String>),
+	/// The `<link>` is a part of a kinematic loop and can therefore not be reached from the root link.
+	#[error("The link '{link}' at line {line} is a part of a kinematic loop")]
+	KinematicLoop { link: String, line: usize },
+}
+
+/// A construct of a SDFormat description, which can not be represented and is therefore not kept.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+#[non_exhaustive]
+pub enum SDFWarning {
+	/// The element is not supported and is ignored.
+	#[error("<{element}> at line {line} can not be represented and is ignored")]
+	UnsupportedElement { element: String, line: usize },
+	/// The joint type is not supported, the joint is read as a [`Fixed`](crate::JointType::Fixed) joint.
+	#[error("The joint '{joint}' at line {line} has the unsupported type '{joint_type}' and is read as a fixed joint")]
+	UnsupportedJointType {
+		joint: String,
+		joint_type: String,
+		line: usize,
+	},
+	/// The geometry shape is not supported, the `<visual>` or `<collision>` containing it is ignored.
+	#[error("The <{shape}> geometry at line {line} is not supported, the element containing it is ignored")]
+	UnsupportedGeometry { shape: String, line: usize },
+	/// The `<material>` does not contain a color or an albedo map, so it is ignored.
+	#[error("The <material> at line {line} has no color or albedo map and is ignored")]
+	UnsupportedMaterial { line: usize },
+	/// The joint connects a link to the world and is ignored.
+	#[error("The joint '{joint}' at line {line} connects to the world and is ignored")]
+	WorldJoint { joint: String, line: usize },
+	/// The explicit frame is used to resolve poses, but is not kept.
+	#[error("The frame '{frame}' at line {line} is used to resolve poses, but is not kept")]
+	FrameNotKept { frame: String, line: usize },
+}
+
+/// The state which is shared while reading the elements of a SDFormat description.
+#[derive(Debug)]
+pub(crate) struct FromSDFContext {
+	/// The warnings reported so far.
+	pub warnings: Vec<SDFWarning>,
+	/// The resolved poses of all frames relative to the world, by their scoped name.
+	frames: HashMap<String, Isometry3<f64>>,
+	/// The scope of the model being read, which is empty for the top level model.
+	scope: String,
+	/// The pose of the frame in which the transforms are expressed.
+	frame: Isometry3<f64>,
+	/// The scoped name of the frame, which a `<pose>` is relative to if it does not specify `relative_to`.
+	default_frame: String,
+}
+
+impl Default for FromSDFContext {
+	fn default() -> Self {
+		Self {
+			warnings: Vec::new(),
+			frames: HashMap::new(),
+			scope: String::new(),
+			frame: Isometry3::identity(),
+			default_frame: String::from(MODEL_FRAME),
+		}
+	}
+}
+
+impl FromSDFContext {
+	/// Reports the `warning`.
+	pub fn warn(&mut self, warning: SDFWarning) {
+		#[cfg(any(feature = "logging", test))]
+		log::warn!("{warning}");
+
+		self.warnings.push(warning);
+	}
+
+	/// Reports all child elements of the `element`, which are not in the `supported` list.
+	pub fn warn_unsupported(&mut self, element: &XMLElement, supported: &[&str]) {
+		for child in element
+			.children
+			.iter()
+			.filter(|child| !supported.contains(&child.name.as_str()))
+		{
+			self.warn(SDFWarning::UnsupportedElement {
+				element: child.name.clone(),
+				line: child.line,
+			});
+		}
+	}
+
+	/// Gets the name for this crate of the element named `name` in the current scope.
+	pub fn name(&self, name: &str) -> String {
+		crate_name(&scoped_name(&self.scope, name))
+	}
+
+	/// Gets the name for this crate of the `element`, based on its `name` attribute.
+	pub fn required_name(&self, element: &XMLElement) -> Result<String, FromSDFError> {
+		required_attribute(element, "name").map(|name| self.name(name))
+	}
+
+	/// Gets the resolved pose of the frame, which is referred to by `reference` in the current scope.
+	///
+	/// The `element` is used for error reporting.
+	pub fn frame_pose(
+		&self,
+		element: &XMLElement,
+		reference: &str,
+	) -> Result<Isometry3<f64>, FromSDFError> {
+		self.frames
+			.get(&frame_key(&self.scope, reference))
+			.copied()
+			.ok_or_else(|| FromSDFError::UnknownFrame {
+				element: element.name.clone(),
+				frame: reference.to_string(),
+				line: element.line,
+			})
+	}
+
+	/// Gets the pose of the frame of the current model.
+	pub fn model_pose(&self) -> Isometry3<f64> {
+		self.frames
+			.get(&frame_key(&self.scope, MODEL_FRAME))
+			.copied()
+			.unwrap_or_default()
+	}
+
+	/// Gets the pose relative to the world described by the optional `<pose>` child element of the `element`.
+	pub fn pose(&mut self, element: &XMLElement) -> Result<Isometry3<f64>, FromSDFError> {
+		match element.child("pose") {
+			Some(pose) => {
+				let relative_to = match pose.attribute("relative_to") {
+					Some(reference) => self.frame_pose(pose, reference)?,
+					None => self.frames[&self.default_frame],
+				};
+				Ok(relative_to * to_isometry(&Transform::from_sdf(pose, self)?))
+			}
+			None => Ok(self.frames[&self.default_frame]),
+		}
+	}
+
+	/// Reads the optional `<pose>` child element of the `element`, as a `Transform` relative to the current frame.
+	///
+	/// An identity pose is read as `None`.
+	pub fn transform(&mut self, element: &XMLElement) -> Result<Option<Transform>, FromSDFError> {
+		let pose = self.pose(element)?;
+		Ok(to_transform(&(self.frame.inverse() * pose)))
+	}
+}
+
+/// A trait to allow parts of a `Robot` to be read from the SDFormat.
+///
+/// This is the counterpart of [`ToSDF`](crate::to_rdf::to_sdf::ToSDF).
+pub(crate) trait FromSDF: Sized {
+	/// Reads the element from its SDFormat representation.
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError>;
+}
+
+/// Gets the value of the required `attribute` of the `element`.
+pub(crate) fn required_attribute<'a>(
+	element: &'a XMLElement,
+	attribute: &str,
+) -> Result<&'a str, FromSDFError> {
+	element
+		.attribute(attribute)
+		.ok_or_else(|| FromSDFError::MissingAttribute {
+			element: element.name.clone(),
+			attribute: attribute.to_string(),
+			line: element.line,
+		})
+}
+
+/// Gets the required child element with the specified `name` of the `element`.
+pub(crate) fn required_child<'a>(
+	element: &'a XMLElement,
+	name: &str,
+) -> Result<&'a XMLElement, FromSDFError> {
+	element
+		.child(name)
+		.ok_or_else(|| FromSDFError::MissingElement {
+			element: element.name.clone(),
+			child: name.to_string(),
+			line: element.line,
+		})
+}
+
+/// Creates a [`FromSDFError::InvalidContent`] for the `element`.
+pub(crate) fn invalid_content(element: &XMLElement) -> FromSDFError {
+	FromSDFError::InvalidContent {
+		element: element.name.clone(),
+		content: element.text.clone(),
+		line: element.line,
+	}
+}
+
+/// Parses the number in the text content of the `element`.
+pub(crate) fn content_float(element: &XMLElement) -> Result<Float, FromSDFError> {
+	element
+		.text
+		.trim()
+		.parse()
+		.map_err(|_| invalid_content(element))
+}
+
+/// Parses the whitespace separated list of `N` numbers in the text content of the `element`.
+pub(crate) fn content_float_array<const N: usize>(
+	element: &XMLElement,
+) -> Result<[Float; N], FromSDFError> {
+	let mut result = [0.; N];
+	let mut parts = element.text.split_whitespace();
+
+	for number in result.iter_mut() {
+		*number = parts
+			.next()
+			.and_then(|part| part.parse().ok())
+			.ok_or_else(|| invalid_content(element))?;
+	}
+
+	match parts.next() {
+		Some(_) => Err(invalid_content(element)),
+		None => Ok(result),
+	}
+}
+
+/// Parses the whitespace separated vector in the text content of the `element`.
+pub(crate) fn content_vector3(element: &XMLElement) -> Result<(Float, Float, Float), FromSDFError> {
+	content_float_array(element).map(|[x, y, z]| (x, y, z))
+}
+
+/// Parses the number in the optional child element with the specified `name` of the `element`.
+pub(crate) fn optional_child_float(
+	element: &XMLElement,
+	name: &str,
+) -> Result<Option<Float>, FromSDFError> {
+	element.child(name).map(content_float).transpose()
+}
+
+/// Parses the text content of the optional boolean child element with the specified `name` of the `element`.
+pub(crate) fn optional_child_bool(
+	element: &XMLElement,
+	name: &str,
+) -> Result<Option<bool>, FromSDFError> {
+	element
+		.child(name)
+		.map(|child| match child.text.trim() {
+			"true" | "1" => Ok(true),
+			"false" | "0" => Ok(false),
+			_ => Err(invalid_content(child)),
+		})
+		.transpose()
+}
+
+/// Joins the `name` with the `scope` it is defined in.
+fn scoped_name(scope: &str, name: &str) -> String {
+	match scope.is_empty() {
+		true => name.to_string(),
+		false => format!("{scope}{SCOPE_DELIMITER}{name}"),
+	}
+}
+
+/// Gets the scoped name of the frame, which is referred to by `reference` in the `scope`.
+fn frame_key(scope: &str, reference: &str) -> String {
+	if reference == WORLD_FRAME {
+		return reference.to_string();
+	}
+
+	match reference.strip_suffix(MODEL_FRAME) {
+		// The frame of the top level model can not be scoped, since it has no name.
+		Some("") if scope.is_empty() => reference.to_string(),
+		Some("") => scope.to_string(),
+		Some(model) => match model.strip_suffix(SCOPE_DELIMITER) {
+			Some(model) => scoped_name(scope, model),
+			None => scoped_name(scope, reference),
+		},
+		None => scoped_name(scope, reference),
+	}
+}
+
+/// Converts the scoped name of a link or joint into the name used in this crate.
+///
+/// The scope gets converted to a [`GroupID`], all other `GroupID` delimiters are escaped.
+fn crate_name(scoped_name: &str) -> String {
+	match scoped_name.rsplit_once(SCOPE_DELIMITER) {
+		Some((scope, name)) => format!(
+			"{DELIMITER_OPEN_GROUPID}{}{DELIMITER_CLOSE_GROUPID}{SCOPE_DELIMITER}{}",
+			escape_group_id_delimiters(scope),
+			escape_group_id_delimiters(name)
+		),
+		None => escape_group_id_delimiters(scoped_name),
+	}
+}
+
+/// A frame of the description, with its pose relative to another frame.
+struct FrameNode<'a> {
+	element: &'a XMLElement,
+	/// The scoped name of the frame the `pose` is relative to.
+	relative_to: String,
+	/// The element which specified `relative_to`, used for error reporting.
+	reference_element: &'a XMLElement,
+	pose: Isometry3<f64>,
+}
+
+/// The frames, links and joints of a `<model>` and its nested models.
+#[derive(Default)]
+struct ModelContent<'a> {
+	frames: HashMap<String, FrameNode<'a>>,
+	/// The scoped names of the frames, in document order.
+	frame_order: Vec<String>,
+	/// The links with the scope they are defined in.
+	links: Vec<(String, &'a XMLElement)>,
+	/// The joints with the scope they are defined in.
+	joints: Vec<(String, &'a XMLElement)>,
+}
+
+/// Collects the frames, links and joints of the `model` with the specified `scope`.
+fn collect_model<'a>(
+	model: &'a XMLElement,
+	scope: &str,
+	content: &mut ModelContent<'a>,
+	sdf_context: &mut FromSDFContext,
+) -> Result<(), FromSDFError> {
+	let model_frame = frame_key(scope, MODEL_FRAME);
+
+	for element in model.children.iter() {
+		let default_frame = match element.name.as_str() {
+			"link" | "model" => model_frame.clone(),
+			"joint" => frame_key(scope, &required_child(element, "child")?.text),
+			"frame" => match element.attribute("attached_to") {
+				Some(attached_to) => frame_key(scope, attached_to),
+				None => model_frame.clone(),
+			},
+			"pose" => continue,
+			_ => {
+				sdf_context.warn(SDFWarning::UnsupportedElement {
+					element: element.name.clone(),
+					line: element.line,
+				});
+				continue;
+			}
+		};
+
+		let name = required_attribute(element, "name")?;
+		let key = scoped_name(scope, name);
+
+		if content.frames.contains_key(&key) || name == WORLD_FRAME || name == MODEL_FRAME {
+			return Err(FromSDFError::DuplicateName {
+				name: key,
+				line: element.line,
+			});
+		}
+
+		let (pose, relative_to, reference_element) = match element.child("pose") {
+			Some(pose) => (
+				to_isometry(&Transform::from_sdf(pose, sdf_context)?),
+				pose.attribute("relative_to")
+					.map(|reference| frame_key(scope, reference))
+					.unwrap_or(default_frame),
+				pose,
+			),
+			None => (Isometry3::identity(), default_frame, element),
+		};
+
+		content.frames.insert(
+			key.clone(),
+			FrameNode {
+				element,
+				relative_to,
+				reference_element,
+				pose,
+			},
+		);
+		content.frame_order.push(key.clone());
+
+		match element.name.as_str() {
+			"link" => content.links.push((scope.to_string(), element)),
+			"joint" => content.joints.push((scope.to_string(), element)),
+			"frame" => sdf_context.warn(SDFWarning::FrameNotKept {
+				frame: key,
+				line: element.line,
+			}),
+			"model" => collect_model(element, &key, content, sdf_context)?,
+			_ => unreachable!(),
+		}
+	}
+
+	Ok(())
+}
+
+/// Resolves the pose relative to the world of the frame with the scoped name `key`.
+///
+/// The frames on the path are tracked in `visiting` to detect cycles.
+fn resolve_frame(
+	key: &str,
+	frames: &HashMap<String, FrameNode>,
+	resolved: &mut HashMap<String, Isometry3<f64>>,
+	visiting: &mut Vec<String>,
+) -> Result<Isometry3<f64>, FromSDFError> {
+	if let Some(pose) = resolved.get(key) {
+		return Ok(*pose);
+	}
+
+	// Only frames which exist get resolved.
+	let node = &frames[key];
+	if visiting.iter().any(|frame| frame == key) {
+		return Err(FromSDFError::CyclicFrame {
+			frame: key.to_string(),
+			line: node.element.line,
+		});
+	}
+
+	if !(frames.contains_key(&node.relative_to) || resolved.contains_key(&node.relative_to)) {
+		return Err(FromSDFError::UnknownFrame {
+			element: node.reference_element.name.clone(),
+			frame: node.relative_to.clone(),
+			line: node.reference_element.line,
+		});
+	}
+
+	visiting.push(key.to_string());
+	let pose = resolve_frame(&node.relative_to, frames, resolved, visiting)? * node.pose;
+	visiting.pop();
+
+	resolved.insert(key.to_string(), pose);
+	Ok(pose)
+}
+
+/// The intermediate result of reading a SDFormat description.
+struct SDFDescription {
+	name: String,
+	root: LinkBuilder,
+	warnings: Vec<SDFWarning>,
+}
+
+/// Reads the [`Robot`] described by the `<model>` in the SDFormat description `sdf`.
+///
+/// Returns the `Robot` with the [`SDFWarning`]s for everything which could not be represented.
+/// See the [module documentation](self) for how the description is interpreted.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{from_rdf::from_sdf::from_sdf, KinematicInterface};
+/// let description = r#"<?xml version="1.0"?>
+/// <sdf version="1.9">
+///   <model name="my-robot">
+///     <link name="base"/>
+///     <joint name="base_to_arm" type="revolute">
+///       <pose relative_to="base">0 0 0.5 0 0 0</pose>
+///       <parent>base</parent>
+///       <child>arm</child>
+///       <axis><xyz>0 0 1</xyz></axis>
+///     </joint>
+///     <link name="arm">
+///       <pose relative_to="base_to_arm"/>
+///       <sensor name="camera" type="camera"/>
+///     </link>
+///   </model>
+/// </sdf>"#;
+///
+/// let (robot, warnings) = from_sdf(description).unwrap();
+///
+/// assert_eq!(robot.name(), "my-robot");
+/// assert!(robot.get_joint("base_to_arm").is_some());
+/// assert_eq!(
+///     warnings.first().map(ToString::to_string).as_deref(),
+///     Some("<sensor> at line 13 can not be represented and is ignored")
+/// );
+/// ```
+pub fn from_sdf(sdf: &str) -> Result<(Robot, Vec<SDFWarning>), FromSDFError> {
+	let description = read_sdf(sdf)?;
+	Ok((
+		description.root.build_tree().to_robot(description.name),
+		description.warnings,
+	))
+}
+
+/// Reads the `<model>` in the SDFormat description `sdf` as a [`Chained<LinkBuilder>`], starting at the root link.
+///
+/// This can be used to attach a described part (e.g. a gripper) to another tree.
+/// The name of the `<model>` is not kept, since a [`Chained<LinkBuilder>`] can not contain it.
+///
+/// See [`from_sdf`] for more information.
+pub fn chain_from_sdf(sdf: &str) -> Result<(Chained<LinkBuilder>, Vec<SDFWarning>), FromSDFError> {
+	let description = read_sdf(sdf)?;
+	Ok((Chained(description.root), description.warnings))
+}
+
+/// Reads the SDFormat description and assembles the tree of builders.
+fn read_sdf(sdf: &str) -> Result<SDFDescription, FromSDFError> {
+	let root = XMLElement::parse(sdf)?;
+
+	if root.name != "sdf" {
+		return Err(FromSDFError::NotSDF {
+			element: root.name,
+			line: root.line,
+		});
+	}
+
+	let mut sdf_context = FromSDFContext::default();
+	sdf_context.warn_unsupported(&root, &["model"]);
+
+	let model = match root.children_named("model").collect::<Vec<_>>().as_slice() {
+		[] => return Err(FromSDFError::MissingModel { line: root.line }),
+		[model] => *model,
+		_ => return Err(FromSDFError::MultipleModels { line: root.line }),
+	};
+	let name = escape_group_id_delimiters(required_attribute(model, "name")?);
+
+	let mut content = ModelContent::default();
+	collect_model(model, "", &mut content, &mut sdf_context)?;
+
+	// The top level model is placed in the world by its own pose.
+	content.frames.insert(
+		String::from(MODEL_FRAME),
+		FrameNode {
+			element: model,
+			relative_to: String::from(WORLD_FRAME),
+			reference_element: model,
+			pose: to_isometry(
+				&model
+					.child("pose")
+					.map(|pose| Transform::from_sdf(pose, &mut sdf_context))
+					.transpose()?
+					.unwrap_or_default(),
+			),
+		},
+	);
+
+	let link_keys = content
+		.links
+		.iter()
+		.map(|(scope, link)| required_attribute(link, "name").map(|name| scoped_name(scope, name)))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	// The scoped names of the parent and child links of the joints, and the joint itself.
+	let mut connections: Vec<(String, String, String)> = Vec::new();
+	for (scope, joint) in content.joints.iter() {
+		let joint_name = scoped_name(scope, required_attribute(joint, "name")?);
+		let [parent, child] = ["parent", "child"]
+			.map(|tag| required_child(joint, tag).map(|element| frame_key(scope, &element.text)));
+		let (parent, child) = (parent?, child?);
+
+		if parent == WORLD_FRAME {
+			sdf_context.warn(SDFWarning::WorldJoint {
+				joint: joint_name,
+				line: joint.line,
+			});
+			continue;
+		}
+
+		for link in [&parent, &child] {
+			if !link_keys.contains(link) {
+				return Err(FromSDFError::UnknownLink {
+					joint: joint_name,
+					link: link.clone(),
+					line: joint.line,
+				});
+			}
+		}
+
+		if connections.iter().any(|(_, other, _)| other == &child) {
+			return Err(FromSDFError::MultipleParents {
+				link: child,
+				line: joint.line,
+			});
+		}
+
+		connections.push((parent, child, joint_name));
+	}
+
+	let mut frames = HashMap::from([(String::from(WORLD_FRAME), Isometry3::identity())]);
+	for key in content.frame_order.iter() {
+		resolve_frame(key, &content.frames, &mut frames, &mut Vec::new())?;
+	}
+	sdf_context.frames = frames;
+
+	// A `Link` in this crate shares the frame of its parent `Joint`.
+	let link_frame = |link: &str, frames: &HashMap<String, Isometry3<f64>>| {
+		connections
+			.iter()
+			.find(|(_, child, _)| child == link)
+			.map_or(frames[link], |(_, _, joint)| frames[joint])
+	};
+
+	let mut links: HashMap<String, (Option<LinkBuilder>, usize)> = HashMap::new();
+	let mut link_order = Vec::new();
+	for ((scope, link), key) in content.links.iter().zip(link_keys) {
+		sdf_context.scope = scope.clone();
+		sdf_context.frame = link_frame(&key, &sdf_context.frames);
+		sdf_context.default_frame = key.clone();
+		let link_builder = LinkBuilder::from_sdf(link, &mut sdf_context)?;
+
+		link_order.push(link_builder.name().clone());
+		links.insert(link_builder.name().clone(), (Some(link_builder), link.line));
+	}
+
+	let mut joints: Vec<JointDescription> = Vec::new();
+	for (scope, joint) in content.joints.iter() {
+		let key = scoped_name(scope, required_attribute(joint, "name")?);
+		let Some((parent, child, _)) = connections.iter().find(|(_, _, other)| other == &key)
+		else {
+			// Joints connecting to the world are skipped.
+			continue;
+		};
+
+		sdf_context.scope = scope.clone();
+		sdf_context.frame = link_frame(parent, &sdf_context.frames);
+		sdf_context.default_frame = child.clone();
+		let builder = JointBuilder::from_sdf(joint, &mut sdf_context)?;
+
+		joints.push(JointDescription {
+			builder,
+			parent: crate_name(parent),
+			child: crate_name(child),
+			line: joint.line,
+		});
+	}
+
+	for joint in joints.iter() {
+		if let Some(mimic) = joint.builder.mimic() {
+			if !joints
+				.iter()
+				.any(|other| other.builder.name() == &mimic.joint_name)
+			{
+				return Err(FromSDFError::UnknownMimicJoint {
+					joint: joint.builder.name().display(),
+					mimicked_joint: mimic.joint_name.display(),
+					line: joint.line,
+				});
+			}
+		}
+	}
+
+	if let Some(joint) = find_mimic_cycle(&joints) {
+		return Err(FromSDFError::MimicCycle {
+			joint: joint.builder.name().display(),
+			line: joint.line,
+		});
+	}
+
+	let roots: Vec<&String> = link_order
+		.iter()
+		.filter(|link| !joints.iter().any(|joint| &&joint.child == link))
+		.collect();
+
+	let root = match roots.as_slice() {
+		[] => return Err(FromSDFError::NoRootLink),
+		[root] => (*root).clone(),
+		_ => {
+			return Err(FromSDFError::MultipleRootLinks(
+				roots.iter().map(|link| link.display()).collect(),
+			))
+		}
+	};
+
+	let mut joints = joints.into_iter().map(Some).collect();
+	let root = assemble_link(&root, &mut links, &mut joints);
+
+	// All links which are not taken at this point, are unreachable from the root.
+	if let Some((link, (_, line))) = link_order
+		.iter()
+		.filter_map(|link| links.get_key_value(link))
+		.find(|(_, (builder, _))| builder.is_some())
+	{
+		return Err(FromSDFError::KinematicLoop {
+			link: link.display(),
+			line: *line,
+		});
+	}
+
+	Ok(SDFDescription {
+		name,
+		root,
+		warnings: sdf_context.warnings,
+	})
+}
+
+/// Rotates the `vector` expressed in the frame with pose `from` to the frame with pose `to`.
+pub(crate) fn express_in(
+	vector: (Float, Float, Float),
+	from: &Isometry3<f64>,
+	to: &Isometry3<f64>,
+) -> (Float, Float, Float) {
+	let vector = (to.rotation.inverse() * from.rotation)
+		* Vector3::new(vector.0.into(), vector.1.into(), vector.2.into());
+	(
+		remove_noise(vector.x),
+		remove_noise(vector.y),
+		remove_noise(vector.z),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{chain_from_sdf, from_sdf, FromSDFError, SDFWarning};
+	use crate::{
+		cluster_objects::KinematicInterface,
+		identifiers::{GroupID, GroupIDChanger},
+	};
+	use test_log::test;
+
+	/// Wraps the `model` content in a SDFormat document.
+	fn sdf(model: &str) -> String {
+		format!(
+			r#"<?xml version="1.0"?><sdf version="1.9"><model name="test">{model}</model></sdf>"#
+		)
+	}
+
+	#[test]
+	fn nested_model() {
+		let (robot, warnings) = from_sdf(&sdf(r#"
+			<link name="arm"/>
+			<joint name="arm_to_gripper" type="fixed">
+				<parent>arm</parent>
+				<child>gripper::palm</child>
+			</joint>
+			<model name="gripper">
+				<pose>0 0 1 0 0 0</pose>
+				<link name="palm"/>
+				<joint name="finger_joint" type="prismatic">
+					<parent>palm</parent>
+					<child>finger</child>
+					<axis><xyz>1 0 0</xyz><limit><lower>0</lower><upper>0.1</upper></limit></axis>
+				</joint>
+				<link name="finger">
+					<pose relative_to="__model__">0.5 0 0 0 0 0</pose>
+				</link>
+			</model>"#))
+		.unwrap();
+
+		assert!(warnings.is_empty());
+		assert!(robot.get_link("[[gripper]]::palm").is_some());
+		assert_eq!(
+			robot
+				.get_joint("[[gripper]]::finger_joint")
+				.unwrap()
+				.read()
+				.unwrap()
+				.name()
+				.display(),
+			"gripper::finger_joint"
+		);
+
+		let palm = robot.get_joint("arm_to_gripper").unwrap();
+		assert_eq!(
+			palm.read().unwrap().transform().translation,
+			Some((0., 0., 1.))
+		);
+		let finger = robot.get_joint("[[gripper]]::finger_joint").unwrap();
+		assert_eq!(
+			finger.read().unwrap().transform().translation,
+			Some((0.5, 0., 0.))
+		);
+
+		let (mut gripper, _) = chain_from_sdf(&sdf(r#"
+			<model name="gripper">
+				<link name="palm"/>
+			</model>"#))
+		.unwrap();
+		gripper.change_group_id("left_gripper").unwrap();
+		assert_eq!(gripper.0.name(), "[[left_gripper]]::palm");
+	}
+
+	#[test]
+	fn warnings() {
+		let (robot, warnings) = from_sdf(&sdf(r#"
+			<static>false</static>
+			<link name="base">
+				<sensor name="camera" type="camera"/>
+				<visual name="floor"><geometry><plane><normal>0 0 1</normal></plane></geometry></visual>
+				<visual name="box">
+					<geometry><box><size>1 1 1</size></box></geometry>
+					<material><script><name>Gazebo/Grey</name></script></material>
+				</visual>
+			</link>
+			<frame name="tip" attached_to="base"/>
+			<joint name="to_world" type="fixed">
+				<parent>world</parent>
+				<child>base</child>
+			</joint>
+			<joint name="ball" type="ball">
+				<parent>base</parent>
+				<child>head</child>
+			</joint>
+			<link name="head"/>"#))
+		.unwrap();
+
+		assert_eq!(
+			warnings,
+			vec![
+				SDFWarning::UnsupportedElement {
+					element: String::from("static"),
+					line: 2
+				},
+				SDFWarning::FrameNotKept {
+					frame: String::from("tip"),
+					line: 11
+				},
+				SDFWarning::WorldJoint {
+					joint: String::from("to_world"),
+					line: 12
+				},
+				SDFWarning::UnsupportedElement {
+					element: String::from("sensor"),
+					line: 4
+				},
+				SDFWarning::UnsupportedGeometry {
+					shape: String::from("plane"),
+					line: 5
+				},
+				SDFWarning::UnsupportedElement {
+					element: String::from("script"),
+					line: 8
+				},
+				SDFWarning::UnsupportedMaterial { line: 8 },
+				SDFWarning::UnsupportedJointType {
+					joint: String::from("ball"),
+					joint_type: String::from("ball"),
+					line: 16
+				},
+			]
+		);
+
+		let base = robot.get_link("base").unwrap();
+		assert_eq!(base.read().unwrap().visuals().len(), 1);
+		assert!(base.read().unwrap().visuals()[0].material().is_none());
+		assert_eq!(
+			robot
+				.get_joint("ball")
+				.unwrap()
+				.read()
+				.unwrap()
+				.joint_type(),
+			crate::JointType::Fixed
+		);
+	}
+
+	#[test]
+	fn axis_expressed_in() {
+		let (robot, _) = from_sdf(&sdf(r#"
+			<link name="base"/>
+			<joint name="joint" type="revolute">
+				<pose relative_to="base">0 0 0 0 0 1.5707963267948966</pose>
+				<parent>base</parent>
+				<child>arm</child>
+				<axis>
+					<xyz expressed_in="base">1 0 0</xyz>
+					<limit><lower>-1</lower><upper>1</upper><effort>5</effort></limit>
+				</axis>
+			</joint>
+			<link name="arm">
+				<pose relative_to="joint"/>
+			</link>"#))
+		.unwrap();
+
+		let joint = robot.get_joint("joint").unwrap();
+		let joint = joint.read().unwrap();
+		assert_eq!(joint.joint_type(), crate::JointType::Revolute);
+		assert_eq!(joint.axis(), Some((0., -1., 0.)));
+		let limit = *joint.rebuild().limit().unwrap();
+		assert_eq!(
+			(limit.lower, limit.upper, limit.effort, limit.velocity),
+			(Some(-1.), Some(1.), 5., -1.)
+		);
+	}
+
+	#[test]
+	fn continuous_revolute() {
+		let (robot, _) = from_sdf(&sdf(r#"
+			<link name="base"/>
+			<joint name="wheel_joint" type="revolute">
+				<parent>base</parent>
+				<child>wheel</child>
+				<axis>
+					<xyz>0 1 0</xyz>
+					<limit><lower>-1e16</lower><upper>1e16</upper></limit>
+				</axis>
+			</joint>
+			<link name="wheel"/>"#))
+		.unwrap();
+
+		let joint = robot.get_joint("wheel_joint").unwrap();
+		let joint = joint.read().unwrap();
+		assert_eq!(joint.joint_type(), crate::JointType::Continuous);
+		assert!(joint.rebuild().limit().is_none());
+	}
+
+	#[test]
+	fn not_sdf() {
+		assert!(matches!(
+			from_sdf(r#"<robot name="test"/>"#),
+			Err(FromSDFError::NotSDF { line: 1, .. })
+		));
+	}
+
+	#[test]
+	fn unknown_frame() {
+		assert!(matches!(
+			from_sdf(&sdf(r#"<link name="base"><pose relative_to="nowhere"/></link>"#)),
+			Err(FromSDFError::UnknownFrame { frame, .. }) if frame == "nowhere"
+		));
+	}
+
+	#[test]
+	fn cyclic_frame() {
+		assert!(matches!(
+			from_sdf(&sdf(r#"
+				<link name="a"><pose relative_to="b"/></link>
+				<link name="b"><pose relative_to="a"/></link>
+				<joint name="j" type="fixed"><parent>a</parent><child>b</child></joint>"#)),
+			Err(FromSDFError::CyclicFrame { .. })
+		));
+	}
+
+	#[test]
+	fn unknown_link() {
+		assert!(matches!(
+			from_sdf(&sdf(r#"
+				<link name="base"/>
+				<joint name="j" type="fixed"><parent>base</parent><child>arm</child></joint>"#)),
+			Err(FromSDFError::UnknownLink { link, .. }) if link == "arm"
+		));
+	}
+
+	#[test]
+	fn mimic_cycle() {
+		assert!(matches!(
+			from_sdf(&sdf(r#"
+				<link name="a"/>
+				<link name="b"/>
+				<joint name="j" type="revolute"><parent>a</parent><child>b</child><axis><xyz>1 0 0</xyz><mimic joint="j"/></axis></joint>"#)),
+			Err(FromSDFError::MimicCycle { joint, line: 4 }) if joint == "j"
+		));
+		assert!(matches!(
+			from_sdf(&sdf(r#"
+				<link name="a"/>
+				<link name="b"/>
+				<link name="c"/>
+				<joint name="j" type="revolute"><parent>a</parent><child>b</child><axis><xyz>1 0 0</xyz><mimic joint="k"/></axis></joint>
+				<joint name="k" type="revolute"><parent>b</parent><child>c</child><axis><xyz>1 0 0</xyz><mimic joint="j"/></axis></joint>"#)),
+			Err(FromSDFError::MimicCycle { joint, line: 5 }) if joint == "j"
+		));
+	}
+
+	#[test]
+	fn unconnected_nested_model() {
+		assert!(matches!(
+			from_sdf(&sdf(r#"<link name="base"/><model name="other"><link name="base"/></model>"#)),
+			Err(FromSDFError::MultipleRootLinks(roots)) if roots == vec!["base", "other::base"]
+		));
+	}
+
+	#[cfg(feature = "urdf")]
+	mod urdf {
+		use super::{from_sdf, sdf, test};
+		use crate::{
+			from_rdf::from_urdf::from_urdf,
+			to_rdf::{
+				to_sdf::{to_sdf, SDFConfig, SDFVersion},
+				to_urdf::{to_urdf, URDFConfig},
+				xml_writer_to_string, XMLMode,
+			},
+		};
+
+		fn urdf(robot: &crate::Robot) -> String {
+			xml_writer_to_string(
+				to_urdf(
+					robot,
+					URDFConfig {
+						xml_mode: XMLMode::Indent(' ', 2),
+						..Default::default()
+					},
+				)
+				.unwrap(),
+			)
+		}
+
+		/// Asserts that the URDF descriptions are equal, apart from the noise of resolving the poses in the numbers.
+		fn assert_urdf_eq(actual: &str, expected: &str) {
+			let tokens = |urdf: &str| {
+				urdf.split(|c: char| c.is_whitespace() || c == '"')
+					.map(str::to_string)
+					.collect::<Vec<_>>()
+			};
+			let (actual_tokens, expected_tokens) = (tokens(actual), tokens(expected));
+
+			assert!(
+				actual_tokens.len() == expected_tokens.len()
+					&& actual_tokens.iter().zip(expected_tokens.iter()).all(
+						|(actual, expected)| match (actual.parse::<f64>(), expected.parse::<f64>())
+						{
+							(Ok(actual), Ok(expected)) => (actual - expected).abs() < 1e-9,
+							_ => actual == expected,
+						}
+					),
+				"{actual}\n!=\n{expected}"
+			);
+		}
+
+		const DESCRIPTION: &str = r#"<robot name="arm">
+  <link name="base">
+    <inertial>
+      <origin xyz="0 0 0.1"/>
+      <mass value="2"/>
+      <inertia ixx="0.1" ixy="0" ixz="0" iyy="0.1" iyz="0" izz="0.2"/>
+    </inertial>
+    <visual name="base_visual">
+      <origin xyz="0 0 0.2"/>
+      <geometry><box size="1 1 0.2"/></geometry>
+      <material><color rgba="0 0 1 1"/></material>
+    </visual>
+    <collision name="base_collision">
+      <geometry><mesh filename="package://arm/meshes/base.stl" scale="2 2 2"/></geometry>
+    </collision>
+  </link>
+  <joint name="shoulder" type="revolute">
+    <origin xyz="0 0 0.2" rpy="0 0 1.5"/>
+    <parent link="base"/>
+    <child link="upper_arm"/>
+    <axis xyz="0 1 0"/>
+    <dynamics damping="0.5"/>
+    <limit lower="-1" upper="1" effort="10" velocity="2"/>
+  </joint>
+  <link name="upper_arm">
+    <visual name="upper_arm_visual">
+      <geometry><cylinder radius="0.1" length="1"/></geometry>
+      <material><texture filename="package://arm/textures/arm.png"/></material>
+    </visual>
+  </link>
+  <joint name="elbow" type="continuous">
+    <origin xyz="1 0 0"/>
+    <parent link="upper_arm"/>
+    <child link="forearm"/>
+    <axis xyz="0 0 1"/>
+    <mimic joint="shoulder" multiplier="2"/>
+  </joint>
+  <link name="forearm">
+    <collision name="forearm_collision">
+      <geometry><sphere radius="0.5"/></geometry>
+    </collision>
+  </link>
+</robot>"#;
+
+		#[test]
+		fn round_trip() {
+			let robot = from_urdf(DESCRIPTION).unwrap();
+			let expected = urdf(&robot);
+
+			for sdf_version in [SDFVersion::V1_6, SDFVersion::V1_9, SDFVersion::V1_11] {
+				let sdf = xml_writer_to_string(
+					to_sdf(
+						&robot,
+						SDFConfig {
+							sdf_version,
+							..Default::default()
+						},
+					)
+					.unwrap(),
+				);
+				let (read_robot, warnings) = from_sdf(&sdf).unwrap();
+
+				assert!(warnings.is_empty());
+				// Mimic joints are only supported by SDFormat 1.11 and newer.
+				match sdf_version {
+					SDFVersion::V1_11 => assert_urdf_eq(&urdf(&read_robot), &expected),
+					_ => assert_urdf_eq(
+						&urdf(&read_robot),
+						&expected.replace("\n    <mimic joint=\"shoulder\" multiplier=\"2\"/>", ""),
+					),
+				}
+			}
+		}
+
+		#[test]
+		fn link_frame_offset() {
+			// The link frame of `arm` is rotated a quarter turn around Z and shifted relative to its joint.
+			let (robot, _) = from_sdf(&sdf(r#"
+				<link name="base"/>
+				<joint name="joint" type="fixed">
+					<pose relative_to="base">1 0 0 0 0 0</pose>
+					<parent>base</parent>
+					<child>arm</child>
+				</joint>
+				<link name="arm">
+					<pose>1 0 0.5 0 0 1.5707963267948966</pose>
+					<visual name="visual">
+						<pose>1 0 0 0 0 0</pose>
+						<geometry><sphere><radius>1</radius></sphere></geometry>
+					</visual>
+				</link>"#))
+			.unwrap();
+
+			assert!(urdf(&robot).contains(
+				r#"<joint name="joint" type="fixed">
+    <origin xyz="1 0 0"/>"#
+			));
+			// The resolved yaw is rounded to the precision of `Float`, double precision keeps the noise of the calculation.
+			#[cfg(not(feature = "f64"))]
+			let yaw = "1.5707964";
+			#[cfg(feature = "f64")]
+			let yaw = "1.5707963267948963";
+			assert!(urdf(&robot).contains(&format!(
+				r#"<visual name="visual">
+      <origin xyz="0 1 0.5" rpy="0 0 {yaw}"/>"#
+			)));
+		}
+	}
+}
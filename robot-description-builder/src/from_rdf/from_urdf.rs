@@ -0,0 +1,857 @@
+//! The infrastructure to read a `Robot` from the Universal Robot Description Format (URDF).
+//!
+//! A description can be read as a [`Robot`] with [`from_urdf`], or as a [`Chained<LinkBuilder>`] with [`chain_from_urdf`].
+//!
+//! # Limitations
+//! - Elements which are not a part of the [URDF specification](http://wiki.ros.org/urdf/XML) (e.g. `<gazebo>`) are ignored.
+//! - The bounding box of a [`MeshGeometry`](crate::link_data::geometry::MeshGeometry) can not be determined from the description and is set to `(0, 0, 0)`.
+//! - Named [`Material`s](crate::material::Material) which are not used by any [`Visual`](crate::link_data::Visual) are not kept.
+//! - A `<material>` with both a `<color>` and a `<texture>` is read as the color.
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::{
+	assemble_link, find_mimic_cycle, xml_element::XMLElement, JointDescription, XMLReadError,
+};
+use crate::{
+	chained::Chained,
+	cluster_objects::{kinematic_data_errors::AddTransmissionError, KinematicInterface, Robot},
+	identifiers::{escape_group_id_delimiters, GroupID},
+	joint::JointBuilder,
+	link::builder::LinkBuilder,
+	material::MaterialDescriptor,
+	transform::Transform,
+	transmission::{
+		transmission_builder_state::{WithActuator, WithJoints},
+		TransmissionBuilder,
+	},
+	Float,
+};
+
+/// An error, which can occur while reading a URDF description.
+///
+/// Most variants contain the `line` of the offending element to make it easy to find the problem.
+#[derive(Debug, Error)]
+pub enum FromURDFError {
+	/// The description is not valid XML.
+	#[error(transparent)]
+	XML(#[from] XMLReadError),
+	/// The root element of the description is not a `<robot>` element.
+	#[error("Expected the root element to be <robot>, found <{element}> at line {line}")]
+	NotARobot { element: String, line: usize },
+	/// A required attribute is missing.
+	#[error("<{element}> at line {line} is missing the required attribute '{attribute}'")]
+	MissingAttribute {
+		element: String,
+		attribute: String,
+		line: usize,
+	},
+	/// A required child element is missing.
+	#[error("<{element}> at line {line} is missing the required element <{child}>")]
+	MissingElement {
+		element: String,
+		child: String,
+		line: usize,
+	},
+	/// The value of an attribute could not be interpreted.
+	#[error("<{element}> at line {line} has an invalid value \"{value}\" for the attribute '{attribute}'")]
+	InvalidValue {
+		element: String,
+		attribute: String,
+		value: String,
+		line: usize,
+	},
+	/// The text content of an element could not be interpreted.
+	#[error("<{element}> at line {line} has invalid content \"{content}\"")]
+	InvalidContent {
+		element: String,
+		content: String,
+		line: usize,
+	},
+	/// The `<geometry>` element does not contain a supported shape.
+	#[error("<geometry> at line {line} does not contain a supported shape")]
+	UnsupportedGeometry { line: usize },
+	/// A `<material>` refers to a named `Material`, which has not been defined before.
+	#[error("The material '{name}' at line {line} is referenced, but has not been defined")]
+	UnknownMaterial { name: String, line: usize },
+	/// A `<material>` is defined multiple times with different data.
+	#[error("The material '{name}' at line {line} conflicts with an earlier definition")]
+	ConflictingMaterial { name: String, line: usize },
+	/// Multiple `<link>` elements have the same name.
+	#[error("The link name '{name}' at line {line} is already in use")]
+	DuplicateLink { name: String, line: usize },
+	/// Multiple `<joint>` elements have the same name.
+	#[error("The joint name '{name}' at line {line} is already in use")]
+	DuplicateJoint { name: String, line: usize },
+	/// A `<joint>` refers to a `<link>` which does not exist.
+	#[error("The joint '{joint}' at line {line} refers to the unknown link '{link}'")]
+	UnknownLink {
+		joint: String,
+		link: String,
+		line: usize,
+	},
+	/// A `<link>` is the child of multiple `<joint>` elements.
+	#[error("The link '{link}' is the child of multiple joints, the joint at line {line} is the second parent")]
+	MultipleParents { link: String, line: usize },
+	/// A `<joint>` mimics a `<joint>` which does not exist.
+	#[error("The joint '{joint}' at line {line} mimics the unknown joint '{mimicked_joint}'")]
+	UnknownMimicJoint {
+		joint: String,
+		mimicked_joint: String,
+		line: usize,
+	},
+	/// A `<joint>` mimics itself, directly or through a cycle of mimicking `<joint>`s.
+	#[error("The joint '{joint}' at line {line} mimics itself through a cycle of mimics")]
+	MimicCycle { joint: String, line: usize },
+	/// The description does not contain a root `<link>`.
+	///
+	/// This happens when the description does not contain any links, or every link has a parent.
+	#[error("The description has no root link")]
+	NoRootLink,
+	/// The description contains multiple `<link>` elements without a parent.
+	#[error("The description has multiple root links: {0:?}")]
+	MultipleRootLinks(Vec<String>),
+	/// The `<link>` is a part of a kinematic loop and can therefore not be reached from the root link.
+	#[error("The link '{link}' at line {line} is a part of a kinematic loop")]
+	KinematicLoop { link: String, line: usize },
+	/// A `<transmission>` refers to a `<joint>` which does not exist.
+	#[error(
+		"The transmission '{transmission}' at line {line} refers to the unknown joint '{joint}'"
+	)]
+	UnknownTransmissionJoint {
+		transmission: String,
+		joint: String,
+		line: usize,
+	},
+	/// A `<transmission>` could not be added to the `Robot`.
+	#[error("The transmission at line {line} could not be added: {source}")]
+	Transmission {
+		line: usize,
+		#[source]
+		source: AddTransmissionError,
+	},
+}
+
+/// The state which is shared while reading the elements of a URDF description.
+#[derive(Debug, Default)]
+pub(crate) struct FromURDFContext {
+	/// The named `Material`s defined so far, by their (escaped) name.
+	pub materials: HashMap<String, MaterialDescriptor>,
+}
+
+/// A trait to allow parts of a `Robot` to be read from the URDF format.
+///
+/// This is the counterpart of [`ToURDF`](crate::to_rdf::to_urdf::ToURDF).
+pub(crate) trait FromURDF: Sized {
+	/// Reads the element from its URDF representation.
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError>;
+}
+
+/// Gets the value of the required `attribute` of the `element`.
+pub(crate) fn required_attribute<'a>(
+	element: &'a XMLElement,
+	attribute: &str,
+) -> Result<&'a str, FromURDFError> {
+	element
+		.attribute(attribute)
+		.ok_or_else(|| FromURDFError::MissingAttribute {
+			element: element.name.clone(),
+			attribute: attribute.to_string(),
+			line: element.line,
+		})
+}
+
+/// Gets the required child element with the specified `name` of the `element`.
+pub(crate) fn required_child<'a>(
+	element: &'a XMLElement,
+	name: &str,
+) -> Result<&'a XMLElement, FromURDFError> {
+	element
+		.child(name)
+		.ok_or_else(|| FromURDFError::MissingElement {
+			element: element.name.clone(),
+			child: name.to_string(),
+			line: element.line,
+		})
+}
+
+/// Gets the value of the `name` attribute of the `element`, with the `GroupID` delimiters escaped.
+pub(crate) fn required_name(element: &XMLElement) -> Result<String, FromURDFError> {
+	required_attribute(element, "name").map(escape_group_id_delimiters)
+}
+
+/// Creates a [`FromURDFError::InvalidValue`] for the `attribute` of the `element`.
+pub(crate) fn invalid_value(element: &XMLElement, attribute: &str, value: &str) -> FromURDFError {
+	FromURDFError::InvalidValue {
+		element: element.name.clone(),
+		attribute: attribute.to_string(),
+		value: value.to_string(),
+		line: element.line,
+	}
+}
+
+/// Parses the optional number in the `attribute` of the `element`.
+pub(crate) fn optional_float(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Option<Float>, FromURDFError> {
+	element
+		.attribute(attribute)
+		.map(|value| {
+			value
+				.trim()
+				.parse()
+				.map_err(|_| invalid_value(element, attribute, value))
+		})
+		.transpose()
+}
+
+/// Parses the required number in the `attribute` of the `element`.
+pub(crate) fn required_float(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Float, FromURDFError> {
+	required_attribute(element, attribute)?;
+	// The attribute exists, so it is always `Some`.
+	optional_float(element, attribute).map(Option::unwrap_or_default)
+}
+
+/// Parses the optional whitespace separated list of `N` numbers in the `attribute` of the `element`.
+pub(crate) fn optional_float_array<const N: usize>(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Option<[Float; N]>, FromURDFError> {
+	element
+		.attribute(attribute)
+		.map(|value| {
+			let mut result = [0.; N];
+			let mut parts = value.split_whitespace();
+
+			for number in result.iter_mut() {
+				*number = parts
+					.next()
+					.and_then(|part| part.parse().ok())
+					.ok_or_else(|| invalid_value(element, attribute, value))?;
+			}
+
+			match parts.next() {
+				Some(_) => Err(invalid_value(element, attribute, value)),
+				None => Ok(result),
+			}
+		})
+		.transpose()
+}
+
+/// Parses the optional whitespace separated vector in the `attribute` of the `element`.
+pub(crate) fn optional_vector3(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<Option<(Float, Float, Float)>, FromURDFError> {
+	optional_float_array(element, attribute).map(|vector| vector.map(|[x, y, z]| (x, y, z)))
+}
+
+/// Parses the required whitespace separated vector in the `attribute` of the `element`.
+pub(crate) fn required_vector3(
+	element: &XMLElement,
+	attribute: &str,
+) -> Result<(Float, Float, Float), FromURDFError> {
+	required_attribute(element, attribute)?;
+	// The attribute exists, so it is always `Some`.
+	optional_vector3(element, attribute).map(Option::unwrap_or_default)
+}
+
+/// Parses the number in the text content of the `element`.
+pub(crate) fn content_float(element: &XMLElement) -> Result<Float, FromURDFError> {
+	element
+		.text
+		.trim()
+		.parse()
+		.map_err(|_| FromURDFError::InvalidContent {
+			element: element.name.clone(),
+			content: element.text.clone(),
+			line: element.line,
+		})
+}
+
+/// Reads the optional `<origin>` child element of the `element`.
+///
+/// An `<origin>` without any attributes is read as `None`.
+pub(crate) fn optional_transform(
+	element: &XMLElement,
+	urdf_context: &mut FromURDFContext,
+) -> Result<Option<Transform>, FromURDFError> {
+	Ok(element
+		.child("origin")
+		.map(|origin| Transform::from_urdf(origin, urdf_context))
+		.transpose()?
+		.filter(Transform::contains_some))
+}
+
+/// The intermediate result of reading a URDF description.
+pub(crate) struct URDFDescription {
+	name: String,
+	pub root: LinkBuilder,
+	transmissions: Vec<(TransmissionBuilder<WithJoints, WithActuator>, usize)>,
+}
+
+impl URDFDescription {
+	/// Builds the `Robot` and adds the transmissions to it.
+	pub fn into_robot(self) -> Result<Robot, FromURDFError> {
+		let robot = self.root.build_tree().to_robot(self.name);
+
+		for (transmission, line) in self.transmissions {
+			robot
+				.try_add_transmission(transmission)
+				.map_err(|source| FromURDFError::Transmission { line, source })?;
+		}
+
+		Ok(robot)
+	}
+}
+
+/// Reads the [`Robot`] described by the URDF in `urdf`.
+///
+/// All the information that can be written with [`to_urdf`](crate::to_rdf::to_urdf::to_urdf) is read,
+/// such that `to_urdf(from_urdf(description))` describes the same `Robot` as `description`.
+///
+/// Names are read literally, so the [`GroupID`](crate::identifiers::GroupID) delimiters in them get escaped.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     from_rdf::from_urdf::from_urdf,
+/// #     to_rdf::{to_urdf::{to_urdf, URDFConfig}, xml_writer_to_string},
+/// #     KinematicInterface,
+/// # };
+/// let description = r#"<?xml version="1.0"?>
+/// <robot name="my-robot">
+///   <link name="base"/>
+///   <joint name="base_to_arm" type="continuous">
+///     <parent link="base"/>
+///     <child link="arm"/>
+///     <axis xyz="0 0 1"/>
+///   </joint>
+///   <link name="arm">
+///     <visual>
+///       <geometry>
+///         <cylinder radius="0.1" length="1"/>
+///       </geometry>
+///     </visual>
+///   </link>
+/// </robot>"#;
+///
+/// let robot = from_urdf(description).unwrap();
+///
+/// assert_eq!(robot.name(), "my-robot");
+/// assert!(robot.get_joint("base_to_arm").is_some());
+/// assert_eq!(
+///     xml_writer_to_string(to_urdf(&robot, URDFConfig::default()).unwrap()),
+///     "\u{feff}<?xml version=\"1.0\"?><robot name=\"my-robot\"><link name=\"base\"></link><joint name=\"base_to_arm\" type=\"continuous\"><parent link=\"base\"/><child link=\"arm\"/><axis xyz=\"0 0 1\"/></joint><link name=\"arm\"><visual><geometry><cylinder radius=\"0.1\" length=\"1\"/></geometry></visual></link></robot>"
+/// );
+/// ```
+pub fn from_urdf(urdf: &str) -> Result<Robot, FromURDFError> {
+	read_urdf(&XMLElement::parse(urdf)?)?.into_robot()
+}
+
+/// Reads the URDF in `urdf` as a [`Chained<LinkBuilder>`], starting at the root link.
+///
+/// This can be used to attach a described part (e.g. a gripper) to another tree.
+/// The name of the `<robot>` and the `<transmission>` elements are not kept, since a [`Chained<LinkBuilder>`] can not contain them.
+///
+/// See [`from_urdf`] for more information.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{from_rdf::from_urdf::chain_from_urdf, prelude::*, Link, SmartJointBuilder};
+/// let gripper = chain_from_urdf(
+///     r#"<robot name="gripper"><link name="palm"/><joint name="finger_joint" type="fixed"><parent link="palm"/><child link="finger"/></joint><link name="finger"/></robot>"#,
+/// )
+/// .unwrap();
+///
+/// let tree = Link::builder("arm").build_tree();
+/// tree.get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(SmartJointBuilder::new_fixed("arm_to_palm"), gripper)
+///     .unwrap();
+///
+/// assert!(tree.get_link("finger").is_some());
+/// ```
+pub fn chain_from_urdf(urdf: &str) -> Result<Chained<LinkBuilder>, FromURDFError> {
+	Ok(Chained(read_urdf(&XMLElement::parse(urdf)?)?.root))
+}
+
+/// Reads the `<robot>` element of a URDF description and assembles the tree of builders.
+pub(crate) fn read_urdf(robot: &XMLElement) -> Result<URDFDescription, FromURDFError> {
+	if robot.name != "robot" {
+		return Err(FromURDFError::NotARobot {
+			element: robot.name.clone(),
+			line: robot.line,
+		});
+	}
+
+	let name = required_name(robot)?;
+	let mut urdf_context = FromURDFContext::default();
+
+	// Materials have to be read first, since they can be referenced before their definition.
+	for material in robot.children_named("material") {
+		// A top-level material has to be named, it registers itself in the `urdf_context`.
+		required_name(material)?;
+		MaterialDescriptor::from_urdf(material, &mut urdf_context)?;
+	}
+
+	let mut links: HashMap<String, (Option<LinkBuilder>, usize)> = HashMap::new();
+	let mut link_order = Vec::new();
+	for link in robot.children_named("link") {
+		let link_builder = LinkBuilder::from_urdf(link, &mut urdf_context)?;
+		let link_name = link_builder.name().clone();
+
+		if links.contains_key(&link_name) {
+			return Err(FromURDFError::DuplicateLink {
+				name: link_name.display(),
+				line: link.line,
+			});
+		}
+
+		link_order.push(link_name.clone());
+		links.insert(link_name, (Some(link_builder), link.line));
+	}
+
+	let mut joints: Vec<JointDescription> = Vec::new();
+	for joint in robot.children_named("joint") {
+		let builder = JointBuilder::from_urdf(joint, &mut urdf_context)?;
+
+		if joints
+			.iter()
+			.any(|other| other.builder.name() == builder.name())
+		{
+			return Err(FromURDFError::DuplicateJoint {
+				name: builder.name().display(),
+				line: joint.line,
+			});
+		}
+
+		let [parent, child] = ["parent", "child"].map(|tag| {
+			required_child(joint, tag)
+				.and_then(|element| required_attribute(element, "link"))
+				.map(escape_group_id_delimiters)
+		});
+		let (parent, child) = (parent?, child?);
+
+		for link in [&parent, &child] {
+			if !links.contains_key(link) {
+				return Err(FromURDFError::UnknownLink {
+					joint: builder.name().display(),
+					link: link.display(),
+					line: joint.line,
+				});
+			}
+		}
+
+		if joints.iter().any(|other| other.child == child) {
+			return Err(FromURDFError::MultipleParents {
+				link: child.display(),
+				line: joint.line,
+			});
+		}
+
+		joints.push(JointDescription {
+			builder,
+			parent,
+			child,
+			line: joint.line,
+		});
+	}
+
+	for joint in joints.iter() {
+		if let Some(mimic) = joint.builder.mimic() {
+			if !joints
+				.iter()
+				.any(|other| other.builder.name() == &mimic.joint_name)
+			{
+				return Err(FromURDFError::UnknownMimicJoint {
+					joint: joint.builder.name().display(),
+					mimicked_joint: mimic.joint_name.display(),
+					line: joint.line,
+				});
+			}
+		}
+	}
+
+	if let Some(joint) = find_mimic_cycle(&joints) {
+		return Err(FromURDFError::MimicCycle {
+			joint: joint.builder.name().display(),
+			line: joint.line,
+		});
+	}
+
+	let mut transmissions = Vec::new();
+	for transmission in robot.children_named("transmission") {
+		let builder = TransmissionBuilder::from_urdf(transmission, &mut urdf_context)?;
+
+		// `joints` always returns `Some` for a `TransmissionBuilder<WithJoints, _>`.
+		for transmission_joint in builder.joints().into_iter().flatten() {
+			if !joints
+				.iter()
+				.any(|joint| joint.builder.name() == transmission_joint.name())
+			{
+				return Err(FromURDFError::UnknownTransmissionJoint {
+					transmission: builder.name().display(),
+					joint: transmission_joint.name().display(),
+					line: transmission.line,
+				});
+			}
+		}
+
+		transmissions.push((builder, transmission.line));
+	}
+
+	let roots: Vec<&String> = link_order
+		.iter()
+		.filter(|link| !joints.iter().any(|joint| &&joint.child == link))
+		.collect();
+
+	let root = match roots.as_slice() {
+		[] => return Err(FromURDFError::NoRootLink),
+		[root] => (*root).clone(),
+		_ => {
+			return Err(FromURDFError::MultipleRootLinks(
+				roots.iter().map(|link| link.display()).collect(),
+			))
+		}
+	};
+
+	let mut joints = joints.into_iter().map(Some).collect();
+	let root = assemble_link(&root, &mut links, &mut joints);
+
+	// All links which are not taken at this point, are unreachable from the root.
+	if let Some((link, (_, line))) = link_order
+		.iter()
+		.filter_map(|link| links.get_key_value(link))
+		.find(|(_, (builder, _))| builder.is_some())
+	{
+		return Err(FromURDFError::KinematicLoop {
+			link: link.display(),
+			line: *line,
+		});
+	}
+
+	Ok(URDFDescription {
+		name,
+		root,
+		transmissions,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{chain_from_urdf, from_urdf, FromURDFError};
+	use crate::{
+		cluster_objects::KinematicInterface,
+		from_rdf::xml_element::XMLElement,
+		joint::SmartJointBuilder,
+		link::Link,
+		to_rdf::{
+			to_urdf::{to_urdf, URDFConfig},
+			xml_writer_to_string, XMLMode,
+		},
+	};
+	use test_log::test;
+
+	/// A description which uses (almost) everything which can be described.
+	const FULL_DESCRIPTION: &str = r#"<?xml version="1.0"?>
+<robot name="full[[robot]]">
+  <material name="blue">
+    <color rgba="0 0 0.8 1"/>
+  </material>
+  <material name="textured">
+    <texture filename="package://robot/textures/wood.png"/>
+  </material>
+  <link name="base_link">
+    <inertial>
+      <origin xyz="0 0 0.5" rpy="0 0 0"/>
+      <mass value="10"/>
+      <inertia ixx="0.4" ixy="0" ixz="0" iyy="0.4" iyz="0" izz="0.2"/>
+    </inertial>
+    <visual name="base_visual">
+      <origin xyz="0 0 0.1"/>
+      <geometry>
+        <box size="1 0.5 0.2"/>
+      </geometry>
+      <material name="blue"/>
+    </visual>
+    <visual>
+      <geometry>
+        <mesh filename="package://robot/meshes/base.dae" scale="1 1 1"/>
+      </geometry>
+      <material name="textured"/>
+    </visual>
+    <collision name="base_collision">
+      <origin rpy="0 0 1.57"/>
+      <geometry>
+        <cylinder radius="0.3" length="0.2"/>
+      </geometry>
+    </collision>
+  </link>
+  <joint name="shoulder" type="revolute">
+    <origin xyz="0 0 0.2" rpy="0 0.5 0"/>
+    <parent link="base_link"/>
+    <child link="upper_arm"/>
+    <axis xyz="0 1 0"/>
+    <calibration rising="0.1" falling="0.2"/>
+    <dynamics damping="0.7" friction="0.3"/>
+    <limit effort="30" velocity="1.5" lower="-1.57" upper="1.57"/>
+    <safety_controller soft_lower_limit="-1.5" soft_upper_limit="1.5" k_position="15" k_velocity="10"/>
+  </joint>
+  <link name="upper_arm">
+    <visual>
+      <geometry>
+        <sphere radius="0.1"/>
+      </geometry>
+      <material name="red">
+        <color rgba="1 0 0 1"/>
+      </material>
+    </visual>
+  </link>
+  <joint name="elbow" type="continuous">
+    <parent link="upper_arm"/>
+    <child link="forearm"/>
+    <axis xyz="0 1 0"/>
+    <mimic joint="shoulder" multiplier="-2" offset="0.1"/>
+  </joint>
+  <link name="forearm">
+    <visual>
+      <geometry>
+        <sphere radius="0.05"/>
+      </geometry>
+      <material>
+        <color rgba="0.5 0.5 0.5 1"/>
+      </material>
+    </visual>
+  </link>
+  <joint name="tool" type="fixed">
+    <parent link="base_link"/>
+    <child link="tool_link"/>
+  </joint>
+  <link name="tool_link"/>
+  <transmission name="shoulder_transmission">
+    <type>transmission_interface/SimpleTransmission</type>
+    <joint name="shoulder">
+      <hardwareInterface>hardware_interface/EffortJointInterface</hardwareInterface>
+    </joint>
+    <actuator name="shoulder_motor">
+      <mechanicalReduction>50</mechanicalReduction>
+    </actuator>
+  </transmission>
+</robot>"#;
+
+	/// Normalizes a URDF description, so descriptions of the same robot compare equal.
+	///
+	/// The line numbers are dropped, the attributes are sorted and named materials
+	/// are defined once at the top level, sorted by name, and referenced everywhere else.
+	fn normalize(urdf: &str) -> XMLElement {
+		fn visit(element: &mut XMLElement, materials: &mut Vec<XMLElement>) {
+			element.line = 0;
+			element.attributes.sort();
+			element
+				.children
+				.iter_mut()
+				.for_each(|child| visit(child, materials));
+
+			if element.name == "material"
+				&& element.attribute("name").is_some()
+				&& !element.children.is_empty()
+			{
+				materials.push(element.clone());
+				element.children.clear();
+			}
+		}
+
+		let mut robot = XMLElement::parse(urdf).unwrap();
+		let mut materials = Vec::new();
+		visit(&mut robot, &mut materials);
+
+		materials.sort_by(|a, b| a.attribute("name").cmp(&b.attribute("name")));
+		materials.dedup();
+		robot.children.retain(|child| child.name != "material");
+		robot.children.splice(0..0, materials);
+		robot
+	}
+
+	#[test]
+	fn round_trip() {
+		let config = URDFConfig {
+			xml_mode: XMLMode::Indent(' ', 2),
+			..Default::default()
+		};
+
+		let robot = from_urdf(FULL_DESCRIPTION).unwrap();
+		let first = xml_writer_to_string(to_urdf(&robot, config.clone()).unwrap());
+
+		assert_eq!(normalize(&first), normalize(FULL_DESCRIPTION));
+
+		let robot = from_urdf(&first).unwrap();
+		let second = xml_writer_to_string(to_urdf(&robot, config).unwrap());
+
+		assert_eq!(first, second);
+	}
+
+	#[test]
+	fn full_description() {
+		let robot = from_urdf(FULL_DESCRIPTION).unwrap();
+
+		assert_eq!(robot.name(), "full[\\[robot]\\]");
+		assert_eq!(robot.get_root_link().read().unwrap().name(), "base_link");
+		assert_eq!(robot.get_links().read().unwrap().len(), 4);
+		assert_eq!(robot.get_joints().read().unwrap().len(), 3);
+		assert!(robot.get_transmission("shoulder_transmission").is_some());
+
+		let elbow = robot.get_joint("elbow").unwrap().read().unwrap().rebuild();
+		let mimic = elbow.mimic().unwrap();
+		assert_eq!(mimic.joint_name, "shoulder");
+		assert_eq!(mimic.multiplier, Some(-2.));
+		assert_eq!(mimic.offset, Some(0.1));
+
+		let urdf = xml_writer_to_string(to_urdf(&robot, URDFConfig::default()).unwrap());
+		assert!(urdf.contains(r#"<robot name="full[[robot]]">"#));
+		assert!(urdf.contains(r#"<material name="red"><color rgba="1 0 0 1"/></material>"#));
+		assert!(urdf.contains(r#"<mimic joint="shoulder" multiplier="-2" offset="0.1"/>"#));
+		assert!(urdf.contains(r#"<limit effort="30" velocity="1.5" lower="-1.57" upper="1.57"/>"#));
+		assert!(urdf.contains("<mechanicalReduction>50</mechanicalReduction>"));
+	}
+
+	#[test]
+	fn chain_with_mimic() {
+		let chain = chain_from_urdf(FULL_DESCRIPTION).unwrap();
+		let tree = Link::builder("world").build_tree();
+
+		tree.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(SmartJointBuilder::new_fixed("world_to_base"), chain)
+			.unwrap();
+
+		assert!(tree.get_link("tool_link").is_some());
+		assert!(tree.get_transmission("shoulder_transmission").is_none());
+		// The mimic has to be resolved after the joints are registered in the tree.
+		assert_eq!(
+			xml_writer_to_string(to_urdf(&tree.to_robot("chain"), URDFConfig::default()).unwrap())
+				.matches(r#"<mimic joint="shoulder" multiplier="-2" offset="0.1"/>"#)
+				.count(),
+			1
+		);
+	}
+
+	#[test]
+	fn malformed_xml() {
+		assert!(matches!(
+			from_urdf("<robot name=\"r\">\n  <link name=\"a\">\n</robot>"),
+			Err(FromURDFError::XML(error)) if error.line() == 3
+		));
+	}
+
+	#[test]
+	fn not_a_robot() {
+		assert!(matches!(
+			from_urdf("<sdf/>"),
+			Err(FromURDFError::NotARobot { element, line: 1 }) if element == "sdf"
+		));
+	}
+
+	#[test]
+	fn missing_attribute() {
+		assert!(matches!(
+			from_urdf("<robot name=\"r\">\n  <link/>\n</robot>"),
+			Err(FromURDFError::MissingAttribute { element, attribute, line: 2 })
+				if element == "link" && attribute == "name"
+		));
+	}
+
+	#[test]
+	fn invalid_value() {
+		assert!(matches!(
+			from_urdf(
+				"<robot name=\"r\">\n  <link name=\"a\">\n    <visual><geometry><box size=\"1 2\"/></geometry></visual>\n  </link>\n</robot>"
+			),
+			Err(FromURDFError::InvalidValue { element, attribute, value, line: 3 })
+				if element == "box" && attribute == "size" && value == "1 2"
+		));
+	}
+
+	#[test]
+	fn unknown_material() {
+		assert!(matches!(
+			from_urdf(
+				"<robot name=\"r\">\n  <link name=\"a\">\n    <visual>\n      <geometry><sphere radius=\"1\"/></geometry>\n      <material name=\"green\"/>\n    </visual>\n  </link>\n</robot>"
+			),
+			Err(FromURDFError::UnknownMaterial { name, line: 5 }) if name == "green"
+		));
+	}
+
+	#[test]
+	fn duplicate_link() {
+		assert!(matches!(
+			from_urdf("<robot name=\"r\">\n  <link name=\"a\"/>\n  <link name=\"a\"/>\n</robot>"),
+			Err(FromURDFError::DuplicateLink { name, line: 3 }) if name == "a"
+		));
+	}
+
+	#[test]
+	fn unknown_link() {
+		assert!(matches!(
+			from_urdf(
+				"<robot name=\"r\">\n  <link name=\"a\"/>\n  <joint name=\"j\" type=\"fixed\">\n    <parent link=\"a\"/>\n    <child link=\"b\"/>\n  </joint>\n</robot>"
+			),
+			Err(FromURDFError::UnknownLink { joint, link, line: 3 }) if joint == "j" && link == "b"
+		));
+	}
+
+	#[test]
+	fn unknown_mimic_joint() {
+		assert!(matches!(
+			from_urdf(
+				"<robot name=\"r\">\n  <link name=\"a\"/>\n  <link name=\"b\"/>\n  <joint name=\"j\" type=\"continuous\">\n    <parent link=\"a\"/>\n    <child link=\"b\"/>\n    <mimic joint=\"k\"/>\n  </joint>\n</robot>"
+			),
+			Err(FromURDFError::UnknownMimicJoint { joint, mimicked_joint, line: 4 })
+				if joint == "j" && mimicked_joint == "k"
+		));
+	}
+
+	#[test]
+	fn mimic_cycle() {
+		assert!(matches!(
+			from_urdf(
+				"<robot name=\"r\">\n  <link name=\"a\"/>\n  <link name=\"b\"/>\n  <joint name=\"j\" type=\"continuous\">\n    <parent link=\"a\"/>\n    <child link=\"b\"/>\n    <mimic joint=\"j\"/>\n  </joint>\n</robot>"
+			),
+			Err(FromURDFError::MimicCycle { joint, line: 4 }) if joint == "j"
+		));
+		assert!(matches!(
+			from_urdf(
+				"<robot name=\"r\">\n  <link name=\"a\"/>\n  <link name=\"b\"/>\n  <link name=\"c\"/>\n  <joint name=\"j\" type=\"continuous\">\n    <parent link=\"a\"/>\n    <child link=\"b\"/>\n    <mimic joint=\"k\"/>\n  </joint>\n  <joint name=\"k\" type=\"continuous\">\n    <parent link=\"b\"/>\n    <child link=\"c\"/>\n    <mimic joint=\"j\"/>\n  </joint>\n</robot>"
+			),
+			Err(FromURDFError::MimicCycle { joint, line: 5 }) if joint == "j"
+		));
+	}
+
+	#[test]
+	fn multiple_root_links() {
+		assert!(matches!(
+			from_urdf("<robot name=\"r\"><link name=\"a\"/><link name=\"b\"/></robot>"),
+			Err(FromURDFError::MultipleRootLinks(roots)) if roots == ["a", "b"]
+		));
+	}
+
+	#[test]
+	fn kinematic_loop() {
+		assert!(matches!(
+			from_urdf(
+				"<robot name=\"r\">\n  <link name=\"a\"/>\n  <link name=\"b\"/>\n  <link name=\"c\"/>\n  <joint name=\"j1\" type=\"fixed\"><parent link=\"b\"/><child link=\"c\"/></joint>\n  <joint name=\"j2\" type=\"fixed\"><parent link=\"c\"/><child link=\"b\"/></joint>\n</robot>"
+			),
+			Err(FromURDFError::KinematicLoop { link, line: 3 }) if link == "b"
+		));
+	}
+}
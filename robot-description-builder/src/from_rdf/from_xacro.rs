@@ -0,0 +1,1193 @@
+//! The infrastructure to read a `Robot` from a [xacro](http://wiki.ros.org/xacro) URDF description.
+//!
+//! The xacro description is expanded into a plain URDF description, which is read with [`from_urdf`](super::from_urdf).
+//! No ROS installation is required, the locations of the ROS packages are provided with a [`XacroConfig`].
+//!
+//! The following xacro features are supported:
+//! - Properties (`<xacro:property>`), including block properties and the `default` and `scope` attributes.
+//! - Expressions (`${...}`) with a subset of Python:
+//!   - Integer, float, string and boolean literals.
+//!   - The arithmetic operators, (chained) comparisons, boolean operators and conditional expressions.
+//!   - The functions and constants of the Python `math` module (optionally prefixed by `math.`),
+//!     and the builtins `abs`, `min`, `max`, `round`, `int`, `float`, `str`, `bool` and `len`.
+//! - Macros (`<xacro:macro>`) with default (`:=`), forwarded (`:=^` and `:=^|`) and block (`*` and `**`) parameters.
+//! - Conditionals (`<xacro:if>` and `<xacro:unless>`).
+//! - Includes (`<xacro:include>`), where `$(find package)` and `package://` are resolved with the [`XacroConfig::packages`].
+//!   Relative paths are resolved against the directory of the including file.
+//! - Arguments (`<xacro:arg>` and `$(arg name)`), which can be set with the [`XacroConfig::args`].
+//! - The `$(eval ...)`, `$(env ...)`, `$(optenv ...)` and `$(dirname)` substitutions.
+//! - `<xacro:element>`, `<xacro:attribute>`, `<xacro:insert_block>` and `<xacro:call>`.
+//!
+//! # Limitations
+//! - The xacro elements need to use the `xacro:` prefix.
+//! - Properties are evaluated when they are defined, so they can only refer to properties which are defined before them.
+//! - YAML loading and other Python functionality outside of the supported subset is not available.
+//! - The line numbers in errors refer to the file in which the offending element is written.
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	rc::Rc,
+};
+
+use thiserror::Error;
+
+use super::{
+	from_urdf::{read_urdf, FromURDFError, URDFDescription},
+	xml_element::XMLElement,
+	XMLReadError,
+};
+use crate::{chained::Chained, cluster_objects::Robot, link::builder::LinkBuilder};
+
+mod expression;
+
+use expression::Value;
+pub use expression::XacroExpressionError;
+
+/// The maximum depth of nested macro calls and includes, to stop infinite recursion.
+const MAX_DEPTH: usize = 100;
+
+/// A Configuration for the expansion of [xacro](http://wiki.ros.org/xacro) descriptions.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::from_rdf::from_xacro::XacroConfig;
+/// let xacro_config = XacroConfig::default()
+///     .with_package("my_robot_description", "/home/user/ws/src/my_robot_description")
+///     .with_arg("use_gripper", "true");
+///
+/// assert_eq!(xacro_config.args["use_gripper"], "true");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct XacroConfig {
+	/// The directories of the ROS packages by package name, which are used to resolve `$(find package)` and `package://` paths.
+	pub packages: HashMap<String, PathBuf>,
+	/// The values of the `xacro:arg`s by name. These take precedence over the defaults in the description.
+	pub args: HashMap<String, String>,
+}
+
+impl XacroConfig {
+	/// Adds the directory of a ROS package.
+	pub fn with_package(mut self, package: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+		self.packages.insert(package.into(), path.into());
+		self
+	}
+
+	/// Sets the value of a `xacro:arg`.
+	pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.args.insert(name.into(), value.into());
+		self
+	}
+}
+
+/// An error, which can occur while reading a xacro description.
+///
+/// Most variants contain the `line` of the offending element to make it easy to find the problem.
+#[derive(Debug, Error)]
+pub enum FromXacroError {
+	/// The description is not valid XML.
+	#[error(transparent)]
+	XML(#[from] XMLReadError),
+	/// A file could not be read.
+	#[error("Could not read \"{}\": {source}", path.display())]
+	Io {
+		path: PathBuf,
+		#[source]
+		source: std::io::Error,
+	},
+	/// An included file is not valid XML.
+	#[error("Malformed XML in \"{}\": {source}", path.display())]
+	IncludedXML {
+		path: PathBuf,
+		#[source]
+		source: XMLReadError,
+	},
+	/// The expanded description is not a valid URDF description.
+	#[error(transparent)]
+	URDF(#[from] FromURDFError),
+	/// A required attribute of a xacro element is missing.
+	#[error("<{element}> at line {line} is missing the required attribute '{attribute}'")]
+	MissingAttribute {
+		element: String,
+		attribute: String,
+		line: usize,
+	},
+	/// An expression could not be evaluated.
+	#[error("The expression \"{expression}\" at line {line} could not be evaluated: {source}")]
+	Expression {
+		expression: String,
+		line: usize,
+		#[source]
+		source: XacroExpressionError,
+	},
+	/// A `$(...)` substitution is not closed.
+	#[error("The text \"{text}\" at line {line} contains an unclosed substitution")]
+	UnclosedSubstitution { text: String, line: usize },
+	/// A `$(...)` substitution is not supported.
+	#[error("The substitution \"$({substitution})\" at line {line} is not supported")]
+	UnsupportedSubstitution { substitution: String, line: usize },
+	/// A `$(arg ...)` refers to an argument which has no value.
+	#[error("The argument '{name}' at line {line} is not defined")]
+	UndefinedArgument { name: String, line: usize },
+	/// A `$(env ...)` refers to an environment variable which is not set.
+	#[error("The environment variable '{name}' at line {line} is not set")]
+	UndefinedEnvironmentVariable { name: String, line: usize },
+	/// A package is used, which is not in [`XacroConfig::packages`].
+	#[error("The package '{package}' at line {line} is unknown")]
+	UnknownPackage { package: String, line: usize },
+	/// The condition of a `<xacro:if>` or `<xacro:unless>` is not a boolean.
+	#[error("The condition \"{value}\" at line {line} is not a boolean")]
+	InvalidCondition { value: String, line: usize },
+	/// The parameters of a `<xacro:macro>` could not be interpreted.
+	#[error("The macro '{macro_name}' at line {line} has an invalid parameter \"{parameter}\"")]
+	InvalidParameter {
+		macro_name: String,
+		parameter: String,
+		line: usize,
+	},
+	/// A macro is called, which has not been defined.
+	#[error("The macro '{name}' at line {line} has not been defined")]
+	UnknownMacro { name: String, line: usize },
+	/// A macro is called with a parameter which it does not have.
+	#[error("The macro '{macro_name}' at line {line} has no parameter '{parameter}'")]
+	UnknownParameter {
+		macro_name: String,
+		parameter: String,
+		line: usize,
+	},
+	/// A macro is called without a value for a parameter which has no default.
+	#[error("The macro '{macro_name}' at line {line} is missing a value for the parameter '{parameter}'")]
+	MissingParameter {
+		macro_name: String,
+		parameter: String,
+		line: usize,
+	},
+	/// A `<xacro:insert_block>` refers to a block which does not exist.
+	#[error("The block '{name}' at line {line} has not been defined")]
+	UnknownBlock { name: String, line: usize },
+	/// The macros or includes are nested too deep, which is most likely caused by infinite recursion.
+	#[error("The nesting of macros and includes at line {line} is too deep, which is likely caused by recursion")]
+	RecursionLimit { line: usize },
+}
+
+/// Reads the [`Robot`] described by the xacro description in `xacro`.
+///
+/// Relative includes are resolved against the current working directory.
+/// See the [module-level documentation](self) for the supported xacro features.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     from_rdf::from_xacro::{from_xacro, XacroConfig},
+/// #     KinematicInterface,
+/// # };
+/// let description = r#"<?xml version="1.0"?>
+/// <robot name="two_wheels" xmlns:xacro="http://www.ros.org/wiki/xacro">
+///   <xacro:property name="wheel_radius" value="0.1"/>
+///   <xacro:macro name="wheel" params="side reflect">
+///     <joint name="${side}_wheel_joint" type="continuous">
+///       <origin xyz="0 ${reflect*0.2} ${wheel_radius}" rpy="${-pi/2} 0 0"/>
+///       <parent link="base"/>
+///       <child link="${side}_wheel"/>
+///       <axis xyz="0 0 1"/>
+///     </joint>
+///     <link name="${side}_wheel">
+///       <visual><geometry><cylinder radius="${wheel_radius}" length="0.05"/></geometry></visual>
+///     </link>
+///   </xacro:macro>
+///   <link name="base"/>
+///   <xacro:wheel side="left" reflect="1"/>
+///   <xacro:wheel side="right" reflect="-1"/>
+/// </robot>"#;
+///
+/// let robot = from_xacro(description, &XacroConfig::default()).unwrap();
+///
+/// assert_eq!(robot.name(), "two_wheels");
+/// assert!(robot.get_joint("left_wheel_joint").is_some());
+/// assert!(robot.get_link("right_wheel").is_some());
+/// ```
+pub fn from_xacro(xacro: &str, xacro_config: &XacroConfig) -> Result<Robot, FromXacroError> {
+	Ok(read_xacro(xacro, xacro_config, PathBuf::new())?.into_robot()?)
+}
+
+/// Reads the [`Robot`] described by the xacro description in the file at `path`.
+///
+/// Relative includes are resolved against the directory of the file.
+///
+/// See [`from_xacro`] for more information.
+pub fn from_xacro_file(
+	path: impl AsRef<Path>,
+	xacro_config: &XacroConfig,
+) -> Result<Robot, FromXacroError> {
+	let path = path.as_ref();
+	let xacro = fs::read_to_string(path).map_err(|source| FromXacroError::Io {
+		path: path.to_path_buf(),
+		source,
+	})?;
+
+	Ok(read_xacro(&xacro, xacro_config, directory_of(path))?.into_robot()?)
+}
+
+/// Reads the xacro description in `xacro` as a [`Chained<LinkBuilder>`], starting at the root link.
+///
+/// This can be used to attach a described part (e.g. a gripper) to another tree.
+/// The name of the `<robot>` and the `<transmission>` elements are not kept, since a [`Chained<LinkBuilder>`] can not contain them.
+///
+/// See [`from_xacro`] for more information.
+pub fn chain_from_xacro(
+	xacro: &str,
+	xacro_config: &XacroConfig,
+) -> Result<Chained<LinkBuilder>, FromXacroError> {
+	Ok(Chained(
+		read_xacro(xacro, xacro_config, PathBuf::new())?.root,
+	))
+}
+
+/// Gets the directory, against which the relative includes of the file at `path` are resolved.
+fn directory_of(path: &Path) -> PathBuf {
+	path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Expands the xacro description and reads the resulting URDF description.
+fn read_xacro(
+	xacro: &str,
+	xacro_config: &XacroConfig,
+	directory: PathBuf,
+) -> Result<URDFDescription, FromXacroError> {
+	let root = XMLElement::parse(xacro)?;
+
+	let mut expander = Expander {
+		xacro_config,
+		args: xacro_config.args.clone(),
+		scopes: vec![Scope::default()],
+		directories: vec![directory],
+		depth: 0,
+	};
+
+	let mut robot = XMLElement {
+		name: root.name.clone(),
+		attributes: root
+			.attributes
+			.iter()
+			.filter(|(key, _)| !key.starts_with("xmlns"))
+			.map(|(key, value)| Ok((key.clone(), expander.evaluate_string(value, root.line)?)))
+			.collect::<Result<_, FromXacroError>>()?,
+		line: root.line,
+		..Default::default()
+	};
+	expander.expand(&root.children, &mut robot)?;
+
+	Ok(read_urdf(&robot)?)
+}
+
+/// A xacro property.
+#[derive(Debug, Clone)]
+enum Property {
+	Value(Value),
+	/// A block of XML elements, which can be inserted with `<xacro:insert_block>`.
+	///
+	/// Block parameters of macros are `expanded` in the scope of the caller, block properties are expanded when they are inserted.
+	Block {
+		elements: Vec<XMLElement>,
+		expanded: bool,
+	},
+}
+
+/// A parameter of a xacro macro.
+#[derive(Debug)]
+enum Parameter {
+	/// A parameter with an optional `default`.
+	Value {
+		name: String,
+		default: Option<ParameterDefault>,
+	},
+	/// A block parameter (`*name`), which takes a single element.
+	Block(String),
+	/// A block parameter (`**name`), which takes the children of a single element.
+	Blocks(String),
+}
+
+/// The default of a macro parameter.
+#[derive(Debug)]
+enum ParameterDefault {
+	/// A default value (`name:=value`).
+	Value(String),
+	/// A value forwarded from the scope of the caller (`name:=^`), with an optional default (`name:=^|value`).
+	Forwarded(Option<String>),
+}
+
+/// A xacro macro.
+#[derive(Debug)]
+struct Macro {
+	parameters: Vec<Parameter>,
+	body: Vec<XMLElement>,
+}
+
+/// The properties and macros defined in a scope.
+#[derive(Debug, Default)]
+struct Scope {
+	properties: HashMap<String, Property>,
+	macros: HashMap<String, Rc<Macro>>,
+}
+
+/// The state of the expansion of a xacro description.
+struct Expander<'a> {
+	xacro_config: &'a XacroConfig,
+	/// The values of the `xacro:arg`s.
+	args: HashMap<String, String>,
+	/// The nested scopes, the innermost scope is last.
+	scopes: Vec<Scope>,
+	/// The directories of the files being expanded, the directory of the innermost include is last.
+	directories: Vec<PathBuf>,
+	/// The current depth of nested macro calls and includes.
+	depth: usize,
+}
+
+/// Gets the value of the required `attribute` of the xacro `element`.
+fn required_attribute<'a>(
+	element: &'a XMLElement,
+	attribute: &str,
+) -> Result<&'a str, FromXacroError> {
+	element
+		.attribute(attribute)
+		.ok_or_else(|| FromXacroError::MissingAttribute {
+			element: element.name.clone(),
+			attribute: attribute.to_string(),
+			line: element.line,
+		})
+}
+
+/// Finds the end of a substitution, starting after its opening bracket.
+///
+/// Returns the position of the matching `close` bracket, quoted text is skipped.
+fn find_closing(text: &str, open: char, close: char) -> Option<usize> {
+	let mut depth = 0;
+	let mut quote = None;
+
+	for (position, c) in text.char_indices() {
+		match (quote, c) {
+			(Some(q), c) if c == q => quote = None,
+			(Some(_), _) => (),
+			(None, '\'' | '"') => quote = Some(c),
+			(None, c) if c == open => depth += 1,
+			(None, c) if c == close && depth == 0 => return Some(position),
+			(None, c) if c == close => depth -= 1,
+			_ => (),
+		}
+	}
+
+	None
+}
+
+/// Converts a macro or property value into a `Value`, in the same way as xacro.
+fn literal(value: Value) -> Value {
+	match value {
+		Value::Text(text) => Value::from_literal(&text),
+		value => value,
+	}
+}
+
+impl Expander<'_> {
+	/// Finds the property with the specified `name` in the innermost scope which defines it.
+	fn property(&self, name: &str) -> Option<&Property> {
+		self.scopes
+			.iter()
+			.rev()
+			.find_map(|scope| scope.properties.get(name))
+	}
+
+	/// Finds the macro with the specified `name` in the innermost scope which defines it.
+	fn find_macro(&self, name: &str) -> Option<Rc<Macro>> {
+		self.scopes
+			.iter()
+			.rev()
+			.find_map(|scope| scope.macros.get(name))
+			.cloned()
+	}
+
+	fn current_directory(&self) -> &Path {
+		self.directories
+			.last()
+			.expect("The directory of the description is always present")
+	}
+
+	/// Evaluates the Python `expression`.
+	fn evaluate_expression(&self, expression: &str, line: usize) -> Result<Value, FromXacroError> {
+		expression::evaluate(expression, &|name| match self.property(name) {
+			Some(Property::Value(value)) => Some(value.clone()),
+			_ => None,
+		})
+		.map_err(|source| FromXacroError::Expression {
+			expression: expression.to_string(),
+			line,
+			source,
+		})
+	}
+
+	/// Evaluates the `$(...)` substitution with the specified `content`.
+	fn substitute(&self, content: &str, line: usize) -> Result<String, FromXacroError> {
+		let (command, argument) = content
+			.trim()
+			.split_once(char::is_whitespace)
+			.map(|(command, argument)| (command, argument.trim()))
+			.unwrap_or((content.trim(), ""));
+
+		match command {
+			"arg" => {
+				self.args
+					.get(argument)
+					.cloned()
+					.ok_or_else(|| FromXacroError::UndefinedArgument {
+						name: argument.to_string(),
+						line,
+					})
+			}
+			"find" => self
+				.xacro_config
+				.packages
+				.get(argument)
+				.map(|path| path.display().to_string())
+				.ok_or_else(|| FromXacroError::UnknownPackage {
+					package: argument.to_string(),
+					line,
+				}),
+			"eval" => Ok(self.evaluate_expression(argument, line)?.to_string()),
+			"env" => {
+				std::env::var(argument).map_err(|_| FromXacroError::UndefinedEnvironmentVariable {
+					name: argument.to_string(),
+					line,
+				})
+			}
+			"optenv" => {
+				let (name, default) = argument
+					.split_once(char::is_whitespace)
+					.map(|(name, default)| (name, default.trim()))
+					.unwrap_or((argument, ""));
+				Ok(std::env::var(name).unwrap_or_else(|_| default.to_string()))
+			}
+			"dirname" => Ok(self.current_directory().display().to_string()),
+			_ => Err(FromXacroError::UnsupportedSubstitution {
+				substitution: content.to_string(),
+				line,
+			}),
+		}
+	}
+
+	/// Evaluates all substitutions in the `text`.
+	///
+	/// If the `text` consists of a single `${...}` expression, its `Value` is returned as is.
+	/// Otherwise the result is the text with all substitutions replaced.
+	fn evaluate_text(&self, text: &str, line: usize) -> Result<Value, FromXacroError> {
+		let unclosed = || FromXacroError::UnclosedSubstitution {
+			text: text.to_string(),
+			line,
+		};
+
+		let mut parts: Vec<Value> = Vec::new();
+		let mut literal_text = String::new();
+		let mut rest = text;
+
+		while let Some(position) = rest.find('$') {
+			literal_text.push_str(&rest[..position]);
+			rest = &rest[position..];
+
+			if rest.starts_with("$${") || rest.starts_with("$$(") {
+				literal_text.push_str(&rest[1..3]);
+				rest = &rest[3..];
+			} else if let Some(expression) = rest.strip_prefix("${") {
+				let end = find_closing(expression, '{', '}').ok_or_else(unclosed)?;
+				if !literal_text.is_empty() {
+					parts.push(Value::Text(std::mem::take(&mut literal_text)));
+				}
+				parts.push(self.evaluate_expression(&expression[..end], line)?);
+				rest = &expression[end + 1..];
+			} else if let Some(substitution) = rest.strip_prefix("$(") {
+				let end = find_closing(substitution, '(', ')').ok_or_else(unclosed)?;
+				literal_text.push_str(&self.substitute(&substitution[..end], line)?);
+				rest = &substitution[end + 1..];
+			} else {
+				literal_text.push('$');
+				rest = &rest[1..];
+			}
+		}
+		literal_text.push_str(rest);
+		if !literal_text.is_empty() {
+			parts.push(Value::Text(literal_text));
+		}
+
+		match parts.len() {
+			0 => Ok(Value::Text(String::new())),
+			1 => Ok(parts.remove(0)),
+			_ => Ok(Value::Text(parts.iter().map(Value::to_string).collect())),
+		}
+	}
+
+	/// Evaluates all substitutions in the `text` and converts the result to a `String`.
+	fn evaluate_string(&self, text: &str, line: usize) -> Result<String, FromXacroError> {
+		self.evaluate_text(text, line)
+			.map(|value| value.to_string())
+	}
+
+	/// Evaluates the condition in the `value` attribute of a `<xacro:if>` or `<xacro:unless>` `element`.
+	fn evaluate_condition(&self, element: &XMLElement) -> Result<bool, FromXacroError> {
+		match self.evaluate_text(required_attribute(element, "value")?, element.line)? {
+			Value::Text(text) => match text.trim() {
+				"true" | "True" => Ok(true),
+				"false" | "False" => Ok(false),
+				trimmed => trimmed.parse::<i64>().map(|value| value != 0).map_err(|_| {
+					FromXacroError::InvalidCondition {
+						value: text.clone(),
+						line: element.line,
+					}
+				}),
+			},
+			value => Ok(value.is_truthy()),
+		}
+	}
+
+	/// Expands the xacro `elements` and adds the results to the `parent`.
+	fn expand(
+		&mut self,
+		elements: &[XMLElement],
+		parent: &mut XMLElement,
+	) -> Result<(), FromXacroError> {
+		for element in elements {
+			match element.name.as_str() {
+				"xacro:property" => self.define_property(element)?,
+				"xacro:arg" => {
+					let name = required_attribute(element, "name")?;
+					if !self.args.contains_key(name) {
+						let default = self.evaluate_string(
+							element.attribute("default").unwrap_or_default(),
+							element.line,
+						)?;
+						self.args.insert(name.to_string(), default);
+					}
+				}
+				"xacro:macro" => self.define_macro(element)?,
+				"xacro:if" => {
+					if self.evaluate_condition(element)? {
+						self.expand(&element.children, parent)?;
+					}
+				}
+				"xacro:unless" => {
+					if !self.evaluate_condition(element)? {
+						self.expand(&element.children, parent)?;
+					}
+				}
+				"xacro:include" => self.include(element, parent)?,
+				"xacro:insert_block" => {
+					let name =
+						self.evaluate_string(required_attribute(element, "name")?, element.line)?;
+					match self.property(&name).cloned() {
+						Some(Property::Block {
+							elements,
+							expanded: true,
+						}) => parent.children.extend(elements),
+						Some(Property::Block {
+							elements,
+							expanded: false,
+						}) => self.expand(&elements, parent)?,
+						_ => {
+							return Err(FromXacroError::UnknownBlock {
+								name,
+								line: element.line,
+							})
+						}
+					}
+				}
+				"xacro:element" => {
+					let name = self.evaluate_string(
+						required_attribute(element, "xacro:name")?,
+						element.line,
+					)?;
+					let mut output = self.expand_element(element)?;
+					output.name = name;
+					output.attributes.retain(|(key, _)| key != "xacro:name");
+					self.expand(&element.children, &mut output)?;
+					parent.children.push(output);
+				}
+				"xacro:attribute" => {
+					let name =
+						self.evaluate_string(required_attribute(element, "name")?, element.line)?;
+					let value =
+						self.evaluate_string(required_attribute(element, "value")?, element.line)?;
+					parent.attributes.retain(|(key, _)| key != &name);
+					parent.attributes.push((name, value));
+				}
+				"xacro:call" => {
+					let name =
+						self.evaluate_string(required_attribute(element, "macro")?, element.line)?;
+					self.call_macro(&name, element, parent)?;
+				}
+				name => match name.strip_prefix("xacro:") {
+					Some(name) => self.call_macro(name, element, parent)?,
+					None => {
+						let mut output = self.expand_element(element)?;
+						self.expand(&element.children, &mut output)?;
+						parent.children.push(output);
+					}
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Creates a copy of the `element` without children, in which the substitutions in the attributes and text are evaluated.
+	fn expand_element(&self, element: &XMLElement) -> Result<XMLElement, FromXacroError> {
+		Ok(XMLElement {
+			name: element.name.clone(),
+			attributes: element
+				.attributes
+				.iter()
+				.map(|(key, value)| Ok((key.clone(), self.evaluate_string(value, element.line)?)))
+				.collect::<Result<_, FromXacroError>>()?,
+			children: Vec::new(),
+			text: self.evaluate_string(&element.text, element.line)?,
+			line: element.line,
+		})
+	}
+
+	fn define_property(&mut self, element: &XMLElement) -> Result<(), FromXacroError> {
+		let name = required_attribute(element, "name")?.to_string();
+
+		let property = match (element.attribute("value"), element.attribute("default")) {
+			(Some(value), _) => Property::Value(literal(self.evaluate_text(value, element.line)?)),
+			(None, Some(default)) => {
+				if self.property(&name).is_some() {
+					return Ok(());
+				}
+				Property::Value(literal(self.evaluate_text(default, element.line)?))
+			}
+			(None, None) => Property::Block {
+				elements: element.children.clone(),
+				expanded: false,
+			},
+		};
+
+		let scope_index = match element.attribute("scope") {
+			Some("global") => 0,
+			Some("parent") => self.scopes.len().saturating_sub(2),
+			_ => self.scopes.len() - 1,
+		};
+		self.scopes[scope_index].properties.insert(name, property);
+
+		Ok(())
+	}
+
+	fn define_macro(&mut self, element: &XMLElement) -> Result<(), FromXacroError> {
+		let name = required_attribute(element, "name")?;
+		let name = name.strip_prefix("xacro:").unwrap_or(name).to_string();
+
+		let parameters = element
+			.attribute("params")
+			.unwrap_or_default()
+			.split_whitespace()
+			.map(|parameter| {
+				let invalid = || FromXacroError::InvalidParameter {
+					macro_name: name.clone(),
+					parameter: parameter.to_string(),
+					line: element.line,
+				};
+
+				let parameter = if let Some(block) = parameter.strip_prefix("**") {
+					Parameter::Blocks(block.to_string())
+				} else if let Some(block) = parameter.strip_prefix('*') {
+					Parameter::Block(block.to_string())
+				} else {
+					match parameter
+						.split_once(":=")
+						.or_else(|| parameter.split_once('='))
+					{
+						Some((name, default)) => Parameter::Value {
+							name: name.to_string(),
+							default: Some(match default.strip_prefix('^') {
+								Some("") => ParameterDefault::Forwarded(None),
+								Some(forwarded) => ParameterDefault::Forwarded(Some(
+									forwarded.strip_prefix('|').ok_or_else(invalid)?.to_string(),
+								)),
+								None => ParameterDefault::Value(default.to_string()),
+							}),
+						},
+						None => Parameter::Value {
+							name: parameter.to_string(),
+							default: None,
+						},
+					}
+				};
+
+				match &parameter {
+					Parameter::Value { name, .. }
+					| Parameter::Block(name)
+					| Parameter::Blocks(name)
+						if name.is_empty() =>
+					{
+						Err(invalid())
+					}
+					_ => Ok(parameter),
+				}
+			})
+			.collect::<Result<_, _>>()?;
+
+		self.scopes
+			.last_mut()
+			.expect("There is always a scope")
+			.macros
+			.insert(
+				name,
+				Rc::new(Macro {
+					parameters,
+					body: element.children.clone(),
+				}),
+			);
+
+		Ok(())
+	}
+
+	/// Calls the macro with the specified `name`, with the parameters and blocks of the calling `element`.
+	fn call_macro(
+		&mut self,
+		name: &str,
+		element: &XMLElement,
+		parent: &mut XMLElement,
+	) -> Result<(), FromXacroError> {
+		let called_macro = self
+			.find_macro(name)
+			.ok_or_else(|| FromXacroError::UnknownMacro {
+				name: name.to_string(),
+				line: element.line,
+			})?;
+
+		let parameter_error = |parameter: &str| FromXacroError::MissingParameter {
+			macro_name: name.to_string(),
+			parameter: parameter.to_string(),
+			line: element.line,
+		};
+
+		if let Some((key, _)) = element.attributes.iter().find(|(key, _)| {
+			key != "macro"
+				&& !called_macro.parameters.iter().any(
+					|parameter| matches!(parameter, Parameter::Value { name, .. } if name == key),
+				)
+		}) {
+			return Err(FromXacroError::UnknownParameter {
+				macro_name: name.to_string(),
+				parameter: key.clone(),
+				line: element.line,
+			});
+		}
+
+		let mut scope = Scope::default();
+		let mut blocks = element.children.iter();
+
+		for parameter in called_macro.parameters.iter() {
+			let (name, property) = match parameter {
+				Parameter::Value { name, default } => {
+					let value = match (element.attribute(name), default) {
+						(Some(value), _) => self.evaluate_text(value, element.line)?,
+						(None, Some(ParameterDefault::Value(default))) => {
+							self.evaluate_text(default, element.line)?
+						}
+						(None, Some(ParameterDefault::Forwarded(default))) => {
+							match (self.property(name), default) {
+								(Some(Property::Value(value)), _) => value.clone(),
+								(_, Some(default)) => self.evaluate_text(default, element.line)?,
+								(_, None) => return Err(parameter_error(name)),
+							}
+						}
+						(None, None) => return Err(parameter_error(name)),
+					};
+					(name, Property::Value(literal(value)))
+				}
+				Parameter::Block(name) | Parameter::Blocks(name) => {
+					let block = blocks.next().ok_or_else(|| parameter_error(name))?;
+					let block = match parameter {
+						Parameter::Blocks(_) => block.children.as_slice(),
+						_ => std::slice::from_ref(block),
+					};
+
+					// Blocks are expanded in the scope of the caller.
+					let mut expanded = XMLElement::default();
+					self.expand(block, &mut expanded)?;
+					(
+						name,
+						Property::Block {
+							elements: expanded.children,
+							expanded: true,
+						},
+					)
+				}
+			};
+			scope.properties.insert(name.clone(), property);
+		}
+
+		self.enter(element.line)?;
+		self.scopes.push(scope);
+		let result = self.expand(&called_macro.body, parent);
+		self.scopes.pop();
+		self.depth -= 1;
+
+		result
+	}
+
+	/// Expands the file included by the `<xacro:include>` `element` and adds the results to the `parent`.
+	fn include(
+		&mut self,
+		element: &XMLElement,
+		parent: &mut XMLElement,
+	) -> Result<(), FromXacroError> {
+		let filename =
+			self.evaluate_string(required_attribute(element, "filename")?, element.line)?;
+
+		let path = match filename.strip_prefix("package://") {
+			Some(package_path) => {
+				let (package, file) = package_path.split_once('/').unwrap_or((package_path, ""));
+				self.xacro_config
+					.packages
+					.get(package)
+					.ok_or_else(|| FromXacroError::UnknownPackage {
+						package: package.to_string(),
+						line: element.line,
+					})?
+					.join(file)
+			}
+			None => self.current_directory().join(filename),
+		};
+
+		let xacro = fs::read_to_string(&path).map_err(|source| FromXacroError::Io {
+			path: path.clone(),
+			source,
+		})?;
+		let root = XMLElement::parse(&xacro).map_err(|source| FromXacroError::IncludedXML {
+			path: path.clone(),
+			source,
+		})?;
+
+		self.enter(element.line)?;
+		self.directories.push(directory_of(&path));
+		let result = self.expand(&root.children, parent);
+		self.directories.pop();
+		self.depth -= 1;
+
+		result
+	}
+
+	/// Increases the depth of nested macro calls and includes, and checks it against the [`MAX_DEPTH`].
+	fn enter(&mut self, line: usize) -> Result<(), FromXacroError> {
+		match self.depth < MAX_DEPTH {
+			true => {
+				self.depth += 1;
+				Ok(())
+			}
+			false => Err(FromXacroError::RecursionLimit { line }),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{fs, path::PathBuf};
+
+	use super::{chain_from_xacro, from_xacro, from_xacro_file, FromXacroError, XacroConfig};
+	use crate::{
+		cluster_objects::KinematicInterface,
+		float::consts::{FRAC_PI_2, PI},
+		to_rdf::{
+			to_urdf::{to_urdf, URDFConfig},
+			xml_writer_to_string,
+		},
+		JointType,
+	};
+	use test_log::test;
+
+	fn expand(xacro: &str) -> String {
+		expand_with(xacro, &XacroConfig::default())
+	}
+
+	fn expand_with(xacro: &str, xacro_config: &XacroConfig) -> String {
+		xml_writer_to_string(
+			to_urdf(
+				&from_xacro(xacro, xacro_config).unwrap(),
+				URDFConfig::default(),
+			)
+			.unwrap(),
+		)
+	}
+
+	/// Creates an empty temporary directory for tests with files.
+	fn temporary_directory(name: &str) -> PathBuf {
+		let directory = std::env::temp_dir().join(format!(
+			"robot-description-builder-{}-{name}",
+			std::process::id()
+		));
+		let _ = fs::remove_dir_all(&directory);
+		fs::create_dir_all(&directory).unwrap();
+		directory
+	}
+
+	#[test]
+	fn properties_and_expressions() {
+		let urdf = expand(
+			r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:property name="length" value="0.5"/>
+  <xacro:property name="half" value="${length / 2}"/>
+  <xacro:property name="name" value="body"/>
+  <xacro:property name="length" default="2"/>
+  <link name="${name}_link">
+    <visual>
+      <origin xyz="0 0 ${half}" rpy="${pi} 0 ${radians(90) if length > 0.1 else 0}"/>
+      <geometry><box size="${length} ${2*length} 1"/></geometry>
+    </visual>
+  </link>
+</robot>"#,
+		);
+
+		assert!(urdf.contains(r#"<link name="body_link">"#));
+		assert!(urdf.contains(&format!(
+			r#"<origin xyz="0 0 0.25" rpy="{} 0 {}"/>"#,
+			PI, FRAC_PI_2
+		)));
+		assert!(urdf.contains(r#"<box size="0.5 1 1"/>"#));
+	}
+
+	#[test]
+	fn macros() {
+		let urdf = expand(
+			r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:macro name="leg" params="prefix parent reflect:=1 *origin **visuals">
+    <joint name="${prefix}_joint" type="fixed">
+      <xacro:insert_block name="origin"/>
+      <parent link="${parent}"/>
+      <child link="${prefix}_leg"/>
+    </joint>
+    <link name="${prefix}_leg">
+      <visual>
+        <origin xyz="0 ${reflect*0.1} 0"/>
+        <geometry><sphere radius="0.1"/></geometry>
+      </visual>
+      <xacro:insert_block name="visuals"/>
+    </link>
+  </xacro:macro>
+  <xacro:property name="offset" value="0.3"/>
+  <link name="base"/>
+  <xacro:leg prefix="left" parent="base">
+    <origin xyz="0 ${offset} 0"/>
+    <visuals>
+      <visual><geometry><box size="1 1 1"/></geometry></visual>
+    </visuals>
+  </xacro:leg>
+  <xacro:leg prefix="right" parent="base" reflect="-1">
+    <origin xyz="0 ${-offset} 0"/>
+    <visuals/>
+  </xacro:leg>
+</robot>"#,
+		);
+
+		assert!(urdf.contains(r#"<joint name="left_joint" type="fixed"><origin xyz="0 0.3 0"/><parent link="base"/><child link="left_leg"/></joint>"#));
+		assert!(urdf.contains(r#"<joint name="right_joint" type="fixed"><origin xyz="0 -0.3 0"/><parent link="base"/><child link="right_leg"/></joint>"#));
+		assert!(urdf.contains(r#"<link name="left_leg"><visual><origin xyz="0 0.1 0"/><geometry><sphere radius="0.1"/></geometry></visual><visual><geometry><box size="1 1 1"/></geometry></visual></link>"#));
+		assert!(urdf.contains(r#"<link name="right_leg"><visual><origin xyz="0 -0.1 0"/><geometry><sphere radius="0.1"/></geometry></visual></link>"#));
+	}
+
+	#[test]
+	fn recursive_macro() {
+		let robot = from_xacro(
+			r#"<robot name="snake" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:macro name="segment" params="index count">
+    <xacro:if value="${index &lt; count}">
+      <joint name="joint_${index}" type="revolute">
+        <parent link="link_${index}"/>
+        <child link="link_${index + 1}"/>
+        <axis xyz="0 0 1"/>
+        <limit effort="1" velocity="1" lower="-1" upper="1"/>
+      </joint>
+      <link name="link_${index + 1}"/>
+      <xacro:segment index="${index + 1}" count="${count}"/>
+    </xacro:if>
+  </xacro:macro>
+  <link name="link_0"/>
+  <xacro:segment index="0" count="4"/>
+</robot>"#,
+			&XacroConfig::default(),
+		)
+		.unwrap();
+
+		assert!(robot.get_link("link_4").is_some());
+		assert!(robot.get_link("link_5").is_none());
+		assert_eq!(
+			robot
+				.get_joint("joint_3")
+				.unwrap()
+				.read()
+				.unwrap()
+				.joint_type(),
+			JointType::Revolute
+		);
+	}
+
+	#[test]
+	fn conditionals_and_args() {
+		let xacro = r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:arg name="gripper" default="false"/>
+  <xacro:property name="mass" value="2"/>
+  <link name="base"/>
+  <xacro:if value="$(arg gripper)">
+    <joint name="gripper_joint" type="fixed"><parent link="base"/><child link="gripper"/></joint>
+    <link name="gripper"/>
+  </xacro:if>
+  <xacro:unless value="${mass == 2}">
+    <link name="unreachable"/>
+  </xacro:unless>
+</robot>"#;
+
+		let robot = from_xacro(xacro, &XacroConfig::default()).unwrap();
+		assert!(robot.get_link("gripper").is_none());
+
+		let robot = from_xacro(xacro, &XacroConfig::default().with_arg("gripper", "true")).unwrap();
+		assert!(robot.get_link("gripper").is_some());
+		assert!(robot.get_link("unreachable").is_none());
+	}
+
+	#[test]
+	fn element_and_attribute() {
+		let urdf = expand(
+			r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:property name="shape" value="sphere"/>
+  <link name="base">
+    <visual>
+      <geometry>
+        <xacro:element xacro:name="${shape}">
+          <xacro:attribute name="radius" value="${0.25 * 2}"/>
+        </xacro:element>
+      </geometry>
+    </visual>
+  </link>
+</robot>"#,
+		);
+
+		assert!(urdf.contains(r#"<sphere radius="0.5"/>"#));
+	}
+
+	#[test]
+	fn escaped_substitutions() {
+		let urdf = expand(
+			r#"<robot name="$${not_a_property}$$(not_an_arg)" xmlns:xacro="http://www.ros.org/wiki/xacro"><link name="cost_$5"/></robot>"#,
+		);
+
+		assert!(urdf.contains(r#"<robot name="${not_a_property}$(not_an_arg)">"#));
+		assert!(urdf.contains(r#"<link name="cost_$5">"#));
+	}
+
+	#[test]
+	fn include() {
+		let package = temporary_directory("include");
+		fs::create_dir_all(package.join("urdf")).unwrap();
+		fs::write(
+			package.join("urdf/wheel.xacro"),
+			r#"<robot xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:include filename="constants.xacro"/>
+  <xacro:macro name="wheel" params="name">
+    <joint name="${name}_joint" type="continuous"><parent link="base"/><child link="${name}"/></joint>
+    <link name="${name}"><visual><geometry><cylinder radius="${wheel_radius}" length="0.1"/></geometry></visual></link>
+  </xacro:macro>
+</robot>"#,
+		)
+		.unwrap();
+		fs::write(
+			package.join("urdf/constants.xacro"),
+			r#"<robot xmlns:xacro="http://www.ros.org/wiki/xacro"><xacro:property name="wheel_radius" value="0.2"/></robot>"#,
+		)
+		.unwrap();
+		fs::write(
+			package.join("robot.urdf.xacro"),
+			r#"<robot name="cart" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:include filename="$(find cart_description)/urdf/wheel.xacro"/>
+  <link name="base"/>
+  <xacro:wheel name="front_wheel"/>
+</robot>"#,
+		)
+		.unwrap();
+
+		let xacro_config = XacroConfig::default().with_package("cart_description", &package);
+		let robot = from_xacro_file(package.join("robot.urdf.xacro"), &xacro_config).unwrap();
+
+		assert!(
+			xml_writer_to_string(to_urdf(&robot, URDFConfig::default()).unwrap())
+				.contains(r#"<cylinder radius="0.2" length="0.1"/>"#)
+		);
+
+		assert!(matches!(
+			from_xacro_file(package.join("robot.urdf.xacro"), &XacroConfig::default()),
+			Err(FromXacroError::UnknownPackage { package, line: 2 }) if package == "cart_description"
+		));
+
+		fs::remove_dir_all(package).unwrap();
+	}
+
+	#[test]
+	fn chain() {
+		let chain = chain_from_xacro(
+			r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:property name="finger" value="finger"/>
+  <link name="palm"/>
+  <joint name="${finger}_joint" type="fixed"><parent link="palm"/><child link="${finger}"/></joint>
+  <link name="${finger}"/>
+</robot>"#,
+			&XacroConfig::default(),
+		)
+		.unwrap();
+
+		assert_eq!(chain.joints[0].name(), "finger_joint");
+	}
+
+	#[test]
+	fn errors() {
+		let error = |xacro: &str| {
+			from_xacro(
+				&format!(
+					"<robot name=\"r\" xmlns:xacro=\"http://www.ros.org/wiki/xacro\">\n{xacro}\n</robot>"
+				),
+				&XacroConfig::default(),
+			)
+			.unwrap_err()
+		};
+
+		assert!(matches!(
+			error(r#"<link name="${undefined}"/>"#),
+			FromXacroError::Expression { line: 2, .. }
+		));
+		assert!(matches!(
+			error(r#"<xacro:missing/>"#),
+			FromXacroError::UnknownMacro { name, line: 2 } if name == "missing"
+		));
+		assert!(matches!(
+			error(r#"<xacro:macro name="m" params="a"/><xacro:m/>"#),
+			FromXacroError::MissingParameter { parameter, .. } if parameter == "a"
+		));
+		assert!(matches!(
+			error(r#"<xacro:macro name="m" params="a"/><xacro:m a="1" b="2"/>"#),
+			FromXacroError::UnknownParameter { parameter, .. } if parameter == "b"
+		));
+		assert!(matches!(
+			error(r#"<xacro:macro name="m" params=""><xacro:m/></xacro:macro><xacro:m/>"#),
+			FromXacroError::RecursionLimit { .. }
+		));
+		assert!(matches!(
+			error(r#"<xacro:if value="maybe"/>"#),
+			FromXacroError::InvalidCondition { value, .. } if value == "maybe"
+		));
+		assert!(matches!(
+			error(r#"<link name="$(arg missing)"/>"#),
+			FromXacroError::UndefinedArgument { name, .. } if name == "missing"
+		));
+		assert!(matches!(
+			error(r#"<xacro:insert_block name="missing"/>"#),
+			FromXacroError::UnknownBlock { .. }
+		));
+		assert!(matches!(
+			error(r#"<link name="a"/><link name="a"/>"#),
+			FromXacroError::URDF(_)
+		));
+	}
+}
@@ -0,0 +1,842 @@
+//! An evaluator for the Python expressions used in xacro substitutions (`${...}` and `$(eval ...)`).
+//!
+//! The supported subset of Python consists of:
+//! - Integer, float, string and boolean (`True`/`False`) literals.
+//! - The arithmetic operators `+`, `-`, `*`, `/`, `//`, `%` and `**`.
+//! - The (chained) comparisons `==`, `!=`, `<`, `<=`, `>` and `>=`.
+//! - The boolean operators `and`, `or` and `not` and conditional expressions (`a if condition else b`).
+//! - The functions and constants of the Python `math` module (optionally prefixed by `math.`),
+//!   and the builtins `abs`, `min`, `max`, `round`, `int`, `float`, `str`, `bool` and `len`.
+use std::{f64::consts, fmt};
+
+use thiserror::Error;
+
+/// An error, which can occur while evaluating a xacro expression.
+#[derive(Debug, PartialEq, Clone, Error)]
+pub enum XacroExpressionError {
+	/// The expression is not valid.
+	#[error("Invalid syntax: {0}")]
+	Syntax(String),
+	/// The expression refers to a property which is not defined.
+	#[error("The name '{0}' is not defined")]
+	UndefinedName(String),
+	/// The expression calls a function which is not supported.
+	#[error("The function '{0}' is not supported")]
+	UnknownFunction(String),
+	/// An operator or function is applied to values of the wrong type.
+	#[error("Invalid operand types: {0}")]
+	Type(String),
+	/// A division or modulo by zero.
+	#[error("Division by zero")]
+	DivisionByZero,
+}
+
+/// A value of a xacro property or expression.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+	/// An integer (`int` in Python).
+	Int(i64),
+	/// A floating point number (`float` in Python).
+	Float(f64),
+	/// A boolean (`bool` in Python).
+	Bool(bool),
+	/// A string (`str` in Python).
+	Text(String),
+}
+
+impl Value {
+	/// Interprets a literal text in the same way xacro does.
+	///
+	/// Quoted text is unquoted, numbers become numbers and booleans become booleans.
+	/// Text containing an underscore is always kept as text.
+	pub fn from_literal(text: &str) -> Self {
+		if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+			return Self::Text(text[1..text.len() - 1].to_string());
+		}
+
+		if text.contains('_') {
+			return Self::Text(text.to_string());
+		}
+
+		let trimmed = text.trim();
+		if let Ok(value) = trimmed.parse() {
+			Self::Int(value)
+		} else if let Ok(value) = trimmed.parse() {
+			Self::Float(value)
+		} else {
+			match trimmed {
+				"true" | "True" => Self::Bool(true),
+				"false" | "False" => Self::Bool(false),
+				_ => Self::Text(text.to_string()),
+			}
+		}
+	}
+
+	/// Gets the truth value of the `Value`, as in Python.
+	pub fn is_truthy(&self) -> bool {
+		match self {
+			Self::Int(value) => *value != 0,
+			Self::Float(value) => *value != 0.,
+			Self::Bool(value) => *value,
+			Self::Text(text) => !text.is_empty(),
+		}
+	}
+
+	/// Gets the numeric value, booleans are treated as integers.
+	fn as_f64(&self) -> Option<f64> {
+		match self {
+			Self::Int(value) => Some(*value as f64),
+			Self::Float(value) => Some(*value),
+			Self::Bool(value) => Some(*value as i64 as f64),
+			Self::Text(_) => None,
+		}
+	}
+
+	/// Gets the integer value, booleans are treated as integers.
+	fn as_i64(&self) -> Option<i64> {
+		match self {
+			Self::Int(value) => Some(*value),
+			Self::Bool(value) => Some(*value as i64),
+			Self::Float(_) | Self::Text(_) => None,
+		}
+	}
+
+	fn type_name(&self) -> &'static str {
+		match self {
+			Self::Int(_) => "int",
+			Self::Float(_) => "float",
+			Self::Bool(_) => "bool",
+			Self::Text(_) => "str",
+		}
+	}
+}
+
+/// Formats the `Value` like Python's `str` does.
+impl fmt::Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Int(value) => write!(f, "{value}"),
+			Self::Float(value) if value.is_nan() => write!(f, "nan"),
+			// The debug representation always contains a decimal point or exponent, like Python.
+			Self::Float(value) => write!(f, "{value:?}"),
+			Self::Bool(true) => write!(f, "True"),
+			Self::Bool(false) => write!(f, "False"),
+			Self::Text(text) => write!(f, "{text}"),
+		}
+	}
+}
+
+/// Evaluates the Python `expression`, where names are resolved by `lookup`.
+pub fn evaluate(
+	expression: &str,
+	lookup: &dyn Fn(&str) -> Option<Value>,
+) -> Result<Value, XacroExpressionError> {
+	let tokens = tokenize(expression)?;
+	let mut parser = Parser {
+		tokens: &tokens,
+		position: 0,
+	};
+	let ast = parser.expression()?;
+
+	match parser.tokens.get(parser.position) {
+		None => ast.evaluate(lookup),
+		Some(token) => Err(XacroExpressionError::Syntax(format!("unexpected {token}"))),
+	}
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+	Int(i64),
+	Float(f64),
+	Text(String),
+	Name(String),
+	Operator(&'static str),
+}
+
+impl fmt::Display for Token {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Token::Int(value) => write!(f, "'{value}'"),
+			Token::Float(value) => write!(f, "'{value}'"),
+			Token::Text(text) => write!(f, "{text:?}"),
+			Token::Name(name) => write!(f, "'{name}'"),
+			Token::Operator(operator) => write!(f, "'{operator}'"),
+		}
+	}
+}
+
+/// The operators in order of matching, longer operators have to be matched first.
+const OPERATORS: [&str; 17] = [
+	"**", "//", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "(", ")", ",", ".",
+];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, XacroExpressionError> {
+	let mut tokens = Vec::new();
+	let mut rest = expression.trim_start();
+
+	while let Some(next) = rest.chars().next() {
+		let length = if next.is_ascii_digit()
+			|| (next == '.' && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+		{
+			let mut length = rest
+				.find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+				.unwrap_or(rest.len());
+			// Include the sign of an exponent.
+			if rest[..length].ends_with(['e', 'E']) && rest[length..].starts_with(['+', '-']) {
+				length += 1 + rest[length + 1..]
+					.find(|c: char| !c.is_ascii_digit())
+					.unwrap_or(rest.len() - length - 1);
+			}
+
+			let number = &rest[..length];
+			tokens.push(match number.parse() {
+				Ok(value) => Token::Int(value),
+				Err(_) => Token::Float(number.parse().map_err(|_| {
+					XacroExpressionError::Syntax(format!("invalid number '{number}'"))
+				})?),
+			});
+			length
+		} else if next.is_alphabetic() || next == '_' {
+			let length = rest
+				.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+				.unwrap_or(rest.len());
+			tokens.push(Token::Name(rest[..length].to_string()));
+			length
+		} else if next == '\'' || next == '"' {
+			let end = rest[1..].find(next).ok_or_else(|| {
+				XacroExpressionError::Syntax("unterminated string literal".to_string())
+			})?;
+			tokens.push(Token::Text(rest[1..end + 1].to_string()));
+			end + 2
+		} else {
+			let operator = OPERATORS
+				.into_iter()
+				.find(|operator| rest.starts_with(operator))
+				.ok_or_else(|| XacroExpressionError::Syntax(format!("unexpected '{next}'")))?;
+			tokens.push(Token::Operator(operator));
+			operator.len()
+		};
+
+		rest = rest[length..].trim_start();
+	}
+
+	Ok(tokens)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Expression {
+	Literal(Value),
+	Name(String),
+	Call(String, Vec<Expression>),
+	Negate(Box<Expression>),
+	Not(Box<Expression>),
+	Binary(&'static str, Box<Expression>, Box<Expression>),
+	Comparison(Box<Expression>, Vec<(&'static str, Expression)>),
+	And(Box<Expression>, Box<Expression>),
+	Or(Box<Expression>, Box<Expression>),
+	Conditional {
+		condition: Box<Expression>,
+		then: Box<Expression>,
+		otherwise: Box<Expression>,
+	},
+}
+
+/// A recursive descent parser following the precedence of Python.
+struct Parser<'a> {
+	tokens: &'a [Token],
+	position: usize,
+}
+
+impl Parser<'_> {
+	fn peek_operator(&self, operators: &[&'static str]) -> Option<&'static str> {
+		match self.tokens.get(self.position) {
+			Some(Token::Operator(operator)) if operators.contains(operator) => Some(operator),
+			_ => None,
+		}
+	}
+
+	fn peek_keyword(&self, keyword: &str) -> bool {
+		matches!(self.tokens.get(self.position), Some(Token::Name(name)) if name == keyword)
+	}
+
+	fn expect_operator(&mut self, operator: &'static str) -> Result<(), XacroExpressionError> {
+		match self.peek_operator(&[operator]) {
+			Some(_) => {
+				self.position += 1;
+				Ok(())
+			}
+			None => Err(XacroExpressionError::Syntax(format!(
+				"expected '{operator}'"
+			))),
+		}
+	}
+
+	fn expression(&mut self) -> Result<Expression, XacroExpressionError> {
+		let then = self.or()?;
+
+		if !self.peek_keyword("if") {
+			return Ok(then);
+		}
+		self.position += 1;
+		let condition = self.or()?;
+
+		if !self.peek_keyword("else") {
+			return Err(XacroExpressionError::Syntax("expected 'else'".to_string()));
+		}
+		self.position += 1;
+		let otherwise = self.expression()?;
+
+		Ok(Expression::Conditional {
+			condition: Box::new(condition),
+			then: Box::new(then),
+			otherwise: Box::new(otherwise),
+		})
+	}
+
+	fn or(&mut self) -> Result<Expression, XacroExpressionError> {
+		let mut left = self.and()?;
+		while self.peek_keyword("or") {
+			self.position += 1;
+			left = Expression::Or(Box::new(left), Box::new(self.and()?));
+		}
+		Ok(left)
+	}
+
+	fn and(&mut self) -> Result<Expression, XacroExpressionError> {
+		let mut left = self.not()?;
+		while self.peek_keyword("and") {
+			self.position += 1;
+			left = Expression::And(Box::new(left), Box::new(self.not()?));
+		}
+		Ok(left)
+	}
+
+	fn not(&mut self) -> Result<Expression, XacroExpressionError> {
+		match self.peek_keyword("not") {
+			true => {
+				self.position += 1;
+				Ok(Expression::Not(Box::new(self.not()?)))
+			}
+			false => self.comparison(),
+		}
+	}
+
+	fn comparison(&mut self) -> Result<Expression, XacroExpressionError> {
+		let left = self.sum()?;
+
+		let mut comparisons = Vec::new();
+		while let Some(operator) = self.peek_operator(&["==", "!=", "<", "<=", ">", ">="]) {
+			self.position += 1;
+			comparisons.push((operator, self.sum()?));
+		}
+
+		match comparisons.is_empty() {
+			true => Ok(left),
+			false => Ok(Expression::Comparison(Box::new(left), comparisons)),
+		}
+	}
+
+	fn sum(&mut self) -> Result<Expression, XacroExpressionError> {
+		let mut left = self.term()?;
+		while let Some(operator) = self.peek_operator(&["+", "-"]) {
+			self.position += 1;
+			left = Expression::Binary(operator, Box::new(left), Box::new(self.term()?));
+		}
+		Ok(left)
+	}
+
+	fn term(&mut self) -> Result<Expression, XacroExpressionError> {
+		let mut left = self.factor()?;
+		while let Some(operator) = self.peek_operator(&["*", "/", "//", "%"]) {
+			self.position += 1;
+			left = Expression::Binary(operator, Box::new(left), Box::new(self.factor()?));
+		}
+		Ok(left)
+	}
+
+	fn factor(&mut self) -> Result<Expression, XacroExpressionError> {
+		match self.peek_operator(&["+", "-"]) {
+			Some(operator) => {
+				self.position += 1;
+				let operand = self.factor()?;
+				Ok(match operator {
+					"-" => Expression::Negate(Box::new(operand)),
+					_ => operand,
+				})
+			}
+			None => self.power(),
+		}
+	}
+
+	fn power(&mut self) -> Result<Expression, XacroExpressionError> {
+		let base = self.primary()?;
+		match self.peek_operator(&["**"]) {
+			Some(operator) => {
+				self.position += 1;
+				// The exponent binds tighter than a unary operator on the left, but looser on the right.
+				Ok(Expression::Binary(
+					operator,
+					Box::new(base),
+					Box::new(self.factor()?),
+				))
+			}
+			None => Ok(base),
+		}
+	}
+
+	fn primary(&mut self) -> Result<Expression, XacroExpressionError> {
+		let token = self
+			.tokens
+			.get(self.position)
+			.ok_or_else(|| XacroExpressionError::Syntax("unexpected end".to_string()))?;
+		self.position += 1;
+
+		match token {
+			Token::Int(value) => Ok(Expression::Literal(Value::Int(*value))),
+			Token::Float(value) => Ok(Expression::Literal(Value::Float(*value))),
+			Token::Text(text) => Ok(Expression::Literal(Value::Text(text.clone()))),
+			Token::Operator("(") => {
+				let expression = self.expression()?;
+				self.expect_operator(")")?;
+				Ok(expression)
+			}
+			Token::Name(name) => {
+				let mut name = name.clone();
+				// Only the `math` module is available, so its prefix can be removed.
+				if name == "math" && self.peek_operator(&["."]).is_some() {
+					self.position += 1;
+					match self.tokens.get(self.position) {
+						Some(Token::Name(attribute)) => {
+							self.position += 1;
+							name = attribute.clone();
+						}
+						_ => {
+							return Err(XacroExpressionError::Syntax(
+								"expected a name after 'math.'".to_string(),
+							))
+						}
+					}
+				}
+
+				match self.peek_operator(&["("]) {
+					Some(_) => {
+						self.position += 1;
+						let mut arguments = Vec::new();
+						while self.peek_operator(&[")"]).is_none() {
+							arguments.push(self.expression()?);
+							if self.peek_operator(&[","]).is_none() {
+								break;
+							}
+							self.position += 1;
+						}
+						self.expect_operator(")")?;
+						Ok(Expression::Call(name, arguments))
+					}
+					None => Ok(Expression::Name(name)),
+				}
+			}
+			token => Err(XacroExpressionError::Syntax(format!("unexpected {token}"))),
+		}
+	}
+}
+
+impl Expression {
+	fn evaluate(
+		&self,
+		lookup: &dyn Fn(&str) -> Option<Value>,
+	) -> Result<Value, XacroExpressionError> {
+		match self {
+			Expression::Literal(value) => Ok(value.clone()),
+			Expression::Name(name) => lookup(name)
+				.or_else(|| constant(name))
+				.ok_or_else(|| XacroExpressionError::UndefinedName(name.clone())),
+			Expression::Call(name, arguments) => call(
+				name,
+				arguments
+					.iter()
+					.map(|argument| argument.evaluate(lookup))
+					.collect::<Result<Vec<_>, _>>()?,
+			),
+			Expression::Negate(operand) => match operand.evaluate(lookup)? {
+				Value::Float(value) => Ok(Value::Float(-value)),
+				value => value
+					.as_i64()
+					.map(|value| Value::Int(-value))
+					.ok_or_else(|| type_error("-", &[&value])),
+			},
+			Expression::Not(operand) => Ok(Value::Bool(!operand.evaluate(lookup)?.is_truthy())),
+			Expression::Binary(operator, left, right) => {
+				binary(operator, left.evaluate(lookup)?, right.evaluate(lookup)?)
+			}
+			Expression::Comparison(left, comparisons) => {
+				let mut left = left.evaluate(lookup)?;
+				for (operator, right) in comparisons {
+					let right = right.evaluate(lookup)?;
+					if !compare(operator, &left, &right)? {
+						return Ok(Value::Bool(false));
+					}
+					left = right;
+				}
+				Ok(Value::Bool(true))
+			}
+			Expression::And(left, right) => {
+				let left = left.evaluate(lookup)?;
+				match left.is_truthy() {
+					true => right.evaluate(lookup),
+					false => Ok(left),
+				}
+			}
+			Expression::Or(left, right) => {
+				let left = left.evaluate(lookup)?;
+				match left.is_truthy() {
+					true => Ok(left),
+					false => right.evaluate(lookup),
+				}
+			}
+			Expression::Conditional {
+				condition,
+				then,
+				otherwise,
+			} => match condition.evaluate(lookup)?.is_truthy() {
+				true => then.evaluate(lookup),
+				false => otherwise.evaluate(lookup),
+			},
+		}
+	}
+}
+
+fn type_error(operator: &str, values: &[&Value]) -> XacroExpressionError {
+	XacroExpressionError::Type(format!(
+		"'{operator}' does not support ({})",
+		values
+			.iter()
+			.map(|value| value.type_name())
+			.collect::<Vec<_>>()
+			.join(", ")
+	))
+}
+
+/// Gets the value of the constants of the Python `math` module and the Python boolean literals.
+fn constant(name: &str) -> Option<Value> {
+	match name {
+		"pi" => Some(Value::Float(consts::PI)),
+		"e" => Some(Value::Float(consts::E)),
+		"tau" => Some(Value::Float(consts::TAU)),
+		"inf" => Some(Value::Float(f64::INFINITY)),
+		"nan" => Some(Value::Float(f64::NAN)),
+		"True" => Some(Value::Bool(true)),
+		"False" => Some(Value::Bool(false)),
+		_ => None,
+	}
+}
+
+fn binary(operator: &str, left: Value, right: Value) -> Result<Value, XacroExpressionError> {
+	if let (Value::Text(left), Value::Text(right)) = (&left, &right) {
+		return match operator {
+			"+" => Ok(Value::Text(format!("{left}{right}"))),
+			_ => Err(type_error(
+				operator,
+				&[&Value::Text(left.clone()), &Value::Text(right.clone())],
+			)),
+		};
+	}
+
+	if let (Some(a), Some(b)) = (left.as_i64(), right.as_i64()) {
+		let result = match operator {
+			"+" => a.checked_add(b),
+			"-" => a.checked_sub(b),
+			"*" => a.checked_mul(b),
+			"//" | "%" if b == 0 => return Err(XacroExpressionError::DivisionByZero),
+			"//" => Some(a.div_euclid(b) - i64::from(b < 0 && a.rem_euclid(b) != 0)),
+			"%" => Some(a - b * (a.div_euclid(b) - i64::from(b < 0 && a.rem_euclid(b) != 0))),
+			"**" if b >= 0 => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+			_ => None,
+		};
+
+		if let Some(result) = result {
+			return Ok(Value::Int(result));
+		}
+	}
+
+	match (left.as_f64(), right.as_f64()) {
+		(Some(a), Some(b)) => match operator {
+			"+" => Ok(Value::Float(a + b)),
+			"-" => Ok(Value::Float(a - b)),
+			"*" => Ok(Value::Float(a * b)),
+			"/" | "//" | "%" if b == 0. => Err(XacroExpressionError::DivisionByZero),
+			"/" => Ok(Value::Float(a / b)),
+			"//" => Ok(Value::Float((a / b).floor())),
+			"%" => Ok(Value::Float(a - b * (a / b).floor())),
+			"**" => Ok(Value::Float(a.powf(b))),
+			_ => Err(type_error(operator, &[&left, &right])),
+		},
+		_ => Err(type_error(operator, &[&left, &right])),
+	}
+}
+
+fn compare(operator: &str, left: &Value, right: &Value) -> Result<bool, XacroExpressionError> {
+	let ordering = match (left, right) {
+		(Value::Text(a), Value::Text(b)) => a.partial_cmp(b),
+		_ => match (left.as_f64(), right.as_f64()) {
+			(Some(a), Some(b)) => a.partial_cmp(&b),
+			// Values of different types are never equal.
+			_ => match operator {
+				"==" => return Ok(false),
+				"!=" => return Ok(true),
+				_ => return Err(type_error(operator, &[left, right])),
+			},
+		},
+	};
+
+	use std::cmp::Ordering::*;
+	Ok(match operator {
+		"==" => ordering == Some(Equal),
+		"!=" => ordering != Some(Equal),
+		"<" => ordering == Some(Less),
+		"<=" => matches!(ordering, Some(Less | Equal)),
+		">" => ordering == Some(Greater),
+		">=" => matches!(ordering, Some(Greater | Equal)),
+		_ => unreachable!("Only comparison operators are parsed as comparisons"),
+	})
+}
+
+/// Calls the builtin or `math` function `name`.
+fn call(name: &str, arguments: Vec<Value>) -> Result<Value, XacroExpressionError> {
+	let numbers = || {
+		arguments
+			.iter()
+			.map(|argument| {
+				argument
+					.as_f64()
+					.ok_or_else(|| type_error(name, &[argument]))
+			})
+			.collect::<Result<Vec<_>, _>>()
+	};
+	let arity_error = || {
+		XacroExpressionError::Type(format!(
+			"'{name}' does not take {} arguments",
+			arguments.len()
+		))
+	};
+
+	let unary: Option<fn(f64) -> f64> = match name {
+		"sin" => Some(f64::sin),
+		"cos" => Some(f64::cos),
+		"tan" => Some(f64::tan),
+		"asin" => Some(f64::asin),
+		"acos" => Some(f64::acos),
+		"atan" => Some(f64::atan),
+		"sinh" => Some(f64::sinh),
+		"cosh" => Some(f64::cosh),
+		"tanh" => Some(f64::tanh),
+		"sqrt" => Some(f64::sqrt),
+		"exp" => Some(f64::exp),
+		"log10" => Some(f64::log10),
+		"log2" => Some(f64::log2),
+		"fabs" => Some(f64::abs),
+		"radians" => Some(f64::to_radians),
+		"degrees" => Some(f64::to_degrees),
+		_ => None,
+	};
+	if let Some(function) = unary {
+		return match numbers()?.as_slice() {
+			[value] => Ok(Value::Float(function(*value))),
+			_ => Err(arity_error()),
+		};
+	}
+
+	let binary: Option<fn(f64, f64) -> f64> = match name {
+		"atan2" => Some(f64::atan2),
+		"pow" => Some(f64::powf),
+		"hypot" => Some(f64::hypot),
+		"fmod" => Some(|a, b| a % b),
+		"copysign" => Some(f64::copysign),
+		_ => None,
+	};
+	if let Some(function) = binary {
+		return match numbers()?.as_slice() {
+			[a, b] => Ok(Value::Float(function(*a, *b))),
+			_ => Err(arity_error()),
+		};
+	}
+
+	match (name, arguments.as_slice()) {
+		("log", [_]) => Ok(Value::Float(numbers()?[0].ln())),
+		("log", [_, _]) => {
+			let numbers = numbers()?;
+			Ok(Value::Float(numbers[0].log(numbers[1])))
+		}
+		("floor" | "ceil" | "trunc", [_]) => {
+			let value = numbers()?[0];
+			Ok(Value::Int(match name {
+				"floor" => value.floor(),
+				"ceil" => value.ceil(),
+				_ => value.trunc(),
+			} as i64))
+		}
+		("abs", [value]) => match value {
+			Value::Float(value) => Ok(Value::Float(value.abs())),
+			value => value
+				.as_i64()
+				.map(|value| Value::Int(value.abs()))
+				.ok_or_else(|| type_error(name, &[value])),
+		},
+		("min" | "max", [_, ..]) => {
+			numbers()?;
+			arguments
+				.iter()
+				.cloned()
+				.reduce(|a, b| {
+					let (x, y) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+					match (name == "min" && y < x) || (name == "max" && y > x) {
+						true => b,
+						false => a,
+					}
+				})
+				.ok_or_else(arity_error)
+		}
+		("round", [_]) => Ok(Value::Int(round_half_even(numbers()?[0]) as i64)),
+		("round", [_, digits]) => {
+			let digits = digits.as_i64().ok_or_else(|| type_error(name, &[digits]))?;
+			let scale = 10f64.powi(digits as i32);
+			Ok(Value::Float(round_half_even(numbers()?[0] * scale) / scale))
+		}
+		("int", [Value::Text(text)]) => text
+			.trim()
+			.parse()
+			.map(Value::Int)
+			.map_err(|_| type_error(name, &[&arguments[0]])),
+		("int", [_]) => Ok(Value::Int(numbers()?[0].trunc() as i64)),
+		("float", [Value::Text(text)]) => text
+			.trim()
+			.parse()
+			.map(Value::Float)
+			.map_err(|_| type_error(name, &[&arguments[0]])),
+		("float", [_]) => Ok(Value::Float(numbers()?[0])),
+		("str", [value]) => Ok(Value::Text(value.to_string())),
+		("bool", [value]) => Ok(Value::Bool(value.is_truthy())),
+		("len", [Value::Text(text)]) => Ok(Value::Int(text.chars().count() as i64)),
+		(
+			"log" | "floor" | "ceil" | "trunc" | "abs" | "min" | "max" | "round" | "int" | "float"
+			| "str" | "bool" | "len",
+			_,
+		) => Err(arity_error()),
+		_ => Err(XacroExpressionError::UnknownFunction(name.to_string())),
+	}
+}
+
+/// Rounds to the nearest integer, with ties rounded to the even integer like Python does.
+fn round_half_even(value: f64) -> f64 {
+	let rounded = value.round();
+	match (value - value.trunc()).abs() == 0.5 && rounded % 2. != 0. {
+		true => rounded - value.signum(),
+		false => rounded,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{evaluate, Value, XacroExpressionError};
+	use test_log::test;
+
+	fn eval(expression: &str) -> Result<Value, XacroExpressionError> {
+		evaluate(expression, &|name| match name {
+			"width" => Some(Value::Float(0.5)),
+			"count" => Some(Value::Int(3)),
+			"side" => Some(Value::Text("left".to_string())),
+			_ => None,
+		})
+	}
+
+	#[test]
+	fn arithmetic() {
+		assert_eq!(eval("1 + 2 * 3"), Ok(Value::Int(7)));
+		assert_eq!(eval("(1 + 2) * 3"), Ok(Value::Int(9)));
+		assert_eq!(eval("7 / 2"), Ok(Value::Float(3.5)));
+		assert_eq!(eval("7 // 2"), Ok(Value::Int(3)));
+		assert_eq!(eval("-7 // 2"), Ok(Value::Int(-4)));
+		assert_eq!(eval("-7 % 3"), Ok(Value::Int(2)));
+		assert_eq!(eval("-2**2"), Ok(Value::Int(-4)));
+		assert_eq!(eval("2**-1"), Ok(Value::Float(0.5)));
+		assert_eq!(eval("2 * width + count"), Ok(Value::Float(4.)));
+		assert_eq!(eval("1.5e-3 * 2"), Ok(Value::Float(0.003)));
+		assert_eq!(eval("1 / 0"), Err(XacroExpressionError::DivisionByZero));
+	}
+
+	#[test]
+	fn functions() {
+		assert_eq!(
+			eval("pi / 2"),
+			Ok(Value::Float(std::f64::consts::FRAC_PI_2))
+		);
+		assert_eq!(eval("math.cos(0)"), Ok(Value::Float(1.)));
+		assert_eq!(eval("radians(180)"), Ok(Value::Float(std::f64::consts::PI)));
+		assert_eq!(eval("max(1, width, -2)"), Ok(Value::Int(1)));
+		assert_eq!(eval("abs(-count)"), Ok(Value::Int(3)));
+		assert_eq!(eval("floor(2.7)"), Ok(Value::Int(2)));
+		assert_eq!(eval("round(2.5)"), Ok(Value::Int(2)));
+		assert_eq!(eval("int('4') + float(1)"), Ok(Value::Float(5.)));
+		assert_eq!(
+			eval("load_yaml('file.yaml')"),
+			Err(XacroExpressionError::UnknownFunction(
+				"load_yaml".to_string()
+			))
+		);
+	}
+
+	#[test]
+	fn logic() {
+		assert_eq!(eval("count > 2 and width < 1"), Ok(Value::Bool(true)));
+		assert_eq!(eval("0 < width < 0.2"), Ok(Value::Bool(false)));
+		assert_eq!(eval("not count == 3"), Ok(Value::Bool(false)));
+		assert_eq!(eval("side == 'left'"), Ok(Value::Bool(true)));
+		assert_eq!(eval("1 if side == \"right\" else -1"), Ok(Value::Int(-1)));
+		// The right hand side is not evaluated.
+		assert_eq!(eval("True or undefined"), Ok(Value::Bool(true)));
+		assert_eq!(
+			eval("side + '_arm'"),
+			Ok(Value::Text("left_arm".to_string()))
+		);
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			eval("length * 2"),
+			Err(XacroExpressionError::UndefinedName("length".to_string()))
+		);
+		assert!(matches!(eval("1 +"), Err(XacroExpressionError::Syntax(_))));
+		assert!(matches!(eval("(1"), Err(XacroExpressionError::Syntax(_))));
+		assert!(matches!(
+			eval("side * 'x'"),
+			Err(XacroExpressionError::Type(_))
+		));
+	}
+
+	#[test]
+	fn literals() {
+		assert_eq!(Value::from_literal("3"), Value::Int(3));
+		assert_eq!(Value::from_literal("0.25"), Value::Float(0.25));
+		assert_eq!(Value::from_literal("true"), Value::Bool(true));
+		assert_eq!(
+			Value::from_literal("'quoted'"),
+			Value::Text("quoted".to_string())
+		);
+		assert_eq!(
+			Value::from_literal("1_000"),
+			Value::Text("1_000".to_string())
+		);
+		assert_eq!(
+			Value::from_literal("0 0 1"),
+			Value::Text("0 0 1".to_string())
+		);
+	}
+
+	#[test]
+	fn display() {
+		assert_eq!(Value::Int(3).to_string(), "3");
+		assert_eq!(Value::Float(3.).to_string(), "3.0");
+		assert_eq!(Value::Float(0.1).to_string(), "0.1");
+		assert_eq!(Value::Bool(true).to_string(), "True");
+	}
+}
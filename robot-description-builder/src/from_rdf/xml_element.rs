@@ -0,0 +1,231 @@
+//! A minimal in-memory representation of a XML document, used by the readers of the robot description formats.
+use quick_xml::{
+	errors::SyntaxError,
+	events::{BytesStart, Event},
+	Reader,
+};
+
+use super::XMLReadError;
+
+/// A simplified XML element, which owns all of its data.
+///
+/// Only the information needed to interpret robot descriptions is kept:
+/// the (qualified) `name`, the unescaped `attributes`, the child elements and the concatenated text content.
+/// The `line` at which the element starts is recorded to allow for error reporting.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub(crate) struct XMLElement {
+	/// The qualified name of the element, including the namespace prefix if any (e.g. `"xacro:macro"`).
+	pub name: String,
+	/// The attributes of the element in document order, with their values unescaped.
+	pub attributes: Vec<(String, String)>,
+	/// The child elements in document order.
+	pub children: Vec<XMLElement>,
+	/// The (trimmed) text content of the element, including `CDATA` sections.
+	pub text: String,
+	/// The line number (starting at 1) on which the element starts.
+	pub line: usize,
+}
+
+impl XMLElement {
+	/// Parses `source` and returns the root element of the document.
+	///
+	/// Comments, processing instructions, the XML declaration and the doctype are skipped.
+	pub fn parse(source: &str) -> Result<Self, XMLReadError> {
+		let mut reader = Reader::from_str(source);
+		reader.config_mut().trim_text(true);
+
+		let mut lines = LineCursor::new(source);
+		let mut stack: Vec<XMLElement> = Vec::new();
+		let mut root = None;
+
+		loop {
+			let start_position = reader.buffer_position() as usize;
+			let event = reader.read_event().map_err(|error| XMLReadError {
+				line: lines.line_at(reader.error_position() as usize),
+				source: error,
+			})?;
+
+			match event {
+				Event::Start(start) => {
+					stack.push(Self::from_start(
+						source,
+						&mut lines,
+						start_position,
+						&start,
+					)?);
+				}
+				Event::Empty(start) => {
+					let element = Self::from_start(source, &mut lines, start_position, &start)?;
+					close_element(element, &mut stack, &mut root);
+				}
+				Event::End(_) => {
+					// The reader checks that the end tags match, so the stack can not be empty here.
+					if let Some(element) = stack.pop() {
+						close_element(element, &mut stack, &mut root);
+					}
+				}
+				Event::Text(text) => {
+					if let Some(element) = stack.last_mut() {
+						let text = text.unescape().map_err(|error| XMLReadError {
+							line: lines.line_at(start_position),
+							source: error,
+						})?;
+						element.text.push_str(&text);
+					}
+				}
+				Event::CData(data) => {
+					if let Some(element) = stack.last_mut() {
+						element
+							.text
+							.push_str(&String::from_utf8_lossy(&data.into_inner()));
+					}
+				}
+				Event::Eof => break,
+				_ => {}
+			}
+		}
+
+		root.ok_or_else(|| XMLReadError {
+			line: lines.line_at(source.len()),
+			source: quick_xml::Error::Syntax(SyntaxError::UnclosedTag),
+		})
+	}
+
+	/// Creates a new `XMLElement` without children from a start (or empty) tag.
+	///
+	/// The `position` is the position of the reader before the tag was read.
+	fn from_start(
+		source: &str,
+		lines: &mut LineCursor,
+		position: usize,
+		start: &BytesStart,
+	) -> Result<Self, XMLReadError> {
+		// The position before the event can still be in front of skipped whitespace.
+		let line = lines.line_at(position + source[position..].find('<').unwrap_or_default());
+
+		let attributes = start
+			.attributes()
+			.map(|attribute| -> Result<(String, String), quick_xml::Error> {
+				let attribute = attribute?;
+				Ok((
+					String::from_utf8_lossy(attribute.key.as_ref()).into_owned(),
+					attribute.unescape_value()?.into_owned(),
+				))
+			})
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|error| XMLReadError {
+				line,
+				source: error,
+			})?;
+
+		Ok(Self {
+			name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+			attributes,
+			line,
+			..Default::default()
+		})
+	}
+
+	/// Gets the value of the attribute with the specified `key`, if it exists.
+	pub fn attribute(&self, key: &str) -> Option<&str> {
+		self.attributes
+			.iter()
+			.find(|(attribute_key, _)| attribute_key == key)
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// Gets the first child element with the specified `name`, if it exists.
+	pub fn child(&self, name: &str) -> Option<&XMLElement> {
+		self.children.iter().find(|child| child.name == name)
+	}
+
+	/// Gets an iterator over all child elements with the specified `name`.
+	pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XMLElement> {
+		self.children.iter().filter(move |child| child.name == name)
+	}
+}
+
+/// Attaches a finished `element` to its parent, or makes it the `root` if it has no parent.
+fn close_element(element: XMLElement, stack: &mut [XMLElement], root: &mut Option<XMLElement>) {
+	match stack.last_mut() {
+		Some(parent) => parent.children.push(element),
+		None => {
+			// Only the first root element is kept, the reader rejects trailing elements in most cases.
+			root.get_or_insert(element);
+		}
+	}
+}
+
+/// Keeps track of the line number (starting at 1) of a byte position in `source`.
+///
+/// The positions are visited in (nearly) ascending order while parsing,
+/// so only the bytes between the last and the new position need to be counted.
+struct LineCursor<'a> {
+	source: &'a str,
+	position: usize,
+	line: usize,
+}
+
+impl<'a> LineCursor<'a> {
+	fn new(source: &'a str) -> Self {
+		Self {
+			source,
+			position: 0,
+			line: 1,
+		}
+	}
+
+	/// Calculates the line number (starting at 1) of the byte `position` and moves the cursor there.
+	fn line_at(&mut self, position: usize) -> usize {
+		let position = position.min(self.source.len());
+		let count_newlines = |bytes: &[u8]| bytes.iter().filter(|byte| **byte == b'\n').count();
+
+		if position >= self.position {
+			self.line += count_newlines(&self.source.as_bytes()[self.position..position]);
+		} else {
+			self.line -= count_newlines(&self.source.as_bytes()[position..self.position]);
+		}
+		self.position = position;
+
+		self.line
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{LineCursor, XMLElement};
+	use test_log::test;
+
+	#[test]
+	fn parse() {
+		let root = XMLElement::parse(
+			"<?xml version=\"1.0\"?>\n<!-- comment -->\n<robot name=\"a &amp; b\">\n  <link name=\"l\"/>\n  <type>  text </type>\n</robot>",
+		)
+		.unwrap();
+
+		assert_eq!(root.name, "robot");
+		assert_eq!(root.attribute("name"), Some("a & b"));
+		assert_eq!(root.line, 3);
+		assert_eq!(root.children.len(), 2);
+		assert_eq!(root.child("link").unwrap().line, 4);
+		assert_eq!(root.child("link").unwrap().attribute("name"), Some("l"));
+		assert_eq!(root.child("type").unwrap().text, "text");
+		assert_eq!(root.children_named("link").count(), 1);
+	}
+
+	#[test]
+	fn parse_error_line() {
+		let error = XMLElement::parse("<robot>\n<link>\n</joint>\n</robot>").unwrap_err();
+		assert_eq!(error.line(), 3);
+	}
+
+	#[test]
+	fn line_cursor() {
+		let mut lines = LineCursor::new("a\nb\n\nc");
+		assert_eq!(lines.line_at(0), 1);
+		assert_eq!(lines.line_at(2), 2);
+		assert_eq!(lines.line_at(5), 4);
+		assert_eq!(lines.line_at(3), 2);
+		assert_eq!(lines.line_at(100), 4);
+	}
+}
@@ -172,6 +172,20 @@ fn replace_group_id_delimiters(input: &str) -> String {
 		.replace(DELIMITER_ESCAPED_CLOSE_GROUPID, DELIMITER_CLOSE_GROUPID)
 }
 
+/// Escapes the [`GroupID`] delimiters in the supplied `&str`
+///
+/// This is used when reading identifiers from a description, so that the [`display`](GroupID::display) of the result matches the original input.
+///
+/// The following replacements get made:
+///  - [`DELIMITER_OPEN_GROUPID`] with [`DELIMITER_ESCAPED_OPEN_GROUPID`]
+///  - [`DELIMITER_CLOSE_GROUPID`] with [`DELIMITER_ESCAPED_CLOSE_GROUPID`]
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+pub(crate) fn escape_group_id_delimiters(input: &str) -> String {
+	input
+		.replace(DELIMITER_OPEN_GROUPID, DELIMITER_ESCAPED_OPEN_GROUPID)
+		.replace(DELIMITER_CLOSE_GROUPID, DELIMITER_ESCAPED_CLOSE_GROUPID)
+}
+
 /// Format and validation trait for `GroupID`s
 ///
 /// This trait is used to expand [`String`] and string slices for validity checks and `GroupID` escaped formatting applied
@@ -530,6 +544,22 @@ mod tests {
 		);
 	}
 
+	#[cfg(feature = "urdf")]
+	#[test]
+	fn test_escape_group_id_delimiters() {
+		use super::escape_group_id_delimiters;
+
+		assert_eq!(escape_group_id_delimiters("nothing"), "nothing");
+		assert_eq!(
+			escape_group_id_delimiters("Leg_[[L04]]_Claw"),
+			"Leg_[\\[L04]\\]_Claw"
+		);
+		assert_eq!(
+			replace_group_id_delimiters(&escape_group_id_delimiters("Front[[:[[Center]]:]]Back")),
+			"Front[[:[[Center]]:]]Back"
+		);
+	}
+
 	mod group_id {
 		use super::{test, DELIMITER_ESCAPED_CLOSE_GROUPID, DELIMITER_ESCAPED_OPEN_GROUPID};
 		use crate::identifiers::{GroupID, GroupIDError, GroupIDErrorKind};
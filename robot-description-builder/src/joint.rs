@@ -18,20 +18,34 @@ pub use smartjointbuilder::SmartJointBuilder;
 
 #[cfg(feature = "xml")]
 use std::borrow::Cow;
-use std::sync::{Arc, Weak};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Weak},
+};
 
+#[cfg(feature = "mjcf")]
+use crate::to_rdf::to_mjcf::ToMJCF;
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 use crate::{
 	chained::Chained,
-	cluster_objects::kinematic_data_tree::KinematicDataTree,
+	cluster_objects::{
+		kinematic_data_errors::AddJointError, kinematic_data_tree::KinematicDataTree,
+	},
 	identifiers::GroupID,
 	link::Link,
 	transform::Transform,
 	utils::{ArcLock, ArcRW, WeakLock},
 	yank_errors::{RebuildBranchError, YankJointError},
+	Float,
 };
 
+#[cfg(feature = "mjcf")]
+use nalgebra::Vector3;
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -50,12 +64,17 @@ pub struct Joint {
 	///
 	/// In URDF this field is refered to as `<origin>`
 	transform: Transform,
-	axis: Option<(f32, f32, f32)>,
+	axis: Option<(Float, Float, Float)>,
 	calibration: joint_data::CalibrationData,
 	dynamics: joint_data::DynamicsData,
 	limit: Option<joint_data::LimitData>,
 	/// TODO: Should be editable
 	mimic: Option<joint_data::MimicData>,
+	/// The `MimicBuilderData` which still needs to be resolved to `MimicData`.
+	///
+	/// When building a chain, the mimicked `Joint` might not be registered yet.
+	/// The tree resolves it after the whole chain has been registered.
+	pending_mimic: Option<joint_data::MimicBuilderData>,
 	safety_controller: Option<joint_data::SafetyControllerData>,
 
 	me: WeakLock<Joint>,
@@ -97,11 +116,26 @@ impl Joint {
 		&self.transform
 	}
 
-	pub fn axis(&self) -> Option<(f32, f32, f32)> {
+	pub fn axis(&self) -> Option<(Float, Float, Float)> {
 		// This is fine since it implements Copy
 		self.axis
 	}
 
+	/// Gets a reference to the `LimitData` of this `Joint`, if specified.
+	pub fn limit(&self) -> Option<&joint_data::LimitData> {
+		self.limit.as_ref()
+	}
+
+	/// Gets a reference to the `MimicData` of this `Joint`, if specified.
+	pub fn mimic(&self) -> Option<&joint_data::MimicData> {
+		self.mimic.as_ref()
+	}
+
+	/// Gets a reference to the `SafetyControllerData` of this `Joint`, if specified.
+	pub fn safety_controller(&self) -> Option<&joint_data::SafetyControllerData> {
+		self.safety_controller.as_ref()
+	}
+
 	/// Make a `JointBuilder` to build a 'Clone' of the `Joint`.
 	///
 	/// This method does not clone the child of the [`Joint`], only the `Joint` is self.
@@ -120,11 +154,41 @@ impl Joint {
 			calibration: self.calibration,
 			dynamics: self.dynamics,
 			limit: self.limit,
-			mimic: self.mimic.clone().map(|mimic| mimic.into()),
+			mimic: self
+				.mimic
+				.clone()
+				.map(|mimic| mimic.into())
+				.or_else(|| self.pending_mimic.clone()),
 			safety_controller: self.safety_controller,
 		}
 	}
 
+	/// Resolves the pending `MimicBuilderData` of this `Joint`, if any, with the `Joint` index of the tree.
+	///
+	/// This should only be called after the mimicked `Joint` has been registered in the tree.
+	pub(crate) fn resolve_pending_mimic(
+		&mut self,
+		joint_index: &HashMap<String, WeakLock<Joint>>,
+	) -> Result<(), AddJointError> {
+		if let Some(mimic) = self.pending_mimic.as_ref() {
+			let joint =
+				joint_index
+					.get(&mimic.joint_name)
+					.ok_or_else(|| AddJointError::UnknownMimic {
+						joint: self.name.clone(),
+						mimicked: mimic.joint_name.clone(),
+					})?;
+
+			self.mimic = Some(joint_data::MimicData {
+				joint: Weak::clone(joint),
+				multiplier: mimic.multiplier,
+				offset: mimic.offset,
+			});
+			self.pending_mimic = None;
+		}
+		Ok(())
+	}
+
 	pub(crate) fn rebuild_branch_continued(&self) -> Result<JointBuilder, RebuildBranchError> {
 		#[cfg(any(feature = "logging", test))]
 		log::info!(target: "JointBuilder","Rebuilding: {}", self.name());
@@ -278,6 +342,291 @@ impl ToURDF for Joint {
 	}
 }
 
+/// The limits used to describe a [`Continuous`](JointType::Continuous) joint as a `revolute` joint in SDFormat.
+///
+/// Limits with at least this magnitude are read as no limit.
+#[cfg(feature = "sdf")]
+pub(crate) const SDF_CONTINUOUS_LIMIT: Float = 1e16;
+
+#[cfg(feature = "sdf")]
+impl ToSDF for Joint {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let sdf_joint_type = match self.joint_type() {
+			JointType::Fixed => Some("fixed"),
+			JointType::Revolute | JointType::Continuous => Some("revolute"),
+			JointType::Prismatic => Some("prismatic"),
+			// SDFormat has no equivalent for these joint types.
+			JointType::Floating | JointType::Planar => None,
+		};
+
+		match sdf_joint_type {
+			Some(sdf_joint_type) => {
+				let parent_link_name = self.parent_link().read().unwrap().name().display();
+
+				let element = writer
+					.create_element("joint")
+					.with_attribute(Attribute {
+						key: QName(b"name"),
+						value: self.name().display().as_bytes().into(),
+					})
+					.with_attribute(Attribute {
+						key: QName(b"type"),
+						value: sdf_joint_type.as_bytes().into(),
+					});
+
+				element.write_inner_content(|writer| -> quick_xml::Result<()> {
+					// Before SDFormat 1.7 the joint frame is the child link frame, so no pose is required.
+					if sdf_config.sdf_version.supports_relative_to() {
+						self.transform()
+							.write_sdf_pose(writer, Some(&parent_link_name))?;
+					}
+
+					writer
+						.create_element("parent")
+						.write_text_content(BytesText::new(&parent_link_name))?;
+
+					writer
+						.create_element("child")
+						.write_text_content(BytesText::new(
+							&self.child_link().read().unwrap().name().display(),
+						))?;
+
+					if self.joint_type() != JointType::Fixed {
+						writer.create_element("axis").write_inner_content(
+							|writer| -> quick_xml::Result<()> {
+								// The URDF default axis is used, when no axis is specified.
+								let (x, y, z) = self.axis.unwrap_or((1., 0., 0.));
+								writer
+									.create_element("xyz")
+									.write_text_content(BytesText::new(&format!(
+										"{} {} {}",
+										x, y, z
+									)))?;
+
+								self.dynamics.to_sdf(writer, sdf_config)?;
+
+								let limit = match self.joint_type() {
+									JointType::Continuous => Some(joint_data::LimitData {
+										lower: Some(-SDF_CONTINUOUS_LIMIT),
+										upper: Some(SDF_CONTINUOUS_LIMIT),
+										// Negative values mean unlimited in SDFormat.
+										..self.limit.unwrap_or(joint_data::LimitData {
+											effort: -1.,
+											velocity: -1.,
+											..Default::default()
+										})
+									}),
+									_ => self.limit,
+								};
+
+								if let Some(limit) = &limit {
+									limit.to_sdf(writer, sdf_config)?;
+								}
+
+								if let (true, Some(mimic)) =
+									(sdf_config.sdf_version.supports_mimic(), &self.mimic)
+								{
+									mimic.to_sdf(writer, sdf_config)?;
+								}
+
+								Ok(())
+							},
+						)?;
+					}
+
+					Ok(())
+				})?;
+			}
+			None => {
+				#[cfg(any(feature = "logging", test))]
+				log::warn!(
+					"The joint '{}' has no SDFormat equivalent for its type {:?}, it is not written",
+					self.name(),
+					self.joint_type()
+				);
+			}
+		}
+
+		self.child_link()
+			.read()
+			.unwrap()
+			.to_sdf(writer, sdf_config)?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl Joint {
+	/// Writes a single MJCF `<joint>` named `name`, with the `DynamicsData` of this `Joint`.
+	///
+	/// The `axis` and the `range` are only written when they are specified.
+	fn write_mjcf_joint(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		name: &str,
+		mjcf_joint_type: &str,
+		axis: Option<(Float, Float, Float)>,
+		range: Option<(Float, Float)>,
+	) -> Result<(), quick_xml::Error> {
+		let mut element = writer
+			.create_element("joint")
+			.with_attribute(Attribute {
+				key: QName(b"name"),
+				value: name.as_bytes().into(),
+			})
+			.with_attribute(Attribute {
+				key: QName(b"type"),
+				value: mjcf_joint_type.as_bytes().into(),
+			});
+
+		if let Some((x, y, z)) = axis {
+			// Adding `0.` turns `-0.` into `0.`, which keeps the descriptions clean.
+			element = element.with_attribute(Attribute {
+				key: QName(b"axis"),
+				value: format!("{} {} {}", x + 0., y + 0., z + 0.)
+					.as_bytes()
+					.into(),
+			});
+		}
+
+		if let Some((lower, upper)) = range {
+			element = element
+				.with_attribute(Attribute {
+					key: QName(b"limited"),
+					value: b"true".as_slice().into(),
+				})
+				.with_attribute(Attribute {
+					key: QName(b"range"),
+					value: format!("{} {}", lower, upper).as_bytes().into(),
+				});
+		}
+
+		// The effort limit only applies to the single degree of freedom joints.
+		if let (JointType::Revolute | JointType::Continuous | JointType::Prismatic, Some(limit)) =
+			(self.joint_type(), self.limit)
+		{
+			if limit.effort > 0. && limit.effort < crate::from_rdf::from_mjcf::UNKNOWN_LIMIT {
+				element = element.with_attribute(Attribute {
+					key: QName(b"actuatorfrcrange"),
+					value: format!("{} {}", -limit.effort, limit.effort)
+						.as_bytes()
+						.into(),
+				});
+			}
+		}
+
+		element
+			.with_attributes(self.dynamics.mjcf_attributes())
+			.write_empty()?;
+		Ok(())
+	}
+
+	/// Gets the `MimicData` of this `Joint`, if it can be written as a MJCF equality constraint.
+	///
+	/// MJCF can only constrain single axis joints to each other,
+	/// so the `MimicData` is only returned when both `Joint`s are written as a single `hinge` or `slide` joint.
+	pub(crate) fn mjcf_mimic(&self) -> Option<&joint_data::MimicData> {
+		let is_single_axis = |joint_type| {
+			matches!(
+				joint_type,
+				JointType::Revolute | JointType::Continuous | JointType::Prismatic
+			)
+		};
+
+		self.mimic.as_ref().filter(|mimic| {
+			is_single_axis(self.joint_type())
+				&& mimic.joint.upgrade().is_some_and(|mimicked_joint| {
+					is_single_axis(mimicked_joint.read().unwrap().joint_type())
+				})
+		})
+	}
+}
+
+/// Writes the `<joint>` elements of the `Joint`, which belong in the `<body>` of the child `Link`.
+#[cfg(feature = "mjcf")]
+impl ToMJCF for Joint {
+	fn to_mjcf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_mjcf_config: &crate::to_rdf::to_mjcf::MJCFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let name = self.name().display();
+		// The URDF default axis is used, when no axis is specified.
+		let axis = self.axis.unwrap_or((1., 0., 0.));
+		// The URDF default limits are used, when no lower or upper limit is specified.
+		let range = self.limit.map(|limit| {
+			(
+				limit.lower.unwrap_or_default(),
+				limit.upper.unwrap_or_default(),
+			)
+		});
+
+		match self.joint_type() {
+			// A `<body>` without `<joint>` elements is welded to its parent.
+			JointType::Fixed => Ok(()),
+			JointType::Revolute => self.write_mjcf_joint(writer, &name, "hinge", Some(axis), range),
+			JointType::Continuous => {
+				self.write_mjcf_joint(writer, &name, "hinge", Some(axis), None)
+			}
+			JointType::Prismatic => {
+				self.write_mjcf_joint(writer, &name, "slide", Some(axis), range)
+			}
+			JointType::Floating => {
+				if self.parent_link().read().unwrap().is_mjcf_world() {
+					return self.write_mjcf_joint(writer, &name, "free", None, None);
+				}
+
+				// MJCF only allows `free` joints in the top level `<body>` elements.
+				for (suffix, axis) in [
+					("x", (1., 0., 0.)),
+					("y", (0., 1., 0.)),
+					("z", (0., 0., 1.)),
+				] {
+					self.write_mjcf_joint(
+						writer,
+						&format!("{}_{}", name, suffix),
+						"slide",
+						Some(axis),
+						None,
+					)?;
+				}
+				self.write_mjcf_joint(writer, &format!("{}_ball", name), "ball", None, None)
+			}
+			JointType::Planar => {
+				// The axis of a planar joint is the normal of the plane.
+				let normal = Vector3::new(axis.0, axis.1, axis.2).normalize();
+				let reference = match normal.x.abs() < 0.9 {
+					true => Vector3::x(),
+					false => Vector3::y(),
+				};
+				let u = (reference - normal * normal.dot(&reference)).normalize();
+				let v = normal.cross(&u);
+
+				for (suffix, axis) in [("u", u), ("v", v)] {
+					self.write_mjcf_joint(
+						writer,
+						&format!("{}_{}", name, suffix),
+						"slide",
+						Some((axis.x, axis.y, axis.z)),
+						None,
+					)?;
+				}
+				self.write_mjcf_joint(
+					writer,
+					&format!("{}_hinge", name),
+					"hinge",
+					Some((normal.x, normal.y, normal.z)),
+					None,
+				)
+			}
+		}
+	}
+}
+
 /// TODO: Maybe remove some fields from check, since it will always match if name and tree are true
 impl PartialEq for Joint {
 	fn eq(&self, other: &Self) -> bool {
@@ -557,8 +906,8 @@ mod tests {
 								.add_transform(Transform::new_translation(4., 0., 0.))
 								.with_axis((0., 0., 1.))
 								.with_limit(100., 1000.)
-								.set_upper_limit(std::f32::consts::FRAC_PI_6)
-								.set_lower_limit(-std::f32::consts::FRAC_PI_6),
+								.set_upper_limit(crate::float::consts::FRAC_PI_6)
+								.set_lower_limit(-crate::float::consts::FRAC_PI_6),
 							LinkBuilder::new("link-1-1").add_visual(
 								Visual::builder(CylinderGeometry::new(0.5, 18.))
 									.named("link-1-1-vis")
@@ -734,8 +1083,8 @@ mod tests {
 					limit: Some(joint_data::LimitData {
 						effort: 100.,
 						velocity: 1000.,
-						lower: Some(-std::f32::consts::FRAC_PI_6),
-						upper: Some(std::f32::consts::FRAC_PI_6),
+						lower: Some(-crate::float::consts::FRAC_PI_6),
+						upper: Some(crate::float::consts::FRAC_PI_6),
 					}),
 					..Default::default()
 				}
@@ -839,8 +1188,8 @@ mod tests {
 							limit: Some(joint_data::LimitData {
 								effort: 100.,
 								velocity: 1000.,
-								lower: Some(-std::f32::consts::FRAC_PI_6),
-								upper: Some(std::f32::consts::FRAC_PI_6),
+								lower: Some(-crate::float::consts::FRAC_PI_6),
+								upper: Some(crate::float::consts::FRAC_PI_6),
 							}),
 							..Default::default()
 						}],
@@ -1,11 +1,12 @@
+use crate::Float;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
 // TODO: DOC
 pub struct CalibrationData {
-	pub rising: Option<f32>,
-	pub falling: Option<f32>,
+	pub rising: Option<Float>,
+	pub falling: Option<Float>,
 }
 
 impl CalibrationData {
@@ -73,6 +74,21 @@ impl crate::to_rdf::to_urdf::ToURDF for CalibrationData {
 	}
 }
 
+#[cfg(feature = "urdf")]
+impl crate::from_rdf::from_urdf::FromURDF for CalibrationData {
+	fn from_urdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		_urdf_context: &mut crate::from_rdf::from_urdf::FromURDFContext,
+	) -> Result<Self, crate::from_rdf::from_urdf::FromURDFError> {
+		use crate::from_rdf::from_urdf::optional_float;
+
+		Ok(Self {
+			rising: optional_float(element, "rising")?,
+			falling: optional_float(element, "falling")?,
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::joint::joint_data::calibration_data::CalibrationData;
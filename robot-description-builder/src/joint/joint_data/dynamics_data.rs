@@ -1,10 +1,13 @@
+use crate::Float;
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
 pub struct DynamicsData {
-	pub damping: Option<f32>,
-	pub friction: Option<f32>,
+	pub damping: Option<Float>,
+	pub friction: Option<Float>,
 }
 
 impl DynamicsData {
@@ -72,6 +75,106 @@ impl crate::to_rdf::to_urdf::ToURDF for DynamicsData {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl crate::to_rdf::to_sdf::ToSDF for DynamicsData {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		if self.contains_some() {
+			writer.create_element("dynamics").write_inner_content(
+				|writer| -> quick_xml::Result<()> {
+					if let Some(damping) = self.damping {
+						writer
+							.create_element("damping")
+							.write_text_content(BytesText::new(&damping.to_string()))?;
+					}
+
+					if let Some(friction) = self.friction {
+						writer
+							.create_element("friction")
+							.write_text_content(BytesText::new(&friction.to_string()))?;
+					}
+
+					Ok(())
+				},
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl DynamicsData {
+	/// Gets the attributes of a MJCF `<joint>` for the specified fields.
+	///
+	/// The `friction` is written as the `frictionloss` of the joint.
+	pub(crate) fn mjcf_attributes(&self) -> Vec<Attribute<'static>> {
+		[
+			(QName(b"damping"), self.damping),
+			(QName(b"frictionloss"), self.friction),
+		]
+		.into_iter()
+		.filter_map(|(key, value)| {
+			value.map(|value| Attribute {
+				key,
+				value: value.to_string().into_bytes().into(),
+			})
+		})
+		.collect()
+	}
+}
+
+#[cfg(feature = "urdf")]
+impl crate::from_rdf::from_urdf::FromURDF for DynamicsData {
+	fn from_urdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		_urdf_context: &mut crate::from_rdf::from_urdf::FromURDFContext,
+	) -> Result<Self, crate::from_rdf::from_urdf::FromURDFError> {
+		use crate::from_rdf::from_urdf::optional_float;
+
+		Ok(Self {
+			damping: optional_float(element, "damping")?,
+			friction: optional_float(element, "friction")?,
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl crate::from_rdf::from_sdf::FromSDF for DynamicsData {
+	fn from_sdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		sdf_context: &mut crate::from_rdf::from_sdf::FromSDFContext,
+	) -> Result<Self, crate::from_rdf::from_sdf::FromSDFError> {
+		use crate::from_rdf::from_sdf::optional_child_float;
+
+		sdf_context.warn_unsupported(element, &["damping", "friction"]);
+
+		Ok(Self {
+			damping: optional_child_float(element, "damping")?,
+			friction: optional_child_float(element, "friction")?,
+		})
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads the `damping` and `frictionloss` attributes of a `<joint>`.
+impl crate::from_rdf::from_mjcf::FromMJCF for DynamicsData {
+	fn from_mjcf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		_mjcf_context: &mut crate::from_rdf::from_mjcf::FromMJCFContext,
+	) -> Result<Self, crate::from_rdf::from_mjcf::FromMJCFError> {
+		use crate::from_rdf::from_mjcf::optional_float;
+
+		Ok(Self {
+			damping: optional_float(element, "damping")?,
+			friction: optional_float(element, "frictionloss")?,
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::joint::joint_data::DynamicsData;
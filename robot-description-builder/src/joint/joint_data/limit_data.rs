@@ -1,12 +1,15 @@
+use crate::Float;
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
 pub struct LimitData {
-	pub lower: Option<f32>,
-	pub upper: Option<f32>,
-	pub effort: f32,
-	pub velocity: f32,
+	pub lower: Option<Float>,
+	pub upper: Option<Float>,
+	pub effort: Float,
+	pub velocity: Float,
 }
 
 #[cfg(feature = "urdf")]
@@ -47,6 +50,77 @@ impl crate::to_rdf::to_urdf::ToURDF for LimitData {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl crate::to_rdf::to_sdf::ToSDF for LimitData {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("limit")
+			.write_inner_content(|writer| -> quick_xml::Result<()> {
+				for (name, value) in [
+					("lower", self.lower),
+					("upper", self.upper),
+					("effort", Some(self.effort)),
+					("velocity", Some(self.velocity)),
+				] {
+					if let Some(value) = value {
+						writer
+							.create_element(name)
+							.write_text_content(BytesText::new(&value.to_string()))?;
+					}
+				}
+				Ok(())
+			})?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "urdf")]
+impl crate::from_rdf::from_urdf::FromURDF for LimitData {
+	fn from_urdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		_urdf_context: &mut crate::from_rdf::from_urdf::FromURDFContext,
+	) -> Result<Self, crate::from_rdf::from_urdf::FromURDFError> {
+		use crate::from_rdf::from_urdf::{optional_float, required_float};
+
+		Ok(Self {
+			lower: optional_float(element, "lower")?,
+			upper: optional_float(element, "upper")?,
+			effort: required_float(element, "effort")?,
+			velocity: required_float(element, "velocity")?,
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Limits with a magnitude of at least [`SDF_CONTINUOUS_LIMIT`](crate::joint::SDF_CONTINUOUS_LIMIT) are read as `None`.
+/// A missing `effort` or `velocity` is read as the SDFormat default of `-1`, which means unlimited.
+impl crate::from_rdf::from_sdf::FromSDF for LimitData {
+	fn from_sdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		sdf_context: &mut crate::from_rdf::from_sdf::FromSDFContext,
+	) -> Result<Self, crate::from_rdf::from_sdf::FromSDFError> {
+		use crate::{from_rdf::from_sdf::optional_child_float, joint::SDF_CONTINUOUS_LIMIT};
+
+		sdf_context.warn_unsupported(element, &["lower", "upper", "effort", "velocity"]);
+
+		let finite = |name: &str| {
+			optional_child_float(element, name)
+				.map(|value| value.filter(|value| value.abs() < SDF_CONTINUOUS_LIMIT))
+		};
+
+		Ok(Self {
+			lower: finite("lower")?,
+			upper: finite("upper")?,
+			effort: optional_child_float(element, "effort")?.unwrap_or(-1.),
+			velocity: optional_child_float(element, "velocity")?.unwrap_or(-1.),
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::joint::joint_data::LimitData;
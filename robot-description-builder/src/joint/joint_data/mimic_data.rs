@@ -1,18 +1,20 @@
 use std::sync::Weak;
 
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
 use crate::{
 	cluster_objects::kinematic_data_tree::KinematicDataTree, identifiers::GroupID, joint::Joint,
-	utils::WeakLock,
+	utils::WeakLock, Float,
 };
 
 #[derive(Debug, Clone)]
 pub struct MimicData {
 	pub joint: WeakLock<Joint>,
-	pub multiplier: Option<f32>,
-	pub offset: Option<f32>,
+	pub multiplier: Option<Float>,
+	pub offset: Option<Float>,
 }
 
 #[cfg(feature = "urdf")]
@@ -55,6 +57,91 @@ impl crate::to_rdf::to_urdf::ToURDF for MimicData {
 	}
 }
 
+#[cfg(feature = "mjcf")]
+impl MimicData {
+	/// Writes the mimic as a MJCF `<joint>` equality constraint for the mimicking joint named `joint_name`.
+	///
+	/// The position of the mimicking joint is constrained to `offset + multiplier * position`, where `position` is the position of the mimicked joint.
+	pub(crate) fn write_mjcf_equality(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		joint_name: &str,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("joint")
+			.with_attribute(Attribute {
+				key: QName(b"joint1"),
+				value: joint_name.as_bytes().into(),
+			})
+			.with_attribute(Attribute {
+				key: QName(b"joint2"),
+				value: self
+					.joint
+					.upgrade()
+					.unwrap()
+					.read()
+					.unwrap()
+					.name()
+					.display()
+					.as_bytes()
+					.into(),
+			})
+			.with_attribute(Attribute {
+				key: QName(b"polycoef"),
+				value: format!(
+					"{} {} 0 0 0",
+					self.offset.unwrap_or(0.),
+					self.multiplier.unwrap_or(1.)
+				)
+				.as_bytes()
+				.into(),
+			})
+			.write_empty()?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl crate::to_rdf::to_sdf::ToSDF for MimicData {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("mimic")
+			.with_attribute(Attribute {
+				key: QName(b"joint"),
+				value: self
+					.joint
+					.upgrade()
+					.unwrap()
+					.read()
+					.unwrap()
+					.name()
+					.display()
+					.as_bytes()
+					.into(),
+			})
+			.write_inner_content(|writer| -> quick_xml::Result<()> {
+				if let Some(multiplier) = self.multiplier {
+					writer
+						.create_element("multiplier")
+						.write_text_content(BytesText::new(&multiplier.to_string()))?;
+				}
+
+				if let Some(offset) = self.offset {
+					writer
+						.create_element("offset")
+						.write_text_content(BytesText::new(&offset.to_string()))?;
+				}
+
+				Ok(())
+			})?;
+		Ok(())
+	}
+}
+
 impl PartialEq for MimicData {
 	fn eq(&self, other: &Self) -> bool {
 		Weak::ptr_eq(&self.joint, &other.joint)
@@ -83,8 +170,8 @@ impl From<MimicData> for MimicBuilderData {
 #[derive(Debug, PartialEq, Clone)]
 pub struct MimicBuilderData {
 	pub joint_name: String,
-	pub multiplier: Option<f32>,
-	pub offset: Option<f32>,
+	pub multiplier: Option<Float>,
+	pub offset: Option<Float>,
 }
 
 impl MimicBuilderData {
@@ -105,6 +192,43 @@ impl MimicBuilderData {
 	}
 }
 
+#[cfg(feature = "urdf")]
+impl crate::from_rdf::from_urdf::FromURDF for MimicBuilderData {
+	fn from_urdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		_urdf_context: &mut crate::from_rdf::from_urdf::FromURDFContext,
+	) -> Result<Self, crate::from_rdf::from_urdf::FromURDFError> {
+		use crate::{
+			from_rdf::from_urdf::{optional_float, required_attribute},
+			identifiers::escape_group_id_delimiters,
+		};
+
+		Ok(Self {
+			joint_name: escape_group_id_delimiters(required_attribute(element, "joint")?),
+			multiplier: optional_float(element, "multiplier")?,
+			offset: optional_float(element, "offset")?,
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl crate::from_rdf::from_sdf::FromSDF for MimicBuilderData {
+	fn from_sdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		sdf_context: &mut crate::from_rdf::from_sdf::FromSDFContext,
+	) -> Result<Self, crate::from_rdf::from_sdf::FromSDFError> {
+		use crate::from_rdf::from_sdf::{optional_child_float, required_attribute};
+
+		sdf_context.warn_unsupported(element, &["multiplier", "offset"]);
+
+		Ok(Self {
+			joint_name: sdf_context.name(required_attribute(element, "joint")?),
+			multiplier: optional_child_float(element, "multiplier")?,
+			offset: optional_child_float(element, "offset")?,
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	// use crate::joint::joint_data::MimicData;
@@ -1,3 +1,4 @@
+use crate::Float;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -7,19 +8,19 @@ pub struct SafetyControllerData {
 	//
 	// An attribute specifying the lower joint boundary where the safety controller starts limiting the position of the joint. This limit needs to be larger than the lower joint limit (see above). See See safety limits for more details.
 	// TODO: FIX DOCUMENTATION
-	pub soft_lower_limit: Option<f32>,
+	pub soft_lower_limit: Option<Float>,
 	// (optional, defaults to 0)
 	//
 	// An attribute specifying the upper joint boundary where the safety controller starts limiting the position of the joint. This limit needs to be smaller than the upper joint limit (see above). See See safety limits for more details.
 	// TODO: FIX DOCUMENTATION
-	pub soft_upper_limit: Option<f32>,
+	pub soft_upper_limit: Option<Float>,
 	//  (optional, defaults to 0)
 	//
 	// An attribute specifying the relation between position and velocity limits. See See safety limits for more details.
 	// TODO: FIX DOCUMENTATION
-	pub k_position: Option<f32>,
+	pub k_position: Option<Float>,
 	// An attribute specifying the relation between effort and velocity limits. See See safety limits for more details.
-	pub k_velocity: f32,
+	pub k_velocity: Float,
 }
 
 #[cfg(feature = "urdf")]
@@ -63,6 +64,23 @@ impl crate::to_rdf::to_urdf::ToURDF for SafetyControllerData {
 	}
 }
 
+#[cfg(feature = "urdf")]
+impl crate::from_rdf::from_urdf::FromURDF for SafetyControllerData {
+	fn from_urdf(
+		element: &crate::from_rdf::xml_element::XMLElement,
+		_urdf_context: &mut crate::from_rdf::from_urdf::FromURDFContext,
+	) -> Result<Self, crate::from_rdf::from_urdf::FromURDFError> {
+		use crate::from_rdf::from_urdf::{optional_float, required_float};
+
+		Ok(Self {
+			soft_lower_limit: optional_float(element, "soft_lower_limit")?,
+			soft_upper_limit: optional_float(element, "soft_upper_limit")?,
+			k_position: optional_float(element, "k_position")?,
+			k_velocity: required_float(element, "k_velocity")?,
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::joint::joint_data::SafetyControllerData;
@@ -3,6 +3,7 @@ use nalgebra::Matrix3;
 use crate::{
 	link::LinkShapeData,
 	transform::{Mirror, MirrorUpdater, Transform},
+	Float,
 };
 
 #[derive(Debug, PartialEq, Clone)]
@@ -21,7 +22,7 @@ impl JointTransformMode {
 }
 
 impl Mirror for JointTransformMode {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		match self {
 			JointTransformMode::Direct(transform) => {
 				Self::Direct(transform.mirrored(mirror_matrix))
@@ -32,14 +33,14 @@ impl Mirror for JointTransformMode {
 }
 
 impl MirrorUpdater for JointTransformMode {
-	fn update_mirror_matrix(&self, mirror_matrix: &Matrix3<f32>) -> Matrix3<f32> {
+	fn update_mirror_matrix(&self, mirror_matrix: &Matrix3<Float>) -> Matrix3<Float> {
 		match self {
 			JointTransformMode::Direct(transform) => transform.update_mirror_matrix(mirror_matrix),
 			JointTransformMode::FigureItOut(_) => todo!("I do not know how to do this yet."),
 		}
 	}
 
-	fn mirrored_update_matrix(&self, mirror_matrix: &Matrix3<f32>) -> (Self, Matrix3<f32>) {
+	fn mirrored_update_matrix(&self, mirror_matrix: &Matrix3<Float>) -> (Self, Matrix3<Float>) {
 		match self {
 			JointTransformMode::Direct(transform) => {
 				let (new_transform, new_mirror_matrix) =
@@ -13,8 +13,21 @@ use crate::{
 	},
 	transform::{Mirror, MirrorUpdater, Transform},
 	utils::{ArcLock, WeakLock},
+	Float,
 };
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{FromMJCF, FromMJCFContext, FromMJCFError};
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{FromSDF, FromSDFContext, FromSDFError};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{
+	invalid_value, optional_transform, optional_vector3, required_attribute, required_name,
+	FromURDF, FromURDFContext, FromURDFError,
+};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
+
 pub trait BuildJoint: Into<JointBuilder> {
 	// Creates the joint ?? and subscribes it to the right right places
 	fn build(
@@ -49,7 +62,7 @@ pub struct JointBuilder {
 
 	//Consider making everything below pub to remove need for all the functions
 	// TODO: MAYBE CHANGE TO Vec3D Or something
-	pub(crate) axis: Option<(f32, f32, f32)>,
+	pub(crate) axis: Option<(Float, Float, Float)>,
 	pub(crate) calibration: joint_data::CalibrationData,
 	pub(crate) dynamics: joint_data::DynamicsData,
 	pub(crate) limit: Option<joint_data::LimitData>,
@@ -68,7 +81,7 @@ impl JointBuilder {
 	}
 
 	// TODO: rename transform
-	pub fn add_origin_offset(mut self, offset: (f32, f32, f32)) -> Self {
+	pub fn add_origin_offset(mut self, offset: (Float, Float, Float)) -> Self {
 		match &mut self.transform {
 			JointTransformMode::Direct(transform) => transform.translation = Some(offset),
 			JointTransformMode::FigureItOut(_) => todo!("Don't know how to do this"),
@@ -77,7 +90,7 @@ impl JointBuilder {
 	}
 
 	// TODO: rename transform
-	pub fn add_origin_rotation(mut self, rotation: (f32, f32, f32)) -> Self {
+	pub fn add_origin_rotation(mut self, rotation: (Float, Float, Float)) -> Self {
 		match &mut self.transform {
 			JointTransformMode::Direct(tranform) => tranform.rotation = Some(rotation),
 			JointTransformMode::FigureItOut(_) => todo!("Don't know how to do this yet"),
@@ -102,7 +115,7 @@ impl JointBuilder {
 	// TODO: HAS A CONFUSING NAME WITH SmartJointBuilder::with_axis, which consumes
 	/// Add the [`axis`](JointBuilder::axis) to the `JointBuillder`.
 	#[inline]
-	pub fn with_axis(&mut self, axis: (f32, f32, f32)) {
+	pub fn with_axis(&mut self, axis: (Float, Float, Float)) {
 		self.axis = Some(axis);
 	}
 
@@ -179,11 +192,11 @@ impl JointBuilder {
 		self.child.as_ref()
 	}
 
-	pub fn axis(&self) -> Option<(f32, f32, f32)> {
+	pub fn axis(&self) -> Option<(Float, Float, Float)> {
 		self.axis
 	}
 
-	pub fn axis_mut(&mut self) -> Option<&mut (f32, f32, f32)> {
+	pub fn axis_mut(&mut self) -> Option<&mut (Float, Float, Float)> {
 		self.axis.as_mut()
 	}
 
@@ -229,7 +242,7 @@ impl JointBuilder {
 }
 
 impl Mirror for JointBuilder {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		let (transform, new_mirror_matrix) = self.transform.mirrored_update_matrix(mirror_matrix);
 		Self {
 			name: self.name.clone(), // FIXME: Rename
@@ -296,6 +309,7 @@ impl BuildJoint for JointBuilder {
 				dynamics: self.dynamics,
 				limit: self.limit,
 				mimic: self.mimic.map(|mimic| mimic.to_mimic_data(&tree)),
+				pending_mimic: None,
 				safety_controller: self.safety_controller,
 				me: Weak::clone(me),
 			})
@@ -329,9 +343,9 @@ impl BuildJointChain for JointBuilder {
 				calibration: self.calibration,
 				dynamics: self.dynamics,
 				limit: self.limit,
-				mimic: self
-					.mimic
-					.map(|mimic| mimic.to_mimic_data(tree)),
+				// The mimicked `Joint` might not be registered yet, so it gets resolved after registration of the chain.
+				mimic: None,
+				pending_mimic: self.mimic,
 				safety_controller: self.safety_controller,
 				me: Weak::clone(me),
 			})
@@ -357,6 +371,269 @@ impl GroupIDChanger for JointBuilder {
 	}
 }
 
+#[cfg(feature = "urdf")]
+/// Reads a `<joint>` element, without its `parent` and `child` links.
+impl FromURDF for JointBuilder {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let joint_type = match required_attribute(element, "type")? {
+			"fixed" => JointType::Fixed,
+			"revolute" => JointType::Revolute,
+			"continuous" => JointType::Continuous,
+			"prismatic" => JointType::Prismatic,
+			"floating" => JointType::Floating,
+			"planar" => JointType::Planar,
+			other => return Err(invalid_value(element, "type", other)),
+		};
+
+		let mut joint_builder = Self::new(required_name(element)?, joint_type);
+
+		if let Some(transform) = optional_transform(element, urdf_context)? {
+			joint_builder.set_transform_simple(transform);
+		}
+
+		if let Some(axis) = element
+			.child("axis")
+			.map(|axis| optional_vector3(axis, "xyz"))
+			.transpose()?
+			.flatten()
+		{
+			joint_builder.with_axis(axis);
+		}
+
+		if let Some(calibration) = element.child("calibration") {
+			joint_builder.with_calibration_data(FromURDF::from_urdf(calibration, urdf_context)?);
+		}
+
+		if let Some(dynamics) = element.child("dynamics") {
+			joint_builder.with_dynamics_data(FromURDF::from_urdf(dynamics, urdf_context)?);
+		}
+
+		if let Some(limit) = element.child("limit") {
+			joint_builder.with_limit_data(FromURDF::from_urdf(limit, urdf_context)?);
+		}
+
+		if let Some(mimic) = element.child("mimic") {
+			joint_builder.with_mimic_data(FromURDF::from_urdf(mimic, urdf_context)?);
+		}
+
+		if let Some(safety_controller) = element.child("safety_controller") {
+			joint_builder
+				.with_safety_controller(FromURDF::from_urdf(safety_controller, urdf_context)?);
+		}
+
+		Ok(joint_builder)
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads a `<joint>` element, without its `parent` and `child` links.
+///
+/// A `revolute` joint without limits on its position is read as a [`Continuous`](JointType::Continuous) joint.
+/// Joint types which can not be represented are reported as a warning and read as a [`Fixed`](JointType::Fixed) joint.
+impl FromSDF for JointBuilder {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		use crate::{
+			from_rdf::from_sdf::{
+				content_vector3, express_in, optional_child_bool, required_attribute, SDFWarning,
+			},
+			identifiers::GroupID,
+		};
+
+		sdf_context.warn_unsupported(element, &["pose", "parent", "child", "axis"]);
+
+		let name = sdf_context.required_name(element)?;
+		let axis = element.child("axis");
+		let limit = axis
+			.and_then(|axis| axis.child("limit"))
+			.map(|limit| joint_data::LimitData::from_sdf(limit, sdf_context))
+			.transpose()?;
+
+		let joint_type = match required_attribute(element, "type")? {
+			"fixed" => JointType::Fixed,
+			"revolute"
+				if limit.map_or(true, |limit| limit.lower.is_none() && limit.upper.is_none()) =>
+			{
+				JointType::Continuous
+			}
+			"revolute" => JointType::Revolute,
+			"continuous" => JointType::Continuous,
+			"prismatic" => JointType::Prismatic,
+			joint_type @ ("ball" | "universal" | "revolute2" | "screw" | "gearbox") => {
+				sdf_context.warn(SDFWarning::UnsupportedJointType {
+					joint: name.display(),
+					joint_type: joint_type.to_string(),
+					line: element.line,
+				});
+				JointType::Fixed
+			}
+			joint_type => {
+				return Err(FromSDFError::InvalidValue {
+					element: element.name.clone(),
+					attribute: String::from("type"),
+					value: joint_type.to_string(),
+					line: element.line,
+				})
+			}
+		};
+
+		let mut joint_builder = Self::new(name, joint_type);
+
+		if let Some(transform) = sdf_context.transform(element)? {
+			joint_builder.set_transform_simple(transform);
+		}
+
+		if joint_type == JointType::Fixed {
+			return Ok(joint_builder);
+		}
+
+		// The axis of SDFormat defaults to the Z-axis.
+		let xyz = axis.and_then(|axis| axis.child("xyz"));
+		let mut vector = xyz
+			.map(content_vector3)
+			.transpose()?
+			.unwrap_or((0., 0., 1.));
+
+		let expressed_in = match (xyz, axis) {
+			(Some(xyz), _) if xyz.attribute("expressed_in").is_some() => {
+				// The attribute exists, so it is always `Some`.
+				Some(
+					sdf_context
+						.frame_pose(xyz, xyz.attribute("expressed_in").unwrap_or_default())?,
+				)
+			}
+			(_, Some(axis))
+				if optional_child_bool(axis, "use_parent_model_frame")? == Some(true) =>
+			{
+				Some(sdf_context.model_pose())
+			}
+			_ => None,
+		};
+
+		if let Some(frame) = expressed_in {
+			vector = express_in(vector, &frame, &sdf_context.pose(element)?);
+		}
+		joint_builder.with_axis(vector);
+
+		if let Some(axis) = axis {
+			sdf_context.warn_unsupported(
+				axis,
+				&[
+					"xyz",
+					"dynamics",
+					"limit",
+					"mimic",
+					"use_parent_model_frame",
+				],
+			);
+
+			if let Some(dynamics) = axis.child("dynamics") {
+				joint_builder.with_dynamics_data(FromSDF::from_sdf(dynamics, sdf_context)?);
+			}
+
+			if let Some(mimic) = axis.child("mimic") {
+				joint_builder.with_mimic_data(FromSDF::from_sdf(mimic, sdf_context)?);
+			}
+		}
+
+		// A limit without any finite values does not contain any information.
+		if let Some(limit) = limit.filter(|limit| {
+			limit.lower.is_some()
+				|| limit.upper.is_some()
+				|| limit.effort >= 0.
+				|| limit.velocity >= 0.
+		}) {
+			joint_builder.with_limit_data(limit);
+		}
+
+		Ok(joint_builder)
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads a `hinge`, `slide` or `free` `<joint>`, without its `pos`.
+///
+/// A `hinge` is read as a [`Revolute`](JointType::Revolute) joint when it is limited and as a [`Continuous`](JointType::Continuous) joint otherwise.
+/// Since MJCF has no velocity limit, the `velocity` of the limit is set to [`UNKNOWN_LIMIT`](crate::from_rdf::from_mjcf::UNKNOWN_LIMIT).
+/// The `effort` is set to the largest magnitude of the `actuatorfrcrange`, or `UNKNOWN_LIMIT` when it is not specified.
+/// Every unknown limit is reported as a [`MJCFWarning::UnknownLimit`](crate::errors::MJCFWarning::UnknownLimit).
+impl FromMJCF for JointBuilder {
+	fn from_mjcf(
+		element: &XMLElement,
+		mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		use crate::{
+			from_rdf::from_mjcf::{
+				invalid_value, optional_bool, optional_float_array, required_attribute,
+				MJCFWarning, UNKNOWN_LIMIT,
+			},
+			identifiers::escape_group_id_delimiters,
+		};
+
+		let name = escape_group_id_delimiters(required_attribute(element, "name")?);
+		let joint_type = element.attribute("type").unwrap_or("hinge");
+
+		if joint_type == "free" {
+			return Ok(Self::new(name, JointType::Floating));
+		}
+
+		let range = optional_float_array(element, "range")?;
+		let limited = match element.attribute("limited") {
+			None | Some("auto") => mjcf_context.autolimits && range.is_some(),
+			Some(_) => optional_bool(element, "limited")?.unwrap_or_default(),
+		};
+
+		let (joint_type, range) = match (joint_type, range.filter(|_| limited)) {
+			("hinge", Some([lower, upper])) => (
+				JointType::Revolute,
+				Some([lower, upper].map(|angle| mjcf_context.angle(angle) as Float)),
+			),
+			("hinge", None) => (JointType::Continuous, None),
+			("slide", range) => (JointType::Prismatic, range),
+			_ => return Err(invalid_value(element, "type")),
+		};
+
+		let mut joint_builder = Self::new(name, joint_type);
+		joint_builder.with_axis(
+			optional_float_array(element, "axis")?.map_or((0., 0., 1.), |[x, y, z]| (x, y, z)),
+		);
+
+		if let Some([lower, upper]) = range {
+			let effort = optional_float_array(element, "actuatorfrcrange")?
+				.map(|[lower, upper]: [Float; 2]| lower.abs().max(upper.abs()));
+
+			for (limit, known) in [("velocity", false), ("effort", effort.is_some())] {
+				if !known {
+					mjcf_context.warn(MJCFWarning::UnknownLimit {
+						joint: joint_builder.name.clone(),
+						limit,
+						line: element.line,
+					});
+				}
+			}
+
+			joint_builder.with_limit_data(joint_data::LimitData {
+				lower: Some(lower),
+				upper: Some(upper),
+				effort: effort.unwrap_or(UNKNOWN_LIMIT),
+				velocity: UNKNOWN_LIMIT,
+			});
+		}
+
+		let dynamics = joint_data::DynamicsData::from_mjcf(element, mjcf_context)?;
+		if dynamics.contains_some() {
+			joint_builder.with_dynamics_data(dynamics);
+		}
+
+		Ok(joint_builder)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{JointBuilder, JointType};
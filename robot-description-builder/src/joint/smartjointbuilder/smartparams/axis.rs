@@ -1,3 +1,4 @@
+use crate::Float;
 use crate::joint::{
 	jointbuilder::JointBuilder,
 	smartjointbuilder::{smart_joint_datatraits, SmartJointBuilder},
@@ -12,7 +13,7 @@ pub struct NoAxis;
 impl smart_joint_datatraits::AxisDataType for NoAxis {}
 
 #[derive(Debug, Default, Clone)]
-pub struct WithAxis(f32, f32, f32);
+pub struct WithAxis(Float, Float, Float);
 impl smart_joint_datatraits::AxisDataType for WithAxis {
 	fn simplify(&self, joint_builder: &mut JointBuilder) {
 		joint_builder.with_axis((self.0, self.1, self.2));
@@ -32,9 +33,9 @@ where
 {
 	pub fn with_axis(
 		self,
-		axis: (f32, f32, f32),
+		axis: (Float, Float, Float),
 	) -> SmartJointBuilder<Type, WithAxis, Calibration, Dynamics, Limit, Mimic, SafetyController> {
-		let length = f32::sqrt(axis.0 * axis.0 + axis.1 * axis.1 + axis.2 * axis.2);
+		let length = Float::sqrt(axis.0 * axis.0 + axis.1 * axis.1 + axis.2 * axis.2);
 		SmartJointBuilder {
 			name: self.name,
 			joint_type: self.joint_type,
@@ -59,7 +60,7 @@ where
 	Mimic: smart_joint_datatraits::MimicDataType,
 	SafetyController: smart_joint_datatraits::SafetyControllerDataType,
 {
-	pub fn axis(&self) -> (f32, f32, f32) {
+	pub fn axis(&self) -> (Float, Float, Float) {
 		(self.axis.0, self.axis.1, self.axis.2)
 	}
 }
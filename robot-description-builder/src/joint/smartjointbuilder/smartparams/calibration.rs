@@ -1,3 +1,4 @@
+use crate::Float;
 use crate::joint::{
 	joint_data,
 	jointbuilder::JointBuilder,
@@ -14,8 +15,8 @@ impl smart_joint_datatraits::CalibrationDataType for NoCalibration {}
 
 #[derive(Debug, Default, Clone)]
 pub struct WithCalibration {
-	rising: Option<f32>,
-	falling: Option<f32>,
+	rising: Option<Float>,
+	falling: Option<Float>,
 }
 
 impl From<WithCalibration> for joint_data::CalibrationData {
@@ -70,21 +71,21 @@ where
 	Mimic: smart_joint_datatraits::MimicDataType,
 	SafetyController: smart_joint_datatraits::SafetyControllerDataType,
 {
-	pub fn set_rising_calibration(mut self, rising: f32) -> Self {
+	pub fn set_rising_calibration(mut self, rising: Float) -> Self {
 		self.calibration.rising = Some(rising);
 		self
 	}
 
-	pub fn rising_calibration(&self) -> Option<f32> {
+	pub fn rising_calibration(&self) -> Option<Float> {
 		self.calibration.rising
 	}
 
-	pub fn set_falling_calibration(mut self, falling: f32) -> Self {
+	pub fn set_falling_calibration(mut self, falling: Float) -> Self {
 		self.calibration.falling = Some(falling);
 		self
 	}
 
-	pub fn falling_calibration(&self) -> Option<f32> {
+	pub fn falling_calibration(&self) -> Option<Float> {
 		self.calibration.falling
 	}
 }
@@ -1,3 +1,4 @@
+use crate::Float;
 use crate::joint::{
 	joint_data,
 	jointbuilder::JointBuilder,
@@ -14,8 +15,8 @@ impl smart_joint_datatraits::DynamicsDataType for NoDynamics {}
 
 #[derive(Debug, Default, Clone)]
 pub struct WithDynamics {
-	damping: Option<f32>,
-	friction: Option<f32>,
+	damping: Option<Float>,
+	friction: Option<Float>,
 }
 
 impl From<WithDynamics> for joint_data::DynamicsData {
@@ -70,21 +71,21 @@ where
 	Mimic: smart_joint_datatraits::MimicDataType,
 	SafetyController: smart_joint_datatraits::SafetyControllerDataType,
 {
-	pub fn set_damping(mut self, damping: f32) -> Self {
+	pub fn set_damping(mut self, damping: Float) -> Self {
 		self.dynamics.damping = Some(damping);
 		self
 	}
 
-	pub fn damping(&self) -> Option<f32> {
+	pub fn damping(&self) -> Option<Float> {
 		self.dynamics.damping
 	}
 
-	pub fn set_friction(mut self, friction: f32) -> Self {
+	pub fn set_friction(mut self, friction: Float) -> Self {
 		self.dynamics.friction = Some(friction);
 		self
 	}
 
-	pub fn friction(&self) -> Option<f32> {
+	pub fn friction(&self) -> Option<Float> {
 		self.dynamics.friction
 	}
 }
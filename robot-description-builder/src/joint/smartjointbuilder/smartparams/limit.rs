@@ -1,3 +1,4 @@
+use crate::Float;
 use crate::joint::{
 	joint_data,
 	jointbuilder::JointBuilder,
@@ -16,13 +17,13 @@ impl smart_joint_datatraits::LimitDataType for NoLimit {}
 #[derive(Debug, Default, Clone)]
 pub struct WithLimit {
 	/// An attribute specifying the lower joint limit (in radians for revolute joints, in metres for prismatic joints). Omit if joint is continuous.
-	lower: Option<f32>,
+	lower: Option<Float>,
 	/// An attribute specifying the upper joint limit (in radians for revolute joints, in metres for prismatic joints). Omit if joint is continuous.
-	upper: Option<f32>,
+	upper: Option<Float>,
 	/// An attribute for enforcing the maximum joint effort (|applied effort| < |effort|).
-	effort: f32,
+	effort: Float,
 	/// An attribute for enforcing the maximum joint velocity (in radians per second [rad/s] for revolute joints, in metres per second [m/s] for prismatic joints).
-	velocity: f32,
+	velocity: Float,
 }
 
 impl From<WithLimit> for joint_data::LimitData {
@@ -65,8 +66,8 @@ where
 {
 	pub fn with_limit(
 		self,
-		effort: f32,
-		velocity: f32,
+		effort: Float,
+		velocity: Float,
 	) -> SmartJointBuilder<Type, Axis, Calibration, Dynamics, WithLimit, Mimic, SafetyController> {
 		SmartJointBuilder {
 			name: self.name,
@@ -97,23 +98,23 @@ where
 	Mimic: smart_joint_datatraits::MimicDataType,
 	SafetyController: smart_joint_datatraits::SafetyControllerDataType,
 {
-	pub fn set_effort(mut self, effort: f32) -> Self {
+	pub fn set_effort(mut self, effort: Float) -> Self {
 		self.limit.effort = effort;
 		self
 	}
 
-	pub fn effort(&self) -> f32 {
+	pub fn effort(&self) -> Float {
 		self.limit.effort
 	}
 
 	/// Sets the velocity limit to the specified value in m/s or rad/s ([`velocity`](crate::joint::joint_data::LimitData::velocity)).
-	pub fn set_velocity(mut self, velocity: f32) -> Self {
+	pub fn set_velocity(mut self, velocity: Float) -> Self {
 		self.limit.velocity = velocity;
 		self
 	}
 
 	/// Retrieves the set velocity limit in m/s or rad/s ([`velocity`](crate::joint::joint_data::LimitData::velocity)).
-	pub fn velocity(&self) -> f32 {
+	pub fn velocity(&self) -> Float {
 		self.limit.velocity
 	}
 }
@@ -130,24 +131,24 @@ where
 	SafetyController: smart_joint_datatraits::SafetyControllerDataType,
 {
 	/// Sets the upper limit ([`upper`](crate::joint::joint_data::LimitData::upper)) in meters or radians.
-	pub fn set_upper_limit(mut self, upper_limit: f32) -> Self {
+	pub fn set_upper_limit(mut self, upper_limit: Float) -> Self {
 		self.limit.upper = Some(upper_limit);
 		self
 	}
 
 	/// Retrieves the upper limit ([`upper`](crate::joint::joint_data::LimitData::upper)) in meters or radians.
-	pub fn upper_limit(&self) -> Option<f32> {
+	pub fn upper_limit(&self) -> Option<Float> {
 		self.limit.upper
 	}
 
 	/// Sets the lower limit ([`lower`](crate::joint::joint_data::LimitData::lower)) in meters or radians.
-	pub fn set_lower_limit(mut self, lower_limit: f32) -> Self {
+	pub fn set_lower_limit(mut self, lower_limit: Float) -> Self {
 		self.limit.lower = Some(lower_limit);
 		self
 	}
 
 	/// Retrieves the lower limit ([`lower`](crate::joint::joint_data::LimitData::lower)) in meters or radians.
-	pub fn lower_limit(&self) -> Option<f32> {
+	pub fn lower_limit(&self) -> Option<Float> {
 		self.limit.lower
 	}
 }
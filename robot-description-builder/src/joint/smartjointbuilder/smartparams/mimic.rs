@@ -1,3 +1,4 @@
+use crate::Float;
 use crate::joint::{
 	joint_data,
 	jointbuilder::JointBuilder,
@@ -21,9 +22,9 @@ pub struct WithMimic {
 	/// This specifies the name of the joint to mimic.
 	joint_name: String,
 	/// Specifies the multiplicative factor in the formula above.
-	multiplier: Option<f32>,
+	multiplier: Option<Float>,
 	/// Specifies the offset to add in the formula above. Defaults to 0 (radians for revolute joints, meters for prismatic joints).
-	offset: Option<f32>,
+	offset: Option<Float>,
 }
 
 impl From<WithMimic> for joint_data::MimicBuilderData {
@@ -93,22 +94,22 @@ where
 		&self.mimic.joint_name
 	}
 
-	pub fn set_mimic_multiplier(mut self, multiplier: f32) -> Self {
+	pub fn set_mimic_multiplier(mut self, multiplier: Float) -> Self {
 		self.mimic.multiplier = Some(multiplier);
 		self
 	}
 
-	pub fn mimic_multiplier(&self) -> Option<f32> {
+	pub fn mimic_multiplier(&self) -> Option<Float> {
 		self.mimic.multiplier
 	}
 
 	/// Specifies the offset to add in the formula above. Defaults to 0 (radians for revolute joints, meters for prismatic joints).
-	pub fn set_mimic_offset(mut self, offset: f32) -> Self {
+	pub fn set_mimic_offset(mut self, offset: Float) -> Self {
 		self.mimic.offset = Some(offset);
 		self
 	}
 
-	pub fn mimic_offset(&self) -> Option<f32> {
+	pub fn mimic_offset(&self) -> Option<Float> {
 		self.mimic.offset
 	}
 }
@@ -1,3 +1,4 @@
+use crate::Float;
 use crate::joint::{
 	joint_data,
 	jointbuilder::JointBuilder,
@@ -19,19 +20,19 @@ pub struct WithSafetyController {
 	//
 	// An attribute specifying the lower joint boundary where the safety controller starts limiting the position of the joint. This limit needs to be larger than the lower joint limit (see above). See See safety limits for more details.
 	// TODO: FIX DOCUMENTATION
-	soft_lower_limit: Option<f32>,
+	soft_lower_limit: Option<Float>,
 	// (optional, defaults to 0)
 	//
 	// An attribute specifying the upper joint boundary where the safety controller starts limiting the position of the joint. This limit needs to be smaller than the upper joint limit (see above). See See safety limits for more details.
 	// TODO: FIX DOCUMENTATION
-	soft_upper_limit: Option<f32>,
+	soft_upper_limit: Option<Float>,
 	//  (optional, defaults to 0)
 	//
 	// An attribute specifying the relation between position and velocity limits. See See safety limits for more details.
 	// TODO: FIX DOCUMENTATION
-	k_position: Option<f32>,
+	k_position: Option<Float>,
 	// An attribute specifying the relation between effort and velocity limits. See See safety limits for more details.
-	k_velocity: f32,
+	k_velocity: Float,
 }
 
 impl From<WithSafetyController> for joint_data::SafetyControllerData {
@@ -63,7 +64,7 @@ where
 {
 	pub fn with_safety_controller(
 		self,
-		k_velocity: f32,
+		k_velocity: Float,
 	) -> SmartJointBuilder<Type, Axis, Calibration, Dynamics, Limit, Mimic, WithSafetyController> {
 		SmartJointBuilder {
 			name: self.name,
@@ -94,24 +95,24 @@ where
 	Mimic: smart_joint_datatraits::MimicDataType,
 {
 	// Defaults 0
-	pub fn set_k_position(mut self, k_position: f32) -> Self {
+	pub fn set_k_position(mut self, k_position: Float) -> Self {
 		self.safety_controller.k_position = Some(k_position);
 		self
 	}
 
 	// Defaults 0
-	pub fn k_position(&self) -> Option<f32> {
+	pub fn k_position(&self) -> Option<Float> {
 		self.safety_controller.k_position
 	}
 
 	/// Sets the k_velocity limit to the specified value in m/s or rad/s ([`k_velocity`](crate::joint::joint_data::SafetyControllerData::k_velocity)).
-	pub fn set_k_velocity(mut self, k_velocity: f32) -> Self {
+	pub fn set_k_velocity(mut self, k_velocity: Float) -> Self {
 		self.safety_controller.k_velocity = k_velocity;
 		self
 	}
 
 	/// Retrieves the set k_velocity limit in m/s or rad/s ([`k_velocity`](crate::joint::joint_data::SafetyControllerData::k_velocity)).
-	pub fn k_velocity(&self) -> f32 {
+	pub fn k_velocity(&self) -> Float {
 		self.safety_controller.k_velocity
 	}
 }
@@ -128,24 +129,24 @@ where
 	Mimic: smart_joint_datatraits::MimicDataType,
 {
 	/// Sets the soft lower limit ([`soft_lower_limit`](crate::joint::joint_data::SafetyControllerData::soft_lower_limit)).
-	pub fn set_soft_lower_limit(mut self, soft_lower_limit: f32) -> Self {
+	pub fn set_soft_lower_limit(mut self, soft_lower_limit: Float) -> Self {
 		self.safety_controller.soft_lower_limit = Some(soft_lower_limit);
 		self
 	}
 
 	/// Retrieve the specified soft lower limit ([`soft_lower_limit`](crate::joint::joint_data::SafetyControllerData::soft_lower_limit)).
-	pub fn soft_lower_limit(&self) -> Option<f32> {
+	pub fn soft_lower_limit(&self) -> Option<Float> {
 		self.safety_controller.soft_lower_limit
 	}
 
 	/// Sets the soft upper limit ([`soft_upper_limit`](crate::joint::joint_data::SafetyControllerData::soft_upper_limit)).
-	pub fn set_soft_upper_limit(mut self, soft_upper_limit: f32) -> Self {
+	pub fn set_soft_upper_limit(mut self, soft_upper_limit: Float) -> Self {
 		self.safety_controller.soft_upper_limit = Some(soft_upper_limit);
 		self
 	}
 
 	/// Retrieve the specified soft upper limit ([`soft_upper_limit`](crate::joint::joint_data::SafetyControllerData::soft_upper_limit)).
-	pub fn soft_upper_limit(&self) -> Option<f32> {
+	pub fn soft_upper_limit(&self) -> Option<Float> {
 		self.safety_controller.soft_upper_limit
 	}
 }
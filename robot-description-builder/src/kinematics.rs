@@ -0,0 +1,533 @@
+//! The kinematic analysis of a kinematic tree.
+//!
+//! The positions of the `Joint`s are specified with a [`JointState`],
+//! which can be used to calculate the pose of every `Link` with [`KinematicInterface::forward_kinematics`]
+//! The [`KinematicChain`] between two `Link`s is found with [`KinematicInterface::get_chain`],
+//! which can be used to calculate the [`Jacobian`] of a chain of `Link`s with [`KinematicInterface::jacobian`].
+//! The positions of the `Joint`s of a chain, which bring its tip to a target, can be found with [`KinematicInterface::inverse_kinematics`].
+use std::collections::HashMap;
+
+use nalgebra::{Isometry3, Translation3, Unit, UnitQuaternion, Vector3};
+use thiserror::Error;
+
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::{Joint, JointType},
+	link::Link,
+	transform::Transform,
+	Float,
+};
+
+mod chain;
+mod inverse_kinematics;
+mod jacobian;
+
+pub use chain::{ChainDirection, KinematicChain};
+pub(crate) use inverse_kinematics::solve as solve_inverse_kinematics;
+pub use inverse_kinematics::{IKConfig, IKSolution};
+pub(crate) use jacobian::independent_joint;
+pub use jacobian::Jacobian;
+
+/// An error, which can occur during the kinematic analysis of a chain of `Link`s.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum KinematicsError {
+	/// A `Link` with the specified name does not exist in the kinematic tree.
+	#[error("Could not find Link \"{0}\"")]
+	InvalidLink(String),
+	/// The `tip` `Link` is not a descendant of the `base` `Link`, so they do not form a chain.
+	#[error("Link \"{tip}\" is not a descendant of Link \"{base}\"")]
+	NotInChain {
+		/// The name of the base `Link` of the chain.
+		base: String,
+		/// The name of the tip `Link` of the chain.
+		tip: String,
+	},
+}
+
+/// The position of a single `Joint`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JointPosition {
+	/// The position of a single degree of freedom `Joint`.
+	///
+	/// This is the angle in radians for [`Revolute`](JointType::Revolute) and [`Continuous`](JointType::Continuous) `Joint`s
+	/// and the distance in meters along the axis for [`Prismatic`](JointType::Prismatic) `Joint`s.
+	Scalar(Float),
+	/// The position of a [`Planar`](JointType::Planar) `Joint`.
+	///
+	/// The plane is perpendicular to the axis of the `Joint`.
+	/// - `x` and `y` are the translation in meters in the plane.
+	///   For an axis along the z-axis, these are along the x-axis and the y-axis of the `Joint`.
+	/// - `angle` is the rotation in radians around the axis.
+	Planar {
+		/// The translation along the first direction of the plane.
+		x: Float,
+		/// The translation along the second direction of the plane.
+		y: Float,
+		/// The rotation around the axis.
+		angle: Float,
+	},
+	/// The position of a [`Floating`](JointType::Floating) `Joint`, as the `Transform` relative to its origin.
+	Floating(Transform),
+}
+
+/// The positions of the `Joint`s of a kinematic tree, indexed by the name of the `Joint`.
+///
+/// `Joint`s without a position are at their zero position.
+/// The positions of mimicking `Joint`s are ignored, since they follow the `Joint` they mimic.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{kinematics::{JointPosition, JointState}, Transform};
+/// let joint_state = JointState::new()
+///     .with_position("shoulder", 0.5)
+///     .with_planar("base", 1., 2., 0.)
+///     .with_floating("camera", Transform::new_translation(0., 0., 1.));
+///
+/// assert_eq!(joint_state.position("shoulder"), Some(0.5));
+/// assert_eq!(
+///     joint_state.get("base"),
+///     Some(&JointPosition::Planar { x: 1., y: 2., angle: 0. })
+/// );
+/// assert_eq!(joint_state.position("base"), None);
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct JointState {
+	positions: HashMap<String, JointPosition>,
+}
+
+impl JointState {
+	/// Creates a new `JointState`, with all `Joint`s at their zero position.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the position of the single degree of freedom `Joint` with the specified name.
+	pub fn with_position(mut self, joint_name: impl Into<String>, position: Float) -> Self {
+		self.set_position(joint_name, position);
+		self
+	}
+
+	/// Sets the position of the [`Planar`](JointType::Planar) `Joint` with the specified name.
+	pub fn with_planar(
+		mut self,
+		joint_name: impl Into<String>,
+		x: Float,
+		y: Float,
+		angle: Float,
+	) -> Self {
+		self.set(joint_name, JointPosition::Planar { x, y, angle });
+		self
+	}
+
+	/// Sets the `Transform` of the [`Floating`](JointType::Floating) `Joint` with the specified name.
+	pub fn with_floating(mut self, joint_name: impl Into<String>, transform: Transform) -> Self {
+		self.set(joint_name, JointPosition::Floating(transform));
+		self
+	}
+
+	/// Sets the position of the single degree of freedom `Joint` with the specified name.
+	pub fn set_position(&mut self, joint_name: impl Into<String>, position: Float) {
+		self.set(joint_name, JointPosition::Scalar(position));
+	}
+
+	/// Sets the [`JointPosition`] of the `Joint` with the specified name.
+	pub fn set(&mut self, joint_name: impl Into<String>, position: JointPosition) {
+		self.positions.insert(joint_name.into(), position);
+	}
+
+	/// Gets the [`JointPosition`] of the `Joint` with the specified name, if it is set.
+	pub fn get(&self, joint_name: &str) -> Option<&JointPosition> {
+		self.positions.get(joint_name)
+	}
+
+	/// Gets the position of the single degree of freedom `Joint` with the specified name, if it is set.
+	pub fn position(&self, joint_name: &str) -> Option<Float> {
+		match self.positions.get(joint_name) {
+			Some(JointPosition::Scalar(position)) => Some(*position),
+			_ => None,
+		}
+	}
+
+	/// Removes the position of the `Joint` with the specified name, which returns it to its zero position.
+	pub fn remove(&mut self, joint_name: &str) -> Option<JointPosition> {
+		self.positions.remove(joint_name)
+	}
+
+	/// Gets an iterator over the names of the `Joint`s and their positions, in arbitrary order.
+	pub fn iter(&self) -> impl Iterator<Item = (&String, &JointPosition)> {
+		self.positions.iter()
+	}
+}
+
+impl<Name: Into<String>> FromIterator<(Name, Float)> for JointState {
+	fn from_iter<T: IntoIterator<Item = (Name, Float)>>(iter: T) -> Self {
+		Self {
+			positions: iter
+				.into_iter()
+				.map(|(name, position)| (name.into(), JointPosition::Scalar(position)))
+				.collect(),
+		}
+	}
+}
+
+/// Gets the position of the single degree of freedom `joint` from the `joint_state`.
+///
+/// The position of a mimicking `Joint` follows the mimicked `Joint`. Missing positions default to zero.
+/// A cycle of mimics has no independent `Joint` to follow, so the first `Joint` of the cycle which is reached uses its own position.
+pub(crate) fn joint_position(joint: &Joint, joint_state: &JointState) -> Float {
+	// The visited `Joint`s, with the `scale` and `offset` which give the position of the `joint` from their position.
+	let mut visited: Vec<(String, Float, Float)> = vec![(joint.name().clone(), 1., 0.)];
+	let mut mimic = joint
+		.mimic()
+		.and_then(|mimic| Some((mimic.joint.upgrade()?, mimic.multiplier, mimic.offset)));
+
+	while let Some((mimicked_joint, multiplier, mimic_offset)) = mimic {
+		let mimicked_joint = mimicked_joint.read().unwrap();
+		if let Some(index) = visited
+			.iter()
+			.position(|(name, _, _)| name == mimicked_joint.name())
+		{
+			visited.truncate(index + 1);
+			break;
+		}
+
+		// This unwrap is Ok, since `visited` starts with the `joint`.
+		let (_, scale, offset) = visited.last().unwrap();
+		visited.push((
+			mimicked_joint.name().clone(),
+			scale * multiplier.unwrap_or(1.),
+			offset + scale * mimic_offset.unwrap_or(0.),
+		));
+		mimic = mimicked_joint
+			.mimic()
+			.and_then(|mimic| Some((mimic.joint.upgrade()?, mimic.multiplier, mimic.offset)));
+	}
+
+	// This unwrap is Ok, since `visited` starts with the `joint`.
+	let (name, scale, offset) = visited.last().unwrap();
+	scale * joint_state.position(name).unwrap_or_default() + offset
+}
+
+/// Gets the unit axis of the `joint`, which defaults to the x-axis like in URDF.
+pub(crate) fn joint_axis(joint: &Joint) -> Unit<Vector3<Float>> {
+	joint
+		.axis()
+		.and_then(|(x, y, z)| Unit::try_new(Vector3::new(x, y, z), Float::EPSILON))
+		.unwrap_or_else(Vector3::x_axis)
+}
+
+/// Gets two perpendicular unit directions in the plane perpendicular to the `axis`.
+///
+/// For an axis along the z-axis, these are the x-axis and the y-axis.
+fn plane_directions(axis: &Unit<Vector3<Float>>) -> (Vector3<Float>, Vector3<Float>) {
+	let reference = match axis.x.abs() < 0.9 {
+		true => Vector3::x(),
+		false => Vector3::y(),
+	};
+	let u = (reference - axis.into_inner() * axis.dot(&reference)).normalize();
+
+	(u, axis.cross(&u))
+}
+
+/// Gets the motion of the `joint` at its position in the `joint_state`.
+///
+/// [`Fixed`](JointType::Fixed) `Joint`s do not move.
+pub(crate) fn joint_motion(joint: &Joint, joint_state: &JointState) -> Isometry3<Float> {
+	match joint.joint_type() {
+		JointType::Revolute | JointType::Continuous => Isometry3::from_parts(
+			Translation3::identity(),
+			UnitQuaternion::from_axis_angle(&joint_axis(joint), joint_position(joint, joint_state)),
+		),
+		JointType::Prismatic => Isometry3::from_parts(
+			Translation3::from(joint_axis(joint).into_inner() * joint_position(joint, joint_state)),
+			UnitQuaternion::identity(),
+		),
+		JointType::Planar => match joint_state.get(joint.name()) {
+			Some(JointPosition::Planar { x, y, angle }) => {
+				let axis = joint_axis(joint);
+				let (u, v) = plane_directions(&axis);
+
+				Isometry3::from_parts(
+					Translation3::from(u * *x + v * *y),
+					UnitQuaternion::from_axis_angle(&axis, *angle),
+				)
+			}
+			_ => Isometry3::identity(),
+		},
+		JointType::Floating => match joint_state.get(joint.name()) {
+			Some(JointPosition::Floating(transform)) => transform.to_isometry(),
+			_ => Isometry3::identity(),
+		},
+		JointType::Fixed => Isometry3::identity(),
+	}
+}
+
+/// Calculates the pose of every `Link` relative to the root `Link`, with the `Joint`s at the positions in the `joint_state`.
+///
+/// The poses are indexed by the name of the `Link`.
+pub(crate) fn link_poses(
+	tree: &impl KinematicInterface,
+	joint_state: &JointState,
+) -> HashMap<String, Isometry3<Float>> {
+	fn add_poses(
+		link: &Link,
+		pose: Isometry3<Float>,
+		joint_state: &JointState,
+		poses: &mut HashMap<String, Isometry3<Float>>,
+	) {
+		for joint in link.joints() {
+			let joint = joint.read().unwrap();
+			let child_pose =
+				pose * joint.transform().to_isometry() * joint_motion(&joint, joint_state);
+
+			add_poses(
+				&joint.child_link().read().unwrap(),
+				child_pose,
+				joint_state,
+				poses,
+			);
+		}
+
+		poses.insert(link.name().clone(), pose);
+	}
+
+	let mut poses = HashMap::new();
+	add_poses(
+		&tree.get_root_link().read().unwrap(),
+		Isometry3::identity(),
+		joint_state,
+		&mut poses,
+	);
+
+	poses
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{float::consts::FRAC_PI_2, Float};
+
+	use super::{JointPosition, JointState};
+	use crate::{
+		joint::joint_data::MimicBuilderData, prelude::*, JointBuilder, JointType, Link, Robot,
+		SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	/// Checks if the translation of the `transform` is equal to `expected` within a small tolerance.
+	fn assert_translation(transform: Transform, expected: (Float, Float, Float)) {
+		let (x, y, z) = transform.translation.unwrap();
+		assert!(
+			(x - expected.0).abs() < 1e-5
+				&& (y - expected.1).abs() < 1e-5
+				&& (z - expected.2).abs() < 1e-5,
+			"{:?} != {:?}",
+			(x, y, z),
+			expected
+		);
+	}
+
+	/// A mobile base with an arm and a gripper with two mimicking fingers.
+	fn robot() -> Robot {
+		let robot = Link::builder("base").build_tree().to_robot("gripper_bot");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_revolute("shoulder")
+					.with_axis((0., 0., 1.))
+					.with_limit(10., 1.)
+					.add_transform(Transform::new_translation(0., 0., 0.5)),
+				Link::builder("arm"),
+			)
+			.unwrap();
+		robot
+			.get_link("arm")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("wrist", JointType::Fixed).add_origin_offset((1., 0., 0.)),
+				Link::builder("hand"),
+			)
+			.unwrap();
+		for (side, direction) in [("left", 1.), ("right", -1.)] {
+			let finger_joint = SmartJointBuilder::new_prismatic(format!("{side}_finger_joint"))
+				.with_axis((0., direction, 0.))
+				.with_limit(10., 0.1)
+				.add_transform(Transform::new_translation(0.1, 0., 0.));
+
+			robot
+				.get_link("hand")
+				.unwrap()
+				.write()
+				.unwrap()
+				.try_attach_child(
+					match side {
+						"right" => JointBuilder::from(
+							finger_joint
+								.with_mimic("left_finger_joint")
+								.set_mimic_multiplier(1.),
+						),
+						_ => finger_joint.into(),
+					},
+					Link::builder(format!("{side}_finger")),
+				)
+				.unwrap();
+		}
+
+		robot
+	}
+
+	#[test]
+	fn zero_configuration() {
+		let poses = robot().forward_kinematics(&JointState::new());
+
+		assert_eq!(poses.len(), 5);
+		assert_translation(poses["base"], (0., 0., 0.));
+		assert_translation(poses["arm"], (0., 0., 0.5));
+		assert_translation(poses["hand"], (1., 0., 0.5));
+		assert_translation(poses["left_finger"], (1.1, 0., 0.5));
+		assert_translation(poses["right_finger"], (1.1, 0., 0.5));
+	}
+
+	#[test]
+	fn revolute_and_mimic() {
+		let robot = robot();
+		let joint_state = JointState::new()
+			.with_position("shoulder", FRAC_PI_2)
+			.with_position("left_finger_joint", 0.05);
+
+		assert_translation(
+			robot.link_pose("hand", &joint_state).unwrap(),
+			(0., 1., 0.5),
+		);
+		// The fingers open symmetrically, since the right finger mimics the left finger.
+		assert_translation(
+			robot.link_pose("left_finger", &joint_state).unwrap(),
+			(-0.05, 1.1, 0.5),
+		);
+		assert_translation(
+			robot.link_pose("right_finger", &joint_state).unwrap(),
+			(0.05, 1.1, 0.5),
+		);
+
+		let (_, _, yaw) = robot
+			.link_pose("hand", &joint_state)
+			.unwrap()
+			.rotation
+			.unwrap();
+		assert!((yaw - FRAC_PI_2).abs() < 1e-5);
+
+		assert_eq!(robot.link_pose("elbow", &joint_state), None);
+	}
+
+	#[test]
+	fn mimic_cycle() {
+		let robot = Link::builder("base").build_tree().to_robot("robot");
+		let attach = |parent: &str, joint_builder: JointBuilder, child: &str| {
+			robot
+				.get_link(parent)
+				.unwrap()
+				.write()
+				.unwrap()
+				.try_attach_child(
+					joint_builder.add_origin_offset((1., 0., 0.)),
+					Link::builder(child),
+				)
+				.unwrap();
+		};
+		let mimic = |joint_name: &str, multiplier: Float| {
+			Some(MimicBuilderData {
+				joint_name: joint_name.to_string(),
+				multiplier: Some(multiplier),
+				offset: None,
+			})
+		};
+
+		attach(
+			"base",
+			JointBuilder::new("shoulder", JointType::Prismatic),
+			"arm",
+		);
+		let mut elbow = JointBuilder::new("elbow", JointType::Prismatic);
+		*elbow.mimic_mut() = mimic("shoulder", 1.);
+		attach("arm", elbow, "forearm");
+		let mut wrist = JointBuilder::new("wrist", JointType::Prismatic);
+		*wrist.mimic_mut() = mimic("elbow", 2.);
+		attach("forearm", wrist, "hand");
+
+		// Reattaching the shoulder, which mimics the elbow in its own chain, closes the cycle.
+		let mut shoulder = robot.yank_joint("shoulder").unwrap();
+		*shoulder.mimic_mut() = mimic("elbow", 1.);
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.attach_joint_chain(shoulder)
+			.unwrap();
+
+		// The joints in the cycle use their own positions and the wrist follows the elbow.
+		let joint_state = JointState::new()
+			.with_position("shoulder", 0.1)
+			.with_position("elbow", 0.2)
+			.with_position("wrist", 0.5);
+		assert_translation(robot.link_pose("arm", &joint_state).unwrap(), (1.1, 0., 0.));
+		assert_translation(
+			robot.link_pose("forearm", &joint_state).unwrap(),
+			(2.3, 0., 0.),
+		);
+		assert_translation(
+			robot.link_pose("hand", &joint_state).unwrap(),
+			(3.7, 0., 0.),
+		);
+	}
+
+	#[test]
+	fn planar_and_floating() {
+		let robot = Link::builder("world").build_tree().to_robot("mobile_bot");
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_planar("base_joint").with_axis((0., 0., 1.)),
+				Link::builder("base"),
+			)
+			.unwrap();
+		robot
+			.get_link("base")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("camera_joint", JointType::Floating)
+					.add_origin_offset((1., 0., 0.)),
+				Link::builder("camera"),
+			)
+			.unwrap();
+
+		let mut joint_state = JointState::new().with_planar("base_joint", 2., 3., FRAC_PI_2);
+		assert_translation(
+			robot.link_pose("camera", &joint_state).unwrap(),
+			(2., 4., 0.),
+		);
+
+		joint_state.set(
+			"camera_joint",
+			JointPosition::Floating(Transform::new_translation(0., 0., 1.)),
+		);
+		assert_translation(
+			robot.link_pose("camera", &joint_state).unwrap(),
+			(2., 4., 1.),
+		);
+
+		// A position of the wrong kind is ignored.
+		joint_state.set_position("base_joint", 1.);
+		assert_translation(
+			robot.link_pose("camera", &joint_state).unwrap(),
+			(1., 0., 1.),
+		);
+	}
+}
@@ -0,0 +1,484 @@
+//! The [`KinematicChain`] between two `Link`s of a kinematic tree.
+use std::sync::{Arc, Weak};
+
+use nalgebra::Isometry3;
+
+use super::{joint_motion, JointState, KinematicsError};
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::Joint,
+	link::{builder::LinkBuilder, link_data::LinkParent, Link},
+	transform::Transform,
+	utils::{ArcLock, WeakLock},
+	Float, Robot,
+};
+
+/// The direction in which a `Joint` is traversed in a [`KinematicChain`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChainDirection {
+	/// The `Joint` is traversed from its parent `Link` to its child `Link`, away from the root of the tree.
+	Forward,
+	/// The `Joint` is traversed from its child `Link` to its parent `Link`, towards the root of the tree.
+	Reversed,
+}
+
+/// The chain of `Link`s and `Joint`s between two `Link`s of a kinematic tree.
+///
+/// The chain goes up from the base `Link` to the closest common ancestor of both `Link`s and then down to the tip `Link`,
+/// like the `getChain` of [KDL](https://www.orocos.org/kdl.html).
+/// The chain refers to the `Link`s and `Joint`s of the kinematic tree, so it stays up to date with their changes.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     kinematics::{ChainDirection, JointState},
+/// #     prelude::*,
+/// #     JointBuilder, JointType, Link, Transform,
+/// # };
+/// let robot = Link::builder("base").build_tree().to_robot("robot");
+///
+/// for (joint, link, y) in [("left_joint", "left_arm", 1.), ("right_joint", "right_arm", -1.)] {
+///     robot
+///         .get_root_link()
+///         .write()
+///         .unwrap()
+///         .try_attach_child(
+///             JointBuilder::new(joint, JointType::Fixed).add_origin_offset((0., y, 0.)),
+///             Link::builder(link),
+///         )
+///         .unwrap();
+/// }
+///
+/// let chain = robot.get_chain("left_arm", "right_arm").unwrap();
+///
+/// assert_eq!(chain.link_names(), vec!["left_arm", "base", "right_arm"]);
+/// assert_eq!(chain.joint_names(), vec!["left_joint", "right_joint"]);
+/// assert_eq!(
+///     chain.directions(),
+///     &vec![ChainDirection::Reversed, ChainDirection::Forward]
+/// );
+/// assert_eq!(
+///     chain.pose(&JointState::new()).translation,
+///     Some((0., -2., 0.))
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct KinematicChain {
+	links: Vec<WeakLock<Link>>,
+	joints: Vec<WeakLock<Joint>>,
+	directions: Vec<ChainDirection>,
+}
+
+/// Gets the `link` and its ancestors, each with the `Joint` connecting it to its parent.
+fn ancestors(link: ArcLock<Link>) -> Vec<(ArcLock<Link>, Option<ArcLock<Joint>>)> {
+	let mut ancestors = Vec::new();
+	let mut current = Some(link);
+
+	while let Some(link) = current {
+		let parent_joint = match link.read().unwrap().parent() {
+			// This unwrap is Ok, since the tree keeps its `Joint`s alive.
+			LinkParent::Joint(joint) => Some(joint.upgrade().unwrap()),
+			LinkParent::KinematicTree(_) => None,
+		};
+
+		current = parent_joint
+			.as_ref()
+			.map(|joint| joint.read().unwrap().parent_link());
+		ancestors.push((link, parent_joint));
+	}
+
+	ancestors
+}
+
+impl KinematicChain {
+	/// Finds the chain from the `Link` named `base_link` to the `Link` named `tip_link` in the `tree`.
+	pub(crate) fn new(
+		tree: &impl KinematicInterface,
+		base_link: &str,
+		tip_link: &str,
+	) -> Result<Self, KinematicsError> {
+		let get_link = |name: &str| {
+			tree.get_link(name)
+				.ok_or_else(|| KinematicsError::InvalidLink(name.to_string()))
+		};
+
+		// This unwrap is Ok, since both `Link`s are in the same tree, so they share at least the root `Link`.
+		Ok(Self::between(get_link(base_link)?, get_link(tip_link)?).unwrap())
+	}
+
+	/// Finds the chain from the `base_link` to the `tip_link`.
+	///
+	/// Returns `None` if the `Link`s are not in the same kinematic tree.
+	pub(crate) fn between(base_link: ArcLock<Link>, tip_link: ArcLock<Link>) -> Option<Self> {
+		let base_ancestors = ancestors(base_link);
+		let tip_ancestors = ancestors(tip_link);
+
+		// The index of the closest common ancestor in both lists of ancestors.
+		let (base_index, tip_index) =
+			base_ancestors
+				.iter()
+				.enumerate()
+				.find_map(|(base_index, (link, _))| {
+					tip_ancestors
+						.iter()
+						.position(|(tip_link, _)| Arc::ptr_eq(link, tip_link))
+						.map(|tip_index| (base_index, tip_index))
+				})?;
+
+		let mut chain = Self {
+			links: Vec::new(),
+			joints: Vec::new(),
+			directions: Vec::new(),
+		};
+		for (link, joint) in base_ancestors.iter().take(base_index) {
+			chain.links.push(Arc::downgrade(link));
+			// This unwrap is Ok, since only the root `Link` has no parent `Joint`.
+			chain.joints.push(Arc::downgrade(joint.as_ref().unwrap()));
+			chain.directions.push(ChainDirection::Reversed);
+		}
+		chain
+			.links
+			.push(Arc::downgrade(&base_ancestors[base_index].0));
+		for (link, joint) in tip_ancestors.iter().take(tip_index).rev() {
+			chain.links.push(Arc::downgrade(link));
+			// This unwrap is Ok, since only the root `Link` has no parent `Joint`.
+			chain.joints.push(Arc::downgrade(joint.as_ref().unwrap()));
+			chain.directions.push(ChainDirection::Forward);
+		}
+
+		Some(chain)
+	}
+
+	/// Gets the `Link`s of the chain, from the base `Link` to the tip `Link`.
+	pub fn links(&self) -> &Vec<WeakLock<Link>> {
+		&self.links
+	}
+
+	/// Gets the `Joint`s of the chain, from the base `Link` to the tip `Link`.
+	///
+	/// The `Joint` at index `i` connects the `Link`s at index `i` and `i + 1`.
+	pub fn joints(&self) -> &Vec<WeakLock<Joint>> {
+		&self.joints
+	}
+
+	/// Gets the direction in which every `Joint` of the chain is traversed, in the same order as the [`joints`](Self::joints).
+	pub fn directions(&self) -> &Vec<ChainDirection> {
+		&self.directions
+	}
+
+	/// Gets the base `Link` of the chain.
+	pub fn base_link(&self) -> WeakLock<Link> {
+		Weak::clone(&self.links[0])
+	}
+
+	/// Gets the tip `Link` of the chain.
+	pub fn tip_link(&self) -> WeakLock<Link> {
+		Weak::clone(self.links.last().unwrap()) // This unwrap is Ok, since the chain has at least one `Link`.
+	}
+
+	/// Gets the current names of the `Link`s of the chain, from the base `Link` to the tip `Link`.
+	pub fn link_names(&self) -> Vec<String> {
+		self.links
+			.iter()
+			.map(|link| link.upgrade().unwrap().read().unwrap().name().clone())
+			.collect()
+	}
+
+	/// Gets the current names of the `Joint`s of the chain, from the base `Link` to the tip `Link`.
+	pub fn joint_names(&self) -> Vec<String> {
+		self.joints
+			.iter()
+			.map(|joint| joint.upgrade().unwrap().read().unwrap().name().clone())
+			.collect()
+	}
+
+	/// Checks if all `Joint`s of the chain are traversed [`Forward`](ChainDirection::Forward),
+	/// which means the tip `Link` is a descendant of the base `Link`.
+	pub fn is_serial(&self) -> bool {
+		self.directions
+			.iter()
+			.all(|direction| *direction == ChainDirection::Forward)
+	}
+
+	/// Calculates the pose of the tip `Link` relative to the base `Link`, with the `Joint`s at the positions in the `joint_state`.
+	pub fn pose(&self, joint_state: &JointState) -> Transform {
+		let pose = self.joints.iter().zip(self.directions.iter()).fold(
+			Isometry3::identity(),
+			|pose, (joint, direction)| {
+				let joint = joint.upgrade().unwrap();
+				let joint = joint.read().unwrap();
+				let step = joint.transform().to_isometry() * joint_motion(&joint, joint_state);
+
+				match direction {
+					ChainDirection::Forward => pose * step,
+					ChainDirection::Reversed => pose * step.inverse(),
+				}
+			},
+		);
+
+		Transform::from_isometry(&pose)
+	}
+
+	/// Creates a new `Robot` with the specified `name`, which only contains the `Link`s and `Joint`s of the chain.
+	///
+	/// The base `Link` of the chain is the root `Link` of the `Robot`.
+	/// [`Reversed`](ChainDirection::Reversed) `Joint`s are turned around:
+	/// the `Joint` is placed at the origin of its original child `Link` and its axis is inverted, such that its position keeps the same meaning.
+	/// The visuals, colliders, inertial and `Joint`s of its original parent `Link` are moved accordingly.
+	/// The positions of reversed planar and floating `Joint`s do not keep the same meaning.
+	///
+	/// Mimics of `Joint`s which are not in the chain are removed.
+	pub fn to_robot(&self, name: impl Into<String>) -> Robot {
+		let joint_names = self.joint_names();
+
+		// The pose of the original frame of the current `Link`, in the frame of the current `Link` in the new `Robot`.
+		let mut offset = Isometry3::identity();
+		let mut links = Vec::new();
+		let mut joints = Vec::new();
+		for (index, link) in self.links.iter().enumerate() {
+			let mut link_builder = link.upgrade().unwrap().read().unwrap().rebuild();
+			offset_link(&mut link_builder, &offset);
+			links.push(link_builder);
+
+			if let (Some(joint), Some(direction)) =
+				(self.joints.get(index), self.directions.get(index))
+			{
+				let joint = joint.upgrade().unwrap();
+				let joint = joint.read().unwrap();
+				let mut joint_builder = joint.rebuild();
+				if joint_builder
+					.mimic
+					.as_ref()
+					.is_some_and(|mimic| !joint_names.contains(&mimic.joint_name))
+				{
+					joint_builder.mimic = None;
+				}
+
+				let transform = joint.transform().to_isometry();
+				let (joint_transform, next_offset) = match direction {
+					ChainDirection::Forward => (offset * transform, Isometry3::identity()),
+					ChainDirection::Reversed => {
+						joint_builder.axis = joint_builder.axis.map(|(x, y, z)| (-x, -y, -z));
+						(offset, transform.inverse())
+					}
+				};
+				joint_builder.transform = Transform::from_isometry(&joint_transform).into();
+
+				joints.push(joint_builder);
+				offset = next_offset;
+			}
+		}
+
+		let root = links
+			.into_iter()
+			.rev()
+			.reduce(|child, mut parent| {
+				// This unwrap is Ok, since there is a `Joint` between every pair of `Link`s.
+				let mut joint = joints.pop().unwrap();
+				joint.child = Some(child);
+				parent.joints.push(joint);
+				parent
+			})
+			.unwrap(); // This unwrap is Ok, since the chain has at least one `Link`.
+
+		root.build_tree().to_robot(name)
+	}
+}
+
+/// Moves the contents of the `link_builder` from its original frame to the new frame, where `offset` is the pose of the original frame.
+fn offset_link(link_builder: &mut LinkBuilder, offset: &Isometry3<Float>) {
+	if *offset == Isometry3::identity() {
+		return;
+	}
+
+	let offset_transform = |transform: Option<Transform>| {
+		Some(Transform::from_isometry(
+			&(offset * transform.unwrap_or_default().to_isometry()),
+		))
+	};
+
+	for visual in link_builder.visuals.iter_mut() {
+		visual.transform = offset_transform(visual.transform);
+	}
+	for collider in link_builder.colliders.iter_mut() {
+		collider.transform = offset_transform(collider.transform);
+	}
+	if let Some(inertial) = link_builder.intertial.as_mut() {
+		inertial.transform = offset_transform(inertial.transform);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{float::consts::FRAC_PI_2, Float};
+
+	use super::ChainDirection;
+	use crate::{
+		kinematics::{JointState, KinematicsError},
+		link_data::{geometry::SphereGeometry, Visual},
+		prelude::*,
+		JointBuilder, JointType, Link, Robot, SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	fn assert_translation(transform: Transform, expected: (Float, Float, Float)) {
+		let (x, y, z) = transform.translation.unwrap();
+		assert!(
+			(x - expected.0).abs() < 1e-5
+				&& (y - expected.1).abs() < 1e-5
+				&& (z - expected.2).abs() < 1e-5,
+			"{:?} != {:?}",
+			(x, y, z),
+			expected
+		);
+	}
+
+	/// A torso with a rotating arm on the left and a tool on the right.
+	fn robot() -> Robot {
+		let robot = Link::builder("torso").build_tree().to_robot("robot");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_revolute("shoulder")
+					.with_axis((0., 0., 1.))
+					.with_limit(10., 1.)
+					.set_lower_limit(-1.)
+					.set_upper_limit(2.)
+					.add_transform(Transform::new_translation(0., 1., 0.)),
+				Link::builder("arm").add_visual(
+					Visual::builder(SphereGeometry::new(0.1))
+						.transformed(Transform::new_translation(0.5, 0., 0.)),
+				),
+			)
+			.unwrap();
+		robot
+			.get_link("arm")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("hand_joint", JointType::Fixed).add_origin_offset((1., 0., 0.)),
+				Link::builder("hand"),
+			)
+			.unwrap();
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("tool_joint", JointType::Fixed).add_origin_offset((0., -1., 0.)),
+				Link::builder("tool"),
+			)
+			.unwrap();
+
+		robot
+	}
+
+	#[test]
+	fn chains() {
+		let robot = robot();
+
+		let chain = robot.get_chain("torso", "hand").unwrap();
+		assert_eq!(chain.link_names(), vec!["torso", "arm", "hand"]);
+		assert_eq!(chain.joint_names(), vec!["shoulder", "hand_joint"]);
+		assert!(chain.is_serial());
+
+		let chain = robot.get_chain("hand", "tool").unwrap();
+		assert_eq!(chain.link_names(), vec!["hand", "arm", "torso", "tool"]);
+		assert_eq!(
+			chain.directions(),
+			&vec![
+				ChainDirection::Reversed,
+				ChainDirection::Reversed,
+				ChainDirection::Forward
+			]
+		);
+		assert!(!chain.is_serial());
+		assert_eq!(
+			chain.tip_link().upgrade().unwrap().read().unwrap().name(),
+			"tool"
+		);
+
+		let chain = robot.get_chain("arm", "arm").unwrap();
+		assert_eq!(chain.link_names(), vec!["arm"]);
+		assert!(chain.joints().is_empty());
+
+		assert_eq!(
+			robot.get_chain("arm", "leg").unwrap_err(),
+			KinematicsError::InvalidLink("leg".into())
+		);
+	}
+
+	#[test]
+	fn pose() {
+		let robot = robot();
+		let joint_state = JointState::new().with_position("shoulder", FRAC_PI_2);
+
+		// The hand is at (0, 2, 0) and the tool at (0, -1, 0).
+		assert_translation(
+			robot.get_chain("torso", "hand").unwrap().pose(&joint_state),
+			(0., 2., 0.),
+		);
+		// In the frame of the hand, which is turned to the left, the tool is behind it.
+		assert_translation(
+			robot.get_chain("hand", "tool").unwrap().pose(&joint_state),
+			(-3., 0., 0.),
+		);
+	}
+
+	#[test]
+	fn to_robot() {
+		let robot = robot();
+
+		let arm = robot.get_chain("torso", "hand").unwrap().to_robot("arm");
+		assert_eq!(arm.name(), "arm");
+		assert_eq!(arm.get_root_link().read().unwrap().name(), "torso");
+		assert!(arm.get_link("tool").is_none());
+		assert!(arm
+			.get_joint("shoulder")
+			.unwrap()
+			.read()
+			.unwrap()
+			.limit()
+			.is_some());
+
+		// The reversed robot has the same kinematics as the original robot.
+		let reversed = robot
+			.get_chain("tool", "hand")
+			.unwrap()
+			.to_robot("reversed");
+		assert_eq!(reversed.get_root_link().read().unwrap().name(), "tool");
+		for position in [-0.5, 0., 1.2] {
+			let joint_state = JointState::new().with_position("shoulder", position);
+			let original = robot.get_chain("tool", "hand").unwrap().pose(&joint_state);
+			assert_translation(
+				reversed.link_pose("hand", &joint_state).unwrap(),
+				original.translation.unwrap(),
+			);
+		}
+
+		// The visual of the arm is still at the same place.
+		let joint_state = JointState::new().with_position("shoulder", 0.3);
+		let hand_to_arm = reversed.get_chain("hand", "arm").unwrap();
+		let visual_pose = reversed
+			.link_pose("arm", &joint_state)
+			.unwrap()
+			.to_isometry()
+			* reversed.get_link("arm").unwrap().read().unwrap().visuals()[0]
+				.transform()
+				.unwrap()
+				.to_isometry();
+		let original_pose = robot.link_pose("arm", &joint_state).unwrap().to_isometry()
+			* Transform::new_translation(0.5, 0., 0.).to_isometry();
+		let tool_pose = robot.link_pose("tool", &joint_state).unwrap().to_isometry();
+		assert!(
+			((tool_pose.inverse() * original_pose).translation.vector
+				- visual_pose.translation.vector)
+				.norm() < 1e-5
+		);
+		assert_eq!(hand_to_arm.joint_names(), vec!["hand_joint"]);
+	}
+}
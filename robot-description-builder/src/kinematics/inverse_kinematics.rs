@@ -0,0 +1,421 @@
+//! The numerical inverse kinematics of a chain of `Link`s, with the damped least squares method.
+use nalgebra::{DMatrix, DVector, Isometry3, Vector3, Vector6};
+
+use super::{link_poses, Jacobian, JointState, KinematicsError};
+use crate::{
+	cluster_objects::KinematicInterface, float::consts::PI, joint::JointType, transform::Transform,
+	Float,
+};
+
+/// A Configuration for the [inverse kinematics](KinematicInterface::inverse_kinematics) solver.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct IKConfig {
+	/// The maximum number of iterations, after which the solver gives up.
+	pub max_iterations: usize,
+	/// The damping factor of the damped least squares method.
+	///
+	/// A larger damping makes the solver more stable near singularities, but makes it converge slower.
+	pub damping: Float,
+	/// The maximum distance in meters between the tip and the target, at which the solution is accepted.
+	pub position_tolerance: Float,
+	/// The maximum angle in radians between the orientation of the tip and the target, at which the solution is accepted.
+	pub orientation_tolerance: Float,
+	/// Only solves for the position of the tip, the orientation of the target is ignored.
+	///
+	/// This is useful to check if a position can be reached by an arm with less than six `Joint`s.
+	pub position_only: bool,
+}
+
+impl Default for IKConfig {
+	fn default() -> Self {
+		Self {
+			max_iterations: 200,
+			damping: 0.05,
+			position_tolerance: 1e-4,
+			orientation_tolerance: 1e-3,
+			position_only: false,
+		}
+	}
+}
+
+/// The result of the [inverse kinematics](KinematicInterface::inverse_kinematics) solver.
+///
+/// If the solver did not converge, the `JointState` is the closest configuration that was found.
+#[derive(Debug, PartialEq, Clone)]
+pub struct IKSolution {
+	joint_state: JointState,
+	converged: bool,
+	iterations: usize,
+	position_error: Float,
+	orientation_error: Float,
+}
+
+impl IKSolution {
+	/// Gets the `JointState` of the solution.
+	///
+	/// It contains the positions of the `Joint`s which were not in the chain, as they were in the initial `JointState`.
+	pub fn joint_state(&self) -> &JointState {
+		&self.joint_state
+	}
+
+	/// Consumes the `IKSolution` and returns its `JointState`.
+	pub fn into_joint_state(self) -> JointState {
+		self.joint_state
+	}
+
+	/// Checks if the tip reached the target within the tolerances of the [`IKConfig`].
+	pub fn converged(&self) -> bool {
+		self.converged
+	}
+
+	/// Gets the number of iterations the solver used.
+	pub fn iterations(&self) -> usize {
+		self.iterations
+	}
+
+	/// Gets the remaining distance in meters between the tip and the target.
+	pub fn position_error(&self) -> Float {
+		self.position_error
+	}
+
+	/// Gets the remaining angle in radians between the orientation of the tip and the target.
+	///
+	/// This is zero if the [`IKConfig`] was `position_only`.
+	pub fn orientation_error(&self) -> Float {
+		self.orientation_error
+	}
+}
+
+/// The bounds of a `Joint`, within which the solver keeps its position.
+#[derive(Debug, Clone, Copy)]
+enum JointBounds {
+	Limited {
+		lower: Float,
+		upper: Float,
+	},
+	/// The position wraps around, such that it stays in `[-π, π)`.
+	Continuous,
+	Unlimited,
+}
+
+impl JointBounds {
+	fn apply(&self, position: Float) -> Float {
+		match self {
+			Self::Limited { lower, upper } => position.clamp(*lower, *upper),
+			Self::Continuous => (position + PI).rem_euclid(2. * PI) - PI,
+			Self::Unlimited => position,
+		}
+	}
+}
+
+/// The smallest fraction of a step the solver tries, before accepting a step which increases the error.
+const MIN_STEP_SCALE: Float = 1. / 16.;
+
+/// Solves the inverse kinematics with the damped least squares method.
+pub(crate) fn solve(
+	tree: &impl KinematicInterface,
+	base_link: &str,
+	tip_link: &str,
+	target: &Transform,
+	initial_state: &JointState,
+	config: &IKConfig,
+) -> Result<IKSolution, KinematicsError> {
+	let target = target.to_isometry();
+	let mut joint_state = initial_state.clone();
+
+	let joints = Jacobian::compute(tree, base_link, tip_link, &joint_state)?
+		.joints()
+		.to_vec();
+	let bounds: Vec<JointBounds> = joints
+		.iter()
+		.map(|name| {
+			// This unwrap is Ok, since the `Jacobian` only contains `Joint`s of the tree.
+			let joint = tree.get_joint(name).unwrap();
+			let joint = joint.read().unwrap();
+
+			match (
+				joint.joint_type(),
+				joint.limit().and_then(|limit| limit.lower.zip(limit.upper)),
+			) {
+				(JointType::Continuous, _) => JointBounds::Continuous,
+				(_, Some((lower, upper))) if lower <= upper => {
+					JointBounds::Limited { lower, upper }
+				}
+				_ => JointBounds::Unlimited,
+			}
+		})
+		.collect();
+
+	// The rows of the `Jacobian` and the error, which are solved for.
+	let rows = match config.position_only {
+		true => 3,
+		false => 6,
+	};
+
+	// The position and orientation error of the tip for a `JointState`.
+	let pose_error = |joint_state: &JointState| {
+		let poses = link_poses(tree, joint_state);
+		let tip_pose: Isometry3<Float> = poses[base_link].inverse() * poses[tip_link];
+
+		let position_error = target.translation.vector - tip_pose.translation.vector;
+		let orientation_error = match config.position_only {
+			true => Vector3::zeros(),
+			false => (target.rotation * tip_pose.rotation.inverse()).scaled_axis(),
+		};
+		(position_error, orientation_error)
+	};
+
+	let mut iterations = 0;
+	let (mut position_error, mut orientation_error) = pose_error(&joint_state);
+	loop {
+		let converged = position_error.norm() <= config.position_tolerance
+			&& orientation_error.norm() <= config.orientation_tolerance;
+		if converged || iterations >= config.max_iterations || joints.is_empty() {
+			return Ok(IKSolution {
+				joint_state,
+				converged,
+				iterations,
+				position_error: position_error.norm(),
+				orientation_error: orientation_error.norm(),
+			});
+		}
+
+		let jacobian = Jacobian::compute(tree, base_link, tip_link, &joint_state)?;
+		let jacobian: DMatrix<Float> = jacobian.matrix().rows(0, rows).clone_owned();
+		let error = DVector::from_iterator(
+			rows,
+			Vector6::new(
+				position_error.x,
+				position_error.y,
+				position_error.z,
+				orientation_error.x,
+				orientation_error.y,
+				orientation_error.z,
+			)
+			.iter()
+			.copied()
+			.take(rows),
+		);
+
+		// dq = Jᵀ (J Jᵀ + λ² I)⁻¹ e
+		let damped = &jacobian * jacobian.transpose()
+			+ DMatrix::identity(rows, rows) * config.damping.powi(2);
+		let step = match damped.cholesky() {
+			Some(cholesky) => jacobian.transpose() * cholesky.solve(&error),
+			// This can only happen without damping, in which case the solver is stuck.
+			None => DVector::zeros(joints.len()),
+		};
+
+		// Steps which increase the error are shortened, since the clamping to the limits can make the linearization overshoot,
+		// which would make the solver oscillate near singularities.
+		let mut scale = 1.;
+		loop {
+			let mut candidate = joint_state.clone();
+			for ((name, bounds), delta) in joints.iter().zip(bounds.iter()).zip(step.iter()) {
+				let position = candidate.position(name).unwrap_or_default() + delta * scale;
+				candidate.set_position(name, bounds.apply(position));
+			}
+
+			let (candidate_position_error, candidate_orientation_error) = pose_error(&candidate);
+			if candidate_position_error.norm_squared() + candidate_orientation_error.norm_squared()
+				<= error.norm_squared()
+				|| scale <= MIN_STEP_SCALE
+			{
+				joint_state = candidate;
+				position_error = candidate_position_error;
+				orientation_error = candidate_orientation_error;
+				break;
+			}
+			scale /= 2.;
+		}
+
+		iterations += 1;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::IKConfig;
+	use crate::{
+		float::consts::{FRAC_PI_2, PI},
+		kinematics::{JointState, KinematicsError},
+		prelude::*,
+		JointBuilder, JointType, Link, Robot, SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	/// A planar arm with two links of one meter, of which the elbow can only bend to one side.
+	fn robot() -> Robot {
+		let robot = Link::builder("base").build_tree().to_robot("arm");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_continuous("shoulder").with_axis((0., 0., 1.)),
+				Link::builder("upper_arm"),
+			)
+			.unwrap();
+		robot
+			.get_link("upper_arm")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_revolute("elbow")
+					.with_axis((0., 0., 1.))
+					.with_limit(10., 1.)
+					.set_lower_limit(0.)
+					.set_upper_limit(2.5)
+					.add_transform(Transform::new_translation(1., 0., 0.)),
+				Link::builder("forearm"),
+			)
+			.unwrap();
+		robot
+			.get_link("forearm")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("wrist", JointType::Fixed).add_origin_offset((1., 0., 0.)),
+				Link::builder("hand"),
+			)
+			.unwrap();
+
+		robot
+	}
+
+	fn position_only() -> IKConfig {
+		IKConfig {
+			position_only: true,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn reachable() {
+		let robot = robot();
+		let target = Transform::new_translation(0., 1.5, 0.);
+
+		let solution = robot
+			.inverse_kinematics("base", "hand", &target, &JointState::new(), position_only())
+			.unwrap();
+
+		assert!(solution.converged());
+		assert!(solution.position_error() <= 1e-4);
+		assert_eq!(solution.orientation_error(), 0.);
+
+		let (x, y, _) = robot
+			.link_pose("hand", solution.joint_state())
+			.unwrap()
+			.translation
+			.unwrap();
+		assert!((x - 0.).abs() < 1e-3 && (y - 1.5).abs() < 1e-3);
+
+		// The elbow can only bend to the left, which determines the solution.
+		let elbow = solution.joint_state().position("elbow").unwrap();
+		assert!((0. ..=2.5).contains(&elbow));
+		let shoulder = solution.joint_state().position("shoulder").unwrap();
+		assert!((-PI..PI).contains(&shoulder));
+	}
+
+	#[test]
+	fn full_pose() {
+		let robot = robot();
+		let initial = JointState::new()
+			.with_position("shoulder", 0.3)
+			.with_position("elbow", 0.2);
+		let target = robot
+			.link_pose(
+				"hand",
+				&JointState::new()
+					.with_position("shoulder", -FRAC_PI_2)
+					.with_position("elbow", 1.),
+			)
+			.unwrap();
+
+		let solution = robot
+			.inverse_kinematics("base", "hand", &target, &initial, IKConfig::default())
+			.unwrap();
+
+		assert!(solution.converged());
+		assert!(solution.orientation_error() <= 1e-3);
+		assert!((solution.joint_state().position("shoulder").unwrap() + FRAC_PI_2).abs() < 1e-3);
+		assert!((solution.joint_state().position("elbow").unwrap() - 1.).abs() < 1e-3);
+	}
+
+	#[test]
+	fn stretched() {
+		let robot = robot();
+
+		// The target is out of reach straight ahead, so the elbow is pushed against its lower limit.
+		// Overshooting steps made the arm swing around the shoulder, instead of settling at the closest pose.
+		let errors: Vec<_> = (100..110)
+			.map(|max_iterations| {
+				robot
+					.inverse_kinematics(
+						"base",
+						"hand",
+						&Transform::new_translation(3., 0., 0.),
+						&JointState::new().with_position("elbow", 0.5),
+						IKConfig {
+							max_iterations,
+							..position_only()
+						},
+					)
+					.unwrap()
+					.position_error()
+			})
+			.collect();
+
+		assert!(
+			errors.iter().all(|error| (error - 1.).abs() < 1e-3),
+			"{errors:?}"
+		);
+	}
+
+	#[test]
+	fn unreachable() {
+		let robot = robot();
+
+		// Too far away.
+		let solution = robot
+			.inverse_kinematics(
+				"base",
+				"hand",
+				&Transform::new_translation(3., 0., 0.),
+				&JointState::new().with_position("elbow", 0.5),
+				position_only(),
+			)
+			.unwrap();
+		assert!(!solution.converged());
+		assert_eq!(solution.iterations(), IKConfig::default().max_iterations);
+		assert!((solution.position_error() - 1.).abs() < 1e-2);
+
+		// Out of the plane of the arm.
+		let solution = robot
+			.inverse_kinematics(
+				"base",
+				"hand",
+				&Transform::new_translation(1., 0., 1.),
+				&JointState::new(),
+				position_only(),
+			)
+			.unwrap();
+		assert!(!solution.converged());
+
+		assert_eq!(
+			robot
+				.inverse_kinematics(
+					"base",
+					"claw",
+					&Transform::default(),
+					&JointState::new(),
+					IKConfig::default()
+				)
+				.unwrap_err(),
+			KinematicsError::InvalidLink("claw".into())
+		);
+	}
+}
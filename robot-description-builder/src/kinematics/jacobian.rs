@@ -0,0 +1,463 @@
+//! The geometric [`Jacobian`] of a chain of `Link`s and the measures derived from it.
+use std::sync::Arc;
+
+use nalgebra::{DVector, Matrix6xX, Vector3};
+
+use super::{joint_axis, link_poses, JointState, KinematicChain, KinematicsError};
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::{Joint, JointType},
+	utils::ArcLock,
+	Float,
+};
+
+/// The geometric Jacobian of a chain of `Link`s, which maps the velocities of the `Joint`s to the velocity of the tip `Link`.
+///
+/// The Jacobian is expressed in the frame of the base `Link` of the chain, with the velocity taken at the origin of the tip `Link`.
+/// The first three rows are the linear velocity and the last three rows are the angular velocity.
+///
+/// Every column corresponds to a `Joint`, in the order of [`joints`](Jacobian::joints).
+/// Mimicking `Joint`s in the chain are added to the column of the `Joint` they mimic, scaled by their multiplier.
+/// [`Planar`](JointType::Planar) and [`Floating`](JointType::Floating) `Joint`s are treated as [`Fixed`](JointType::Fixed) `Joint`s.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{kinematics::JointState, prelude::*, JointBuilder, JointType, Link, SmartJointBuilder};
+/// let robot = Link::builder("base").build_tree().to_robot("planar_arm");
+///
+/// robot
+///     .get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(
+///         SmartJointBuilder::new_continuous("shoulder").with_axis((0., 0., 1.)),
+///         Link::builder("arm"),
+///     )
+///     .unwrap();
+/// robot
+///     .get_link("arm")
+///     .unwrap()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(
+///         JointBuilder::new("wrist", JointType::Fixed).add_origin_offset((1., 0., 0.)),
+///         Link::builder("hand"),
+///     )
+///     .unwrap();
+///
+/// let jacobian = robot.jacobian("base", "hand", &JointState::new()).unwrap();
+///
+/// assert_eq!(jacobian.joints(), &["shoulder".to_string()]);
+/// // Turning the shoulder moves the hand along the y-axis and rotates it around the z-axis.
+/// assert_eq!(jacobian.matrix().column(0).as_slice(), &[0., 1., 0., 0., 0., 1.]);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Jacobian {
+	joints: Vec<String>,
+	matrix: Matrix6xX<Float>,
+}
+
+/// Gets the `Joint` which determines the position of the `joint` and the factor by which the `joint` follows it.
+///
+/// This is the `joint` itself, unless it mimics another `Joint`.
+/// A cycle of mimics has no independent `Joint`, so the first `Joint` of the cycle which is reached is used instead,
+/// like in [`joint_position`](super::joint_position).
+pub(crate) fn independent_joint(joint: &ArcLock<Joint>) -> (String, Float) {
+	// The visited `Joint`s, with the factor by which the `joint` follows them.
+	let mut visited = vec![(Arc::clone(joint), 1.)];
+
+	loop {
+		// This unwrap is Ok, since `visited` starts with the `joint`.
+		let (current, factor) = visited.last().unwrap();
+		let current = current.read().unwrap();
+		let Some((mimicked_joint, multiplier)) = current
+			.mimic()
+			.and_then(|mimic| Some((mimic.joint.upgrade()?, mimic.multiplier)))
+		else {
+			return (current.name().clone(), *factor);
+		};
+
+		if let Some((joint, factor)) = visited
+			.iter()
+			.find(|(joint, _)| Arc::ptr_eq(joint, &mimicked_joint))
+		{
+			return (joint.read().unwrap().name().clone(), *factor);
+		}
+
+		let factor = factor * multiplier.unwrap_or(1.);
+		drop(current);
+		visited.push((mimicked_joint, factor));
+	}
+}
+
+impl Jacobian {
+	/// Computes the `Jacobian` of the chain from the `Link` named `base_link` to the `Link` named `tip_link`.
+	pub(crate) fn compute(
+		tree: &impl KinematicInterface,
+		base_link: &str,
+		tip_link: &str,
+		joint_state: &JointState,
+	) -> Result<Self, KinematicsError> {
+		let chain = KinematicChain::new(tree, base_link, tip_link)?;
+		if !chain.is_serial() {
+			return Err(KinematicsError::NotInChain {
+				base: base_link.to_string(),
+				tip: tip_link.to_string(),
+			});
+		}
+		let poses = link_poses(tree, joint_state);
+		let base_pose = poses[base_link].inverse();
+		let tip_position = (base_pose * poses[tip_link]).translation.vector;
+
+		let mut joints: Vec<String> = Vec::new();
+		let mut columns: Vec<[Float; 6]> = Vec::new();
+		for joint_lock in chain.joints().iter().filter_map(|joint| joint.upgrade()) {
+			let joint = joint_lock.read().unwrap();
+
+			// The motion of the `Joint` does not change its axis, so the pose of the child `Link` can be used.
+			let child_link = joint.child_link();
+			let joint_pose = base_pose * poses[child_link.read().unwrap().name()];
+			let axis = joint_pose.rotation * joint_axis(&joint).into_inner();
+
+			let (linear, angular) = match joint.joint_type() {
+				JointType::Revolute | JointType::Continuous => (
+					axis.cross(&(tip_position - joint_pose.translation.vector)),
+					axis,
+				),
+				JointType::Prismatic => (axis, Vector3::zeros()),
+				JointType::Fixed | JointType::Planar | JointType::Floating => continue,
+			};
+			drop(joint);
+
+			let (name, factor) = independent_joint(&joint_lock);
+			let index = match joints.iter().position(|joint| *joint == name) {
+				Some(index) => index,
+				None => {
+					joints.push(name);
+					columns.push([0.; 6]);
+					columns.len() - 1
+				}
+			};
+
+			for (value, contribution) in columns[index]
+				.iter_mut()
+				.zip(linear.iter().chain(angular.iter()))
+			{
+				*value += factor * contribution;
+			}
+		}
+
+		Ok(Self {
+			joints,
+			matrix: Matrix6xX::from_iterator(columns.len(), columns.into_iter().flatten()),
+		})
+	}
+
+	/// Gets the names of the `Joint`s corresponding to the columns of the `Jacobian`.
+	pub fn joints(&self) -> &[String] {
+		&self.joints
+	}
+
+	/// Gets the 6xN matrix of the `Jacobian`.
+	pub fn matrix(&self) -> &Matrix6xX<Float> {
+		&self.matrix
+	}
+
+	/// Gets the singular values of the `Jacobian` in descending order.
+	///
+	/// A chain with N `Joint`s has `min(6, N)` singular values.
+	pub fn singular_values(&self) -> DVector<Float> {
+		let mut singular_values: Vec<Float> =
+			self.matrix.singular_values().iter().copied().collect();
+		singular_values.sort_by(|a, b| b.total_cmp(a));
+
+		DVector::from_vec(singular_values)
+	}
+
+	/// Gets the manipulability index of [Yoshikawa](https://doi.org/10.1177/027836498500400201), which is a measure of how well the tip can move in all directions.
+	///
+	/// This is the product of the singular values, which is `sqrt(det(J * Jᵀ))` for chains with six or more `Joint`s.
+	/// For chains with less than six `Joint`s, this is `sqrt(det(Jᵀ * J))`, which measures the movement in the directions the chain can reach.
+	/// A chain without `Joint`s has a manipulability of zero.
+	pub fn manipulability(&self) -> Float {
+		match self.joints.is_empty() {
+			true => 0.,
+			false => self.singular_values().product(),
+		}
+	}
+
+	/// Gets the condition number of the `Jacobian`, the ratio of the largest to the smallest singular value.
+	///
+	/// A condition number close to one means the tip can move equally well in all reachable directions.
+	/// At a singularity, the condition number is infinite.
+	pub fn condition_number(&self) -> Float {
+		let singular_values = self.singular_values();
+		match singular_values.as_slice().last() {
+			Some(smallest) if *smallest > Float::EPSILON => singular_values[0] / smallest,
+			_ => Float::INFINITY,
+		}
+	}
+
+	/// Checks if the chain is in a singular configuration,
+	/// which means the smallest singular value is smaller than the `tolerance`.
+	///
+	/// In a singular configuration, the `Joint`s have lost the ability to move the tip in at least one direction.
+	pub fn is_singular(&self, tolerance: Float) -> bool {
+		self.singular_values()
+			.as_slice()
+			.last()
+			.map_or(true, |smallest| *smallest < tolerance)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{float::consts::FRAC_PI_2, Float};
+
+	use crate::{
+		joint::joint_data::MimicBuilderData,
+		kinematics::{JointState, KinematicsError},
+		prelude::*,
+		JointBuilder, JointType, Link, Robot, SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	/// A planar arm with two links of one meter, and a prismatic lift at the base.
+	fn robot() -> Robot {
+		let robot = Link::builder("base").build_tree().to_robot("arm");
+
+		let mut parent = "base".to_string();
+		for (link, joint_builder) in [
+			(
+				"column",
+				JointBuilder::from(
+					SmartJointBuilder::new_prismatic("lift")
+						.with_axis((0., 0., 1.))
+						.with_limit(100., 1.),
+				),
+			),
+			(
+				"upper_arm",
+				SmartJointBuilder::new_continuous("shoulder")
+					.with_axis((0., 0., 1.))
+					.into(),
+			),
+			(
+				"forearm",
+				SmartJointBuilder::new_continuous("elbow")
+					.with_axis((0., 0., 1.))
+					.add_transform(Transform::new_translation(1., 0., 0.))
+					.into(),
+			),
+			(
+				"hand",
+				JointBuilder::new("wrist", JointType::Fixed).add_origin_offset((1., 0., 0.)),
+			),
+		] {
+			robot
+				.get_link(&parent)
+				.unwrap()
+				.write()
+				.unwrap()
+				.try_attach_child(joint_builder, Link::builder(link))
+				.unwrap();
+			parent = link.to_string();
+		}
+
+		robot
+	}
+
+	fn assert_column(actual: &[Float], expected: [Float; 6]) {
+		assert!(
+			actual
+				.iter()
+				.zip(expected.iter())
+				.all(|(actual, expected)| (actual - expected).abs() < 1e-5),
+			"{:?} != {:?}",
+			actual,
+			expected
+		);
+	}
+
+	#[test]
+	fn columns() {
+		let robot = robot();
+		let jacobian = robot
+			.jacobian(
+				"base",
+				"hand",
+				&JointState::new().with_position("elbow", FRAC_PI_2),
+			)
+			.unwrap();
+
+		assert_eq!(jacobian.joints(), &["lift", "shoulder", "elbow"]);
+		assert_eq!(jacobian.matrix().shape(), (6, 3));
+		// The hand is at (1, 1, 0).
+		assert_column(
+			jacobian.matrix().column(0).as_slice(),
+			[0., 0., 1., 0., 0., 0.],
+		);
+		assert_column(
+			jacobian.matrix().column(1).as_slice(),
+			[-1., 1., 0., 0., 0., 1.],
+		);
+		assert_column(
+			jacobian.matrix().column(2).as_slice(),
+			[-1., 0., 0., 0., 0., 1.],
+		);
+
+		// Relative to the upper arm, the shoulder is not in the chain.
+		let jacobian = robot
+			.jacobian(
+				"upper_arm",
+				"hand",
+				&JointState::new().with_position("shoulder", FRAC_PI_2),
+			)
+			.unwrap();
+		assert_eq!(jacobian.joints(), &["elbow"]);
+		assert_column(
+			jacobian.matrix().column(0).as_slice(),
+			[0., 1., 0., 0., 0., 1.],
+		);
+	}
+
+	#[test]
+	fn measures() {
+		let robot = robot();
+		// A wrist with a roll, a pitch and a second roll joint.
+		let mut parent = "hand".to_string();
+		for (joint, axis, link) in [
+			("wrist_roll", (1., 0., 0.), "wrist"),
+			("wrist_pitch", (0., 1., 0.), "flange"),
+			("tool_roll", (1., 0., 0.), "tool"),
+		] {
+			robot
+				.get_link(&parent)
+				.unwrap()
+				.write()
+				.unwrap()
+				.try_attach_child(
+					SmartJointBuilder::new_continuous(joint).with_axis(axis),
+					Link::builder(link),
+				)
+				.unwrap();
+			parent = link.to_string();
+		}
+
+		let bent = robot
+			.jacobian(
+				"hand",
+				"tool",
+				&JointState::new().with_position("wrist_pitch", FRAC_PI_2),
+			)
+			.unwrap();
+		assert!(!bent.is_singular(1e-3));
+		assert!((bent.condition_number() - 1.).abs() < 1e-5);
+		assert!((bent.manipulability() - 1.).abs() < 1e-5);
+
+		// When the pitch is zero, both roll joints turn around the same line.
+		let stretched = robot.jacobian("hand", "tool", &JointState::new()).unwrap();
+		assert!(stretched.is_singular(1e-3));
+		assert!(stretched.condition_number() > 1e3);
+		assert!(stretched.manipulability() < 1e-3);
+
+		let singular_values = robot
+			.jacobian(
+				"base",
+				"hand",
+				&JointState::new().with_position("elbow", FRAC_PI_2),
+			)
+			.unwrap()
+			.singular_values();
+		assert_eq!(singular_values.len(), 3);
+		assert!(singular_values[0] >= singular_values[1]);
+		assert!(singular_values[1] >= singular_values[2]);
+	}
+
+	#[test]
+	fn mimic() {
+		let robot = robot();
+		robot
+			.get_link("hand")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_continuous("finger_joint")
+					.with_axis((0., 0., 1.))
+					.with_mimic("elbow")
+					.set_mimic_multiplier(2.),
+				Link::builder("finger"),
+			)
+			.unwrap();
+
+		let jacobian = robot
+			.jacobian("upper_arm", "finger", &JointState::new())
+			.unwrap();
+
+		// The finger follows the elbow twice as fast, so it adds twice its rotation.
+		assert_eq!(jacobian.joints(), &["elbow"]);
+		assert_column(
+			jacobian.matrix().column(0).as_slice(),
+			[0., 1., 0., 0., 0., 3.],
+		);
+	}
+
+	#[test]
+	fn mimic_cycle() {
+		let robot = robot();
+		for (joint, parent, mimicked_joint) in [
+			("elbow", "upper_arm", "shoulder"),
+			("shoulder", "column", "elbow"),
+		] {
+			let mut joint_builder = robot.yank_joint(joint).unwrap();
+			*joint_builder.mimic_mut() = Some(MimicBuilderData {
+				joint_name: mimicked_joint.to_string(),
+				multiplier: Some(2.),
+				offset: None,
+			});
+			robot
+				.get_link(parent)
+				.unwrap()
+				.write()
+				.unwrap()
+				.attach_joint_chain(joint_builder)
+				.unwrap();
+		}
+
+		// The shoulder and the elbow mimic each other, so both are their own independent joint.
+		let jacobian = robot
+			.jacobian(
+				"base",
+				"hand",
+				&JointState::new().with_position("elbow", FRAC_PI_2),
+			)
+			.unwrap();
+		assert_eq!(jacobian.joints(), &["lift", "shoulder", "elbow"]);
+		assert_column(
+			jacobian.matrix().column(2).as_slice(),
+			[-1., 0., 0., 0., 0., 1.],
+		);
+	}
+
+	#[test]
+	fn errors() {
+		let robot = robot();
+
+		assert_eq!(
+			robot
+				.jacobian("base", "claw", &JointState::new())
+				.unwrap_err(),
+			KinematicsError::InvalidLink("claw".into())
+		);
+		assert_eq!(
+			robot
+				.jacobian("hand", "base", &JointState::new())
+				.unwrap_err(),
+			KinematicsError::NotInChain {
+				base: "hand".into(),
+				tip: "base".into()
+			}
+		);
+	}
+}
@@ -1,6 +1,8 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::unescaped_backticks)]
 #![warn(rustdoc::redundant_explicit_links)]
+// With the `f64` feature, the conversions from `Float` to `f64` for the double precision calculations convert to the same type.
+#![cfg_attr(feature = "f64", allow(clippy::useless_conversion))]
 // #![warn(missing_docs)]
 // This is fine for now.
 #![doc = include_str!("../README.md")]
@@ -13,14 +15,24 @@ mod transform;
 mod utils;
 mod yank_errors;
 
+pub mod collision;
+pub mod dynamics;
+pub mod float;
+pub mod from_rdf;
 pub mod identifiers;
+pub mod kinematics;
 pub mod material;
+pub mod simplify;
+#[cfg(feature = "srdf")]
+pub mod srdf;
 pub mod to_rdf;
 #[doc(hidden)]
 // FIXME: Hidden until correctly implemented
 pub mod transmission;
+pub mod validate;
 pub use chained::Chained;
 pub use cluster_objects::{KinematicInterface, KinematicTree, Robot};
+pub use float::Float;
 pub use joint::{joint_data, Joint, JointBuilder, JointType, SmartJointBuilder};
 pub use link::{helper_functions, link_data, Link};
 pub use transform::{MirrorAxis, Transform};
@@ -43,7 +55,23 @@ pub mod errors {
 	pub use super::cluster_objects::kinematic_data_errors::{
 		AddJointError, AddLinkError, AddMaterialError, AddTransmissionError, AttachChainError,
 	};
+	#[cfg(feature = "mjcf")]
+	pub use super::from_rdf::from_mjcf::{FromMJCFError, MJCFWarning};
+	#[cfg(feature = "sdf")]
+	pub use super::from_rdf::from_sdf::{FromSDFError, SDFWarning};
+	#[cfg(feature = "urdf")]
+	pub use super::from_rdf::from_urdf::FromURDFError;
+	#[cfg(feature = "xacro")]
+	pub use super::from_rdf::from_xacro::{FromXacroError, XacroExpressionError};
+	#[cfg(feature = "xml")]
+	pub use super::from_rdf::XMLReadError;
 	pub use super::identifiers::GroupIDError;
+	pub use super::kinematics::KinematicsError;
+	pub use super::link::InertialError;
+	#[cfg(feature = "srdf")]
+	pub use super::srdf::BuildSRDFError;
+	#[cfg(feature = "urdf")]
+	pub use super::to_rdf::to_urdf::URDFCompatibilityError;
 	pub use super::yank_errors::{RebuildBranchError, YankJointError, YankLinkError};
 }
 
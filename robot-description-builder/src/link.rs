@@ -7,6 +7,7 @@ mod link_parent;
 mod link_shape_data;
 mod visual;
 
+pub use inertial::InertialError;
 pub(crate) use link_shape_data::LinkShapeData;
 
 #[cfg(feature = "xml")]
@@ -42,8 +43,14 @@ pub mod link_data {
 
 use std::sync::{Arc, Weak};
 
+#[cfg(any(feature = "sdf", feature = "mjcf"))]
+use crate::joint::JointType;
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+#[cfg(feature = "sdf")]
+use crate::Float;
 use crate::{
 	chained::Chained,
 	cluster_objects::{
@@ -59,6 +66,13 @@ use crate::{
 	utils::{ArcLock, ArcRW, WeakLock},
 	yank_errors::{RebuildBranchError, YankLinkError},
 };
+#[cfg(feature = "mjcf")]
+use crate::{
+	link::geometry::{GeometryShapeContainer, MeshGeometry},
+	to_rdf::to_mjcf::{mesh_asset_name, ToMJCF},
+};
+#[cfg(feature = "sdf")]
+use nalgebra::Isometry3;
 
 /// A `Link` in a Kinematic Structure.
 ///
@@ -97,7 +111,7 @@ pub struct Link {
 	colliders: Vec<link_data::Collision>,
 	// /// TODO: Maybe array, or thing
 	// /// Or calculate when necessary
-	// end_point: Option<(f32, f32, f32)>,
+	// end_point: Option<(Float, Float, Float)>,
 	me: WeakLock<Self>,
 }
 
@@ -208,6 +222,11 @@ impl Link {
 			.expect("KinematicDataTree should be initialized")
 			.try_add_joint(&joint)?;
 
+		self.tree
+			.upgrade()
+			.expect("KinematicDataTree should be initialized")
+			.resolve_pending_mimics(&joint)?;
+
 		self.joints_mut().push(joint);
 		Ok(())
 	}
@@ -216,7 +235,7 @@ impl Link {
 		self.inertial.as_ref()
 	}
 
-	// pub fn get_end_point(&self) -> Option<(f32, f32, f32)> {
+	// pub fn get_end_point(&self) -> Option<(Float, Float, Float)> {
 	// 	self.end_point
 	// }
 
@@ -332,12 +351,12 @@ impl ToURDF for Link {
 			self.visuals
 				.iter()
 				.map(|visual| visual.to_urdf(writer, urdf_config))
-				.process_results(|iter| iter.collect())?;
+				.process_results(|iter| iter.collect::<()>())?;
 
 			self.colliders
 				.iter()
 				.map(|collider| collider.to_urdf(writer, urdf_config))
-				.process_results(|iter| iter.collect())?;
+				.process_results(|iter| iter.collect::<()>())?;
 
 			Ok(())
 		})?;
@@ -346,7 +365,256 @@ impl ToURDF for Link {
 		self.joints()
 			.iter()
 			.map(|joint| joint.read().unwrap().to_urdf(writer, urdf_config))
-			.process_results(|iter| iter.collect())?;
+			.process_results(|iter| iter.collect::<()>())?;
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl Link {
+	/// Calculates the pose of this `Link` relative to the root `Link`, which is the model frame in SDFormat.
+	fn sdf_model_pose(&self) -> Isometry3<Float> {
+		match &self.direct_parent {
+			LinkParent::KinematicTree(_) => Isometry3::identity(),
+			LinkParent::Joint(joint) => {
+				let joint = joint.upgrade().unwrap();
+				let joint = joint.read().unwrap();
+
+				let parent_pose = joint.parent_link().read().unwrap().sdf_model_pose();
+
+				parent_pose * joint.transform().to_isometry()
+			}
+		}
+	}
+
+	/// Writes the `<pose>` of this `Link` in SDFormat.
+	///
+	/// From SDFormat 1.7 onwards, the pose is relative to the parent `Joint`.
+	/// If the parent `Joint` can not be described in SDFormat, the pose is relative to the parent `Link` instead.
+	/// For older versions, the pose is relative to the model frame.
+	fn write_sdf_pose(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let LinkParent::Joint(joint) = &self.direct_parent else {
+			// The root link is the model frame.
+			return Ok(());
+		};
+
+		if !sdf_config.sdf_version.supports_relative_to() {
+			return Transform::from_isometry(&self.sdf_model_pose()).write_sdf_pose(writer, None);
+		}
+
+		let joint = joint.upgrade().unwrap();
+		let joint = joint.read().unwrap();
+
+		match joint.joint_type() {
+			JointType::Floating | JointType::Planar => joint.transform().write_sdf_pose(
+				writer,
+				Some(&joint.parent_link().read().unwrap().name().display()),
+			),
+			_ => Transform::default().write_sdf_pose(writer, Some(&joint.name().display())),
+		}
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl ToSDF for Link {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let element = writer.create_element("link").with_attribute(Attribute {
+			key: QName(b"name"),
+			value: self.name().display().as_bytes().into(),
+		});
+		element.write_inner_content(|writer| -> Result<(), quick_xml::Error> {
+			self.write_sdf_pose(writer, sdf_config)?;
+
+			if let Some(inertial_data) = self.inertial() {
+				inertial_data.to_sdf(writer, sdf_config)?;
+			}
+
+			self.visuals
+				.iter()
+				.enumerate()
+				.map(|(index, visual)| {
+					visual.to_sdf_named(writer, sdf_config, &format!("visual_{}", index))
+				})
+				.process_results(|iter| iter.collect::<()>())?;
+
+			self.colliders
+				.iter()
+				.enumerate()
+				.map(|(index, collider)| {
+					collider.to_sdf_named(writer, sdf_config, &format!("collision_{}", index))
+				})
+				.process_results(|iter| iter.collect::<()>())?;
+
+			Ok(())
+		})?;
+
+		// Write joints
+		self.joints()
+			.iter()
+			.map(|joint| joint.read().unwrap().to_sdf(writer, sdf_config))
+			.process_results(|iter| iter.collect::<()>())?;
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl Link {
+	/// Checks if this `Link` is written as the MJCF `<worldbody>`.
+	///
+	/// This is the case for a root `Link` named `world`, since that is the reserved name of the world body in MJCF.
+	pub(crate) fn is_mjcf_world(&self) -> bool {
+		matches!(self.direct_parent, LinkParent::KinematicTree(_)) && self.name == "world"
+	}
+
+	/// Gets the meshes of this `Link`, together with the names of their MJCF `<mesh>` assets.
+	pub(crate) fn mjcf_meshes(&self) -> Vec<(String, MeshGeometry)> {
+		let name = self.name().display();
+
+		let visual_meshes = self
+			.visuals
+			.iter()
+			.enumerate()
+			.filter_map(
+				|(index, visual)| match visual.geometry().shape_container() {
+					GeometryShapeContainer::Mesh(mesh) => {
+						Some((mesh_asset_name(&name, "visual", index), mesh))
+					}
+					_ => None,
+				},
+			);
+		let collision_meshes = self
+			.colliders
+			.iter()
+			.enumerate()
+			.filter_map(
+				|(index, collider)| match collider.geometry().shape_container() {
+					GeometryShapeContainer::Mesh(mesh) => {
+						Some((mesh_asset_name(&name, "collision", index), mesh))
+					}
+					_ => None,
+				},
+			);
+
+		visual_meshes.chain(collision_meshes).collect()
+	}
+
+	/// Writes the `<geom>` elements and the child `<body>` elements of this `Link` in MJCF.
+	fn write_mjcf_content(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		mjcf_config: &crate::to_rdf::to_mjcf::MJCFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let name = self.name().display();
+
+		self.visuals
+			.iter()
+			.enumerate()
+			.map(|(index, visual)| {
+				visual.to_mjcf_geom(writer, &mesh_asset_name(&name, "visual", index))
+			})
+			.process_results(|iter| iter.collect::<()>())?;
+
+		self.colliders
+			.iter()
+			.enumerate()
+			.map(|(index, collider)| {
+				collider.to_mjcf_geom(
+					writer,
+					&mesh_asset_name(&name, "collision", index),
+					self.inertial().is_some(),
+				)
+			})
+			.process_results(|iter| iter.collect::<()>())?;
+
+		self.joints()
+			.iter()
+			.map(|joint| {
+				joint
+					.read()
+					.unwrap()
+					.child_link()
+					.read()
+					.unwrap()
+					.to_mjcf(writer, mjcf_config)
+			})
+			.process_results(|iter| iter.collect::<()>())?;
+
+		Ok(())
+	}
+}
+
+/// A `Link` is written as a `<body>`, which is placed at the frame of its parent `Joint`.
+///
+/// A root `Link` named `world` is written as the content of the `<worldbody>`, see [`to_mjcf`](crate::to_rdf::to_mjcf).
+#[cfg(feature = "mjcf")]
+impl ToMJCF for Link {
+	fn to_mjcf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		mjcf_config: &crate::to_rdf::to_mjcf::MJCFConfig,
+	) -> Result<(), quick_xml::Error> {
+		if self.is_mjcf_world() {
+			#[cfg(any(feature = "logging", test))]
+			if self.inertial().is_some() {
+				log::warn!("The world body can not have an inertial in MJCF, it is ignored");
+			}
+
+			return self.write_mjcf_content(writer, mjcf_config);
+		}
+
+		let parent_joint = match &self.direct_parent {
+			LinkParent::Joint(joint) => joint.upgrade(), // This should always be Some
+			LinkParent::KinematicTree(_) => None,
+		};
+
+		let element = writer
+			.create_element("body")
+			.with_attribute(Attribute {
+				key: QName(b"name"),
+				value: self.name().display().as_bytes().into(),
+			})
+			.with_attributes(
+				parent_joint
+					.as_ref()
+					.map(|joint| joint.read().unwrap().transform().mjcf_attributes())
+					.unwrap_or_default(),
+			);
+
+		let has_mjcf_joint = parent_joint
+			.as_ref()
+			.is_some_and(|joint| joint.read().unwrap().joint_type() != JointType::Fixed);
+
+		if !has_mjcf_joint
+			&& self.inertial().is_none()
+			&& self.visuals.is_empty()
+			&& self.colliders.is_empty()
+			&& self.child_joints.is_empty()
+		{
+			element.write_empty()?;
+			return Ok(());
+		}
+
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			if let Some(inertial) = self.inertial() {
+				inertial.to_mjcf(writer, mjcf_config)?;
+			}
+
+			if let Some(joint) = &parent_joint {
+				joint.read().unwrap().to_mjcf(writer, mjcf_config)?;
+			}
+
+			self.write_mjcf_content(writer, mjcf_config)
+		})?;
 
 		Ok(())
 	}
@@ -386,8 +654,11 @@ mod tests {
 	use test_log::test;
 
 	use crate::{
-		cluster_objects::KinematicInterface,
-		joint::{JointBuilder, JointType},
+		cluster_objects::{
+			kinematic_data_errors::{AddJointError, AttachChainError},
+			KinematicInterface,
+		},
+		joint::{joint_data::MimicBuilderData, JointBuilder, JointType},
 		link::{builder::LinkBuilder, link_parent::LinkParent, Link},
 	};
 
@@ -647,4 +918,73 @@ mod tests {
 			0
 		);
 	}
+
+	#[test]
+	fn attach_joint_chain_mimic() {
+		let tree = Link::builder("base").build_tree();
+		tree.get_root_link()
+			.try_write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("wrist", JointType::Fixed),
+				Link::builder("palm"),
+			)
+			.unwrap();
+		let palm = tree.get_link("palm").unwrap();
+		palm.try_write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("left", JointType::Continuous),
+				Link::builder("left_finger"),
+			)
+			.unwrap();
+		let mut right = JointBuilder::new("right", JointType::Continuous);
+		*right.mimic_mut() = Some(MimicBuilderData {
+			joint_name: "left".to_string(),
+			multiplier: Some(-1.),
+			offset: None,
+		});
+		palm.try_write()
+			.unwrap()
+			.try_attach_child(right, Link::builder("right_finger"))
+			.unwrap();
+
+		// The mimicked `Joint` is a part of the attached chain.
+		let other_tree = Link::builder("other_base").build_tree();
+		assert_eq!(
+			other_tree
+				.get_root_link()
+				.try_write()
+				.unwrap()
+				.attach_joint_chain(tree.yank_joint("wrist").unwrap()),
+			Ok(())
+		);
+		assert!(Arc::ptr_eq(
+			&other_tree
+				.get_joint("right")
+				.unwrap()
+				.try_read()
+				.unwrap()
+				.mimic()
+				.unwrap()
+				.joint
+				.upgrade()
+				.unwrap(),
+			&other_tree.get_joint("left").unwrap()
+		));
+
+		// The mimicked `Joint` is not a part of the tree.
+		let lonely_tree = Link::builder("lonely_base").build_tree();
+		assert_eq!(
+			lonely_tree
+				.get_root_link()
+				.try_write()
+				.unwrap()
+				.attach_joint_chain(other_tree.yank_joint("right").unwrap()),
+			Err(AttachChainError::Joint(AddJointError::UnknownMimic {
+				joint: "right".to_string(),
+				mimicked: "left".to_string()
+			}))
+		);
+	}
 }
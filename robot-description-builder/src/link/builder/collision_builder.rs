@@ -8,8 +8,18 @@ use crate::{
 		geometry::{GeometryInterface, GeometryShapeData},
 	},
 	transform::{Mirror, Transform},
+	Float,
 };
 
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{FromSDF, FromSDFContext, FromSDFError};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{
+	optional_transform, required_child, FromURDF, FromURDFContext, FromURDFError,
+};
+#[cfg(any(feature = "urdf", feature = "sdf"))]
+use crate::{from_rdf::xml_element::XMLElement, identifiers::escape_group_id_delimiters};
+
 /// The builder for `Collision` components.
 ///
 /// The `CollisionBuilder` is used to construct [`Collision`] elements of [`Links`](crate::link::Link).
@@ -116,7 +126,7 @@ impl CollisionBuilder {
 }
 
 impl Mirror for CollisionBuilder {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		Self {
 			name: self.name.as_ref().cloned(), // TODO: Rename?
 			transform: self
@@ -185,6 +195,44 @@ impl From<CollisionBuilder> for Collision {
 	}
 }
 
+#[cfg(feature = "urdf")]
+impl FromURDF for CollisionBuilder {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self {
+			name: element.attribute("name").map(escape_group_id_delimiters),
+			transform: optional_transform(element, urdf_context)?,
+			geometry: FromURDF::from_urdf(required_child(element, "geometry")?, urdf_context)?,
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads a `<collision>` element, which is read as `None` when its geometry is not supported.
+impl FromSDF for Option<CollisionBuilder> {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		use crate::from_rdf::from_sdf::required_child;
+
+		sdf_context.warn_unsupported(element, &["pose", "geometry"]);
+
+		let Some(geometry) = FromSDF::from_sdf(required_child(element, "geometry")?, sdf_context)?
+		else {
+			return Ok(None);
+		};
+
+		Ok(Some(CollisionBuilder {
+			name: element.attribute("name").map(escape_group_id_delimiters),
+			transform: sdf_context.transform(element)?,
+			geometry,
+		}))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::CollisionBuilder;
@@ -1,17 +1,26 @@
 use std::sync::{Arc, RwLock, Weak};
 
-use nalgebra::Matrix3;
+use nalgebra::{Isometry3, Matrix3};
 
 use super::{BuildLink, CollisionBuilder, VisualBuilder};
 use crate::{
 	cluster_objects::{kinematic_data_tree::KinematicDataTree, KinematicTree},
+	dynamics::MassProperties,
 	identifiers::GroupIDChanger,
 	joint::{BuildJointChain, Joint, JointBuilder},
 	link::{link_data, Link, LinkParent, LinkShapeData},
 	transform::Mirror,
 	utils::{ArcLock, WeakLock},
+	Float,
 };
 
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{FromSDF, FromSDFContext, FromSDFError};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{required_name, FromURDF, FromURDFContext, FromURDFError};
+#[cfg(any(feature = "urdf", feature = "sdf"))]
+use crate::from_rdf::xml_element::XMLElement;
+
 /// The builder for the `Link` type.
 ///
 /// The `LinkBuilder` is used to construct a [`Link`].
@@ -119,10 +128,79 @@ impl LinkBuilder {
 	pub fn inertial(&self) -> Option<&link_data::Inertial> {
 		self.intertial.as_ref()
 	}
+
+	/// Calculates the combined [`Inertial`](link_data::Inertial) of the `colliders` of this `LinkBuilder`,
+	/// as solids with the specified uniform `density` in kilograms per cubic meter.
+	///
+	/// Every collider is placed with its `transform`, after which they are combined about their common center of mass.
+	/// The axes of the resulting `Inertial` are the axes of the `Link`.
+	/// Overlapping colliders are counted twice and meshes are approximated by their bounding box.
+	///
+	/// Returns `None` if this `LinkBuilder` has no colliders.
+	pub fn colliders_inertial_from_density(&self, density: Float) -> Option<link_data::Inertial> {
+		(!self.colliders.is_empty()).then(|| {
+			self.colliders
+				.iter()
+				.map(|collider| {
+					MassProperties::from_inertial(
+						&link_data::Inertial {
+							transform: collider.transform,
+							..link_data::Inertial::from_density(collider.geometry.as_ref(), density)
+						},
+						&Isometry3::identity(),
+					)
+				})
+				.sum::<MassProperties>()
+				.to_inertial()
+		})
+	}
+
+	/// Calculates the combined [`Inertial`](link_data::Inertial) of the `colliders` of this `LinkBuilder`,
+	/// as solids with the same uniform density and the specified total `mass` in kilograms.
+	///
+	/// See [`LinkBuilder::colliders_inertial_from_density`] for more details.
+	/// Returns `None` if this `LinkBuilder` has no colliders or if their total volume is zero.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{
+	/// #     link_data::{geometry::{BoxGeometry, SphereGeometry}, Collision},
+	/// #     linkbuilding::LinkBuilder,
+	/// #     Transform,
+	/// # };
+	/// let link = LinkBuilder::new("dumbbell")
+	///     .add_collider(
+	///         Collision::builder(SphereGeometry::new(0.1))
+	///             .transformed(Transform::new_translation(0.5, 0., 0.)),
+	///     )
+	///     .add_collider(
+	///         Collision::builder(SphereGeometry::new(0.1))
+	///             .transformed(Transform::new_translation(-0.5, 0., 0.)),
+	///     );
+	///
+	/// let inertial = link.colliders_inertial_from_mass(2.).unwrap();
+	/// assert_eq!(inertial.mass, 2.);
+	/// // The center of mass is between the spheres.
+	/// assert_eq!(inertial.transform, None);
+	/// assert!(inertial.iyy > 0.5 && inertial.iyy == inertial.izz);
+	///
+	/// assert_eq!(LinkBuilder::new("empty").colliders_inertial_from_mass(2.), None);
+	/// ```
+	pub fn colliders_inertial_from_mass(&self, mass: Float) -> Option<link_data::Inertial> {
+		let volume: Float = self
+			.colliders
+			.iter()
+			.map(|collider| collider.geometry.volume())
+			.sum();
+
+		(volume > 0.)
+			.then(|| self.colliders_inertial_from_density(mass / volume))
+			.flatten()
+	}
 }
 
 impl Mirror for LinkBuilder {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		Self {
 			name: self.name.clone(), // TODO: rename mirrored
 			visuals: self
@@ -260,6 +338,64 @@ impl GroupIDChanger for LinkBuilder {
 	}
 }
 
+#[cfg(feature = "urdf")]
+/// Reads a `<link>` element, without any joints.
+impl FromURDF for LinkBuilder {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self {
+			name: required_name(element)?,
+			visuals: element
+				.children_named("visual")
+				.map(|visual| VisualBuilder::from_urdf(visual, urdf_context))
+				.collect::<Result<_, _>>()?,
+			colliders: element
+				.children_named("collision")
+				.map(|collision| CollisionBuilder::from_urdf(collision, urdf_context))
+				.collect::<Result<_, _>>()?,
+			intertial: element
+				.child("inertial")
+				.map(|inertial| link_data::Inertial::from_urdf(inertial, urdf_context))
+				.transpose()?,
+			joints: Vec::new(),
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads a `<link>` element, without any joints.
+///
+/// Visuals and collisions with an unsupported geometry are left out.
+impl FromSDF for LinkBuilder {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		sdf_context.warn_unsupported(element, &["pose", "inertial", "visual", "collision"]);
+
+		Ok(Self {
+			name: sdf_context.required_name(element)?,
+			visuals: element
+				.children_named("visual")
+				.map(|visual| Option::<VisualBuilder>::from_sdf(visual, sdf_context))
+				.filter_map(Result::transpose)
+				.collect::<Result<_, _>>()?,
+			colliders: element
+				.children_named("collision")
+				.map(|collision| Option::<CollisionBuilder>::from_sdf(collision, sdf_context))
+				.filter_map(Result::transpose)
+				.collect::<Result<_, _>>()?,
+			intertial: element
+				.child("inertial")
+				.map(|inertial| link_data::Inertial::from_sdf(inertial, sdf_context))
+				.transpose()?,
+			joints: Vec::new(),
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{BuildLink, LinkBuilder};
@@ -269,11 +405,63 @@ mod tests {
 			geometry::{BoxGeometry, CylinderGeometry, GeometryShapeData, SphereGeometry},
 			link_shape_data::LinkShapeData,
 		},
+		link_data::Inertial,
 		transform::Transform,
 	};
 	use test_log::test;
 	//TODO: Write test
 
+	#[test]
+	fn colliders_inertial() {
+		let link_builder = LinkBuilder::new("a Link");
+		assert_eq!(link_builder.colliders_inertial_from_density(1000.), None);
+		assert_eq!(link_builder.colliders_inertial_from_mass(1.), None);
+
+		let link_builder = LinkBuilder::new("a Link")
+			.add_collider(CollisionBuilder::new(BoxGeometry::new(1., 2., 3.)));
+		assert_eq!(
+			link_builder.colliders_inertial_from_density(2.),
+			Some(Inertial {
+				mass: 12.,
+				ixx: 13.,
+				iyy: 10.,
+				izz: 5.,
+				..Default::default()
+			})
+		);
+		assert_eq!(
+			link_builder.colliders_inertial_from_mass(12.),
+			link_builder.colliders_inertial_from_density(2.)
+		);
+
+		let link_builder = LinkBuilder::new("a Link")
+			.add_collider(
+				CollisionBuilder::new(BoxGeometry::new(1., 2., 3.))
+					.transformed(Transform::new_translation(1., 0., 0.)),
+			)
+			.add_collider(
+				CollisionBuilder::new(BoxGeometry::new(1., 2., 3.))
+					.transformed(Transform::new_translation(1., 0., 2.)),
+			);
+		let inertial = link_builder.colliders_inertial_from_mass(24.).unwrap();
+		assert_eq!(inertial.mass, 24.);
+		assert_eq!(
+			inertial.transform,
+			Some(Transform::new_translation(1., 0., 1.))
+		);
+		// Parallel axis theorem: 2 * (13 + 12 * 1²) and 2 * (10 + 12 * 1²)
+		assert!((inertial.ixx - 50.).abs() < 1e-4);
+		assert!((inertial.iyy - 44.).abs() < 1e-4);
+		assert!((inertial.izz - 10.).abs() < 1e-4);
+		assert!(
+			inertial.ixy.abs() < 1e-4 && inertial.ixz.abs() < 1e-4 && inertial.iyz.abs() < 1e-4
+		);
+
+		let link_builder = LinkBuilder::new("a Link")
+			.add_collider(CollisionBuilder::new(CylinderGeometry::new(1., 0.)));
+		assert_eq!(link_builder.colliders_inertial_from_mass(1.), None);
+	}
+
 	#[test]
 	fn get_shape_data() {
 		{
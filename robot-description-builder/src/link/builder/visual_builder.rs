@@ -9,8 +9,18 @@ use crate::{
 	},
 	material::MaterialDescriptor,
 	transform::{Mirror, Transform},
+	Float,
 };
 
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{FromSDF, FromSDFContext, FromSDFError};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{
+	optional_transform, required_child, FromURDF, FromURDFContext, FromURDFError,
+};
+#[cfg(any(feature = "urdf", feature = "sdf"))]
+use crate::{from_rdf::xml_element::XMLElement, identifiers::escape_group_id_delimiters};
+
 /// The builder for `Visual` components.
 ///
 /// The `VisualBuilder` is used to construct [`Visual`] elements of [`Links`](crate::link::Link).
@@ -132,7 +142,7 @@ impl VisualBuilder {
 }
 
 impl Mirror for VisualBuilder {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		Self {
 			name: self.name.as_ref().cloned(), // TODO: Rename?
 			transform: self
@@ -210,6 +220,53 @@ impl Clone for VisualBuilder {
 	}
 }
 
+#[cfg(feature = "urdf")]
+impl FromURDF for VisualBuilder {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self {
+			name: element.attribute("name").map(escape_group_id_delimiters),
+			transform: optional_transform(element, urdf_context)?,
+			geometry: FromURDF::from_urdf(required_child(element, "geometry")?, urdf_context)?,
+			material_description: element
+				.child("material")
+				.map(|material| MaterialDescriptor::from_urdf(material, urdf_context))
+				.transpose()?,
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads a `<visual>` element, which is read as `None` when its geometry is not supported.
+impl FromSDF for Option<VisualBuilder> {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		use crate::from_rdf::from_sdf::required_child;
+
+		sdf_context.warn_unsupported(element, &["pose", "geometry", "material"]);
+
+		let Some(geometry) = FromSDF::from_sdf(required_child(element, "geometry")?, sdf_context)?
+		else {
+			return Ok(None);
+		};
+
+		Ok(Some(VisualBuilder {
+			name: element.attribute("name").map(escape_group_id_delimiters),
+			transform: sdf_context.transform(element)?,
+			geometry,
+			material_description: element
+				.child("material")
+				.map(|material| Option::<MaterialDescriptor>::from_sdf(material, sdf_context))
+				.transpose()?
+				.flatten(),
+		}))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::VisualBuilder;
@@ -1,3 +1,7 @@
+#[cfg(feature = "mjcf")]
+use crate::to_rdf::to_mjcf::MJCF_COLLISION_GROUP;
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 #[cfg(feature = "xml")]
@@ -99,6 +103,88 @@ impl ToURDF for Collision {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl Collision {
+	/// Writes the `Collision` in SDFormat, using the `fallback_name` if it is unnamed.
+	///
+	/// SDFormat requires `<collision>` elements to be named, so the [`Link`](crate::link::Link) provides a unique `fallback_name`.
+	pub(crate) fn to_sdf_named(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+		fallback_name: &str,
+	) -> Result<(), quick_xml::Error> {
+		let name = self
+			.name()
+			.map(|name| name.display())
+			.unwrap_or_else(|| fallback_name.to_string());
+
+		let element = writer
+			.create_element("collision")
+			.with_attribute(Attribute {
+				key: QName(b"name"),
+				value: name.as_bytes().into(),
+			});
+
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			if let Some(transform) = self.transform() {
+				transform.to_sdf(writer, sdf_config)?
+			}
+
+			self.geometry()
+				.shape_container()
+				.to_sdf(writer, sdf_config)?;
+			Ok(())
+		})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl Collision {
+	/// Writes the `Collision` as a MJCF `<geom>`.
+	///
+	/// A mesh refers to the `<mesh>` asset named `mesh_asset_name`, which is provided by the [`Link`](crate::link::Link).
+	///
+	/// When the [`Link`](crate::link::Link) has an explicit [`Inertial`](crate::link_data::Inertial),
+	/// the `<geom>` gets a zero density, so MuJoCo does not add the mass of the `<geom>` to the `<body>`.
+	pub(crate) fn to_mjcf_geom(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		mesh_asset_name: &str,
+		explicit_inertial: bool,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("geom")
+			.with_attributes(
+				self.geometry()
+					.shape_container()
+					.mjcf_attributes(mesh_asset_name),
+			)
+			.with_attributes(
+				self.transform()
+					.map(Transform::mjcf_attributes)
+					.unwrap_or_default(),
+			)
+			.with_attribute(("group", MJCF_COLLISION_GROUP))
+			.with_attributes(explicit_inertial.then_some(("density", "0")))
+			.write_empty()?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl ToSDF for Collision {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		self.to_sdf_named(writer, sdf_config, "collision")
+	}
+}
+
 impl PartialEq for Collision {
 	fn eq(&self, other: &Self) -> bool {
 		self.name == other.name
@@ -119,7 +205,7 @@ impl Clone for Collision {
 
 #[cfg(test)]
 mod tests {
-	use std::f32::consts::PI;
+	use crate::float::consts::PI;
 	use test_log::test;
 
 	use crate::{
@@ -178,8 +264,10 @@ mod tests {
 			test_to_urdf_collision(
 				Collision::builder(SphereGeometry::new(3.))
 					.transformed(Transform::new((4., 6.78, 1.), (PI, 2. * PI, 0.))),
-				String::from(
-					r#"<collision><origin xyz="4 6.78 1" rpy="3.1415927 6.2831855 0"/><geometry><sphere radius="3"/></geometry></collision>"#,
+				format!(
+					r#"<collision><origin xyz="4 6.78 1" rpy="{} {} 0"/><geometry><sphere radius="3"/></geometry></collision>"#,
+					PI,
+					2. * PI,
 				),
 				&URDFConfig::default(),
 			);
@@ -17,7 +17,15 @@ pub use geometry_shape_data::GeometryShapeContainer;
 
 use std::fmt::Debug;
 
-use crate::transform::Mirror;
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{FromMJCF, FromMJCFContext, FromMJCFError, MJCFWarning};
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{FromSDF, FromSDFContext, FromSDFError, SDFWarning};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{FromURDF, FromURDFContext, FromURDFError};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
+use crate::{transform::Mirror, Float};
 
 // use self::geometry_shape_data::GeometryShapeContainer;
 
@@ -26,7 +34,7 @@ pub trait BoxedMirror {
 	/// Performs a `Mirror::mirrored` on a Boxed Implementor.
 	fn boxed_mirrored(
 		&self,
-		mirror_matrix: &Matrix3<f32>,
+		mirror_matrix: &Matrix3<Float>,
 	) -> Box<dyn GeometryInterface + Sync + Send>;
 }
 
@@ -36,7 +44,7 @@ where
 {
 	fn boxed_mirrored(
 		&self,
-		mirror_matrix: &Matrix3<f32>,
+		mirror_matrix: &Matrix3<Float>,
 	) -> Box<dyn GeometryInterface + Sync + Send> {
 		self.mirrored(mirror_matrix).boxed_clone()
 	}
@@ -46,23 +54,27 @@ where
 // LONGTERM-TODO: DECIDE IF `Box<dyn dyn GeometryInterface + Sync + Send>` should be replaced with [`GeometryShapeContainer`]
 pub trait GeometryInterface: Debug + BoxedMirror {
 	/// Provides the volume of a `Geometry`.
-	fn volume(&self) -> f32;
+	fn volume(&self) -> Float;
 	/// Provides the surface area of a `Geometry`.
-	fn surface_area(&self) -> f32;
+	fn surface_area(&self) -> Float;
+	/// Provides the inertia tensor of a solid `Geometry` with a uniform density and the specified `mass`.
+	///
+	/// The tensor is taken about the center of the `Geometry`, along its axes.
+	fn inertia(&self, mass: Float) -> Matrix3<Float>;
 	/// Allows for Cloning of Boxed Geometries.
 	///
 	/// This has similiar functionality to [`Clone::clone`] except that it allows items to be [`Box`ed](Box).
 	fn boxed_clone(&self) -> Box<dyn GeometryInterface + Sync + Send>;
 
 	/// Get's the untransformed boundingbox size of the geometry from it's center. (X, Y, Z).
-	fn bounding_box(&self) -> (f32, f32, f32);
+	fn bounding_box(&self) -> (Float, Float, Float);
 
 	/// Gets a `GeometryShapeContainer` of the current Shape.
 	fn shape_container(&self) -> GeometryShapeContainer;
 }
 
 impl Mirror for Box<dyn GeometryInterface + Sync + Send> {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		self.boxed_mirrored(mirror_matrix)
 	}
 }
@@ -81,3 +93,114 @@ impl From<&(dyn GeometryInterface + Sync + Send)> for Box<dyn GeometryInterface
 		value.boxed_clone()
 	}
 }
+
+#[cfg(feature = "urdf")]
+/// Reads the shape inside of a `<geometry>` element.
+impl FromURDF for Box<dyn GeometryInterface + Sync + Send> {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		match element.children.first() {
+			Some(shape) if shape.name == "box" => {
+				BoxGeometry::from_urdf(shape, urdf_context).map(Into::into)
+			}
+			Some(shape) if shape.name == "cylinder" => {
+				CylinderGeometry::from_urdf(shape, urdf_context).map(Into::into)
+			}
+			Some(shape) if shape.name == "sphere" => {
+				SphereGeometry::from_urdf(shape, urdf_context).map(Into::into)
+			}
+			Some(shape) if shape.name == "mesh" => {
+				MeshGeometry::from_urdf(shape, urdf_context).map(Into::into)
+			}
+			_ => Err(FromURDFError::UnsupportedGeometry { line: element.line }),
+		}
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads the shape inside of a `<geometry>` element.
+///
+/// An unsupported shape is reported as a warning and read as `None`.
+impl FromSDF for Option<Box<dyn GeometryInterface + Sync + Send>> {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		match element.children.first() {
+			Some(shape) if shape.name == "box" => {
+				BoxGeometry::from_sdf(shape, sdf_context).map(|geometry| Some(geometry.into()))
+			}
+			Some(shape) if shape.name == "cylinder" => {
+				CylinderGeometry::from_sdf(shape, sdf_context).map(|geometry| Some(geometry.into()))
+			}
+			Some(shape) if shape.name == "sphere" => {
+				SphereGeometry::from_sdf(shape, sdf_context).map(|geometry| Some(geometry.into()))
+			}
+			Some(shape) if shape.name == "mesh" => {
+				MeshGeometry::from_sdf(shape, sdf_context).map(|geometry| Some(geometry.into()))
+			}
+			Some(shape) => {
+				sdf_context.warn(SDFWarning::UnsupportedGeometry {
+					shape: shape.name.clone(),
+					line: shape.line,
+				});
+				Ok(None)
+			}
+			None => Err(FromSDFError::MissingElement {
+				element: element.name.clone(),
+				child: String::from("box"),
+				line: element.line,
+			}),
+		}
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads the shape of a `<geom>`, which is a `sphere` when no `type` is specified.
+///
+/// A `<geom>` with a `mesh` gets its shape from the `<mesh>` asset.
+/// An unsupported shape is reported as a warning and read as `None`.
+impl FromMJCF for Option<Box<dyn GeometryInterface + Sync + Send>> {
+	fn from_mjcf(
+		element: &XMLElement,
+		mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		use crate::from_rdf::from_mjcf::{invalid_value, required_attribute};
+
+		let geom_type = element
+			.attribute("type")
+			.or_else(|| element.attribute("mesh").map(|_| "mesh"))
+			.unwrap_or("sphere");
+
+		match geom_type {
+			"box" => {
+				BoxGeometry::from_mjcf(element, mjcf_context).map(|geometry| Some(geometry.into()))
+			}
+			"cylinder" => CylinderGeometry::from_mjcf(element, mjcf_context)
+				.map(|geometry| Some(geometry.into())),
+			"sphere" => SphereGeometry::from_mjcf(element, mjcf_context)
+				.map(|geometry| Some(geometry.into())),
+			"mesh" => {
+				let name = required_attribute(element, "mesh")?;
+				let mesh = mjcf_context.meshes.get(name).cloned().ok_or_else(|| {
+					FromMJCFError::UnknownAsset {
+						element: element.name.clone(),
+						asset: name.to_string(),
+						line: element.line,
+					}
+				})?;
+				MeshGeometry::from_mjcf(&mesh, mjcf_context).map(|geometry| Some(geometry.into()))
+			}
+			"plane" | "hfield" | "capsule" | "ellipsoid" | "sdf" => {
+				mjcf_context.warn(MJCFWarning::UnsupportedGeometry {
+					geom_type: geom_type.to_string(),
+					line: element.line,
+				});
+				Ok(None)
+			}
+			_ => Err(invalid_value(element, "type")),
+		}
+	}
+}
@@ -1,8 +1,26 @@
+use nalgebra::{Matrix3, Vector3};
+
 use super::{GeometryInterface, GeometryShapeContainer};
-use crate::transform::Mirror;
+use crate::{transform::Mirror, Float};
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{
+	required_leading_float_array, FromMJCF, FromMJCFContext, FromMJCFError,
+};
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{
+	content_vector3, required_child, FromSDF, FromSDFContext, FromSDFError,
+};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{required_vector3, FromURDF, FromURDFContext, FromURDFError};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -14,16 +32,16 @@ use quick_xml::{events::attributes::Attribute, name::QName};
 pub struct BoxGeometry {
 	// TODO: Figure out correct field names
 	/// The side-length in the X-direction.
-	pub side1: f32,
+	pub side1: Float,
 	/// The side-length in the Y-direction.
-	pub side2: f32,
+	pub side2: Float,
 	/// The side-length in the Z-direction.
-	pub side3: f32,
+	pub side3: Float,
 }
 
 impl BoxGeometry {
 	/// Creates a new `BoxGeometry` with the specified side lengths.
-	pub fn new(side1: f32, side2: f32, side3: f32) -> Self {
+	pub fn new(side1: Float, side2: Float, side3: Float) -> Self {
 		// TODO: REPLACE PARAMETER NAMES
 		Self {
 			side1,
@@ -34,19 +52,28 @@ impl BoxGeometry {
 }
 
 impl GeometryInterface for BoxGeometry {
-	fn volume(&self) -> f32 {
+	fn volume(&self) -> Float {
 		self.side1 * self.side2 * self.side3
 	}
 
-	fn surface_area(&self) -> f32 {
-		2f32 * (self.side1 * self.side2 + self.side1 * self.side3 + self.side2 * self.side3)
+	fn surface_area(&self) -> Float {
+		2. * (self.side1 * self.side2 + self.side1 * self.side3 + self.side2 * self.side3)
+	}
+
+	fn inertia(&self, mass: Float) -> Matrix3<Float> {
+		let (x, y, z) = (
+			self.side1 * self.side1,
+			self.side2 * self.side2,
+			self.side3 * self.side3,
+		);
+		Matrix3::from_diagonal(&Vector3::new(y + z, x + z, x + y)) * (mass / 12.)
 	}
 
 	fn boxed_clone(&self) -> Box<dyn GeometryInterface + Sync + Send> {
 		Box::new(self.clone())
 	}
 
-	fn bounding_box(&self) -> (f32, f32, f32) {
+	fn bounding_box(&self) -> (Float, Float, Float) {
 		(self.side1, self.side2, self.side3)
 	}
 
@@ -56,7 +83,7 @@ impl GeometryInterface for BoxGeometry {
 }
 
 impl Mirror for BoxGeometry {
-	fn mirrored(&self, _mirror_matrix: &nalgebra::Matrix3<f32>) -> Self {
+	fn mirrored(&self, _mirror_matrix: &nalgebra::Matrix3<Float>) -> Self {
 		self.clone()
 	}
 }
@@ -85,6 +112,92 @@ impl ToURDF for BoxGeometry {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for BoxGeometry {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let element = writer.create_element("geometry");
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			writer.create_element("box").write_inner_content(
+				|writer| -> quick_xml::Result<()> {
+					writer
+						.create_element("size")
+						.write_text_content(BytesText::new(&format!(
+							"{} {} {}",
+							self.side1, self.side2, self.side3
+						)))?;
+					Ok(())
+				},
+			)?;
+			Ok(())
+		})?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl BoxGeometry {
+	/// Gets the `type` and `size` attributes of a MJCF `<geom>`.
+	///
+	/// The `size` of a MJCF box consists of the half sizes of the sides.
+	pub(crate) fn mjcf_attributes(&self) -> [Attribute<'static>; 2] {
+		[
+			Attribute {
+				key: QName(b"type"),
+				value: b"box".as_slice().into(),
+			},
+			Attribute {
+				key: QName(b"size"),
+				value: format!(
+					"{} {} {}",
+					self.side1 / 2.,
+					self.side2 / 2.,
+					self.side3 / 2.
+				)
+				.into_bytes()
+				.into(),
+			},
+		]
+	}
+}
+
+#[cfg(feature = "urdf")]
+impl FromURDF for BoxGeometry {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let (side1, side2, side3) = required_vector3(element, "size")?;
+		Ok(Self::new(side1, side2, side3))
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl FromSDF for BoxGeometry {
+	fn from_sdf(
+		element: &XMLElement,
+		_sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		let (side1, side2, side3) = content_vector3(required_child(element, "size")?)?;
+		Ok(Self::new(side1, side2, side3))
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads the half-sizes from the `size` of a `<geom>`.
+impl FromMJCF for BoxGeometry {
+	fn from_mjcf(
+		element: &XMLElement,
+		_mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		let [x, y, z] = required_leading_float_array(element, "size")?;
+		Ok(Self::new(x * 2., y * 2., z * 2.))
+	}
+}
+
 impl From<BoxGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 	fn from(value: BoxGeometry) -> Self {
 		Box::new(value)
@@ -93,6 +206,7 @@ impl From<BoxGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 
 #[cfg(test)]
 mod tests {
+	use nalgebra::Matrix3;
 	#[cfg(feature = "xml")]
 	use std::io::Seek;
 	use test_log::test;
@@ -100,6 +214,8 @@ mod tests {
 	use crate::link::geometry::{
 		box_geometry::BoxGeometry, geometry_shape_data::GeometryShapeContainer, GeometryInterface,
 	};
+	#[cfg(feature = "sdf")]
+	use crate::to_rdf::to_sdf::{SDFConfig, ToSDF};
 	#[cfg(feature = "urdf")]
 	use crate::to_rdf::to_urdf::{ToURDF, URDFConfig};
 
@@ -119,6 +235,18 @@ mod tests {
 		assert_eq!(BoxGeometry::new(4.5, 20.0, 100.0).surface_area(), 5080.);
 	}
 
+	#[test]
+	fn inertia() {
+		assert_eq!(
+			BoxGeometry::new(1.0, 2.0, 3.0).inertia(12.),
+			Matrix3::from_diagonal(&[13., 10., 5.].into())
+		);
+		assert_eq!(
+			BoxGeometry::new(2.0, 2.0, 2.0).inertia(3.),
+			Matrix3::identity() * 2.
+		);
+	}
+
 	#[test]
 	fn boxed_clone() {
 		assert_eq!(
@@ -235,4 +363,20 @@ mod tests {
 			);
 		}
 	}
+
+	#[cfg(feature = "sdf")]
+	#[test]
+	fn to_sdf() {
+		let mut writer = quick_xml::Writer::new(std::io::Cursor::new(Vec::new()));
+		assert!(BoxGeometry::new(4.5, 20.0, 100.0)
+			.to_sdf(&mut writer, &SDFConfig::default())
+			.is_ok());
+
+		writer.get_mut().rewind().unwrap();
+
+		assert_eq!(
+			std::io::read_to_string(writer.into_inner()).unwrap(),
+			String::from(r#"<geometry><box><size>4.5 20 100</size></box></geometry>"#)
+		);
+	}
 }
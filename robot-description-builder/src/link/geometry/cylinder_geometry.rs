@@ -1,9 +1,30 @@
+use nalgebra::{Matrix3, Vector3};
+
 use super::{GeometryInterface, GeometryShapeContainer};
-use std::f32::consts::{PI, TAU};
+use crate::{
+	float::consts::{PI, TAU},
+	Float,
+};
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{
+	required_leading_float_array, FromMJCF, FromMJCFContext, FromMJCFError,
+};
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{
+	content_float, required_child, FromSDF, FromSDFContext, FromSDFError,
+};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{required_float, FromURDF, FromURDFContext, FromURDFError};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 use crate::transform::Mirror;
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -16,32 +37,41 @@ pub struct CylinderGeometry {
 	/// The radius of the Cylinder.
 	///
 	/// Around the Z-axis for URDF.
-	pub radius: f32,
+	pub radius: Float,
 	/// The Length of the Cylinder.
-	pub length: f32,
+	pub length: Float,
 }
 
 impl CylinderGeometry {
 	/// Creates a new `CylinderGeometry` with the specified `radius` and `length`.
-	pub fn new(radius: f32, length: f32) -> Self {
+	pub fn new(radius: Float, length: Float) -> Self {
 		Self { radius, length }
 	}
 }
 
 impl GeometryInterface for CylinderGeometry {
-	fn volume(&self) -> f32 {
+	fn volume(&self) -> Float {
 		self.radius * self.radius * PI * self.length
 	}
 
-	fn surface_area(&self) -> f32 {
-		2f32 * (self.radius * self.radius * PI) + self.length * self.radius * TAU
+	fn surface_area(&self) -> Float {
+		2. * (self.radius * self.radius * PI) + self.length * self.radius * TAU
+	}
+
+	fn inertia(&self, mass: Float) -> Matrix3<Float> {
+		let radial = mass * (3. * self.radius * self.radius + self.length * self.length) / 12.;
+		Matrix3::from_diagonal(&Vector3::new(
+			radial,
+			radial,
+			mass * self.radius * self.radius / 2.,
+		))
 	}
 
 	fn boxed_clone(&self) -> Box<dyn GeometryInterface + Sync + Send> {
 		Box::new(self.clone())
 	}
 
-	fn bounding_box(&self) -> (f32, f32, f32) {
+	fn bounding_box(&self) -> (Float, Float, Float) {
 		(2. * self.radius, 2. * self.radius, self.length)
 	}
 
@@ -51,7 +81,7 @@ impl GeometryInterface for CylinderGeometry {
 }
 
 impl Mirror for CylinderGeometry {
-	fn mirrored(&self, _mirror_matrix: &nalgebra::Matrix3<f32>) -> Self {
+	fn mirrored(&self, _mirror_matrix: &nalgebra::Matrix3<Float>) -> Self {
 		self.clone()
 	}
 }
@@ -82,6 +112,106 @@ impl ToURDF for CylinderGeometry {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for CylinderGeometry {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let element = writer.create_element("geometry");
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			writer.create_element("cylinder").write_inner_content(
+				|writer| -> quick_xml::Result<()> {
+					writer
+						.create_element("radius")
+						.write_text_content(BytesText::new(&self.radius.to_string()))?;
+					writer
+						.create_element("length")
+						.write_text_content(BytesText::new(&self.length.to_string()))?;
+					Ok(())
+				},
+			)?;
+			Ok(())
+		})?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl CylinderGeometry {
+	/// Gets the `type` and `size` attributes of a MJCF `<geom>`.
+	///
+	/// The `size` of a MJCF cylinder consists of the radius and the half length.
+	pub(crate) fn mjcf_attributes(&self) -> [Attribute<'static>; 2] {
+		[
+			Attribute {
+				key: QName(b"type"),
+				value: b"cylinder".as_slice().into(),
+			},
+			Attribute {
+				key: QName(b"size"),
+				value: format!("{} {}", self.radius, self.length / 2.)
+					.into_bytes()
+					.into(),
+			},
+		]
+	}
+}
+
+#[cfg(feature = "urdf")]
+impl FromURDF for CylinderGeometry {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self::new(
+			required_float(element, "radius")?,
+			required_float(element, "length")?,
+		))
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl FromSDF for CylinderGeometry {
+	fn from_sdf(
+		element: &XMLElement,
+		_sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		Ok(Self::new(
+			content_float(required_child(element, "radius")?)?,
+			content_float(required_child(element, "length")?)?,
+		))
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads the radius and the half-length from the `size` of a `<geom>`.
+///
+/// When the `<geom>` is specified by `fromto`, the length is the distance between the two points instead.
+impl FromMJCF for CylinderGeometry {
+	fn from_mjcf(
+		element: &XMLElement,
+		_mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		use crate::from_rdf::from_mjcf::optional_float_array;
+
+		match optional_float_array(element, "fromto")? {
+			Some([x1, y1, z1, x2, y2, z2]) => {
+				let [radius] = required_leading_float_array(element, "size")?;
+				Ok(Self::new(
+					radius,
+					((x2 - x1).powi(2) + (y2 - y1).powi(2) + (z2 - z1).powi(2)).sqrt(),
+				))
+			}
+			None => {
+				let [radius, half_length] = required_leading_float_array(element, "size")?;
+				Ok(Self::new(radius, half_length * 2.))
+			}
+		}
+	}
+}
+
 impl From<CylinderGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 	fn from(value: CylinderGeometry) -> Self {
 		Box::new(value)
@@ -90,7 +220,8 @@ impl From<CylinderGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 
 #[cfg(test)]
 mod tests {
-	use std::f32::consts::PI;
+	use crate::float::consts::{PI, TAU};
+	use nalgebra::Matrix3;
 	#[cfg(feature = "xml")]
 	use std::io::Seek;
 	use test_log::test;
@@ -100,6 +231,8 @@ mod tests {
 		GeometryInterface,
 	};
 
+	#[cfg(feature = "sdf")]
+	use crate::to_rdf::to_sdf::{SDFConfig, ToSDF};
 	#[cfg(feature = "urdf")]
 	use crate::to_rdf::to_urdf::{ToURDF, URDFConfig};
 
@@ -108,17 +241,35 @@ mod tests {
 		assert_eq!(CylinderGeometry::new(1.0, 1.0).volume(), PI);
 		assert_eq!(CylinderGeometry::new(2.0, 3.0).volume(), PI * 12.);
 		assert_eq!(CylinderGeometry::new(9.0, 20.0).volume(), PI * 1620.);
-		assert_eq!(CylinderGeometry::new(4.5, 75.35).volume(), PI * 1525.8375);
+		assert_eq!(
+			CylinderGeometry::new(4.5, 75.35).volume(),
+			4.5 * 4.5 * PI * 75.35
+		);
 	}
 
 	#[test]
 	fn surface_area() {
 		assert_eq!(CylinderGeometry::new(1.0, 1.0).surface_area(), PI * 4.);
 		assert_eq!(CylinderGeometry::new(2.0, 3.0).surface_area(), PI * 20.);
-		assert_eq!(CylinderGeometry::new(9.0, 20.0).surface_area(), PI * 522.);
+		assert_eq!(
+			CylinderGeometry::new(9.0, 20.0).surface_area(),
+			2. * (9. * 9. * PI) + 20. * 9. * TAU
+		);
 		assert_eq!(
 			CylinderGeometry::new(4.5, 75.35).surface_area(),
-			(std::f64::consts::PI * 718.65) as f32
+			2. * (4.5 * 4.5 * PI) + 75.35 * 4.5 * TAU
+		);
+	}
+
+	#[test]
+	fn inertia() {
+		assert_eq!(
+			CylinderGeometry::new(1.0, 2.0).inertia(12.),
+			Matrix3::from_diagonal(&[7., 7., 6.].into())
+		);
+		assert_eq!(
+			CylinderGeometry::new(2.0, 0.0).inertia(1.),
+			Matrix3::from_diagonal(&[1., 1., 2.].into())
 		);
 	}
 
@@ -238,4 +389,22 @@ mod tests {
 			);
 		}
 	}
+
+	#[cfg(feature = "sdf")]
+	#[test]
+	fn to_sdf() {
+		let mut writer = quick_xml::Writer::new(std::io::Cursor::new(Vec::new()));
+		assert!(CylinderGeometry::new(1.5, 20.0)
+			.to_sdf(&mut writer, &SDFConfig::default())
+			.is_ok());
+
+		writer.get_mut().rewind().unwrap();
+
+		assert_eq!(
+			std::io::read_to_string(writer.into_inner()).unwrap(),
+			String::from(
+				r#"<geometry><cylinder><radius>1.5</radius><length>20</length></cylinder></geometry>"#
+			)
+		);
+	}
 }
@@ -1,8 +1,10 @@
 use super::{
 	mesh_geometry::MeshGeometry, BoxGeometry, CylinderGeometry, GeometryInterface, SphereGeometry,
 };
-use crate::transform::Transform;
+use crate::{transform::Transform, Float};
 
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 
@@ -20,7 +22,7 @@ pub struct GeometryShapeData {
 
 impl GeometryShapeData {
 	/// X Y Z Bounding box sizes from center of the origin of the shape.
-	pub fn bounding_box(&self) -> (f32, f32, f32) {
+	pub fn bounding_box(&self) -> (Float, Float, Float) {
 		match &self.geometry {
 			GeometryShapeContainer::Box(g) => g.bounding_box(),
 			GeometryShapeContainer::Cylinder(g) => g.bounding_box(),
@@ -62,6 +64,48 @@ impl ToURDF for GeometryShapeContainer {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for GeometryShapeContainer {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		match self {
+			GeometryShapeContainer::Box(box_geometry) => box_geometry.to_sdf(writer, sdf_config),
+			GeometryShapeContainer::Cylinder(cylinder_geometry) => {
+				cylinder_geometry.to_sdf(writer, sdf_config)
+			}
+			GeometryShapeContainer::Sphere(sphere_geometry) => {
+				sphere_geometry.to_sdf(writer, sdf_config)
+			}
+			GeometryShapeContainer::Mesh(mesh_geometry) => mesh_geometry.to_sdf(writer, sdf_config),
+		}
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl GeometryShapeContainer {
+	/// Gets the attributes, which describe the shape of a MJCF `<geom>`.
+	///
+	/// A mesh refers to the `<mesh>` asset named `mesh_asset_name`.
+	pub(crate) fn mjcf_attributes<'a>(
+		&self,
+		mesh_asset_name: &'a str,
+	) -> [quick_xml::events::attributes::Attribute<'a>; 2] {
+		match self {
+			GeometryShapeContainer::Box(box_geometry) => box_geometry.mjcf_attributes(),
+			GeometryShapeContainer::Cylinder(cylinder_geometry) => {
+				cylinder_geometry.mjcf_attributes()
+			}
+			GeometryShapeContainer::Sphere(sphere_geometry) => sphere_geometry.mjcf_attributes(),
+			GeometryShapeContainer::Mesh(mesh_geometry) => {
+				mesh_geometry.mjcf_attributes(mesh_asset_name)
+			}
+		}
+	}
+}
+
 impl From<BoxGeometry> for GeometryShapeContainer {
 	fn from(value: BoxGeometry) -> Self {
 		Self::Box(value)
@@ -1,10 +1,26 @@
-use super::{GeometryInterface, GeometryShapeContainer};
-use crate::{identifiers::GroupID, transform::Mirror};
+use super::{BoxGeometry, GeometryInterface, GeometryShapeContainer};
+use crate::{identifiers::GroupID, transform::Mirror, Float};
 use itertools::Itertools;
 use nalgebra::{vector, Matrix3};
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{FromMJCF, FromMJCFContext, FromMJCFError};
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{
+	content_vector3, required_child, FromSDF, FromSDFContext, FromSDFError,
+};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{
+	optional_vector3, required_attribute, FromURDF, FromURDFContext, FromURDFError,
+};
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::{from_rdf::xml_element::XMLElement, identifiers::escape_group_id_delimiters};
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -22,12 +38,12 @@ pub struct MeshGeometry {
 	/// This is the size of the bounding box of the mesh at the current [`scale`](MeshGeometry::scale).
 	///
 	/// The bounding box is expected to be measured such that the center of the bounding box is at the origin.
-	pub bounding_box: (f32, f32, f32),
+	pub bounding_box: (Float, Float, Float),
 	/// The desired scale off the mesh.
 	///
 	/// # Important
 	/// If this is non-zero you need to pre-calculate the scaled [`bounding_box`](MeshGeometry::bounding_box).
-	pub scale: (f32, f32, f32),
+	pub scale: (Float, Float, Float),
 }
 
 impl MeshGeometry {
@@ -39,8 +55,8 @@ impl MeshGeometry {
 	/// - [`scale`](MeshGeometry::scale) is either specified or defaults to `(1., 1., 1.)`.
 	pub fn new(
 		path: impl Into<String>,
-		bounding_box: (f32, f32, f32),
-		scale: Option<(f32, f32, f32)>,
+		bounding_box: (Float, Float, Float),
+		scale: Option<(Float, Float, Float)>,
 	) -> Self {
 		Self {
 			path: path.into(),
@@ -52,22 +68,28 @@ impl MeshGeometry {
 
 impl GeometryInterface for MeshGeometry {
 	/// The volume of a mesh is approximated by its boundingbox
-	fn volume(&self) -> f32 {
+	fn volume(&self) -> Float {
 		self.bounding_box.0 * self.bounding_box.1 * self.bounding_box.2
 	}
 
 	/// The surface area of a mesh is approximated by its boundingbox
-	fn surface_area(&self) -> f32 {
+	fn surface_area(&self) -> Float {
 		2. * (self.bounding_box.0 * self.bounding_box.1
 			+ self.bounding_box.1 * self.bounding_box.2
 			+ self.bounding_box.0 * self.bounding_box.2)
 	}
 
+	/// The inertia of a mesh is approximated by its boundingbox
+	fn inertia(&self, mass: Float) -> Matrix3<Float> {
+		let (x, y, z) = self.bounding_box;
+		BoxGeometry::new(x, y, z).inertia(mass)
+	}
+
 	fn boxed_clone(&self) -> Box<dyn GeometryInterface + Sync + Send> {
 		Box::new(self.clone())
 	}
 
-	fn bounding_box(&self) -> (f32, f32, f32) {
+	fn bounding_box(&self) -> (Float, Float, Float) {
 		self.bounding_box
 	}
 
@@ -78,7 +100,7 @@ impl GeometryInterface for MeshGeometry {
 
 // TODO: ADD MIRROR TEST
 impl Mirror for MeshGeometry {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		// TODO: Add Mirrorable Specifier
 		// if let Some(group_id @ ("L" | "R" | "N"))  = self.path.get_group_id() {
 
@@ -124,6 +146,140 @@ impl ToURDF for MeshGeometry {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for MeshGeometry {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let element = writer.create_element("geometry");
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			writer.create_element("mesh").write_inner_content(
+				|writer| -> quick_xml::Result<()> {
+					// Apply GroupID escaping to allow for mirror from `mesh_L` -> `mesh_R`
+					writer
+						.create_element("uri")
+						.write_text_content(BytesText::new(&self.path.display()))?;
+					writer
+						.create_element("scale")
+						.write_text_content(BytesText::new(&format!(
+							"{} {} {}",
+							self.scale.0, self.scale.1, self.scale.2
+						)))?;
+					Ok(())
+				},
+			)?;
+			Ok(())
+		})?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl MeshGeometry {
+	/// Gets the `type` and `mesh` attributes of a MJCF `<geom>`, which refers to the `<mesh>` asset named `asset_name`.
+	pub(crate) fn mjcf_attributes<'a>(&self, asset_name: &'a str) -> [Attribute<'a>; 2] {
+		[
+			Attribute {
+				key: QName(b"type"),
+				value: b"mesh".as_slice().into(),
+			},
+			Attribute {
+				key: QName(b"mesh"),
+				value: asset_name.as_bytes().into(),
+			},
+		]
+	}
+
+	/// Writes the MJCF `<mesh>` asset named `asset_name` for this `MeshGeometry`.
+	pub(crate) fn write_mjcf_asset(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		asset_name: &str,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("mesh")
+			.with_attribute(Attribute {
+				key: QName(b"name"),
+				value: asset_name.as_bytes().into(),
+			})
+			.with_attribute(Attribute {
+				key: QName(b"file"),
+				value: self.path.display().as_bytes().into(),
+			})
+			.with_attribute(Attribute {
+				key: QName(b"scale"),
+				value: format!("{} {} {}", self.scale.0, self.scale.1, self.scale.2)
+					.as_bytes()
+					.into(),
+			})
+			.write_empty()?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "urdf")]
+/// The `bounding_box` can not be determined from a URDF description, so it is set to `(0, 0, 0)`.
+impl FromURDF for MeshGeometry {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self::new(
+			escape_group_id_delimiters(required_attribute(element, "filename")?),
+			(0., 0., 0.),
+			optional_vector3(element, "scale")?,
+		))
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// The `bounding_box` can not be determined from a SDFormat description, so it is set to `(0, 0, 0)`.
+///
+/// Selecting a `<submesh>` is not supported, it is reported as a warning.
+impl FromSDF for MeshGeometry {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		sdf_context.warn_unsupported(element, &["uri", "scale"]);
+
+		Ok(Self::new(
+			escape_group_id_delimiters(&required_child(element, "uri")?.text),
+			(0., 0., 0.),
+			element.child("scale").map(content_vector3).transpose()?,
+		))
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads a `<mesh>` asset, with its `file` relative to the `meshdir` of the `<compiler>`.
+///
+/// The `bounding_box` can not be determined from a MJCF description, so it is set to `(0, 0, 0)`.
+impl FromMJCF for MeshGeometry {
+	fn from_mjcf(
+		element: &XMLElement,
+		mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		use crate::from_rdf::from_mjcf::{optional_float_array, required_attribute};
+
+		let file = required_attribute(element, "file")?;
+		let path = match &mjcf_context.meshdir {
+			Some(meshdir) if !std::path::Path::new(file).is_absolute() => {
+				format!("{meshdir}/{file}")
+			}
+			_ => file.to_string(),
+		};
+
+		Ok(Self::new(
+			escape_group_id_delimiters(&path),
+			(0., 0., 0.),
+			optional_float_array(element, "scale")?.map(|[x, y, z]| (x, y, z)),
+		))
+	}
+}
+
 impl From<MeshGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 	fn from(value: MeshGeometry) -> Self {
 		Box::new(value)
@@ -132,11 +288,14 @@ impl From<MeshGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 
 #[cfg(test)]
 mod tests {
+	use nalgebra::Matrix3;
 	#[cfg(feature = "xml")]
 	use std::io::Seek;
 	use test_log::test;
 
 	use super::{GeometryInterface, GeometryShapeContainer, MeshGeometry};
+	#[cfg(feature = "sdf")]
+	use crate::to_rdf::to_sdf::{SDFConfig, ToSDF};
 	#[cfg(feature = "urdf")]
 	use crate::to_rdf::to_urdf::{ToURDF, URDFConfig};
 
@@ -220,6 +379,20 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn inertia() {
+		// The inertia is approximated by the bounding box.
+		assert_eq!(
+			MeshGeometry::new(
+				"package://my-package/description/meshes/mesh_[[L]].dae",
+				(1., 5., 1.),
+				None
+			)
+			.inertia(12.),
+			Matrix3::from_diagonal(&[26., 2., 26.].into())
+		);
+	}
+
 	#[test]
 	fn boxed_clone() {
 		assert_eq!(
@@ -456,4 +629,26 @@ mod tests {
 			);
 		}
 	}
+
+	#[cfg(feature = "sdf")]
+	#[test]
+	fn to_sdf() {
+		let mut writer = quick_xml::Writer::new(std::io::Cursor::new(Vec::new()));
+		assert!(MeshGeometry::new(
+			"package://my-package/description/meshes/mesh_[[L]].dae",
+			(1., 5., 1.),
+			Some((2., 1., 1.))
+		)
+		.to_sdf(&mut writer, &SDFConfig::default())
+		.is_ok());
+
+		writer.get_mut().rewind().unwrap();
+
+		assert_eq!(
+			std::io::read_to_string(writer.into_inner()).unwrap(),
+			String::from(
+				r#"<geometry><mesh><uri>package://my-package/description/meshes/mesh_L.dae</uri><scale>2 1 1</scale></mesh></geometry>"#
+			)
+		);
+	}
 }
@@ -1,9 +1,30 @@
+use nalgebra::Matrix3;
+
 use super::{GeometryInterface, GeometryShapeContainer};
-use crate::transform::Mirror;
-use std::f32::consts::{FRAC_PI_3, PI};
+use crate::{
+	float::consts::{FRAC_PI_3, PI},
+	transform::Mirror,
+	Float,
+};
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{
+	required_leading_float_array, FromMJCF, FromMJCFContext, FromMJCFError,
+};
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{
+	content_float, required_child, FromSDF, FromSDFContext, FromSDFError,
+};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{required_float, FromURDF, FromURDFContext, FromURDFError};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -14,30 +35,34 @@ use quick_xml::{events::attributes::Attribute, name::QName};
 #[derive(Debug, PartialEq, Clone)]
 pub struct SphereGeometry {
 	/// The radius of the Sphere.
-	pub radius: f32,
+	pub radius: Float,
 }
 
 impl SphereGeometry {
 	/// Creates a new `SphereGeometry` with the specified `radius`.
-	pub fn new(radius: f32) -> Self {
+	pub fn new(radius: Float) -> Self {
 		Self { radius }
 	}
 }
 
 impl GeometryInterface for SphereGeometry {
-	fn volume(&self) -> f32 {
-		4f32 * FRAC_PI_3 * self.radius * self.radius * self.radius
+	fn volume(&self) -> Float {
+		4. * FRAC_PI_3 * self.radius * self.radius * self.radius
+	}
+
+	fn surface_area(&self) -> Float {
+		4. * PI * self.radius * self.radius
 	}
 
-	fn surface_area(&self) -> f32 {
-		4f32 * PI * self.radius * self.radius
+	fn inertia(&self, mass: Float) -> Matrix3<Float> {
+		Matrix3::identity() * (0.4 * mass * self.radius * self.radius)
 	}
 
 	fn boxed_clone(&self) -> Box<dyn GeometryInterface + Sync + Send> {
 		Box::new(self.clone())
 	}
 
-	fn bounding_box(&self) -> (f32, f32, f32) {
+	fn bounding_box(&self) -> (Float, Float, Float) {
 		let diameter = 2. * self.radius;
 		(diameter, diameter, diameter)
 	}
@@ -48,7 +73,7 @@ impl GeometryInterface for SphereGeometry {
 }
 
 impl Mirror for SphereGeometry {
-	fn mirrored(&self, _mirror_matrix: &nalgebra::Matrix3<f32>) -> Self {
+	fn mirrored(&self, _mirror_matrix: &nalgebra::Matrix3<Float>) -> Self {
 		self.clone()
 	}
 }
@@ -75,6 +100,80 @@ impl ToURDF for SphereGeometry {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for SphereGeometry {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let element = writer.create_element("geometry");
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			writer.create_element("sphere").write_inner_content(
+				|writer| -> quick_xml::Result<()> {
+					writer
+						.create_element("radius")
+						.write_text_content(BytesText::new(&self.radius.to_string()))?;
+					Ok(())
+				},
+			)?;
+			Ok(())
+		})?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl SphereGeometry {
+	/// Gets the `type` and `size` attributes of a MJCF `<geom>`.
+	pub(crate) fn mjcf_attributes(&self) -> [Attribute<'static>; 2] {
+		[
+			Attribute {
+				key: QName(b"type"),
+				value: b"sphere".as_slice().into(),
+			},
+			Attribute {
+				key: QName(b"size"),
+				value: self.radius.to_string().into_bytes().into(),
+			},
+		]
+	}
+}
+
+#[cfg(feature = "urdf")]
+impl FromURDF for SphereGeometry {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self::new(required_float(element, "radius")?))
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl FromSDF for SphereGeometry {
+	fn from_sdf(
+		element: &XMLElement,
+		_sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		Ok(Self::new(content_float(required_child(
+			element, "radius",
+		)?)?))
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads the radius from the `size` of a `<geom>`.
+impl FromMJCF for SphereGeometry {
+	fn from_mjcf(
+		element: &XMLElement,
+		_mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		let [radius] = required_leading_float_array(element, "size")?;
+		Ok(Self::new(radius))
+	}
+}
+
 impl From<SphereGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 	fn from(value: SphereGeometry) -> Self {
 		Box::new(value)
@@ -83,7 +182,8 @@ impl From<SphereGeometry> for Box<dyn GeometryInterface + Sync + Send> {
 
 #[cfg(test)]
 mod tests {
-	use std::f32::consts::{FRAC_PI_3, PI};
+	use crate::float::consts::{FRAC_PI_3, PI};
+	use nalgebra::Matrix3;
 	#[cfg(feature = "xml")]
 	use std::io::Seek;
 	use test_log::test;
@@ -92,6 +192,8 @@ mod tests {
 		geometry_shape_data::GeometryShapeContainer, sphere_geometry::SphereGeometry,
 		GeometryInterface,
 	};
+	#[cfg(feature = "sdf")]
+	use crate::to_rdf::to_sdf::{SDFConfig, ToSDF};
 	#[cfg(feature = "urdf")]
 	use crate::to_rdf::to_urdf::{ToURDF, URDFConfig};
 
@@ -105,7 +207,7 @@ mod tests {
 		);
 		assert_eq!(
 			SphereGeometry::new(75.35).volume(),
-			(std::f64::consts::FRAC_PI_3 * 1711235.4215) as f32
+			4. * FRAC_PI_3 * 75.35 * 75.35 * 75.35
 		);
 	}
 
@@ -114,7 +216,16 @@ mod tests {
 		assert_eq!(SphereGeometry::new(1.0).surface_area(), PI * 4.);
 		assert_eq!(SphereGeometry::new(2.0).surface_area(), PI * 16.);
 		assert_eq!(SphereGeometry::new(9.0).surface_area(), PI * 324.);
-		assert_eq!(SphereGeometry::new(75.35).surface_area(), PI * 22710.49);
+		assert_eq!(
+			SphereGeometry::new(75.35).surface_area(),
+			4. * PI * 75.35 * 75.35
+		);
+	}
+
+	#[test]
+	fn inertia() {
+		assert!((SphereGeometry::new(2.0).inertia(5.) - Matrix3::identity() * 8.).amax() < 1e-5);
+		assert_eq!(SphereGeometry::new(1.0).inertia(0.), Matrix3::zeros());
 	}
 
 	#[test]
@@ -224,4 +335,20 @@ mod tests {
 			);
 		}
 	}
+
+	#[cfg(feature = "sdf")]
+	#[test]
+	fn to_sdf() {
+		let mut writer = quick_xml::Writer::new(std::io::Cursor::new(Vec::new()));
+		assert!(SphereGeometry::new(4.5)
+			.to_sdf(&mut writer, &SDFConfig::default())
+			.is_ok());
+
+		writer.get_mut().rewind().unwrap();
+
+		assert_eq!(
+			std::io::read_to_string(writer.into_inner()).unwrap(),
+			String::from(r#"<geometry><sphere><radius>4.5</radius></sphere></geometry>"#)
+		);
+	}
 }
@@ -2,6 +2,7 @@ use crate::link::{
 	builder::{CollisionBuilder, LinkBuilder, VisualBuilder},
 	geometry::{BoxGeometry, CylinderGeometry, GeometryInterface, SphereGeometry},
 };
+use crate::Float;
 
 /// TODO: Finalize, this is temp
 /// TODO: ADD NAMED CHOICE for Vis & Col
@@ -63,9 +64,9 @@ fn new_quick_link_old(
 /// TODO: ADD TEST?
 pub fn new_box_link(
 	link_name: impl Into<String>,
-	side1: f32,
-	side2: f32,
-	side3: f32,
+	side1: Float,
+	side2: Float,
+	side3: Float,
 ) -> LinkBuilder {
 	let geometry = BoxGeometry::new(side1, side2, side3);
 
@@ -78,7 +79,11 @@ pub fn new_box_link(
 /// TODO: Add Inertial data options
 /// TODO: ADD TEST?
 /// TODO: Orientation??
-pub fn new_cylinder_link(link_name: impl Into<String>, radius: f32, length: f32) -> LinkBuilder {
+pub fn new_cylinder_link(
+	link_name: impl Into<String>,
+	radius: Float,
+	length: Float,
+) -> LinkBuilder {
 	let geometry = CylinderGeometry::new(radius, length);
 
 	new_quick_link_old(link_name, geometry.into())
@@ -89,7 +94,7 @@ pub fn new_cylinder_link(link_name: impl Into<String>, radius: f32, length: f32)
 /// TODO: Add material Specifierer
 /// TODO: Add Inertial data options
 /// TODO: ADD TEST?
-pub fn new_sphere_link(link_name: impl Into<String>, radius: f32) -> LinkBuilder {
+pub fn new_sphere_link(link_name: impl Into<String>, radius: Float) -> LinkBuilder {
 	let geometry = SphereGeometry::new(radius);
 
 	new_quick_link_old(link_name, geometry.into())
@@ -101,7 +106,7 @@ mod tests {
 
 	#[test]
 	fn test_new_box_link() {
-		let tree = new_box_link("Zelda", 2f32, 3f32, 5f32).build_tree();
+		let tree = new_box_link("Zelda", 2., 3., 5.).build_tree();
 
 		assert_eq!(tree.get_links().try_read().unwrap().len(), 1);
 		assert_eq!(tree.get_newest_link().try_read().unwrap().name(), "Zelda");
@@ -114,13 +119,13 @@ mod tests {
 			tree.get_newest_link().try_read().unwrap().visuals[0]
 				.geometry
 				.volume(),
-			30f32
+			30.
 		);
 		assert_eq!(
 			tree.get_newest_link().try_read().unwrap().visuals[0]
 				.geometry
 				.surface_area(),
-			62f32
+			62.
 		);
 
 		assert_eq!(
@@ -135,13 +140,13 @@ mod tests {
 			tree.get_newest_link().try_read().unwrap().colliders[0]
 				.geometry
 				.volume(),
-			30f32
+			30.
 		);
 		assert_eq!(
 			tree.get_newest_link().try_read().unwrap().colliders[0]
 				.geometry
 				.surface_area(),
-			62f32
+			62.
 		);
 		// TODO: UPDATE WHEN FUNCTION IS FINALIZED
 		// TODO: TEST INERTIAL DATA
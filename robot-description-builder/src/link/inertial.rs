@@ -1,9 +1,34 @@
-use nalgebra::Matrix3;
+use itertools::Itertools;
+use nalgebra::{Isometry3, Matrix3, Rotation3, SymmetricEigen, Vector3};
+use thiserror::Error;
 
-use crate::transform::{Mirror, Transform};
+use crate::{
+	link::geometry::GeometryInterface,
+	transform::{Mirror, Transform},
+	Float,
+};
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{FromMJCF, FromMJCFContext, FromMJCFError};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{
+	optional_transform, required_child, required_float, FromURDF, FromURDFContext, FromURDFError,
+};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
+#[cfg(feature = "mjcf")]
+use crate::to_rdf::to_mjcf::ToMJCF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+#[cfg(feature = "sdf")]
+use crate::{
+	from_rdf::from_sdf::{
+		content_float, optional_child_float, FromSDF, FromSDFContext, FromSDFError,
+	},
+	to_rdf::to_sdf::ToSDF,
+};
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -17,23 +42,337 @@ pub struct Inertial {
 	/// In URDF this field is refered to as `<origin>`.
 	pub transform: Option<Transform>,
 	/// The mass of the current [`Link`](super::Link).
-	pub mass: f32,
+	pub mass: Float,
 	/// The Moments of ineria around the x axis.
-	pub ixx: f32,
+	pub ixx: Float,
 	/// Product of inertia element xy.
-	pub ixy: f32,
+	pub ixy: Float,
 	/// Product of inertia element xz.
-	pub ixz: f32,
+	pub ixz: Float,
 	/// The Moments of ineria around the y axis.
-	pub iyy: f32,
+	pub iyy: Float,
 	/// Product of inertia element yz.
-	pub iyz: f32,
+	pub iyz: Float,
 	/// The Moments of ineria around the z axis.
-	pub izz: f32,
+	pub izz: Float,
+}
+
+impl Inertial {
+	/// Creates the `Inertial` of a solid `geometry` with a uniform density and the specified `mass` in kilograms.
+	///
+	/// The center of mass is at the center of the `geometry`, so the `transform` is `None`.
+	/// Meshes are approximated by their bounding box.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::link_data::{geometry::BoxGeometry, Inertial};
+	/// let inertial = Inertial::from_mass(&BoxGeometry::new(1., 2., 2.), 3.);
+	///
+	/// assert_eq!(inertial.mass, 3.);
+	/// assert_eq!((inertial.ixx, inertial.iyy, inertial.izz), (2., 1.25, 1.25));
+	/// assert_eq!((inertial.ixy, inertial.ixz, inertial.iyz), (0., 0., 0.));
+	/// ```
+	pub fn from_mass(geometry: &(impl GeometryInterface + ?Sized), mass: Float) -> Self {
+		Self::from_inertia_matrix(None, mass, &geometry.inertia(mass))
+	}
+
+	/// Creates the `Inertial` of a solid `geometry` with the specified uniform `density` in kilograms per cubic meter.
+	///
+	/// The mass is the `density` times the [`volume`](GeometryInterface::volume) of the `geometry`.
+	/// See [`Inertial::from_mass`] for more details.
+	pub fn from_density(geometry: &(impl GeometryInterface + ?Sized), density: Float) -> Self {
+		Self::from_mass(geometry, density * geometry.volume())
+	}
+
+	/// Creates an `Inertial` from a symmetric inertia tensor.
+	pub(crate) fn from_inertia_matrix(
+		transform: Option<Transform>,
+		mass: Float,
+		inertia: &Matrix3<Float>,
+	) -> Self {
+		Self {
+			transform,
+			mass,
+			ixx: inertia.m11,
+			ixy: inertia.m12,
+			ixz: inertia.m13,
+			iyy: inertia.m22,
+			iyz: inertia.m23,
+			izz: inertia.m33,
+		}
+	}
+
+	/// Gets the symmetric inertia tensor, expressed in the frame of the `Inertial`.
+	pub fn inertia_matrix(&self) -> Matrix3<Float> {
+		Matrix3::new(
+			self.ixx, self.ixy, self.ixz, self.ixy, self.iyy, self.iyz, self.ixz, self.iyz,
+			self.izz,
+		)
+	}
+
+	/// Gets the inertia tensor about the origin of the specified `frame` and along its axes.
+	///
+	/// The `frame` is relative to the frame of the [`Link`](super::Link), like the `transform` of the `Inertial`.
+	/// The tensor is moved to the origin of the `frame` with the parallel axis theorem.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{link_data::{geometry::BoxGeometry, Inertial}, Transform};
+	/// # use nalgebra::Matrix3;
+	/// let inertial = Inertial {
+	///     transform: Some(Transform::new_translation(1., 0., 0.)),
+	///     ..Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.)
+	/// };
+	///
+	/// assert_eq!(
+	///     inertial.inertia_matrix_about(&Transform::default()),
+	///     Matrix3::from_diagonal(&[13., 22., 17.].into())
+	/// );
+	/// ```
+	pub fn inertia_matrix_about(&self, frame: &Transform) -> Matrix3<Float> {
+		let isometry = self.isometry();
+		let rotation = isometry.rotation.to_rotation_matrix();
+		let frame = frame.to_isometry();
+
+		let offset = isometry.translation.vector - frame.translation.vector;
+		let inertia = rotation * self.inertia_matrix() * rotation.transpose()
+			+ (Matrix3::identity() * offset.norm_squared() - offset * offset.transpose())
+				* self.mass;
+
+		let frame_rotation = frame.rotation.to_rotation_matrix();
+		frame_rotation.transpose() * inertia * frame_rotation
+	}
+
+	/// Expresses the `Inertial` in the coordinate system in which the `transform` is specified.
+	///
+	/// The `transform` is the pose of the frame of the current [`Link`](super::Link) in the new coordinate system,
+	/// for example the `transform` of a `Joint` to move the `Inertial` to the parent `Link`.
+	/// The moments of inertia stay the same, since they are expressed along the axes of the `Inertial`.
+	pub fn transformed(&self, transform: &Transform) -> Self {
+		Self {
+			transform: Some(transform.compose(&self.transform.unwrap_or_default())),
+			..*self
+		}
+	}
+
+	/// Expresses the inertia tensor along the axes with the specified roll-pitch-yaw angles in radians.
+	///
+	/// The angles are relative to the frame of the [`Link`](super::Link), like the `rotation` of the `transform`.
+	/// The center of mass stays in place.
+	///
+	/// This can be used to remove the rotation of an `Inertial` for formats or tools which do not support it,
+	/// with `inertial.with_rotation((0., 0., 0.))`.
+	pub fn with_rotation(&self, rpy: (Float, Float, Float)) -> Self {
+		let current = self.isometry().rotation.to_rotation_matrix();
+		let rotation = Rotation3::from_euler_angles(rpy.0, rpy.1, rpy.2);
+		let relative = current.transpose() * rotation;
+
+		self.with_axes(
+			rpy,
+			&(relative.transpose() * self.inertia_matrix() * relative),
+		)
+	}
+
+	/// Gets the principal moments of inertia in ascending order.
+	///
+	/// These are the eigenvalues of the inertia tensor, which do not depend on the axes of the `Inertial`.
+	pub fn principal_moments(&self) -> (Float, Float, Float) {
+		let moments = SymmetricEigen::new(self.inertia_matrix())
+			.eigenvalues
+			.into_iter()
+			.copied()
+			.sorted_by(Float::total_cmp)
+			.collect_vec();
+
+		(moments[0], moments[1], moments[2])
+	}
+
+	/// Diagonalizes the inertia tensor, by rotating the `Inertial` to its principal axes.
+	///
+	/// The products of inertia of the result are zero and the `rotation` of its `transform` contains the matching roll-pitch-yaw angles.
+	/// Every principal axis is assigned to the closest of the current axes, so a diagonal tensor is left unchanged.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{link_data::{geometry::BoxGeometry, Inertial}, Transform};
+	/// // A box which is rotated 0.3 radians around the z-axis, expressed along the axes of the link.
+	/// let inertial = Inertial {
+	///     transform: Some(Transform::new_rotation(0., 0., 0.3)),
+	///     ..Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.)
+	/// }
+	/// .with_rotation((0., 0., 0.));
+	/// assert_ne!(inertial.ixy, 0.);
+	///
+	/// let principal = inertial.to_principal_axes();
+	/// assert!((principal.ixx - 13.).abs() < 1e-4 && (principal.iyy - 10.).abs() < 1e-4);
+	/// assert!((principal.izz - 5.).abs() < 1e-4);
+	/// assert_eq!((principal.ixy, principal.ixz, principal.iyz), (0., 0., 0.));
+	///
+	/// let (roll, pitch, yaw) = principal.transform.unwrap().rotation.unwrap();
+	/// assert!(roll.abs() < 1e-4 && pitch.abs() < 1e-4 && (yaw - 0.3).abs() < 1e-4);
+	/// ```
+	pub fn to_principal_axes(&self) -> Self {
+		let eigen = SymmetricEigen::new(self.inertia_matrix());
+
+		let order = (0..3)
+			.permutations(3)
+			.max_by(|a, b| {
+				let alignment = |order: &[usize]| {
+					(0..3)
+						.map(|i| eigen.eigenvectors[(i, order[i])].abs())
+						.sum::<Float>()
+				};
+				alignment(a).total_cmp(&alignment(b))
+			})
+			.unwrap(); // The permutations are never empty
+
+		let mut axes = Matrix3::from_fn(|row, column| {
+			eigen.eigenvectors[(row, order[column])]
+				* eigen.eigenvectors[(column, order[column])].signum()
+		});
+		if axes.determinant() < 0. {
+			let column = (0..3)
+				.min_by(|&a, &b| axes[(a, a)].total_cmp(&axes[(b, b)]))
+				.unwrap(); // The range is never empty
+			axes.column_mut(column).neg_mut();
+		}
+
+		let rotation =
+			self.isometry().rotation.to_rotation_matrix() * Rotation3::from_matrix_unchecked(axes);
+		let (roll, pitch, yaw) = rotation.euler_angles();
+
+		self.with_axes(
+			(roll + 0., pitch + 0., yaw + 0.),
+			&Matrix3::from_diagonal(&Vector3::from_fn(|i, _| eigen.eigenvalues[order[i]])),
+		)
+	}
+
+	/// Checks if the `Inertial` is physically valid.
+	///
+	/// An `Inertial` is valid if:
+	/// - all values are finite,
+	/// - the `mass` is not negative,
+	/// - the inertia tensor is positive definite,
+	/// - the principal moments satisfy the triangle inequality, which means that no principal moment is larger than the sum of the other two.
+	///
+	/// A massless `Inertial` is valid if all moments and products of inertia are zero, like the [`Default`].
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{errors::InertialError, link_data::Inertial};
+	/// let inertial = Inertial {
+	///     mass: 1.,
+	///     ixx: 1.,
+	///     iyy: 1.,
+	///     izz: 3.,
+	///     ..Default::default()
+	/// };
+	///
+	/// assert!(matches!(
+	///     inertial.validate(),
+	///     Err(InertialError::TriangleInequality { .. })
+	/// ));
+	/// assert_eq!(Inertial::default().validate(), Ok(()));
+	/// ```
+	pub fn validate(&self) -> Result<(), InertialError> {
+		let inertia = self.inertia_matrix();
+
+		if !self.mass.is_finite()
+			|| !inertia.iter().all(|value| value.is_finite())
+			|| !self
+				.transform
+				.map(|transform| transform.to_matrix().iter().all(|value| value.is_finite()))
+				.unwrap_or(true)
+		{
+			return Err(InertialError::NotFinite);
+		}
+
+		if self.mass < 0. {
+			return Err(InertialError::NegativeMass(self.mass));
+		}
+
+		if self.mass == 0. {
+			return match inertia.iter().all(|value| *value == 0.) {
+				true => Ok(()),
+				false => Err(InertialError::InertiaWithoutMass),
+			};
+		}
+
+		let principal_moments = self.principal_moments();
+		let (smallest, middle, largest) = principal_moments;
+		if smallest <= 0. {
+			return Err(InertialError::NotPositiveDefinite { principal_moments });
+		}
+
+		// Allow for rounding errors, since the triangle inequality is an equality for flat objects.
+		let tolerance = (smallest + middle + largest) * Float::EPSILON * 64.;
+		if largest > smallest + middle + tolerance {
+			return Err(InertialError::TriangleInequality { principal_moments });
+		}
+
+		Ok(())
+	}
+
+	/// Checks if the `Inertial` is physically valid.
+	///
+	/// See [`Inertial::validate`] for the requirements and the reason if it is invalid.
+	pub fn is_valid(&self) -> bool {
+		self.validate().is_ok()
+	}
+
+	/// Gets the pose of the frame of the `Inertial`, relative to the frame of its [`Link`](super::Link).
+	fn isometry(&self) -> Isometry3<Float> {
+		self.transform
+			.map(Transform::to_isometry)
+			.unwrap_or_else(Isometry3::identity)
+	}
+
+	/// Creates a copy of the `Inertial` with its axes rotated to the specified roll-pitch-yaw angles and the matching inertia tensor.
+	fn with_axes(&self, rpy: (Float, Float, Float), inertia: &Matrix3<Float>) -> Self {
+		let transform = Transform {
+			translation: self.transform.and_then(|transform| transform.translation),
+			rotation: (rpy != (0., 0., 0.)).then_some(rpy),
+		};
+
+		Self::from_inertia_matrix(
+			transform.contains_some().then_some(transform),
+			self.mass,
+			inertia,
+		)
+	}
+}
+
+/// An error which indicates that an [`Inertial`] is not physically valid.
+///
+/// Simulators can become unstable or reject the description when a `Link` has an invalid `Inertial`.
+/// See [`Inertial::validate`].
+#[derive(Debug, PartialEq, Clone, Copy, Error)]
+pub enum InertialError {
+	/// The `Inertial` contains a value which is infinite or `NaN`.
+	#[error("The Inertial contains a value which is not finite")]
+	NotFinite,
+	/// The mass of the `Inertial` is negative.
+	#[error("The mass of the Inertial is negative: {0}")]
+	NegativeMass(Float),
+	/// The `Inertial` has no mass, but its moments or products of inertia are not zero.
+	#[error("The Inertial has no mass, but a non-zero inertia tensor")]
+	InertiaWithoutMass,
+	/// The inertia tensor is not positive definite, so at least one principal moment is zero or negative.
+	#[error("The inertia tensor is not positive definite, the principal moments are {principal_moments:?}")]
+	NotPositiveDefinite {
+		/// The principal moments of inertia in ascending order.
+		principal_moments: (Float, Float, Float),
+	},
+	/// The largest principal moment is larger than the sum of the other two principal moments, which is impossible for a real object.
+	#[error("The principal moments {principal_moments:?} do not satisfy the triangle inequality")]
+	TriangleInequality {
+		/// The principal moments of inertia in ascending order.
+		principal_moments: (Float, Float, Float),
+	},
 }
 
 impl Mirror for Inertial {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		Self {
 			transform: self
 				.transform
@@ -84,11 +423,485 @@ impl ToURDF for Inertial {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for Inertial {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let element = writer.create_element("inertial");
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			if let Some(transform) = &self.transform {
+				transform.to_sdf(writer, sdf_config)?;
+			}
+
+			writer
+				.create_element("mass")
+				.write_text_content(BytesText::new(&self.mass.to_string()))?;
+
+			writer.create_element("inertia").write_inner_content(
+				|writer| -> quick_xml::Result<()> {
+					for (name, value) in [
+						("ixx", self.ixx),
+						("ixy", self.ixy),
+						("ixz", self.ixz),
+						("iyy", self.iyy),
+						("iyz", self.iyz),
+						("izz", self.izz),
+					] {
+						writer
+							.create_element(name)
+							.write_text_content(BytesText::new(&value.to_string()))?;
+					}
+					Ok(())
+				},
+			)?;
+
+			Ok(())
+		})?;
+
+		Ok(())
+	}
+}
+
+/// The inertia is rotated into the frame of the `<body>`, since MJCF uses the `quat` of an `<inertial>` as the principal axes.
+#[cfg(feature = "mjcf")]
+impl ToMJCF for Inertial {
+	fn to_mjcf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_mjcf_config: &crate::to_rdf::to_mjcf::MJCFConfig,
+	) -> Result<(), quick_xml::Error> {
+		let (x, y, z) = self
+			.transform
+			.and_then(|transform| transform.translation)
+			.unwrap_or_default();
+		let (roll, pitch, yaw) = self
+			.transform
+			.and_then(|transform| transform.rotation)
+			.unwrap_or_default();
+
+		// The rotation is performed with double precision, to prevent rounding errors in the description.
+		let rotation = Rotation3::from_euler_angles(roll.into(), pitch.into(), yaw.into());
+		let inertia = rotation
+			* Matrix3::<f64>::new(
+				self.ixx.into(),
+				self.ixy.into(),
+				self.ixz.into(),
+				self.ixy.into(),
+				self.iyy.into(),
+				self.iyz.into(),
+				self.ixz.into(),
+				self.iyz.into(),
+				self.izz.into(),
+			) * rotation.transpose();
+		// Values which are indistinguishable from zero at the precision of `Float` are numerical noise.
+		let noise_level = inertia.amax() * f64::from(Float::EPSILON);
+
+		writer
+			.create_element("inertial")
+			// The position is required in MJCF.
+			.with_attribute(Attribute {
+				key: QName(b"pos"),
+				value: format!("{} {} {}", x, y, z).as_bytes().into(),
+			})
+			.with_attribute(Attribute {
+				key: QName(b"mass"),
+				value: self.mass.to_string().as_bytes().into(),
+			})
+			.with_attribute(Attribute {
+				key: QName(b"fullinertia"),
+				value: [
+					inertia.m11,
+					inertia.m22,
+					inertia.m33,
+					inertia.m12,
+					inertia.m13,
+					inertia.m23,
+				]
+				.map(|value| match value.abs() < noise_level {
+					true => String::from("0"),
+					false => crate::float::from_calculated(value).to_string(),
+				})
+				.join(" ")
+				.as_bytes()
+				.into(),
+			})
+			.write_empty()?;
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "urdf")]
+impl FromURDF for Inertial {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let inertia = required_child(element, "inertia")?;
+
+		Ok(Self {
+			transform: optional_transform(element, urdf_context)?,
+			mass: required_float(required_child(element, "mass")?, "value")?,
+			ixx: required_float(inertia, "ixx")?,
+			ixy: required_float(inertia, "ixy")?,
+			ixz: required_float(inertia, "ixz")?,
+			iyy: required_float(inertia, "iyy")?,
+			iyz: required_float(inertia, "iyz")?,
+			izz: required_float(inertia, "izz")?,
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads an `<inertial>` element, with the SDFormat defaults for the missing values.
+impl FromSDF for Inertial {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		sdf_context.warn_unsupported(element, &["pose", "mass", "inertia"]);
+
+		let inertia = element.child("inertia");
+		let moment = |name: &str, default: Float| {
+			inertia
+				.and_then(|inertia| inertia.child(name))
+				.map(content_float)
+				.transpose()
+				.map(|value| value.unwrap_or(default))
+		};
+
+		Ok(Self {
+			transform: sdf_context.transform(element)?,
+			mass: optional_child_float(element, "mass")?.unwrap_or(1.),
+			ixx: moment("ixx", 1.)?,
+			ixy: moment("ixy", 0.)?,
+			ixz: moment("ixz", 0.)?,
+			iyy: moment("iyy", 1.)?,
+			iyz: moment("iyz", 0.)?,
+			izz: moment("izz", 1.)?,
+		})
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads an `<inertial>` element, with the moments of inertia specified by either `diaginertia` or `fullinertia`.
+impl FromMJCF for Inertial {
+	fn from_mjcf(
+		element: &XMLElement,
+		mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		use crate::from_rdf::from_mjcf::{
+			invalid_value, optional_float, optional_float_array, required_attribute,
+		};
+
+		mjcf_context.warn_unsupported_attributes(
+			element,
+			&[
+				"pos",
+				"quat",
+				"axisangle",
+				"euler",
+				"xyaxes",
+				"zaxis",
+				"mass",
+				"diaginertia",
+				"fullinertia",
+			],
+		);
+
+		required_attribute(element, "mass")?;
+		let [ixx, iyy, izz, ixy, ixz, iyz] = match (
+			optional_float_array(element, "diaginertia")?,
+			optional_float_array(element, "fullinertia")?,
+		) {
+			(Some([ixx, iyy, izz]), None) => [ixx, iyy, izz, 0., 0., 0.],
+			(None, Some(fullinertia)) => fullinertia,
+			(Some(_), Some(_)) => return Err(invalid_value(element, "fullinertia")),
+			(None, None) => {
+				required_attribute(element, "diaginertia")?;
+				unreachable!()
+			}
+		};
+
+		Ok(Self {
+			transform: Some(Transform::from_mjcf(element, mjcf_context)?)
+				.filter(Transform::contains_some),
+			mass: optional_float(element, "mass")?.unwrap_or_default(),
+			ixx,
+			ixy,
+			ixz,
+			iyy,
+			iyz,
+			izz,
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use super::Inertial;
+	use super::{Inertial, InertialError};
+	use crate::{
+		float::consts::FRAC_PI_2,
+		link::geometry::{BoxGeometry, CylinderGeometry, SphereGeometry},
+		transform::Transform,
+		Float,
+	};
+	use nalgebra::Matrix3;
 	use test_log::test;
 
+	#[test]
+	fn from_mass() {
+		assert_eq!(
+			Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.),
+			Inertial {
+				mass: 12.,
+				ixx: 13.,
+				iyy: 10.,
+				izz: 5.,
+				..Default::default()
+			}
+		);
+		assert_eq!(
+			Inertial::from_mass(&CylinderGeometry::new(1., 2.), 12.),
+			Inertial {
+				mass: 12.,
+				ixx: 7.,
+				iyy: 7.,
+				izz: 6.,
+				..Default::default()
+			}
+		);
+		assert_eq!(
+			Inertial::from_mass(&SphereGeometry::new(1.), 0.),
+			Inertial::default()
+		);
+	}
+
+	#[test]
+	fn from_density() {
+		assert_eq!(
+			Inertial::from_density(&BoxGeometry::new(1., 2., 3.), 2.),
+			Inertial {
+				mass: 12.,
+				ixx: 13.,
+				iyy: 10.,
+				izz: 5.,
+				..Default::default()
+			}
+		);
+		assert_eq!(
+			Inertial::from_density(&BoxGeometry::new(1., 2., 3.), 0.),
+			Inertial::default()
+		);
+	}
+
+	fn assert_matrix(left: Matrix3<Float>, right: Matrix3<Float>) {
+		assert!((left - right).amax() < 1e-4, "{left} != {right}");
+	}
+
+	#[test]
+	fn inertia_matrix_about() {
+		let inertial = Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.);
+
+		assert_eq!(
+			inertial.inertia_matrix_about(&Transform::default()),
+			inertial.inertia_matrix()
+		);
+		assert_eq!(
+			inertial.inertia_matrix_about(&Transform::new_translation(0., 0., 1.)),
+			Matrix3::from_diagonal(&[25., 22., 5.].into())
+		);
+		assert_matrix(
+			inertial.inertia_matrix_about(&Transform::new_rotation(0., 0., FRAC_PI_2)),
+			Matrix3::from_diagonal(&[10., 13., 5.].into()),
+		);
+
+		let inertial = Inertial {
+			transform: Some(Transform::new((0., 1., 0.), (FRAC_PI_2, 0., 0.))),
+			..inertial
+		};
+		// About the link origin, along the link axes: the y and z axes are swapped and shifted by 1 along y.
+		assert_matrix(
+			inertial.inertia_matrix_about(&Transform::default()),
+			Matrix3::from_diagonal(&[25., 5., 22.].into()),
+		);
+		assert_matrix(
+			inertial.inertia_matrix_about(&Transform::new((0., 1., 0.), (FRAC_PI_2, 0., 0.))),
+			inertial.inertia_matrix(),
+		);
+	}
+
+	#[test]
+	fn transformed() {
+		let inertial = Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.);
+
+		assert_eq!(
+			inertial.transformed(&Transform::new_translation(1., 2., 3.)),
+			Inertial {
+				transform: Some(Transform::new_translation(1., 2., 3.)),
+				..inertial
+			}
+		);
+
+		let inertial = Inertial {
+			transform: Some(Transform::new_translation(1., 0., 0.)),
+			..inertial
+		};
+		let transformed = inertial.transformed(&Transform::new((0., 0., 1.), (0., 0., FRAC_PI_2)));
+		let (x, y, z) = transformed.transform.unwrap().translation.unwrap();
+		assert!(x.abs() < 1e-6 && (y - 1.).abs() < 1e-6 && (z - 1.).abs() < 1e-6);
+		assert_eq!(
+			transformed.transform.unwrap().rotation,
+			Some((0., 0., FRAC_PI_2))
+		);
+		assert_eq!(transformed.inertia_matrix(), inertial.inertia_matrix());
+
+		// The inertia tensor about the new origin is the same as about the original origin, moved by the transform.
+		assert_matrix(
+			transformed.inertia_matrix_about(&Transform::new((0., 0., 1.), (0., 0., FRAC_PI_2))),
+			inertial.inertia_matrix_about(&Transform::default()),
+		);
+	}
+
+	#[test]
+	fn with_rotation() {
+		let inertial = Inertial {
+			transform: Some(Transform::new_translation(1., 2., 3.)),
+			..Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.)
+		};
+
+		let rotated = inertial.with_rotation((0., 0., FRAC_PI_2));
+		assert_eq!(
+			rotated.transform,
+			Some(Transform::new((1., 2., 3.), (0., 0., FRAC_PI_2)))
+		);
+		assert_matrix(
+			rotated.inertia_matrix(),
+			Matrix3::from_diagonal(&[10., 13., 5.].into()),
+		);
+		assert_matrix(
+			rotated.inertia_matrix_about(&Transform::default()),
+			inertial.inertia_matrix_about(&Transform::default()),
+		);
+
+		let restored = rotated.with_rotation((0., 0., 0.));
+		assert_eq!(restored.transform, inertial.transform);
+		assert_matrix(restored.inertia_matrix(), inertial.inertia_matrix());
+
+		assert_eq!(
+			Inertial::default().with_rotation((0., 0., 0.)),
+			Inertial::default()
+		);
+	}
+
+	#[test]
+	fn principal_axes() {
+		let inertial = Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.);
+		assert_eq!(inertial.principal_moments(), (5., 10., 13.));
+		assert_eq!(inertial.to_principal_axes(), inertial);
+
+		let rotated = Inertial {
+			transform: Some(Transform::new_rotation(0.1, -0.2, 0.3)),
+			..inertial
+		}
+		.with_rotation((0., 0., 0.));
+		assert!(rotated.ixy.abs() > 0.1);
+		let (smallest, middle, largest) = rotated.principal_moments();
+		assert!((smallest - 5.).abs() < 1e-4);
+		assert!((middle - 10.).abs() < 1e-4);
+		assert!((largest - 13.).abs() < 1e-4);
+
+		let principal = rotated.to_principal_axes();
+		assert!((principal.ixx - 13.).abs() < 1e-4);
+		assert!((principal.iyy - 10.).abs() < 1e-4);
+		assert!((principal.izz - 5.).abs() < 1e-4);
+		assert_eq!((principal.ixy, principal.ixz, principal.iyz), (0., 0., 0.));
+		assert_eq!(principal.transform.unwrap().translation, None);
+
+		let (roll, pitch, yaw) = principal.transform.unwrap().rotation.unwrap();
+		assert!((roll - 0.1).abs() < 1e-4);
+		assert!((pitch + 0.2).abs() < 1e-4);
+		assert!((yaw - 0.3).abs() < 1e-4);
+	}
+
+	#[test]
+	fn validate() {
+		let inertial = Inertial::from_mass(&BoxGeometry::new(1., 2., 3.), 12.);
+		assert_eq!(inertial.validate(), Ok(()));
+		assert!(inertial.is_valid());
+		assert_eq!(Inertial::default().validate(), Ok(()));
+
+		// A flat plate is on the edge of the triangle inequality.
+		assert_eq!(
+			Inertial::from_mass(&BoxGeometry::new(0.3, 0.7, 0.), 1.3).validate(),
+			Ok(())
+		);
+
+		assert_eq!(
+			Inertial {
+				mass: Float::NAN,
+				..inertial
+			}
+			.validate(),
+			Err(InertialError::NotFinite)
+		);
+		assert_eq!(
+			Inertial {
+				transform: Some(Transform::new_translation(Float::INFINITY, 0., 0.)),
+				..inertial
+			}
+			.validate(),
+			Err(InertialError::NotFinite)
+		);
+		assert_eq!(
+			Inertial {
+				mass: -1.,
+				..inertial
+			}
+			.validate(),
+			Err(InertialError::NegativeMass(-1.))
+		);
+		assert_eq!(
+			Inertial {
+				mass: 0.,
+				..inertial
+			}
+			.validate(),
+			Err(InertialError::InertiaWithoutMass)
+		);
+		assert_eq!(
+			Inertial {
+				izz: 0.,
+				..inertial
+			}
+			.validate(),
+			Err(InertialError::NotPositiveDefinite {
+				principal_moments: (0., 10., 13.)
+			})
+		);
+		assert_eq!(
+			Inertial {
+				mass: 1.,
+				ixx: 1.,
+				iyy: 1.,
+				izz: 3.,
+				..Default::default()
+			}
+			.validate(),
+			Err(InertialError::TriangleInequality {
+				principal_moments: (1., 1., 3.)
+			})
+		);
+		assert!(!Inertial {
+			ixy: 20.,
+			..inertial
+		}
+		.is_valid());
+	}
+
 	#[cfg(feature = "urdf")]
 	mod to_urdf {
 		use super::{test, *};
@@ -1,6 +1,10 @@
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
+#[cfg(feature = "mjcf")]
+use crate::to_rdf::to_mjcf::MJCF_VISUAL_GROUP;
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 use crate::{
@@ -164,6 +168,90 @@ impl ToURDF for Visual {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl Visual {
+	/// Writes the `Visual` in SDFormat, using the `fallback_name` if it is unnamed.
+	///
+	/// SDFormat requires `<visual>` elements to be named, so the [`Link`](crate::link::Link) provides a unique `fallback_name`.
+	pub(crate) fn to_sdf_named(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+		fallback_name: &str,
+	) -> Result<(), quick_xml::Error> {
+		let name = self
+			.name()
+			.map(|name| name.display())
+			.unwrap_or_else(|| fallback_name.to_string());
+
+		let element = writer.create_element("visual").with_attribute(Attribute {
+			key: QName(b"name"),
+			value: name.as_bytes().into(),
+		});
+
+		element.write_inner_content(|writer| -> quick_xml::Result<()> {
+			if let Some(transform) = self.transform() {
+				transform.to_sdf(writer, sdf_config)?
+			}
+
+			self.geometry()
+				.shape_container()
+				.to_sdf(writer, sdf_config)?;
+			if let Some(material) = self.material() {
+				material.to_sdf(writer, sdf_config)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl Visual {
+	/// Writes the `Visual` as a non-colliding MJCF `<geom>` without mass.
+	///
+	/// A mesh refers to the `<mesh>` asset named `mesh_asset_name`, which is provided by the [`Link`](crate::link::Link).
+	pub(crate) fn to_mjcf_geom(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		mesh_asset_name: &str,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("geom")
+			.with_attributes(
+				self.geometry()
+					.shape_container()
+					.mjcf_attributes(mesh_asset_name),
+			)
+			.with_attributes(
+				self.transform()
+					.map(Transform::mjcf_attributes)
+					.unwrap_or_default(),
+			)
+			.with_attributes([
+				("contype", "0"),
+				("conaffinity", "0"),
+				("group", MJCF_VISUAL_GROUP),
+				("density", "0"),
+			])
+			.with_attributes(self.material().and_then(Material::mjcf_rgba))
+			.write_empty()?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "sdf")]
+impl ToSDF for Visual {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		self.to_sdf_named(writer, sdf_config, "visual")
+	}
+}
+
 impl PartialEq for Visual {
 	fn eq(&self, other: &Self) -> bool {
 		self.name == other.name
@@ -190,7 +278,7 @@ impl Clone for Visual {
 
 #[cfg(test)]
 mod tests {
-	use std::f32::consts::PI;
+	use crate::float::consts::PI;
 	use test_log::test;
 
 	use crate::{
@@ -248,8 +336,10 @@ mod tests {
 			test_to_urdf_visual(
 				Visual::builder(SphereGeometry::new(3.))
 					.transformed(Transform::new((4., 6.78, 1.), (PI, 2. * PI, 0.))),
-				String::from(
-					r#"<visual><origin xyz="4 6.78 1" rpy="3.1415927 6.2831855 0"/><geometry><sphere radius="3"/></geometry></visual>"#,
+				format!(
+					r#"<visual><origin xyz="4 6.78 1" rpy="{} {} 0"/><geometry><sphere radius="3"/></geometry></visual>"#,
+					PI,
+					2. * PI,
 				),
 				&URDFConfig::default(),
 			);
@@ -13,6 +13,8 @@ pub use descriptor::MaterialDescriptor;
 
 use std::sync::{Arc, RwLock};
 
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::{ToURDF, URDFMaterialMode};
 #[cfg(feature = "xml")]
@@ -179,6 +181,46 @@ impl ToURDF for Material {
 	}
 }
 
+/// SDFormat has no global materials, so the `Material` is always written in full and its name is not used.
+#[cfg(feature = "sdf")]
+impl ToSDF for Material {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		writer
+			.create_element("material")
+			.write_inner_content(|writer| match &self.0 {
+				MaterialKind::Named { data, .. } => data.to_sdf(writer, sdf_config),
+				MaterialKind::Unnamed(data) => data.to_sdf(writer, sdf_config),
+			})?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl Material {
+	/// Gets the `rgba` attribute of a MJCF `<geom>` with this `Material`.
+	///
+	/// MJCF `<geom>`s can only be colored directly, so textures are not written.
+	pub(crate) fn mjcf_rgba(&self) -> Option<Attribute<'static>> {
+		match MaterialData::try_from(self.material_data()) {
+			Ok(MaterialData::Color(red, green, blue, alpha)) => Some(Attribute {
+				key: quick_xml::name::QName(b"rgba"),
+				value: format!("{} {} {} {}", red, green, blue, alpha)
+					.into_bytes()
+					.into(),
+			}),
+			Ok(MaterialData::Texture(_)) | Err(_) => {
+				#[cfg(any(feature = "logging", test))]
+				log::warn!("The texture of a material can not be written in MJCF, it is ignored");
+				None
+			}
+		}
+	}
+}
+
 #[cfg(feature = "wrapper")]
 impl From<(String, ArcLock<MaterialData>)> for Material {
 	fn from(value: (String, ArcLock<MaterialData>)) -> Self {
@@ -5,10 +5,17 @@
 //!  - The [`MaterialDataReference`], which is a referenced which can refer to a material data from both (Global) named  and unnamed materials.
 use std::sync::Arc;
 
-use crate::utils::{ArcLock, ArcRW, ErroredRead};
+use crate::{
+	utils::{ArcLock, ArcRW, ErroredRead},
+	Float,
+};
 
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -18,7 +25,7 @@ pub enum MaterialData {
 	/// Color as RGBA.
 	///
 	/// The fields need to be between 0 and 1 (for most simulators). (Not enforced)
-	Color(f32, f32, f32, f32),
+	Color(Float, Float, Float, Float),
 	/// Texture, containing the texture path as a valid package path (e.g. `"package://robot_description/textures/{texture}"`). You are on your own here.
 	Texture(String),
 }
@@ -57,6 +64,48 @@ impl ToURDF for MaterialData {
 	}
 }
 
+/// Writes the content of a SDFormat `<material>`.
+///
+/// A color is used for both the `<ambient>` and `<diffuse>` color,
+/// a texture is written as the `<albedo_map>` of a metal `<pbr>` workflow.
+#[cfg(feature = "sdf")]
+impl ToSDF for MaterialData {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		match self {
+			MaterialData::Color(red, green, blue, alpha) => {
+				let color = format!("{} {} {} {}", red, green, blue, alpha);
+				writer
+					.create_element("ambient")
+					.write_text_content(BytesText::new(&color))?;
+				writer
+					.create_element("diffuse")
+					.write_text_content(BytesText::new(&color))?;
+				Ok(())
+			}
+			MaterialData::Texture(texture_path) => {
+				writer.create_element("pbr").write_inner_content(
+					|writer| -> quick_xml::Result<()> {
+						writer.create_element("metal").write_inner_content(
+							|writer| -> quick_xml::Result<()> {
+								writer
+									.create_element("albedo_map")
+									.write_text_content(BytesText::new(texture_path))?;
+								Ok(())
+							},
+						)?;
+						Ok(())
+					},
+				)?;
+				Ok(())
+			}
+		}
+	}
+}
+
 /// A wrapper for [`MaterialData`] references.
 ///
 /// This is neccessary for the global [`Material`](super::Material) implementation.
@@ -2,10 +2,26 @@
  o Internal Module Doc
  - MaterialDescriptor
 */
-use crate::identifiers::GroupIDChanger;
+use crate::{identifiers::GroupIDChanger, Float};
 
 use super::{data::MaterialData, Material};
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{FromMJCF, FromMJCFContext, FromMJCFError};
+#[cfg(feature = "sdf")]
+use crate::from_rdf::from_sdf::{
+	invalid_content, FromSDF, FromSDFContext, FromSDFError, SDFWarning,
+};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
+#[cfg(feature = "urdf")]
+use crate::{
+	from_rdf::from_urdf::{
+		optional_float_array, required_attribute, FromURDF, FromURDFContext, FromURDFError,
+	},
+	identifiers::escape_group_id_delimiters,
+};
+
 /// A descriptor for a future `Material`.
 ///
 /// A [`MaterialDescriptor`] is used to construct a [`Material`].
@@ -47,7 +63,7 @@ impl MaterialDescriptor {
 	/// MaterialDescriptor::new_color(1., 0.4, 0.6, 0.5)
 	/// # ;
 	/// ```
-	pub fn new_color(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+	pub fn new_color(red: Float, green: Float, blue: Float, alpha: Float) -> Self {
 		MaterialDescriptor {
 			name: None,
 			data: MaterialData::Color(red, green, blue, alpha),
@@ -65,7 +81,7 @@ impl MaterialDescriptor {
 	/// MaterialDescriptor::new_rgb(1., 0.4, 0.6)
 	/// # ;
 	/// ```
-	pub fn new_rgb(red: f32, green: f32, blue: f32) -> Self {
+	pub fn new_rgb(red: Float, green: Float, blue: Float) -> Self {
 		MaterialDescriptor {
 			name: None,
 			data: MaterialData::Color(red, green, blue, 1.),
@@ -149,6 +165,164 @@ impl GroupIDChanger for MaterialDescriptor {
 	}
 }
 
+#[cfg(feature = "urdf")]
+/// Reads a `<material>` element.
+///
+/// A named `<material>` without content refers to an earlier definition in the `urdf_context`.
+/// A named `<material>` with content gets registered in the `urdf_context`.
+impl FromURDF for MaterialDescriptor {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let name = element.attribute("name").map(escape_group_id_delimiters);
+
+		let data = match (element.child("color"), element.child("texture")) {
+			(Some(color), _) => {
+				required_attribute(color, "rgba")?;
+				// The attribute exists, so it is always `Some`.
+				let [red, green, blue, alpha] =
+					optional_float_array(color, "rgba")?.unwrap_or_default();
+				Some(MaterialData::Color(red, green, blue, alpha))
+			}
+			(None, Some(texture)) => Some(MaterialData::Texture(
+				required_attribute(texture, "filename")?.to_string(),
+			)),
+			(None, None) => None,
+		};
+
+		match (name, data) {
+			(Some(name), Some(data)) => {
+				let descriptor = Self::new_data(data).named(name.clone());
+
+				match urdf_context.materials.get(&name) {
+					Some(other) if other != &descriptor => {
+						Err(FromURDFError::ConflictingMaterial {
+							name: element.attribute("name").unwrap_or_default().to_string(),
+							line: element.line,
+						})
+					}
+					_ => {
+						urdf_context.materials.insert(name, descriptor.clone());
+						Ok(descriptor)
+					}
+				}
+			}
+			(Some(name), None) => urdf_context.materials.get(&name).cloned().ok_or_else(|| {
+				FromURDFError::UnknownMaterial {
+					name: element.attribute("name").unwrap_or_default().to_string(),
+					line: element.line,
+				}
+			}),
+			(None, Some(data)) => Ok(Self::new_data(data)),
+			(None, None) => Err(FromURDFError::MissingElement {
+				element: element.name.clone(),
+				child: String::from("color"),
+				line: element.line,
+			}),
+		}
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads a `<material>` element.
+///
+/// The `<diffuse>` color is used, or the `<ambient>` color if there is no `<diffuse>` color.
+/// Otherwise the `<albedo_map>` of the metal PBR workflow is used as a texture.
+/// A `<material>` without any of those is reported as a warning and read as `None`.
+impl FromSDF for Option<MaterialDescriptor> {
+	fn from_sdf(
+		element: &XMLElement,
+		sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		sdf_context.warn_unsupported(element, &["ambient", "diffuse", "pbr"]);
+
+		let metal = element.child("pbr").and_then(|pbr| {
+			sdf_context.warn_unsupported(pbr, &["metal"]);
+			pbr.child("metal")
+		});
+		let albedo_map = metal.and_then(|metal| {
+			sdf_context.warn_unsupported(metal, &["albedo_map"]);
+			metal.child("albedo_map")
+		});
+
+		match (
+			element
+				.child("diffuse")
+				.or_else(|| element.child("ambient")),
+			albedo_map,
+		) {
+			(Some(color), _) => {
+				let values = color
+					.text
+					.split_whitespace()
+					.map(str::parse)
+					.collect::<Result<Vec<Float>, _>>()
+					.map_err(|_| invalid_content(color))?;
+
+				match values.as_slice() {
+					[red, green, blue] => {
+						Ok(Some(MaterialDescriptor::new_rgb(*red, *green, *blue)))
+					}
+					[red, green, blue, alpha] => Ok(Some(MaterialDescriptor::new_color(
+						*red, *green, *blue, *alpha,
+					))),
+					_ => Err(invalid_content(color)),
+				}
+			}
+			(None, Some(albedo_map)) => Ok(Some(MaterialDescriptor::new_texture(
+				albedo_map.text.clone(),
+			))),
+			(None, None) => {
+				sdf_context.warn(SDFWarning::UnsupportedMaterial { line: element.line });
+				Ok(None)
+			}
+		}
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads the color of a `<geom>`.
+///
+/// The `rgba` of the `<geom>` takes precedence over the `rgba` of its `<material>` asset.
+/// A named material is only read when the `<geom>` does not have its own `rgba`.
+/// A `<geom>` without either is read as `None`.
+impl FromMJCF for Option<MaterialDescriptor> {
+	fn from_mjcf(
+		element: &XMLElement,
+		mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		use crate::{
+			from_rdf::from_mjcf::optional_float_array, identifiers::escape_group_id_delimiters,
+		};
+
+		if let Some([red, green, blue, alpha]) = optional_float_array(element, "rgba")? {
+			return Ok(Some(MaterialDescriptor::new_color(red, green, blue, alpha)));
+		}
+
+		let Some(name) = element.attribute("material") else {
+			return Ok(None);
+		};
+
+		let material =
+			mjcf_context
+				.materials
+				.get(name)
+				.ok_or_else(|| FromMJCFError::UnknownAsset {
+					element: element.name.clone(),
+					asset: name.to_string(),
+					line: element.line,
+				})?;
+
+		// The default color of a MuJoCo material is white.
+		let [red, green, blue, alpha] = optional_float_array(material, "rgba")?.unwrap_or([1.; 4]);
+		Ok(Some(
+			MaterialDescriptor::new_color(red, green, blue, alpha)
+				.named(escape_group_id_delimiters(name)),
+		))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::MaterialDescriptor;
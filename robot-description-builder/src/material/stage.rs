@@ -3,6 +3,8 @@ use std::sync::Arc;
 
 use crate::utils::ArcLock;
 
+#[cfg(feature = "sdf")]
+use crate::to_rdf::to_sdf::ToSDF;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 
@@ -62,6 +64,22 @@ impl ToURDF for MaterialStage {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for MaterialStage {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		match self {
+			MaterialStage::PreInit(data) => data.to_sdf(writer, sdf_config),
+			MaterialStage::Initialized(arc_data) => {
+				arc_data.read().unwrap().to_sdf(writer, sdf_config)
+			}
+		}
+	}
+}
+
 impl PartialEq for MaterialStage {
 	fn eq(&self, other: &Self) -> bool {
 		match (self, other) {
@@ -0,0 +1,460 @@
+//! Simplifications of a kinematic tree, which reduce the number of `Link`s.
+//!
+//! Simulators and controllers prefer fewer bodies, so [`Robot::lump_fixed_joints`] merges the `Link`s,
+//! which are connected by a [`Fixed`](JointType::Fixed) `Joint`, into their parent `Link`.
+use std::collections::BTreeMap;
+
+use nalgebra::Isometry3;
+
+use crate::{
+	cluster_objects::KinematicInterface,
+	dynamics::MassProperties,
+	joint::{JointTransformMode, JointType},
+	link::builder::LinkBuilder,
+	link_data::Inertial,
+	transform::Transform,
+	utils::ArcRW,
+	yank_errors::RebuildBranchError,
+	Robot,
+};
+
+/// A Configuration for [`Robot::lump_fixed_joints`].
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct LumpConfig {
+	/// The names of the [`Fixed`](JointType::Fixed) `Joint`s, which are kept with their child `Link`.
+	///
+	/// This can be used to keep sensor frames or end effectors, which are needed by name.
+	pub keep_joints: Vec<String>,
+	/// Keeps the removed `Link`s as [`frames`](Robot::frames) of the new `Robot`, which are written as `<frame>`s in SDFormat.
+	///
+	/// SDFormat only supports `<frame>`s from [`SDFVersion::V1_7`](crate::to_rdf::to_sdf::SDFVersion::V1_7) onwards,
+	/// older versions and the other formats drop the frames.
+	pub preserve_frames: bool,
+}
+
+/// A frame, which is attached to a `Link` of a [`Robot`].
+///
+/// Frames have no physical properties, they only name a pose relative to a `Link`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Frame {
+	/// The name of the `Link` the frame is attached to.
+	pub attached_to: String,
+	/// The transform from the origin of the `Link` to the frame.
+	pub transform: Transform,
+}
+
+/// The result of [`Robot::lump_fixed_joints`].
+#[derive(Debug)]
+pub struct LumpedRobot {
+	robot: Robot,
+	frames: BTreeMap<String, Frame>,
+}
+
+impl LumpedRobot {
+	/// Gets the simplified `Robot`.
+	pub fn robot(&self) -> &Robot {
+		&self.robot
+	}
+
+	/// Consumes the `LumpedRobot` and returns the simplified `Robot`.
+	pub fn into_robot(self) -> Robot {
+		self.robot
+	}
+
+	/// Gets the [`Frame`]s of the removed `Link`s, indexed by the name of the removed `Link`.
+	///
+	/// Every `Frame` is attached to the `Link` the removed `Link` was merged into.
+	pub fn frames(&self) -> &BTreeMap<String, Frame> {
+		&self.frames
+	}
+
+	/// Gets the [`Frame`] of the removed `Link` with the specified name.
+	pub fn frame(&self, link_name: &str) -> Option<&Frame> {
+		self.frames.get(link_name)
+	}
+}
+
+impl Robot {
+	/// Creates a new `Robot`, in which the `Link`s connected by [`Fixed`](JointType::Fixed) `Joint`s are merged into their parent `Link`.
+	///
+	/// The merged `Link` keeps the name of the parent `Link` and gets:
+	/// - the visuals and colliders of both `Link`s, with the `Transform` of the removed `Joint` composed into the `Transform`s of the child;
+	/// - the combined `Inertial` of both `Link`s, at their common center of mass;
+	/// - the child `Joint`s of both `Link`s, of which the `Joint`s of the child are re-anchored to the frame of the parent.
+	///
+	/// The `Joint`s in [`LumpConfig::keep_joints`] are kept.
+	/// The frames of the removed `Link`s are reported in the [`LumpedRobot`].
+	/// `Transmission`s are not copied to the new `Robot`.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{prelude::*, simplify::LumpConfig, JointBuilder, JointType, Link, Transform};
+	/// let robot = Link::builder("base").build_tree().to_robot("rover");
+	///
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(
+	///         JointBuilder::new("camera_mount", JointType::Fixed).add_origin_offset((0.5, 0., 0.2)),
+	///         Link::builder("camera"),
+	///     )
+	///     .unwrap();
+	///
+	/// let lumped = robot
+	///     .lump_fixed_joints(&LumpConfig {
+	///         preserve_frames: true,
+	///         ..Default::default()
+	///     })
+	///     .unwrap();
+	///
+	/// assert!(lumped.robot().get_link("camera").is_none());
+	/// assert_eq!(lumped.frame("camera").unwrap().attached_to, "base");
+	/// assert_eq!(
+	///     lumped.robot().frames()["camera"].transform,
+	///     Transform::new_translation(0.5, 0., 0.2)
+	/// );
+	/// ```
+	pub fn lump_fixed_joints(
+		&self,
+		config: &LumpConfig,
+	) -> Result<LumpedRobot, RebuildBranchError> {
+		let mut root: LinkBuilder = self.get_root_link().mread()?.rebuild_branch()?.into();
+
+		let mut frames = BTreeMap::new();
+		lump_link(&mut root, config, &mut frames);
+
+		let mut robot = root.build_tree().to_robot(self.name());
+		if config.preserve_frames {
+			robot.set_frames(frames.clone());
+		}
+
+		Ok(LumpedRobot { robot, frames })
+	}
+}
+
+/// Merges the children of the `link` which are connected by a [`Fixed`](JointType::Fixed) `Joint`, after simplifying the children themselves.
+///
+/// The frames of the removed `Link`s are added to `frames`.
+fn lump_link(link: &mut LinkBuilder, config: &LumpConfig, frames: &mut BTreeMap<String, Frame>) {
+	for mut joint in std::mem::take(&mut link.joints) {
+		let Some(mut child) = joint.child.take() else {
+			link.joints.push(joint);
+			continue;
+		};
+		lump_link(&mut child, config, frames);
+
+		match (joint.joint_type, &joint.transform) {
+			(JointType::Fixed, JointTransformMode::Direct(offset))
+				if !config.keep_joints.contains(&joint.name) =>
+			{
+				// The frames of the `Link`s, which were merged into the child, move along.
+				for frame in frames
+					.values_mut()
+					.filter(|frame| frame.attached_to == child.name)
+				{
+					frame.transform = offset.compose(&frame.transform);
+					frame.attached_to.clone_from(&link.name);
+				}
+				frames.insert(
+					child.name.clone(),
+					Frame {
+						attached_to: link.name.clone(),
+						transform: *offset,
+					},
+				);
+
+				merge_link(link, child, offset);
+			}
+			_ => {
+				joint.child = Some(child);
+				link.joints.push(joint);
+			}
+		}
+	}
+}
+
+/// Merges the contents of the `child` into the `link`, where `offset` is the transform from the `link` to the `child`.
+fn merge_link(link: &mut LinkBuilder, child: LinkBuilder, offset: &Transform) {
+	let offset_transform =
+		|transform: Option<Transform>| Some(offset.compose(&transform.unwrap_or_default()));
+
+	link.visuals
+		.extend(child.visuals.into_iter().map(|mut visual| {
+			visual.transform = offset_transform(visual.transform);
+			visual
+		}));
+	link.colliders
+		.extend(child.colliders.into_iter().map(|mut collider| {
+			collider.transform = offset_transform(collider.transform);
+			collider
+		}));
+
+	link.intertial = match (link.intertial, child.intertial) {
+		(inertial, None) => inertial,
+		(None, Some(child_inertial)) => Some(Inertial {
+			transform: offset_transform(child_inertial.transform),
+			..child_inertial
+		}),
+		(Some(inertial), Some(child_inertial)) => Some(
+			(MassProperties::from_inertial(&inertial, &Isometry3::identity())
+				+ MassProperties::from_inertial(&child_inertial, &offset.to_isometry()))
+			.to_inertial(),
+		),
+	};
+
+	link.joints
+		.extend(child.joints.into_iter().map(|mut joint| {
+			if let JointTransformMode::Direct(transform) = &joint.transform {
+				joint.transform = JointTransformMode::Direct(offset.compose(transform));
+			}
+			joint
+		}));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Frame, LumpConfig};
+	use crate::{
+		float::consts::FRAC_PI_2,
+		kinematics::JointState,
+		link_data::{
+			geometry::{BoxGeometry, SphereGeometry},
+			Collision, Inertial, Visual,
+		},
+		prelude::*,
+		Float, JointBuilder, JointType, Link, Robot, SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	fn weight(mass: Float) -> Inertial {
+		Inertial {
+			mass,
+			ixx: 0.1,
+			iyy: 0.1,
+			izz: 0.1,
+			..Default::default()
+		}
+	}
+
+	/// A base with a bracket, which carries a sensor and a rotating hand with a tool.
+	fn robot() -> Robot {
+		let robot = Link::builder("base")
+			.add_intertial(weight(2.))
+			.build_tree()
+			.to_robot("arm");
+
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("mount", JointType::Fixed)
+					.add_origin_offset((1., 0., 0.))
+					.add_origin_rotation((0., 0., FRAC_PI_2)),
+				Link::builder("bracket")
+					.add_intertial(weight(2.))
+					.add_visual(Visual::builder(BoxGeometry::new(0.1, 0.2, 0.3)))
+					.add_collider(
+						Collision::builder(SphereGeometry::new(0.5))
+							.transformed(Transform::new_translation(0., 0., 0.25)),
+					),
+			)
+			.unwrap();
+
+		let bracket = robot.get_link("bracket").unwrap();
+		bracket
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("sensor_mount", JointType::Fixed)
+					.add_origin_offset((0., 0., 0.5)),
+				Link::builder("sensor"),
+			)
+			.unwrap();
+		bracket
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_continuous("wrist")
+					.with_axis((0., 0., 1.))
+					.add_transform(Transform::new_translation(0., 1., 0.)),
+				Link::builder("hand").add_intertial(weight(1.)),
+			)
+			.unwrap();
+
+		robot
+			.get_link("hand")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("tool_mount", JointType::Fixed).add_origin_offset((0.2, 0., 0.)),
+				Link::builder("tool"),
+			)
+			.unwrap();
+
+		robot
+	}
+
+	fn assert_translation(transform: Transform, expected: (Float, Float, Float)) {
+		let (x, y, z) = transform.translation.unwrap();
+		assert!(
+			(x - expected.0).abs() < 1e-5
+				&& (y - expected.1).abs() < 1e-5
+				&& (z - expected.2).abs() < 1e-5,
+			"{:?} != {:?}",
+			(x, y, z),
+			expected
+		);
+	}
+
+	#[test]
+	fn lump_fixed_joints() {
+		let robot = robot();
+		let lumped = robot
+			.lump_fixed_joints(&LumpConfig {
+				keep_joints: vec![String::from("tool_mount")],
+				preserve_frames: false,
+			})
+			.unwrap();
+		let simplified = lumped.robot();
+
+		assert_eq!(simplified.name(), "arm");
+		assert!(simplified.get_link("bracket").is_none());
+		assert!(simplified.get_link("sensor").is_none());
+		assert!(simplified.get_joint("mount").is_none());
+		assert!(simplified.get_link("tool").is_some());
+		assert!(simplified.frames().is_empty());
+
+		let base = simplified.get_root_link();
+		let base = base.read().unwrap();
+		assert_eq!(base.visuals().len(), 1);
+		assert_eq!(base.colliders().len(), 1);
+		assert_eq!(
+			base.visuals()[0].transform(),
+			Some(&Transform::new((1., 0., 0.), (0., 0., FRAC_PI_2)))
+		);
+		assert_translation(*base.colliders()[0].transform().unwrap(), (1., 0., 0.25));
+		assert_eq!(
+			base.joints()[0].read().unwrap().name(),
+			"wrist",
+			"The wrist is re-anchored to the base"
+		);
+
+		// The kinematics and the mass properties stay the same.
+		for position in [0., 0.4, -2.] {
+			let joint_state = JointState::new().with_position("wrist", position);
+
+			for link in ["hand", "tool"] {
+				assert_translation(
+					simplified.link_pose(link, &joint_state).unwrap(),
+					robot
+						.link_pose(link, &joint_state)
+						.unwrap()
+						.translation
+						.unwrap(),
+				);
+			}
+		}
+
+		assert_eq!(simplified.total_mass(), robot.total_mass());
+		let original = robot
+			.mass_properties("base", "base", &JointState::new())
+			.unwrap();
+		let merged = simplified
+			.mass_properties("base", "base", &JointState::new())
+			.unwrap();
+		assert!((original.inertia() - merged.inertia()).amax() < 1e-5);
+		// The hand is at the origin of the base, since the bracket is turned to the left.
+		let (x, y, z) = merged.center_of_mass();
+		assert_translation(Transform::new_translation(x, y, z), (0.4, 0., 0.));
+	}
+
+	#[test]
+	fn frames() {
+		let lumped = robot()
+			.lump_fixed_joints(&LumpConfig {
+				preserve_frames: true,
+				..Default::default()
+			})
+			.unwrap();
+
+		assert_eq!(
+			lumped.frames().keys().collect::<Vec<_>>(),
+			["bracket", "sensor", "tool"]
+		);
+		assert_eq!(
+			lumped.frame("bracket"),
+			Some(&Frame {
+				attached_to: String::from("base"),
+				transform: Transform::new((1., 0., 0.), (0., 0., FRAC_PI_2)),
+			})
+		);
+		// The sensor was merged into the bracket first, which moved it along with the bracket.
+		let sensor = lumped.frame("sensor").unwrap();
+		assert_eq!(sensor.attached_to, "base");
+		assert_translation(sensor.transform, (1., 0., 0.5));
+		assert_eq!(lumped.frame("tool").unwrap().attached_to, "hand");
+		assert_eq!(lumped.frame("hand"), None);
+
+		assert_eq!(lumped.robot().frames(), lumped.frames());
+	}
+
+	#[cfg(feature = "sdf")]
+	#[test]
+	fn sdf_frames() {
+		use crate::to_rdf::{
+			to_sdf::{to_sdf, SDFConfig, SDFVersion},
+			xml_writer_to_string,
+		};
+
+		let robot = Link::builder("base").build_tree().to_robot("rover");
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("camera_mount", JointType::Fixed)
+					.add_origin_offset((0.5, 0., 0.2)),
+				Link::builder("camera"),
+			)
+			.unwrap();
+		let lumped = robot
+			.lump_fixed_joints(&LumpConfig {
+				preserve_frames: true,
+				..Default::default()
+			})
+			.unwrap()
+			.into_robot();
+
+		assert_eq!(
+			xml_writer_to_string(to_sdf(&lumped, SDFConfig::default()).unwrap()),
+			String::from(
+				"\u{feff}<?xml version=\"1.0\"?>\
+				<sdf version=\"1.9\"><model name=\"rover\">\
+					<link name=\"base\"></link>\
+					<frame name=\"camera\" attached_to=\"base\"><pose relative_to=\"base\">0.5 0 0.2 0 0 0</pose></frame>\
+				</model></sdf>"
+			)
+		);
+
+		// SDFormat 1.6 can not attach frames to links.
+		assert_eq!(
+			xml_writer_to_string(
+				to_sdf(
+					&lumped,
+					SDFConfig {
+						sdf_version: SDFVersion::V1_6,
+						..Default::default()
+					}
+				)
+				.unwrap()
+			),
+			String::from(
+				"\u{feff}<?xml version=\"1.0\"?>\
+				<sdf version=\"1.6\"><model name=\"rover\"><link name=\"base\"></link></model></sdf>"
+			)
+		);
+	}
+}
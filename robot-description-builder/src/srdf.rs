@@ -0,0 +1,1053 @@
+//! The semantic description of a robot, in the style of the [Semantic Robot Description Format (SRDF)](http://wiki.ros.org/srdf) used by [MoveIt](https://moveit.ros.org/).
+//!
+//! A [`SemanticDescription`] is created with a [`SemanticDescriptionBuilder`], which refers to the `Link`s and `Joint`s by name.
+//! When it is [built](SemanticDescriptionBuilder::build) against a [`KinematicInterface`] implementor the names are resolved into references to the `Link`s and `Joint`s
+//! and the description is validated against the kinematic tree.
+//! The references are written with the current names of the `Link`s and `Joint`s, so the SRDF can not drift out of sync with the URDF.
+//!
+//! The `SemanticDescription` can be written with [`to_srdf`](crate::to_rdf::to_srdf::to_srdf).
+use std::{
+	collections::HashSet,
+	fmt,
+	sync::{Arc, Weak},
+};
+
+use thiserror::Error;
+
+pub use crate::collision::DisabledCollisionReason;
+use crate::{
+	cluster_objects::KinematicInterface,
+	collision::CollisionMatrix,
+	joint::{Joint, JointType},
+	kinematics::KinematicChain,
+	link::{link_data::LinkParent, Link},
+	utils::WeakLock,
+	Float,
+};
+
+/// An error, which can occur when a [`SemanticDescriptionBuilder`] is validated against a kinematic tree.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum BuildSRDFError {
+	/// A `Link` with the specified name does not exist in the kinematic tree.
+	#[error("Could not find Link \"{0}\"")]
+	InvalidLink(String),
+	/// A `Joint` with the specified name does not exist in the kinematic tree.
+	#[error("Could not find Joint \"{0}\"")]
+	InvalidJoint(String),
+	/// A planning group with the specified name has not been defined.
+	#[error("Could not find planning group \"{0}\"")]
+	InvalidGroup(String),
+	/// A planning group with the specified name has been defined more than once.
+	#[error("The planning group \"{0}\" is defined more than once")]
+	DuplicateGroup(String),
+	/// A planning group contains itself through its subgroups.
+	#[error("The planning group \"{0}\" contains itself through its subgroups")]
+	CyclicGroup(String),
+	/// The `base` link of a chain is not an ancestor of its `tip` link.
+	#[error("The chain from \"{base}\" to \"{tip}\" does not exist, since \"{base}\" is not an ancestor of \"{tip}\"")]
+	InvalidChain { base: String, tip: String },
+	/// A group state sets a `Joint` which is not a part of its planning group.
+	#[error("The Joint \"{joint}\" is not a part of the planning group \"{group}\"")]
+	JointNotInGroup { joint: String, group: String },
+	/// A group state sets the wrong number of values for a `Joint`.
+	#[error("The Joint \"{joint}\" takes {expected} value(s), but {found} were given")]
+	InvalidJointValues {
+		joint: String,
+		expected: usize,
+		found: usize,
+	},
+	/// The child link of a virtual joint is not the root link of the kinematic tree.
+	#[error("The virtual joint \"{joint}\" must connect to the root Link, not \"{link}\"")]
+	VirtualJointNotAtRoot { joint: String, link: String },
+}
+
+/// The type of a [`VirtualJoint`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum VirtualJointType {
+	/// The robot is fixed to the parent frame. This is the default.
+	#[default]
+	Fixed,
+	/// The robot can move freely in the plane of the parent frame, like a mobile base.
+	Planar,
+	/// The robot can move freely in all 6 degrees of freedom.
+	Floating,
+}
+
+impl fmt::Display for VirtualJointType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Fixed => write!(f, "fixed"),
+			Self::Planar => write!(f, "planar"),
+			Self::Floating => write!(f, "floating"),
+		}
+	}
+}
+
+/// A builder for a [`VirtualJoint`], which connects the root `Link` of the robot to a frame outside of the robot.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VirtualJointBuilder {
+	name: String,
+	joint_type: VirtualJointType,
+	parent_frame: String,
+	child_link: String,
+}
+
+impl VirtualJointBuilder {
+	/// Creates a new `VirtualJointBuilder`, which connects the `child_link` to the external `parent_frame`.
+	pub fn new(
+		name: impl Into<String>,
+		joint_type: VirtualJointType,
+		parent_frame: impl Into<String>,
+		child_link: impl Into<String>,
+	) -> Self {
+		Self {
+			name: name.into(),
+			joint_type,
+			parent_frame: parent_frame.into(),
+			child_link: child_link.into(),
+		}
+	}
+}
+
+/// A builder for a [`PlanningGroup`].
+///
+/// A planning group consists of `Link`s, `Joint`s, chains and other planning groups.
+/// Adding a `Link` also adds the `Joint` of which it is the child.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::srdf::PlanningGroupBuilder;
+/// let arm = PlanningGroupBuilder::new("arm").add_chain("base_link", "tool0");
+/// let hand = PlanningGroupBuilder::new("hand")
+///     .add_link("hand")
+///     .add_link("left_finger")
+///     .add_joint("finger_joint");
+/// let both = PlanningGroupBuilder::new("arm_with_hand")
+///     .add_subgroup("arm")
+///     .add_subgroup("hand");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PlanningGroupBuilder {
+	name: String,
+	links: Vec<String>,
+	joints: Vec<String>,
+	chains: Vec<(String, String)>,
+	subgroups: Vec<String>,
+}
+
+impl PlanningGroupBuilder {
+	/// Creates a new empty `PlanningGroupBuilder`.
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			links: Vec::new(),
+			joints: Vec::new(),
+			chains: Vec::new(),
+			subgroups: Vec::new(),
+		}
+	}
+
+	/// Adds the `Link` with the specified name to the planning group.
+	pub fn add_link(mut self, link_name: impl Into<String>) -> Self {
+		self.links.push(link_name.into());
+		self
+	}
+
+	/// Adds the `Joint` with the specified name to the planning group.
+	pub fn add_joint(mut self, joint_name: impl Into<String>) -> Self {
+		self.joints.push(joint_name.into());
+		self
+	}
+
+	/// Adds all `Joint`s between the `base_link` and the `tip_link` to the planning group.
+	pub fn add_chain(mut self, base_link: impl Into<String>, tip_link: impl Into<String>) -> Self {
+		self.chains.push((base_link.into(), tip_link.into()));
+		self
+	}
+
+	/// Adds the planning group with the specified name to the planning group.
+	pub fn add_subgroup(mut self, group_name: impl Into<String>) -> Self {
+		self.subgroups.push(group_name.into());
+		self
+	}
+
+	/// Gets a reference to the name of the current `PlanningGroupBuilder`.
+	pub fn name(&self) -> &String {
+		&self.name
+	}
+}
+
+/// A builder for a [`GroupState`], a named configuration of a planning group.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GroupStateBuilder {
+	name: String,
+	group: String,
+	joint_values: Vec<(String, Vec<Float>)>,
+}
+
+impl GroupStateBuilder {
+	/// Creates a new `GroupStateBuilder` for the planning group with the name `group`.
+	pub fn new(name: impl Into<String>, group: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			group: group.into(),
+			joint_values: Vec::new(),
+		}
+	}
+
+	/// Sets the position of the single degree of freedom `Joint` with the specified name.
+	pub fn add_joint_value(self, joint_name: impl Into<String>, value: Float) -> Self {
+		self.add_joint_values(joint_name, vec![value])
+	}
+
+	/// Sets the positions of the `Joint` with the specified name.
+	///
+	/// A [`Planar`](JointType::Planar) `Joint` takes 3 values (x, y, theta)
+	/// and a [`Floating`](JointType::Floating) `Joint` takes 7 values (x, y, z and the quaternion x, y, z, w).
+	pub fn add_joint_values(mut self, joint_name: impl Into<String>, values: Vec<Float>) -> Self {
+		self.joint_values.push((joint_name.into(), values));
+		self
+	}
+}
+
+/// A builder for an [`EndEffector`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EndEffectorBuilder {
+	name: String,
+	parent_link: String,
+	group: String,
+	parent_group: Option<String>,
+}
+
+impl EndEffectorBuilder {
+	/// Creates a new `EndEffectorBuilder`, which makes the planning group `group` an end effector attached to the `parent_link`.
+	pub fn new(
+		name: impl Into<String>,
+		parent_link: impl Into<String>,
+		group: impl Into<String>,
+	) -> Self {
+		Self {
+			name: name.into(),
+			parent_link: parent_link.into(),
+			group: group.into(),
+			parent_group: None,
+		}
+	}
+
+	/// Sets the planning group, which contains the `parent_link`.
+	pub fn with_parent_group(mut self, parent_group: impl Into<String>) -> Self {
+		self.parent_group = Some(parent_group.into());
+		self
+	}
+}
+
+/// A builder for a [`SemanticDescription`].
+///
+/// All `Link`s and `Joint`s are referred to by their full name, including the [`GroupID`](crate::identifiers::GroupID) delimiters.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     prelude::*,
+/// #     srdf::*,
+/// #     JointBuilder, JointType, Link,
+/// # };
+/// let robot = Link::builder("base_link").build_tree().to_robot("arm");
+/// robot
+///     .get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(JointBuilder::new("shoulder", JointType::Continuous), Link::builder("upper_arm"))
+///     .unwrap();
+///
+/// let description = SemanticDescriptionBuilder::new("arm")
+///     .add_virtual_joint(VirtualJointBuilder::new("world_joint", VirtualJointType::Fixed, "world", "base_link"))
+///     .add_group(PlanningGroupBuilder::new("arm").add_chain("base_link", "upper_arm"))
+///     .add_group_state(GroupStateBuilder::new("home", "arm").add_joint_value("shoulder", 0.))
+///     .disable_collisions("base_link", "upper_arm", DisabledCollisionReason::Adjacent)
+///     .build(&robot)
+///     .unwrap();
+///
+/// assert_eq!(description.group_joints("arm").unwrap().len(), 1);
+///
+/// // The description is validated against the tree.
+/// assert_eq!(
+///     SemanticDescriptionBuilder::new("arm")
+///         .add_group(PlanningGroupBuilder::new("arm").add_chain("upper_arm", "base_link"))
+///         .build(&robot)
+///         .unwrap_err(),
+///     BuildSRDFError::InvalidChain { base: "upper_arm".into(), tip: "base_link".into() }
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct SemanticDescriptionBuilder {
+	name: String,
+	virtual_joints: Vec<VirtualJointBuilder>,
+	groups: Vec<PlanningGroupBuilder>,
+	group_states: Vec<GroupStateBuilder>,
+	end_effectors: Vec<EndEffectorBuilder>,
+	passive_joints: Vec<String>,
+	disabled_collisions: Vec<(String, String, DisabledCollisionReason)>,
+}
+
+impl SemanticDescriptionBuilder {
+	/// Creates a new empty `SemanticDescriptionBuilder`.
+	///
+	/// The `name` should be equal to the name of the `Robot` it describes.
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			virtual_joints: Vec::new(),
+			groups: Vec::new(),
+			group_states: Vec::new(),
+			end_effectors: Vec::new(),
+			passive_joints: Vec::new(),
+			disabled_collisions: Vec::new(),
+		}
+	}
+
+	/// Adds a virtual joint to the description.
+	pub fn add_virtual_joint(mut self, virtual_joint: VirtualJointBuilder) -> Self {
+		self.virtual_joints.push(virtual_joint);
+		self
+	}
+
+	/// Adds a planning group to the description.
+	pub fn add_group(mut self, group: PlanningGroupBuilder) -> Self {
+		self.groups.push(group);
+		self
+	}
+
+	/// Adds a group state to the description.
+	pub fn add_group_state(mut self, group_state: GroupStateBuilder) -> Self {
+		self.group_states.push(group_state);
+		self
+	}
+
+	/// Adds an end effector to the description.
+	pub fn add_end_effector(mut self, end_effector: EndEffectorBuilder) -> Self {
+		self.end_effectors.push(end_effector);
+		self
+	}
+
+	/// Marks the `Joint` with the specified name as passive, which means it can not be actuated.
+	pub fn add_passive_joint(mut self, joint_name: impl Into<String>) -> Self {
+		self.passive_joints.push(joint_name.into());
+		self
+	}
+
+	/// Disables the collision checking between the `Link`s with the specified names.
+	pub fn disable_collisions(
+		mut self,
+		link1: impl Into<String>,
+		link2: impl Into<String>,
+		reason: DisabledCollisionReason,
+	) -> Self {
+		self.disabled_collisions
+			.push((link1.into(), link2.into(), reason));
+		self
+	}
+
+	/// Disables the collision checking between all pairs of `Link`s in the [`CollisionMatrix`], with their reasons.
+	pub fn disable_collision_matrix(mut self, collision_matrix: &CollisionMatrix) -> Self {
+		self.disabled_collisions.extend(
+			collision_matrix
+				.iter()
+				.map(|(link1, link2, reason)| (link1.clone(), link2.clone(), reason)),
+		);
+		self
+	}
+
+	/// Resolves all names in the `tree` and validates the description against it.
+	pub fn build(
+		self,
+		tree: &impl KinematicInterface,
+	) -> Result<SemanticDescription, BuildSRDFError> {
+		let link = |name: &String| {
+			tree.get_link(name)
+				.map(|link| Arc::downgrade(&link))
+				.ok_or_else(|| BuildSRDFError::InvalidLink(name.clone()))
+		};
+		let joint = |name: &String| {
+			tree.get_joint(name)
+				.map(|joint| Arc::downgrade(&joint))
+				.ok_or_else(|| BuildSRDFError::InvalidJoint(name.clone()))
+		};
+
+		let mut group_names = HashSet::new();
+		if let Some(group) = self
+			.groups
+			.iter()
+			.find(|group| !group_names.insert(group.name.as_str()))
+		{
+			return Err(BuildSRDFError::DuplicateGroup(group.name.clone()));
+		}
+		let group = |name: &String| match group_names.contains(name.as_str()) {
+			true => Ok(name.clone()),
+			false => Err(BuildSRDFError::InvalidGroup(name.clone())),
+		};
+
+		let root_link = tree.get_root_link();
+		let virtual_joints = self
+			.virtual_joints
+			.into_iter()
+			.map(|virtual_joint| {
+				let child_link = link(&virtual_joint.child_link)?;
+				match Weak::ptr_eq(&child_link, &Arc::downgrade(&root_link)) {
+					true => Ok(VirtualJoint {
+						name: virtual_joint.name,
+						joint_type: virtual_joint.joint_type,
+						parent_frame: virtual_joint.parent_frame,
+						child_link,
+					}),
+					false => Err(BuildSRDFError::VirtualJointNotAtRoot {
+						joint: virtual_joint.name,
+						link: virtual_joint.child_link,
+					}),
+				}
+			})
+			.collect::<Result<_, _>>()?;
+
+		let groups = self
+			.groups
+			.iter()
+			.map(|planning_group| {
+				Ok(PlanningGroup {
+					name: planning_group.name.clone(),
+					links: planning_group
+						.links
+						.iter()
+						.map(link)
+						.collect::<Result<_, _>>()?,
+					joints: planning_group
+						.joints
+						.iter()
+						.map(joint)
+						.collect::<Result<_, _>>()?,
+					chains: planning_group
+						.chains
+						.iter()
+						.map(|(base, tip)| {
+							let chain = (link(base)?, link(tip)?);
+							match serial_chain(&chain.0, &chain.1) {
+								Some(_) => Ok(chain),
+								None => Err(BuildSRDFError::InvalidChain {
+									base: base.clone(),
+									tip: tip.clone(),
+								}),
+							}
+						})
+						.collect::<Result<_, _>>()?,
+					subgroups: planning_group
+						.subgroups
+						.iter()
+						.map(group)
+						.collect::<Result<_, _>>()?,
+				})
+			})
+			.collect::<Result<Vec<_>, BuildSRDFError>>()?;
+
+		let mut description = SemanticDescription {
+			name: self.name,
+			virtual_joints,
+			groups,
+			group_states: Vec::new(),
+			end_effectors: Vec::new(),
+			passive_joints: self
+				.passive_joints
+				.iter()
+				.map(joint)
+				.collect::<Result<_, _>>()?,
+			disabled_collisions: self
+				.disabled_collisions
+				.iter()
+				.map(|(link1, link2, reason)| {
+					Ok(DisabledCollision {
+						link1: link(link1)?,
+						link2: link(link2)?,
+						reason: *reason,
+					})
+				})
+				.collect::<Result<_, BuildSRDFError>>()?,
+		};
+
+		for planning_group in description.groups.iter() {
+			description.collect_group_joints(&planning_group.name, &mut Vec::new())?;
+		}
+
+		description.group_states = self
+			.group_states
+			.into_iter()
+			.map(|group_state| {
+				let group_joints = description
+					.collect_group_joints(&group(&group_state.group)?, &mut Vec::new())?;
+
+				Ok(GroupState {
+					joint_values: group_state
+						.joint_values
+						.into_iter()
+						.map(|(joint_name, values)| {
+							let joint = joint(&joint_name)?;
+							if !group_joints
+								.iter()
+								.any(|member| Weak::ptr_eq(member, &joint))
+							{
+								return Err(BuildSRDFError::JointNotInGroup {
+									joint: joint_name,
+									group: group_state.group.clone(),
+								});
+							}
+
+							// Unwrap is Ok, since the joint was just found in the tree.
+							let expected =
+								match joint.upgrade().unwrap().read().unwrap().joint_type() {
+									JointType::Fixed => 0,
+									JointType::Revolute
+									| JointType::Continuous
+									| JointType::Prismatic => 1,
+									JointType::Planar => 3,
+									JointType::Floating => 7,
+								};
+							match values.len() == expected {
+								true => Ok((joint, values)),
+								false => Err(BuildSRDFError::InvalidJointValues {
+									joint: joint_name,
+									expected,
+									found: values.len(),
+								}),
+							}
+						})
+						.collect::<Result<_, _>>()?,
+					name: group_state.name,
+					group: group_state.group,
+				})
+			})
+			.collect::<Result<_, BuildSRDFError>>()?;
+
+		description.end_effectors = self
+			.end_effectors
+			.into_iter()
+			.map(|end_effector| {
+				Ok(EndEffector {
+					parent_link: link(&end_effector.parent_link)?,
+					group: group(&end_effector.group)?,
+					parent_group: end_effector.parent_group.as_ref().map(group).transpose()?,
+					name: end_effector.name,
+				})
+			})
+			.collect::<Result<_, BuildSRDFError>>()?;
+
+		Ok(description)
+	}
+}
+
+/// Gets the [`KinematicChain`] from the `base` link to the `tip` link.
+///
+/// Returns `None` if the `base` is not an ancestor of the `tip`.
+fn serial_chain(base: &WeakLock<Link>, tip: &WeakLock<Link>) -> Option<KinematicChain> {
+	KinematicChain::between(base.upgrade()?, tip.upgrade()?).filter(KinematicChain::is_serial)
+}
+
+/// A virtual joint, which connects the root `Link` of the robot to a frame outside of the robot.
+#[derive(Debug, Clone)]
+pub struct VirtualJoint {
+	name: String,
+	joint_type: VirtualJointType,
+	parent_frame: String,
+	child_link: WeakLock<Link>,
+}
+
+impl VirtualJoint {
+	/// Gets a reference to the name of the current `VirtualJoint`.
+	pub fn name(&self) -> &String {
+		&self.name
+	}
+
+	/// Gets the `VirtualJointType` of the current `VirtualJoint`.
+	pub fn joint_type(&self) -> VirtualJointType {
+		self.joint_type
+	}
+
+	/// Gets a reference to the name of the external frame, to which the robot is connected.
+	pub fn parent_frame(&self) -> &String {
+		&self.parent_frame
+	}
+
+	/// Gets the root `Link` of the robot.
+	pub fn child_link(&self) -> WeakLock<Link> {
+		Weak::clone(&self.child_link)
+	}
+}
+
+/// A planning group, a set of `Joint`s and `Link`s which are planned for together.
+#[derive(Debug, Clone)]
+pub struct PlanningGroup {
+	name: String,
+	links: Vec<WeakLock<Link>>,
+	joints: Vec<WeakLock<Joint>>,
+	chains: Vec<(WeakLock<Link>, WeakLock<Link>)>,
+	subgroups: Vec<String>,
+}
+
+impl PlanningGroup {
+	/// Gets a reference to the name of the current `PlanningGroup`.
+	pub fn name(&self) -> &String {
+		&self.name
+	}
+
+	/// Gets a reference to the `Link`s which are explicitly added to the `PlanningGroup`.
+	pub fn links(&self) -> &Vec<WeakLock<Link>> {
+		&self.links
+	}
+
+	/// Gets a reference to the `Joint`s which are explicitly added to the `PlanningGroup`.
+	pub fn joints(&self) -> &Vec<WeakLock<Joint>> {
+		&self.joints
+	}
+
+	/// Gets a reference to the chains of the `PlanningGroup`, as pairs of base and tip `Link`s.
+	pub fn chains(&self) -> &Vec<(WeakLock<Link>, WeakLock<Link>)> {
+		&self.chains
+	}
+
+	/// Gets a reference to the names of the planning groups contained in the `PlanningGroup`.
+	pub fn subgroups(&self) -> &Vec<String> {
+		&self.subgroups
+	}
+}
+
+/// A named configuration of a planning group.
+#[derive(Debug, Clone)]
+pub struct GroupState {
+	name: String,
+	group: String,
+	joint_values: Vec<(WeakLock<Joint>, Vec<Float>)>,
+}
+
+impl GroupState {
+	/// Gets a reference to the name of the current `GroupState`.
+	pub fn name(&self) -> &String {
+		&self.name
+	}
+
+	/// Gets a reference to the name of the planning group, to which the `GroupState` belongs.
+	pub fn group(&self) -> &String {
+		&self.group
+	}
+
+	/// Gets a reference to the `Joint`s and their positions.
+	pub fn joint_values(&self) -> &Vec<(WeakLock<Joint>, Vec<Float>)> {
+		&self.joint_values
+	}
+}
+
+/// A planning group, which is an end effector (e.g. a gripper).
+#[derive(Debug, Clone)]
+pub struct EndEffector {
+	name: String,
+	parent_link: WeakLock<Link>,
+	group: String,
+	parent_group: Option<String>,
+}
+
+impl EndEffector {
+	/// Gets a reference to the name of the current `EndEffector`.
+	pub fn name(&self) -> &String {
+		&self.name
+	}
+
+	/// Gets the `Link` to which the end effector is attached.
+	pub fn parent_link(&self) -> WeakLock<Link> {
+		Weak::clone(&self.parent_link)
+	}
+
+	/// Gets a reference to the name of the planning group of the end effector.
+	pub fn group(&self) -> &String {
+		&self.group
+	}
+
+	/// Gets a reference to the name of the planning group which contains the `parent_link`, if specified.
+	pub fn parent_group(&self) -> Option<&String> {
+		self.parent_group.as_ref()
+	}
+}
+
+/// A pair of `Link`s for which the collision checking is disabled.
+#[derive(Debug, Clone)]
+pub struct DisabledCollision {
+	link1: WeakLock<Link>,
+	link2: WeakLock<Link>,
+	reason: DisabledCollisionReason,
+}
+
+impl DisabledCollision {
+	/// Gets the first `Link` of the pair.
+	pub fn link1(&self) -> WeakLock<Link> {
+		Weak::clone(&self.link1)
+	}
+
+	/// Gets the second `Link` of the pair.
+	pub fn link2(&self) -> WeakLock<Link> {
+		Weak::clone(&self.link2)
+	}
+
+	/// Gets the reason why the collisions are disabled.
+	pub fn reason(&self) -> DisabledCollisionReason {
+		self.reason
+	}
+}
+
+/// The semantic description of a robot, validated against its kinematic tree.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct SemanticDescription {
+	name: String,
+	virtual_joints: Vec<VirtualJoint>,
+	groups: Vec<PlanningGroup>,
+	group_states: Vec<GroupState>,
+	end_effectors: Vec<EndEffector>,
+	passive_joints: Vec<WeakLock<Joint>>,
+	disabled_collisions: Vec<DisabledCollision>,
+}
+
+impl SemanticDescription {
+	/// Gets a reference to the name of the described robot.
+	pub fn name(&self) -> &String {
+		&self.name
+	}
+
+	/// Gets a reference to the virtual joints.
+	pub fn virtual_joints(&self) -> &Vec<VirtualJoint> {
+		&self.virtual_joints
+	}
+
+	/// Gets a reference to the planning groups.
+	pub fn groups(&self) -> &Vec<PlanningGroup> {
+		&self.groups
+	}
+
+	/// Gets a reference to the group states.
+	pub fn group_states(&self) -> &Vec<GroupState> {
+		&self.group_states
+	}
+
+	/// Gets a reference to the end effectors.
+	pub fn end_effectors(&self) -> &Vec<EndEffector> {
+		&self.end_effectors
+	}
+
+	/// Gets a reference to the passive `Joint`s.
+	pub fn passive_joints(&self) -> &Vec<WeakLock<Joint>> {
+		&self.passive_joints
+	}
+
+	/// Gets a reference to the pairs of `Link`s for which the collision checking is disabled.
+	pub fn disabled_collisions(&self) -> &Vec<DisabledCollision> {
+		&self.disabled_collisions
+	}
+
+	/// Gets the planning group with the specified `name`.
+	pub fn get_group(&self, name: &str) -> Option<&PlanningGroup> {
+		self.groups.iter().find(|group| group.name == name)
+	}
+
+	/// Gets all `Joint`s in the planning group with the specified `name`, including the `Joint`s of its links, chains and subgroups.
+	///
+	/// If the planning group does not exist `None` is returned.
+	pub fn group_joints(&self, name: &str) -> Option<Vec<WeakLock<Joint>>> {
+		self.collect_group_joints(name, &mut Vec::new()).ok()
+	}
+
+	/// Collects the `Joint`s of a planning group, the `visited` groups are tracked to detect cycles.
+	fn collect_group_joints(
+		&self,
+		name: &str,
+		visited: &mut Vec<String>,
+	) -> Result<Vec<WeakLock<Joint>>, BuildSRDFError> {
+		let planning_group = self
+			.get_group(name)
+			.ok_or_else(|| BuildSRDFError::InvalidGroup(name.to_string()))?;
+
+		if visited.iter().any(|visited| visited == name) {
+			return Err(BuildSRDFError::CyclicGroup(name.to_string()));
+		}
+		visited.push(name.to_string());
+
+		let mut joints = planning_group.joints.clone();
+		joints.extend(planning_group.links.iter().filter_map(|link| {
+			match link.upgrade()?.read().unwrap().parent() {
+				LinkParent::Joint(joint) => Some(Weak::clone(joint)),
+				LinkParent::KinematicTree(_) => None,
+			}
+		}));
+		for (base, tip) in planning_group.chains.iter() {
+			// The `Joint`s of a chain are collected starting at the tip.
+			if let Some(chain) = serial_chain(base, tip) {
+				joints.extend(chain.joints().iter().rev().cloned());
+			}
+		}
+		for subgroup in planning_group.subgroups.iter() {
+			joints.extend(self.collect_group_joints(subgroup, visited)?);
+		}
+		visited.pop();
+
+		let mut unique_joints: Vec<WeakLock<Joint>> = Vec::with_capacity(joints.len());
+		for joint in joints {
+			if !unique_joints
+				.iter()
+				.any(|other| Weak::ptr_eq(other, &joint))
+			{
+				unique_joints.push(joint);
+			}
+		}
+
+		Ok(unique_joints)
+	}
+}
+
+/// Gets the `Link` or `Joint` name, as it is written in the descriptions.
+pub(crate) fn referenced_name<T>(
+	reference: &WeakLock<T>,
+	name: impl FnOnce(&T) -> &String,
+) -> String {
+	use crate::identifiers::GroupID;
+
+	// Unwrap is Ok, since the `SemanticDescription` was validated against a tree which should still exist.
+	name(&reference.upgrade().unwrap().read().unwrap()).display()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		BuildSRDFError, EndEffectorBuilder, GroupStateBuilder, PlanningGroupBuilder,
+		SemanticDescriptionBuilder, VirtualJointBuilder, VirtualJointType,
+	};
+	use crate::{
+		collision::{CollisionMatrix, CollisionMatrixConfig, DisabledCollisionReason},
+		prelude::*,
+		JointBuilder, JointType, Link, Robot,
+	};
+	use test_log::test;
+
+	/// A robot with an arm of two revolute joints and a gripper with a prismatic finger.
+	fn arm_robot() -> Robot {
+		let robot = Link::builder("base_link").build_tree().to_robot("arm_bot");
+
+		let mut parent = robot.get_root_link();
+		for (joint, joint_type, link) in [
+			("shoulder", JointType::Revolute, "upper_arm"),
+			("elbow", JointType::Revolute, "forearm"),
+			("wrist", JointType::Fixed, "hand"),
+			("finger_joint", JointType::Prismatic, "finger"),
+		] {
+			parent
+				.write()
+				.unwrap()
+				.try_attach_child(JointBuilder::new(joint, joint_type), Link::builder(link))
+				.unwrap();
+			parent = robot.get_link(link).unwrap();
+		}
+
+		robot
+	}
+
+	fn builder() -> SemanticDescriptionBuilder {
+		SemanticDescriptionBuilder::new("arm_bot")
+			.add_group(PlanningGroupBuilder::new("arm").add_chain("base_link", "hand"))
+			.add_group(
+				PlanningGroupBuilder::new("gripper")
+					.add_link("hand")
+					.add_link("finger"),
+			)
+	}
+
+	#[test]
+	fn groups() {
+		let robot = arm_robot();
+		let description = builder()
+			.add_group(
+				PlanningGroupBuilder::new("everything")
+					.add_subgroup("arm")
+					.add_subgroup("gripper"),
+			)
+			.build(&robot)
+			.unwrap();
+
+		let joint_names = |group: &str| -> Vec<String> {
+			description
+				.group_joints(group)
+				.unwrap()
+				.iter()
+				.map(|joint| joint.upgrade().unwrap().read().unwrap().name().clone())
+				.collect()
+		};
+
+		assert_eq!(joint_names("arm"), vec!["wrist", "elbow", "shoulder"]);
+		assert_eq!(joint_names("gripper"), vec!["wrist", "finger_joint"]);
+		assert_eq!(
+			joint_names("everything"),
+			vec!["wrist", "elbow", "shoulder", "finger_joint"]
+		);
+	}
+
+	#[test]
+	fn invalid_groups() {
+		let robot = arm_robot();
+
+		assert_eq!(
+			builder()
+				.add_group(PlanningGroupBuilder::new("arm"))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::DuplicateGroup("arm".into())
+		);
+		assert_eq!(
+			builder()
+				.add_group(PlanningGroupBuilder::new("a").add_subgroup("b"))
+				.add_group(PlanningGroupBuilder::new("b").add_subgroup("a"))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::CyclicGroup("a".into())
+		);
+		assert_eq!(
+			builder()
+				.add_group(PlanningGroupBuilder::new("a").add_subgroup("c"))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::InvalidGroup("c".into())
+		);
+		assert_eq!(
+			builder()
+				.add_group(PlanningGroupBuilder::new("a").add_link("tool0"))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::InvalidLink("tool0".into())
+		);
+		assert_eq!(
+			builder()
+				.add_group(PlanningGroupBuilder::new("a").add_chain("finger", "forearm"))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::InvalidChain {
+				base: "finger".into(),
+				tip: "forearm".into()
+			}
+		);
+	}
+
+	#[test]
+	fn group_states() {
+		let robot = arm_robot();
+
+		assert!(builder()
+			.add_group_state(
+				GroupStateBuilder::new("home", "arm")
+					.add_joint_value("shoulder", 0.)
+					.add_joint_value("elbow", 1.)
+			)
+			.build(&robot)
+			.is_ok());
+		assert_eq!(
+			builder()
+				.add_group_state(
+					GroupStateBuilder::new("home", "arm").add_joint_value("finger_joint", 0.)
+				)
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::JointNotInGroup {
+				joint: "finger_joint".into(),
+				group: "arm".into()
+			}
+		);
+		assert_eq!(
+			builder()
+				.add_group_state(GroupStateBuilder::new("home", "arm").add_joint_value("wrist", 0.))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::InvalidJointValues {
+				joint: "wrist".into(),
+				expected: 0,
+				found: 1
+			}
+		);
+		assert_eq!(
+			builder()
+				.add_group_state(GroupStateBuilder::new("home", "legs"))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::InvalidGroup("legs".into())
+		);
+	}
+
+	#[test]
+	fn end_effectors_and_virtual_joints() {
+		let robot = arm_robot();
+
+		let description = builder()
+			.add_end_effector(
+				EndEffectorBuilder::new("hand", "forearm", "gripper").with_parent_group("arm"),
+			)
+			.add_virtual_joint(VirtualJointBuilder::new(
+				"world_joint",
+				VirtualJointType::Floating,
+				"world",
+				"base_link",
+			))
+			.build(&robot)
+			.unwrap();
+		assert_eq!(
+			description.end_effectors()[0].parent_group(),
+			Some(&"arm".to_string())
+		);
+
+		assert_eq!(
+			builder()
+				.add_end_effector(EndEffectorBuilder::new("hand", "forearm", "hand"))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::InvalidGroup("hand".into())
+		);
+		assert_eq!(
+			builder()
+				.add_virtual_joint(VirtualJointBuilder::new(
+					"world_joint",
+					VirtualJointType::Fixed,
+					"world",
+					"hand",
+				))
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::VirtualJointNotAtRoot {
+				joint: "world_joint".into(),
+				link: "hand".into()
+			}
+		);
+		assert_eq!(
+			builder()
+				.add_passive_joint("hip")
+				.build(&robot)
+				.unwrap_err(),
+			BuildSRDFError::InvalidJoint("hip".into())
+		);
+	}
+
+	#[test]
+	fn collision_matrix() {
+		let robot = arm_robot();
+
+		// Without collision geometry, only the links connected by a joint are disabled.
+		let collision_matrix = CollisionMatrix::compute(&robot, CollisionMatrixConfig::default());
+		let description = builder()
+			.disable_collision_matrix(&collision_matrix)
+			.build(&robot)
+			.unwrap();
+
+		assert_eq!(description.disabled_collisions().len(), 4);
+		assert!(description
+			.disabled_collisions()
+			.iter()
+			.all(|disabled_collision| disabled_collision.reason()
+				== DisabledCollisionReason::Adjacent));
+	}
+}
@@ -5,6 +5,10 @@
 //  - Module
 //  - to_urdf
 //  - to_sdf
+//  - to_mjcf
+//  - to_xacro
+//  - to_srdf
+//  - to_dot
 
 #[cfg(feature = "xml")]
 use quick_xml::Writer;
@@ -17,6 +21,18 @@ pub mod to_urdf;
 #[cfg(feature = "sdf")]
 pub mod to_sdf;
 
+#[cfg(feature = "mjcf")]
+pub mod to_mjcf;
+
+#[cfg(feature = "xacro")]
+pub mod to_xacro;
+
+#[cfg(feature = "srdf")]
+pub mod to_srdf;
+
+#[cfg(feature = "dot")]
+pub mod to_dot;
+
 /// A setting for configuring the style of the generated XML representation.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum XMLMode {
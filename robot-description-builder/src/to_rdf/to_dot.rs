@@ -0,0 +1,378 @@
+//! The infrastructure to draw a kinematic tree as a [Graphviz](https://graphviz.org/) graph in the [DOT language](https://graphviz.org/doc/info/lang.html).
+//!
+//! This is the equivalent of the ROS `urdf_to_graphiz` tool, but directly from the kinematic tree.
+//! The [`Link`](crate::Link)s are drawn as boxes and the [`Joint`](crate::Joint)s are drawn as edges between them,
+//! labeled with their name, [`JointType`](crate::JointType), origin and axis.
+//!
+//! The generated graph can be rendered with Graphviz, e.g. `dot -Tpdf robot.gv -o robot.pdf`.
+use std::{
+	collections::{BTreeSet, HashMap},
+	fmt::Write,
+};
+
+use crate::{
+	cluster_objects::KinematicInterface, identifiers::GroupID, joint::JointType, link::Link,
+	transform::Transform,
+};
+
+/// The fill colors used for the `Link`s of different [`GroupID`]s, which are repeated if there are more `GroupID`s.
+const GROUP_ID_COLORS: [&str; 8] = [
+	"lightblue",
+	"lightgreen",
+	"lightpink",
+	"lightyellow",
+	"lightsalmon",
+	"lightcyan",
+	"plum",
+	"wheat",
+];
+
+/// A Configuration for the exporting of a kinematic tree as a [DOT](https://graphviz.org/doc/info/lang.html) graph.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DOTConfig {
+	/// Merges the `Link`s connected by [`Fixed`](JointType::Fixed) `Joint`s into the node of their parent `Link`.
+	///
+	/// The merged `Link`s are listed in the label of the node and
+	/// the origins of the `Joint`s are written relative to the `Link` of the node.
+	pub collapse_fixed: bool,
+	/// Fills the nodes of the `Link`s with a color per [`GroupID`].
+	pub color_group_ids: bool,
+	/// Draws the mimic relations as dashed edges from the child of the mimicked `Joint` to the child of the mimicking `Joint`.
+	pub show_mimics: bool,
+}
+
+/// A node of the graph, which represents one or more `Link`s.
+#[derive(Debug)]
+struct Node {
+	/// The names of the `Link`s in this node, the first name is the name of the node.
+	links: Vec<String>,
+	group_id: Option<String>,
+}
+
+/// An edge of the graph, which represents a `Joint`.
+#[derive(Debug)]
+struct Edge {
+	parent: usize,
+	child: usize,
+	label: Vec<String>,
+}
+
+/// The graph under construction.
+#[derive(Debug, Default)]
+struct Graph {
+	nodes: Vec<Node>,
+	edges: Vec<Edge>,
+	/// The node of the child `Link` of every drawn `Joint`, indexed by the name of the `Joint`.
+	joint_nodes: HashMap<String, usize>,
+	/// The mimicking `Joint`s with the name of the `Joint` they mimic and the label of the relation.
+	mimics: Vec<(String, String, Vec<String>)>,
+}
+
+impl Graph {
+	fn add_node(&mut self, link: &Link) -> usize {
+		self.nodes.push(Node {
+			links: vec![link.name().display()],
+			group_id: link.name().get_group_id().map(str::to_string),
+		});
+		self.nodes.len() - 1
+	}
+
+	/// Adds the children of the `link` to the graph.
+	///
+	/// - `node` is the node the `link` is drawn in.
+	/// - `offset` is the pose of the `link` relative to the first `Link` of the `node`, if the `link` is merged into the `node`.
+	fn add_children(
+		&mut self,
+		link: &Link,
+		node: usize,
+		offset: Option<Transform>,
+		dot_config: &DOTConfig,
+	) {
+		for joint in link.joints() {
+			let joint = joint.read().unwrap();
+			let child_link = joint.child_link();
+			let child_link = child_link.read().unwrap();
+
+			let transform = match offset {
+				Some(offset) => offset.compose(joint.transform()),
+				None => *joint.transform(),
+			};
+
+			if dot_config.collapse_fixed && joint.joint_type() == JointType::Fixed {
+				self.nodes[node].links.push(child_link.name().display());
+				self.add_children(&child_link, node, Some(transform), dot_config);
+				continue;
+			}
+
+			let child = self.add_node(&child_link);
+			self.joint_nodes.insert(joint.name().clone(), child);
+
+			let mut label = vec![
+				joint.name().display(),
+				format!("({})", joint.joint_type().to_string()),
+			];
+			if let Some((x, y, z)) = transform.translation {
+				label.push(format!("xyz: {} {} {}", x, y, z));
+			}
+			if let Some((roll, pitch, yaw)) = transform.rotation {
+				label.push(format!("rpy: {} {} {}", roll, pitch, yaw));
+			}
+			if let Some((x, y, z)) = joint.axis() {
+				label.push(format!("axis: {} {} {}", x, y, z));
+			}
+			self.edges.push(Edge {
+				parent: node,
+				child,
+				label,
+			});
+
+			if let Some(mimic) = joint.mimic() {
+				if let Some(mimicked_joint) = mimic.joint.upgrade() {
+					let mut label = vec!["mimic".to_string()];
+					if let Some(multiplier) = mimic.multiplier {
+						label.push(format!("multiplier: {}", multiplier));
+					}
+					if let Some(offset) = mimic.offset {
+						label.push(format!("offset: {}", offset));
+					}
+
+					self.mimics.push((
+						joint.name().clone(),
+						mimicked_joint.read().unwrap().name().clone(),
+						label,
+					));
+				}
+			}
+
+			self.add_children(&child_link, child, None, dot_config);
+		}
+	}
+}
+
+/// Quotes and escapes a DOT identifier.
+fn quote(id: &str) -> String {
+	format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quotes and escapes a DOT label with multiple lines.
+fn quote_lines(lines: &[String]) -> String {
+	quote(&lines.join("\n")).replace('\n', "\\n")
+}
+
+/// A function to represent a `KinematicInterface` implementor as a [DOT](https://graphviz.org/doc/info/lang.html) graph.
+///
+/// The `Link`s and `Joint`s are written with their current names, without the [`GroupID`] delimiters.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     prelude::*,
+/// #     to_rdf::to_dot::{to_dot, DOTConfig},
+/// #     Link, SmartJointBuilder, Transform,
+/// # };
+/// let robot = Link::builder("base").build_tree().to_robot("my-robot");
+///
+/// robot
+///     .get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(
+///         SmartJointBuilder::new_continuous("wheel_joint")
+///             .with_axis((0., 1., 0.))
+///             .add_transform(Transform::new_translation(0.5, 0., 0.)),
+///         Link::builder("wheel"),
+///     )
+///     .unwrap();
+///
+/// assert_eq!(
+///     to_dot(&robot, DOTConfig::default()),
+///     r#"digraph G {
+///   node [shape=box];
+///   "base";
+///   "wheel";
+///   "base" -> "wheel" [label="wheel_joint\n(continuous)\nxyz: 0.5 0 0\naxis: 0 1 0"];
+/// }
+/// "#
+/// );
+/// ```
+pub fn to_dot(tree: &impl KinematicInterface, dot_config: DOTConfig) -> String {
+	let mut graph = Graph::default();
+	{
+		let root_link = tree.get_root_link();
+		let root_link = root_link.read().unwrap();
+		let root = graph.add_node(&root_link);
+		graph.add_children(&root_link, root, None, &dot_config);
+	}
+
+	let group_ids: Vec<&String> = graph
+		.nodes
+		.iter()
+		.filter_map(|node| node.group_id.as_ref())
+		.collect::<BTreeSet<_>>()
+		.into_iter()
+		.collect();
+
+	// Writing to a `String` can not fail, so the results are ignored.
+	let mut dot = String::from("digraph G {\n  node [shape=box];\n");
+	for node in graph.nodes.iter() {
+		let mut attributes = Vec::new();
+		if node.links.len() > 1 {
+			attributes.push(format!("label={}", quote_lines(&node.links)));
+		}
+		if let (true, Some(group_id)) = (dot_config.color_group_ids, &node.group_id) {
+			let index = group_ids.iter().position(|id| *id == group_id).unwrap(); // This unwrap is Ok, since all `GroupID`s were collected.
+			attributes.push(format!(
+				"style=filled, fillcolor={}",
+				quote(GROUP_ID_COLORS[index % GROUP_ID_COLORS.len()])
+			));
+		}
+
+		match attributes.is_empty() {
+			true => writeln!(dot, "  {};", quote(&node.links[0])),
+			false => writeln!(
+				dot,
+				"  {} [{}];",
+				quote(&node.links[0]),
+				attributes.join(", ")
+			),
+		}
+		.unwrap();
+	}
+
+	for edge in graph.edges.iter() {
+		writeln!(
+			dot,
+			"  {} -> {} [label={}];",
+			quote(&graph.nodes[edge.parent].links[0]),
+			quote(&graph.nodes[edge.child].links[0]),
+			quote_lines(&edge.label)
+		)
+		.unwrap();
+	}
+
+	if dot_config.show_mimics {
+		for (joint, mimicked_joint, label) in graph.mimics.iter() {
+			if let (Some(child), Some(mimicked_child)) = (
+				graph.joint_nodes.get(joint),
+				graph.joint_nodes.get(mimicked_joint),
+			) {
+				writeln!(
+					dot,
+					"  {} -> {} [style=dashed, constraint=false, label={}];",
+					quote(&graph.nodes[*mimicked_child].links[0]),
+					quote(&graph.nodes[*child].links[0]),
+					quote_lines(label)
+				)
+				.unwrap();
+			}
+		}
+	}
+
+	dot.push_str("}\n");
+	dot
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{to_dot, DOTConfig};
+	use crate::{prelude::*, JointBuilder, JointType, Link, Robot, SmartJointBuilder, Transform};
+	use test_log::test;
+
+	/// A base with a sensor mount and a gripper with two mimicking fingers.
+	fn robot() -> Robot {
+		let robot = Link::builder("base").build_tree().to_robot("gripper_bot");
+		let base = robot.get_root_link();
+
+		base.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("mount_joint", JointType::Fixed).add_origin_offset((0., 0., 0.5)),
+				Link::builder("mount"),
+			)
+			.unwrap();
+		robot
+			.get_link("mount")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_prismatic("[[left]]_finger_joint")
+					.with_axis((0., 1., 0.))
+					.with_limit(10., 0.1)
+					.add_transform(Transform::new((0.1, 0., 0.), (0., 0., 1.5))),
+				Link::builder("[[left]]_finger"),
+			)
+			.unwrap();
+		robot
+			.get_link("mount")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_prismatic("[[right]]_finger_joint")
+					.with_axis((0., -1., 0.))
+					.with_limit(10., 0.1)
+					.with_mimic("[[left]]_finger_joint")
+					.set_mimic_multiplier(1.)
+					.add_transform(Transform::new_translation(0.1, 0., 0.)),
+				Link::builder("[[right]]_finger"),
+			)
+			.unwrap();
+
+		robot
+	}
+
+	#[test]
+	fn default() {
+		assert_eq!(
+			to_dot(&robot(), DOTConfig::default()),
+			r#"digraph G {
+  node [shape=box];
+  "base";
+  "mount";
+  "left_finger";
+  "right_finger";
+  "base" -> "mount" [label="mount_joint\n(fixed)\nxyz: 0 0 0.5"];
+  "mount" -> "left_finger" [label="left_finger_joint\n(prismatic)\nxyz: 0.1 0 0\nrpy: 0 0 1.5\naxis: 0 1 0"];
+  "mount" -> "right_finger" [label="right_finger_joint\n(prismatic)\nxyz: 0.1 0 0\naxis: 0 -1 0"];
+}
+"#
+		);
+	}
+
+	#[test]
+	fn all_options() {
+		assert_eq!(
+			to_dot(
+				&robot(),
+				DOTConfig {
+					collapse_fixed: true,
+					color_group_ids: true,
+					show_mimics: true,
+				}
+			),
+			r#"digraph G {
+  node [shape=box];
+  "base" [label="base\nmount"];
+  "left_finger" [style=filled, fillcolor="lightblue"];
+  "right_finger" [style=filled, fillcolor="lightgreen"];
+  "base" -> "left_finger" [label="left_finger_joint\n(prismatic)\nxyz: 0.1 0 0.5\nrpy: 0 0 1.5\naxis: 0 1 0"];
+  "base" -> "right_finger" [label="right_finger_joint\n(prismatic)\nxyz: 0.1 0 0.5\naxis: 0 -1 0"];
+  "left_finger" -> "right_finger" [style=dashed, constraint=false, label="mimic\nmultiplier: 1"];
+}
+"#
+		);
+	}
+
+	#[test]
+	fn escaping() {
+		let robot = Link::builder(r#"a "quoted" \ link"#)
+			.build_tree()
+			.to_robot("robot");
+
+		assert_eq!(
+			to_dot(&robot, DOTConfig::default()),
+			"digraph G {\n  node [shape=box];\n  \"a \\\"quoted\\\" \\\\ link\";\n}\n"
+		);
+	}
+}
@@ -0,0 +1,349 @@
+//! The infrastructure to describe a `Robot` in the [MuJoCo](https://mujoco.org/) XML format (MJCF).
+//!
+//! The [`Robot`](crate::Robot) is written as a `<mujoco>` model, in which every [`Link`](crate::Link) is a nested `<body>`.
+//! A `<body>` is placed at the frame of its parent [`Joint`](crate::Joint), so its `<joint>` elements are placed at the origin of the `<body>`.
+//!
+//! Not all information can be represented in the same way in MJCF:
+//! - A root `Link` named `world` is written as the `<worldbody>`, since `world` is the reserved name of the MJCF world body.
+//! - [`Fixed`](crate::JointType::Fixed) joints are not written, since a `<body>` without `<joint>` elements is welded to its parent.
+//! - [`Revolute`](crate::JointType::Revolute) and [`Continuous`](crate::JointType::Continuous) joints are written as `hinge` joints
+//!   and [`Prismatic`](crate::JointType::Prismatic) joints are written as `slide` joints.
+//! - [`Floating`](crate::JointType::Floating) joints are written as a `free` joint, if the `<body>` is a direct child of the `<worldbody>`.
+//!   MJCF only allows `free` joints there, so other floating joints are written as three `slide` joints (`{name}_x`, `{name}_y` and `{name}_z`) followed by a `ball` joint (`{name}_ball`).
+//! - [`Planar`](crate::JointType::Planar) joints are written as two `slide` joints in the plane (`{name}_u` and `{name}_v`) and a `hinge` joint around the normal (`{name}_hinge`).
+//! - [`DynamicsData`](crate::joint_data::DynamicsData) is written as the `damping` and `frictionloss` of the joint.
+//! - Mimic data is written as an `<equality>` constraint between the joints.
+//! - Effort and velocity limits are not written, since MJCF specifies them on the actuators.
+//!   Calibration and safety controller data are not written either.
+//! - Visual elements are written as non-colliding `<geom>`s without mass in geom group 2 and collision elements are written as `<geom>`s in geom group 3.
+//! - Only the colors of materials are written, as the `rgba` of the `<geom>`.
+//! - Meshes are written as `<mesh>` assets, named `{link}_visual_{index}` or `{link}_collision_{index}`. The path of the mesh is written as is, so `package://` paths need to be resolved.
+//! - [`Transmission`s](crate::transmission::Transmission) are not written, since they are a ROS concept.
+use std::io::Cursor;
+
+use quick_xml::{
+	events::{BytesDecl, Event},
+	Writer,
+};
+
+use super::{make_xml_writer, XMLMode};
+use crate::{cluster_objects::KinematicInterface, Float};
+
+/// The geom group of the `<geom>`s written for [`Visual`](crate::link_data::Visual) elements.
+pub(crate) const MJCF_VISUAL_GROUP: &str = "2";
+/// The geom group of the `<geom>`s written for [`Collision`](crate::link_data::Collision) elements.
+pub(crate) const MJCF_COLLISION_GROUP: &str = "3";
+
+/// A Configuration for the exporting of the description in the [MuJoCo XML format](https://mujoco.readthedocs.io/en/stable/XMLreference.html).
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct MJCFConfig {
+	/// The `armature` (the reflected inertia of the rotor) of all joints.
+	///
+	/// It is written as a `<default>`, such that it can be changed in a single place.
+	/// When it is `None`, the MuJoCo default is used.
+	pub armature: Option<Float>,
+	/// Determines the XML style.
+	pub xml_mode: XMLMode,
+}
+
+/// A trait to allow parts of a `Robot` to be described in the MJCF.
+pub trait ToMJCF {
+	/// Represents the element as in MJCF.
+	fn to_mjcf(
+		&self,
+		writer: &mut Writer<Cursor<Vec<u8>>>,
+		mjcf_config: &MJCFConfig,
+	) -> Result<(), quick_xml::Error>;
+}
+
+/// Gets the name of the `<mesh>` asset of a visual or collision element.
+///
+/// - `link_name` is the name of the `Link` the element belongs to.
+/// - `kind` is either `"visual"` or `"collision"`.
+/// - `index` is the index of the element in the `Link`.
+pub(crate) fn mesh_asset_name(link_name: &str, kind: &str, index: usize) -> String {
+	format!("{}_{}_{}", link_name, kind, index)
+}
+
+/// A function to represent a `KinematicInterface` implementor in the MJCF.
+///
+/// This function should be used to generate the descriptions.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     link_data::{geometry::*, Visual},
+/// #     prelude::*,
+/// #     to_rdf::{
+/// #         to_mjcf::{to_mjcf, MJCFConfig},
+/// #         xml_writer_to_string,
+/// #     },
+/// #     Link, SmartJointBuilder, Transform,
+/// # };
+/// let robot = Link::builder("base")
+///     .add_visual(Visual::builder(BoxGeometry::new(1., 1., 0.2)))
+///     .build_tree()
+///     .to_robot("my-robot");
+///
+/// robot
+///     .get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(
+///         SmartJointBuilder::new_continuous("wheel_joint")
+///             .with_axis((0., 1., 0.))
+///             .add_transform(Transform::new_translation(0.5, 0., 0.)),
+///         Link::builder("wheel"),
+///     )
+///     .unwrap();
+///
+/// assert_eq!(
+///     xml_writer_to_string(to_mjcf(&robot, MJCFConfig::default()).unwrap()),
+///     String::from("\u{feff}<?xml version=\"1.0\"?>\
+///         <mujoco model=\"my-robot\">\
+///             <compiler angle=\"radian\"/>\
+///             <worldbody>\
+///                 <body name=\"base\">\
+///                     <geom type=\"box\" size=\"0.5 0.5 0.1\" contype=\"0\" conaffinity=\"0\" group=\"2\" density=\"0\"/>\
+///                     <body name=\"wheel\" pos=\"0.5 0 0\">\
+///                         <joint name=\"wheel_joint\" type=\"hinge\" axis=\"0 1 0\"/>\
+///                     </body>\
+///                 </body>\
+///             </worldbody>\
+///         </mujoco>")
+/// );
+/// ```
+pub fn to_mjcf(
+	tree: &(impl KinematicInterface + ToMJCF),
+	mjcf_config: MJCFConfig,
+) -> Result<Writer<Cursor<Vec<u8>>>, quick_xml::Error> {
+	let mut writer = make_xml_writer(mjcf_config.xml_mode);
+
+	writer.write_bom()?;
+	writer.write_event(Event::Decl(BytesDecl::new("1.0", None, None)))?;
+	tree.to_mjcf(&mut writer, &mjcf_config)?;
+	Ok(writer)
+}
+
+#[cfg(all(test, feature = "urdf"))]
+mod tests {
+	use super::{to_mjcf, MJCFConfig};
+	use crate::{
+		float::consts::FRAC_PI_4,
+		from_rdf::from_urdf::from_urdf,
+		to_rdf::{xml_writer_to_string, XMLMode},
+	};
+	use test_log::test;
+
+	const DESCRIPTION: &str = r#"<robot name="arm">
+  <material name="blue"><color rgba="0 0 1 1"/></material>
+  <link name="base">
+    <inertial>
+      <origin xyz="0 0 0.1"/>
+      <mass value="2"/>
+      <inertia ixx="0.1" ixy="0" ixz="0" iyy="0.2" iyz="0" izz="0.3"/>
+    </inertial>
+    <visual>
+      <origin xyz="0 0 0.1"/>
+      <geometry><box size="1 1 0.2"/></geometry>
+      <material name="blue"/>
+    </visual>
+    <collision>
+      <geometry><mesh filename="meshes/base.stl" scale="2 2 2"/></geometry>
+    </collision>
+  </link>
+  <joint name="shoulder" type="revolute">
+    <origin xyz="0 0 0.2" rpy="0 0 1.5707963267948966"/>
+    <parent link="base"/>
+    <child link="upper_arm"/>
+    <axis xyz="0 1 0"/>
+    <dynamics damping="0.5" friction="0.1"/>
+    <limit lower="-1" upper="1" effort="10" velocity="2"/>
+  </joint>
+  <link name="upper_arm">
+    <visual>
+      <geometry><cylinder radius="0.1" length="1"/></geometry>
+    </visual>
+  </link>
+  <joint name="elbow" type="continuous">
+    <origin xyz="1 0 0"/>
+    <parent link="upper_arm"/>
+    <child link="forearm"/>
+    <mimic joint="shoulder" multiplier="2" offset="0.5"/>
+  </joint>
+  <link name="forearm">
+    <collision>
+      <origin xyz="0.5 0 0" rpy="0 1.5707963267948966 0"/>
+      <geometry><sphere radius="0.1"/></geometry>
+    </collision>
+  </link>
+  <joint name="slider" type="prismatic">
+    <parent link="forearm"/>
+    <child link="hand"/>
+    <axis xyz="0 0 1"/>
+    <limit lower="0" upper="0.2" effort="10" velocity="2"/>
+  </joint>
+  <link name="hand"/>
+  <joint name="wrist" type="fixed">
+    <origin xyz="0 0 0.1"/>
+    <parent link="hand"/>
+    <child link="tool"/>
+  </joint>
+  <link name="tool"/>
+</robot>"#;
+
+	fn write(description: &str, mjcf_config: MJCFConfig) -> String {
+		xml_writer_to_string(
+			to_mjcf(
+				&from_urdf(description).unwrap(),
+				MJCFConfig {
+					xml_mode: XMLMode::Indent(' ', 2),
+					..mjcf_config
+				},
+			)
+			.unwrap(),
+		)
+	}
+
+	#[test]
+	fn bodies() {
+		let mjcf = write(DESCRIPTION, MJCFConfig::default());
+		// The quaternion of a quarter turn.
+		let (sin, cos) = FRAC_PI_4.sin_cos();
+
+		assert!(mjcf.contains(r#"<mujoco model="arm">"#));
+		assert!(mjcf.contains(r#"<compiler angle="radian"/>"#));
+		assert!(mjcf.contains(
+			&format!(r#"<body name="upper_arm" pos="0 0 0.2" quat="{cos} 0 0 {sin}">
+        <joint name="shoulder" type="hinge" axis="0 1 0" limited="true" range="-1 1" actuatorfrcrange="-10 10" damping="0.5" frictionloss="0.1"/>"#
+		)));
+		assert!(mjcf.contains(
+			r#"<body name="forearm" pos="1 0 0">
+          <joint name="elbow" type="hinge" axis="1 0 0"/>"#
+		));
+		assert!(mjcf.contains(
+			r#"<body name="hand">
+            <joint name="slider" type="slide" axis="0 0 1" limited="true" range="0 0.2" actuatorfrcrange="-10 10"/>
+            <body name="tool" pos="0 0 0.1"/>
+          </body>"#
+		));
+		assert!(!mjcf.contains("<default>"));
+	}
+
+	#[test]
+	fn elements() {
+		let mjcf = write(DESCRIPTION, MJCFConfig::default());
+		// The quaternion of a quarter turn.
+		let (sin, cos) = FRAC_PI_4.sin_cos();
+
+		assert!(mjcf.contains(
+			r#"<asset>
+    <mesh name="base_collision_0" file="meshes/base.stl" scale="2 2 2"/>
+  </asset>"#
+		));
+		assert!(mjcf.contains(
+			r#"<body name="base">
+      <inertial pos="0 0 0.1" mass="2" fullinertia="0.1 0.2 0.3 0 0 0"/>
+      <geom type="box" size="0.5 0.5 0.1" pos="0 0 0.1" contype="0" conaffinity="0" group="2" density="0" rgba="0 0 1 1"/>
+      <geom type="mesh" mesh="base_collision_0" group="3" density="0"/>"#
+		));
+		assert!(mjcf.contains(
+			r#"<geom type="cylinder" size="0.1 0.5" contype="0" conaffinity="0" group="2" density="0"/>"#
+		));
+		assert!(mjcf.contains(&format!(
+			r#"<geom type="sphere" size="0.1" pos="0.5 0 0" quat="{cos} 0 {sin} 0" group="3"/>"#
+		)));
+	}
+
+	#[test]
+	fn mimic() {
+		let mjcf = write(DESCRIPTION, MJCFConfig::default());
+
+		assert!(mjcf.contains(
+			r#"<equality>
+    <joint joint1="elbow" joint2="shoulder" polycoef="0.5 2 0 0 0"/>
+  </equality>"#
+		));
+	}
+
+	#[test]
+	fn armature() {
+		let mjcf = write(
+			DESCRIPTION,
+			MJCFConfig {
+				armature: Some(0.01),
+				..Default::default()
+			},
+		);
+
+		assert!(mjcf.contains(
+			r#"<default>
+    <joint armature="0.01"/>
+  </default>"#
+		));
+	}
+
+	#[test]
+	fn rotated_inertial() {
+		let description = r#"<robot name="rotated">
+  <link name="body">
+    <inertial>
+      <origin rpy="0 0 1.5707963267948966"/>
+      <mass value="1"/>
+      <inertia ixx="1" ixy="0" ixz="0" iyy="2" iyz="0" izz="3"/>
+    </inertial>
+  </link>
+</robot>"#;
+
+		let mjcf = write(description, MJCFConfig::default());
+		assert!(mjcf.contains(r#"<inertial pos="0 0 0" mass="1" fullinertia="2 1 3 0 0 0"/>"#));
+	}
+
+	#[test]
+	fn floating_joints() {
+		let description = r#"<robot name="free">
+  <link name="world"/>
+  <joint name="free_joint" type="floating">
+    <origin xyz="0 0 1"/>
+    <parent link="world"/>
+    <child link="body"/>
+  </joint>
+  <link name="body">
+    <collision><geometry><box size="1 1 1"/></geometry></collision>
+  </link>
+  <joint name="nested_joint" type="floating">
+    <parent link="body"/>
+    <child link="nested"/>
+  </joint>
+  <link name="nested"/>
+  <joint name="planar_joint" type="planar">
+    <parent link="nested"/>
+    <child link="sled"/>
+    <axis xyz="0 0 1"/>
+  </joint>
+  <link name="sled"/>
+</robot>"#;
+
+		let mjcf = write(description, MJCFConfig::default());
+		// The world link is the `<worldbody>`.
+		assert!(!mjcf.contains(r#"<body name="world""#));
+		assert!(mjcf.contains(
+			r#"<worldbody>
+    <body name="body" pos="0 0 1">
+      <joint name="free_joint" type="free"/>"#
+		));
+		assert!(mjcf.contains(
+			r#"<body name="nested">
+        <joint name="nested_joint_x" type="slide" axis="1 0 0"/>
+        <joint name="nested_joint_y" type="slide" axis="0 1 0"/>
+        <joint name="nested_joint_z" type="slide" axis="0 0 1"/>
+        <joint name="nested_joint_ball" type="ball"/>"#
+		));
+		assert!(mjcf.contains(
+			r#"<body name="sled">
+          <joint name="planar_joint_u" type="slide" axis="1 0 0"/>
+          <joint name="planar_joint_v" type="slide" axis="0 1 0"/>
+          <joint name="planar_joint_hinge" type="hinge" axis="0 0 1"/>"#
+		));
+	}
+}
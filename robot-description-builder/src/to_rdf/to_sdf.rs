@@ -1,4 +1,14 @@
 //! The infrastructure to describe a `Robot` in SDFormat (SDF).
+//!
+//! The [`Robot`](crate::Robot) is written as a single `<model>`.
+//! Not all information can be represented in SDFormat:
+//! - [`Floating`](crate::JointType::Floating) and [`Planar`](crate::JointType::Planar) joints have no SDFormat equivalent,
+//!   so they are not written. Their child link is posed like they are there, but it is not connected.
+//! - [`Continuous`](crate::JointType::Continuous) joints are written as `revolute` joints with (practically) infinite limits.
+//! - Calibration and safety controller data are not written, since SDFormat does not support them.
+//! - Mimic data is only written for [`SDFVersion::V1_11`] and newer.
+//! - [`Transmission`s](crate::transmission::Transmission) are not written, since they are a ROS concept.
+//! - Unnamed visual and collision elements are named after their index, since SDFormat requires names for them.
 use std::io::Cursor;
 
 use quick_xml::{
@@ -9,11 +19,63 @@ use quick_xml::{
 use super::{make_xml_writer, XMLMode};
 use crate::cluster_objects::KinematicInterface;
 
+/// A Configuration for the exporting of the description in the [SDFormat](http://sdformat.org/).
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct SDFConfig {
+	/// Determines the version of the SDFormat specification, which is used for the description.
+	pub sdf_version: SDFVersion,
+	/// Determines the XML style.
 	pub xml_mode: XMLMode,
 }
 
+/// The supported versions of the [SDFormat specification](http://sdformat.org/spec).
+///
+/// The version changes how the poses are described:
+/// - For [`V1_6`](SDFVersion::V1_6) all `Link` poses are described relative to the model frame.
+/// - From [`V1_7`](SDFVersion::V1_7) onwards poses are described with `relative_to` frames,
+///   such that every `Joint` is relative to its parent `Link` and every `Link` is relative to its parent `Joint`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum SDFVersion {
+	/// SDFormat version 1.6 (Gazebo Classic 9 and newer).
+	V1_6,
+	/// SDFormat version 1.7 (Gazebo Classic 11 and newer), introduces `relative_to` frames.
+	V1_7,
+	/// SDFormat version 1.8.
+	V1_8,
+	/// SDFormat version 1.9 (Gazebo Garden). This is the default.
+	#[default]
+	V1_9,
+	/// SDFormat version 1.10.
+	V1_10,
+	/// SDFormat version 1.11 (Gazebo Harmonic), introduces `<mimic>` joints.
+	V1_11,
+}
+
+impl SDFVersion {
+	/// Gets the version string, as used in the `version` attribute of the `<sdf>` element.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::V1_6 => "1.6",
+			Self::V1_7 => "1.7",
+			Self::V1_8 => "1.8",
+			Self::V1_9 => "1.9",
+			Self::V1_10 => "1.10",
+			Self::V1_11 => "1.11",
+		}
+	}
+
+	/// Checks if this version supports the `relative_to` attribute of `<pose>`.
+	pub(crate) fn supports_relative_to(&self) -> bool {
+		*self >= Self::V1_7
+	}
+
+	/// Checks if this version supports the `<mimic>` element of a joint `<axis>`.
+	pub(crate) fn supports_mimic(&self) -> bool {
+		*self >= Self::V1_11
+	}
+}
+
 /// A trait to allow parts of a `Robot` to be described in the SDFormat.
 pub trait ToSDF {
 	/// Represents the element as in SDFormat.
@@ -26,15 +88,251 @@ pub trait ToSDF {
 
 /// A function to represent a `KinematicInterface` implementor in the SDFormat.
 ///
-/// TODO: EXPAND
+/// This function should be used to generate the descriptions.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     link_data::{geometry::*, Visual},
+/// #     prelude::*,
+/// #     to_rdf::{
+/// #         to_sdf::{to_sdf, SDFConfig},
+/// #         xml_writer_to_string,
+/// #     },
+/// #     Link, SmartJointBuilder, Transform,
+/// # };
+/// let robot = Link::builder("base")
+///     .add_visual(Visual::builder(BoxGeometry::new(1., 1., 0.2)))
+///     .build_tree()
+///     .to_robot("my-robot");
+///
+/// robot
+///     .get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(
+///         SmartJointBuilder::new_continuous("wheel_joint")
+///             .with_axis((0., 1., 0.))
+///             .add_transform(Transform::new_translation(0.5, 0., 0.)),
+///         Link::builder("wheel"),
+///     )
+///     .unwrap();
+///
+/// assert_eq!(
+///     xml_writer_to_string(to_sdf(&robot, SDFConfig::default()).unwrap()),
+///     String::from("\u{feff}<?xml version=\"1.0\"?>\
+///         <sdf version=\"1.9\"><model name=\"my-robot\">\
+///             <link name=\"base\"><visual name=\"visual_0\"><geometry><box><size>1 1 0.2</size></box></geometry></visual></link>\
+///             <joint name=\"wheel_joint\" type=\"revolute\">\
+///                 <pose relative_to=\"base\">0.5 0 0 0 0 0</pose>\
+///                 <parent>base</parent><child>wheel</child>\
+///                 <axis><xyz>0 1 0</xyz><limit><lower>-10000000000000000</lower><upper>10000000000000000</upper><effort>-1</effort><velocity>-1</velocity></limit></axis>\
+///             </joint>\
+///             <link name=\"wheel\"><pose relative_to=\"wheel_joint\">0 0 0 0 0 0</pose></link>\
+///         </model></sdf>")
+/// );
+/// ```
 pub fn to_sdf(
 	tree: &(impl KinematicInterface + ToSDF),
 	sdf_config: SDFConfig,
 ) -> Result<Writer<Cursor<Vec<u8>>>, quick_xml::Error> {
-	let mut writer = make_xml_writer(self.xml_mode);
+	let mut writer = make_xml_writer(sdf_config.xml_mode);
 
 	writer.write_bom()?;
 	writer.write_event(Event::Decl(BytesDecl::new("1.0", None, None)))?;
 	tree.to_sdf(&mut writer, &sdf_config)?;
 	Ok(writer)
 }
+
+#[cfg(all(test, feature = "urdf"))]
+mod tests {
+	use super::{to_sdf, SDFConfig, SDFVersion};
+	use crate::{
+		from_rdf::from_urdf::from_urdf,
+		to_rdf::{xml_writer_to_string, XMLMode},
+	};
+	use test_log::test;
+
+	const DESCRIPTION: &str = r#"<robot name="arm">
+  <material name="blue"><color rgba="0 0 1 1"/></material>
+  <link name="base">
+    <inertial>
+      <origin xyz="0 0 0.1"/>
+      <mass value="2"/>
+      <inertia ixx="0.1" ixy="0" ixz="0" iyy="0.1" iyz="0" izz="0.2"/>
+    </inertial>
+    <visual name="base_visual">
+      <geometry><box size="1 1 0.2"/></geometry>
+      <material name="blue"/>
+    </visual>
+    <visual>
+      <origin xyz="0 0 0.2"/>
+      <geometry><mesh filename="package://arm/meshes/logo.dae"/></geometry>
+      <material><texture filename="package://arm/textures/logo.png"/></material>
+    </visual>
+    <collision>
+      <geometry><box size="1 1 0.2"/></geometry>
+    </collision>
+  </link>
+  <joint name="shoulder" type="revolute">
+    <origin xyz="0 0 0.2" rpy="0 0 1.5707964"/>
+    <parent link="base"/>
+    <child link="upper_arm"/>
+    <axis xyz="0 1 0"/>
+    <dynamics damping="0.5"/>
+    <limit lower="-1" upper="1" effort="10" velocity="2"/>
+  </joint>
+  <link name="upper_arm"/>
+  <joint name="elbow" type="continuous">
+    <origin xyz="1 0 0"/>
+    <parent link="upper_arm"/>
+    <child link="forearm"/>
+    <mimic joint="shoulder" multiplier="2"/>
+  </joint>
+  <link name="forearm">
+    <collision name="forearm_collision">
+      <geometry><cylinder radius="0.1" length="1"/></geometry>
+    </collision>
+  </link>
+</robot>"#;
+
+	fn write(description: &str, sdf_version: SDFVersion) -> String {
+		xml_writer_to_string(
+			to_sdf(
+				&from_urdf(description).unwrap(),
+				SDFConfig {
+					sdf_version,
+					xml_mode: XMLMode::Indent(' ', 2),
+				},
+			)
+			.unwrap(),
+		)
+	}
+
+	#[test]
+	fn relative_to_frames() {
+		let sdf = write(DESCRIPTION, SDFVersion::V1_9);
+
+		assert!(sdf.contains(r#"<sdf version="1.9">"#));
+		assert!(sdf.contains(
+			r#"<joint name="shoulder" type="revolute">
+      <pose relative_to="base">0 0 0.2 0 0 1.5707964</pose>
+      <parent>base</parent>
+      <child>upper_arm</child>"#
+		));
+		assert!(sdf.contains(
+			r#"<link name="upper_arm">
+      <pose relative_to="shoulder">0 0 0 0 0 0</pose>
+    </link>"#
+		));
+		assert!(sdf.contains(r#"<pose relative_to="upper_arm">1 0 0 0 0 0</pose>"#));
+		assert!(sdf.contains(r#"<pose relative_to="elbow">0 0 0 0 0 0</pose>"#));
+		// The root link is the model frame.
+		assert!(sdf.contains("<link name=\"base\">\n      <inertial>"));
+		// Mimic is not supported before SDFormat 1.11.
+		assert!(!sdf.contains("<mimic"));
+	}
+
+	#[test]
+	fn model_frame_poses() {
+		let sdf = write(DESCRIPTION, SDFVersion::V1_6);
+
+		assert!(sdf.contains(r#"<sdf version="1.6">"#));
+		assert!(!sdf.contains("relative_to"));
+		assert!(sdf.contains(
+			r#"<joint name="shoulder" type="revolute">
+      <parent>base</parent>"#
+		));
+		assert!(sdf.contains(
+			r#"<link name="upper_arm">
+      <pose>0 0 0.2 0 0 1.5707964</pose>
+    </link>"#
+		));
+		// The rounding errors of the composed pose depend on the precision.
+		#[cfg(not(feature = "f64"))]
+		let forearm_pose = "0.000000059604645 0.99999994 0.2 0 0 1.5707964";
+		#[cfg(feature = "f64")]
+		let forearm_pose = "-0.00000007320510353459042 0.9999999999999973 0.2 0 0 1.5707964";
+		assert!(sdf.contains(&format!(
+			r#"<link name="forearm">
+      <pose>{forearm_pose}</pose>"#
+		)));
+	}
+
+	#[test]
+	fn elements() {
+		let sdf = write(DESCRIPTION, SDFVersion::default());
+
+		assert!(sdf.contains(
+			r#"<visual name="base_visual">
+        <geometry>
+          <box>
+            <size>1 1 0.2</size>
+          </box>
+        </geometry>
+        <material>
+          <ambient>0 0 1 1</ambient>
+          <diffuse>0 0 1 1</diffuse>
+        </material>
+      </visual>"#
+		));
+		assert!(sdf.contains(r#"<visual name="visual_1">"#));
+		assert!(sdf.contains("<albedo_map>package://arm/textures/logo.png</albedo_map>"));
+		assert!(sdf.contains(r#"<collision name="collision_0">"#));
+		assert!(sdf.contains(r#"<collision name="forearm_collision">"#));
+		assert!(sdf.contains(
+			r#"<axis>
+        <xyz>0 1 0</xyz>
+        <dynamics>
+          <damping>0.5</damping>
+        </dynamics>
+        <limit>
+          <lower>-1</lower>
+          <upper>1</upper>
+          <effort>10</effort>
+          <velocity>2</velocity>
+        </limit>
+      </axis>"#
+		));
+	}
+
+	#[test]
+	fn mimic() {
+		let sdf = write(DESCRIPTION, SDFVersion::V1_11);
+
+		assert!(sdf.contains(
+			r#"<mimic joint="shoulder">
+          <multiplier>2</multiplier>
+        </mimic>"#
+		));
+	}
+
+	#[test]
+	fn floating_joint() {
+		let description = r#"<robot name="free">
+  <link name="world"/>
+  <joint name="free_joint" type="floating">
+    <origin xyz="0 0 1"/>
+    <parent link="world"/>
+    <child link="body"/>
+  </joint>
+  <link name="body"/>
+</robot>"#;
+
+		let sdf = write(description, SDFVersion::V1_9);
+		assert!(!sdf.contains("<joint"));
+		assert!(sdf.contains(
+			r#"<link name="body">
+      <pose relative_to="world">0 0 1 0 0 0</pose>
+    </link>"#
+		));
+
+		let sdf = write(description, SDFVersion::V1_6);
+		assert!(!sdf.contains("<joint"));
+		assert!(sdf.contains(
+			r#"<link name="body">
+      <pose>0 0 1 0 0 0</pose>
+    </link>"#
+		));
+	}
+}
@@ -0,0 +1,446 @@
+//! The infrastructure to write a [`SemanticDescription`] in the [Semantic Robot Description Format (SRDF)](http://wiki.ros.org/srdf).
+use std::io::Cursor;
+
+use itertools::Itertools;
+use quick_xml::{
+	events::{BytesDecl, Event},
+	Writer,
+};
+
+use super::{make_xml_writer, XMLMode};
+use crate::{
+	joint::Joint,
+	link::Link,
+	srdf::{referenced_name, SemanticDescription},
+	utils::WeakLock,
+};
+
+/// A Configuration for the exporting of a [`SemanticDescription`] in the [SRDF](http://wiki.ros.org/srdf) format.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SRDFConfig {
+	/// Determines the XML style.
+	pub xml_mode: XMLMode,
+}
+
+fn link_name(link: &WeakLock<Link>) -> String {
+	referenced_name(link, Link::name)
+}
+
+fn joint_name(joint: &WeakLock<Joint>) -> String {
+	referenced_name(joint, Joint::name)
+}
+
+/// A function to write a [`SemanticDescription`] in the SRDF format.
+///
+/// The `Link`s and `Joint`s are written with their current names, in the same way as [`to_urdf`](super::to_urdf::to_urdf) does.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     prelude::*,
+/// #     srdf::*,
+/// #     to_rdf::{to_srdf::{to_srdf, SRDFConfig}, xml_writer_to_string, XMLMode},
+/// #     JointBuilder, JointType, Link,
+/// # };
+/// let robot = Link::builder("base_link").build_tree().to_robot("arm");
+/// robot
+///     .get_root_link()
+///     .write()
+///     .unwrap()
+///     .try_attach_child(JointBuilder::new("shoulder", JointType::Continuous), Link::builder("upper_arm"))
+///     .unwrap();
+///
+/// let description = SemanticDescriptionBuilder::new("arm")
+///     .add_group(PlanningGroupBuilder::new("arm").add_chain("base_link", "upper_arm"))
+///     .add_group_state(GroupStateBuilder::new("home", "arm").add_joint_value("shoulder", 0.5))
+///     .disable_collisions("base_link", "upper_arm", DisabledCollisionReason::Adjacent)
+///     .build(&robot)
+///     .unwrap();
+///
+/// assert_eq!(
+///     xml_writer_to_string(
+///         to_srdf(&description, SRDFConfig { xml_mode: XMLMode::Indent(' ', 2) }).unwrap()
+///     ),
+/// r#"﻿<?xml version="1.0"?>
+/// <robot name="arm">
+///   <group name="arm">
+///     <chain base_link="base_link" tip_link="upper_arm"/>
+///   </group>
+///   <group_state name="home" group="arm">
+///     <joint name="shoulder" value="0.5"/>
+///   </group_state>
+///   <disable_collisions link1="base_link" link2="upper_arm" reason="Adjacent"/>
+/// </robot>"#
+/// );
+/// ```
+pub fn to_srdf(
+	description: &SemanticDescription,
+	srdf_config: SRDFConfig,
+) -> Result<Writer<Cursor<Vec<u8>>>, quick_xml::Error> {
+	let mut writer = make_xml_writer(srdf_config.xml_mode);
+
+	writer.write_bom()?;
+	writer.write_event(Event::Decl(BytesDecl::new("1.0", None, None)))?;
+	writer
+		.create_element("robot")
+		.with_attribute(("name", description.name().as_str()))
+		.write_inner_content(|writer| -> quick_xml::Result<()> {
+			for group in description.groups() {
+				writer
+					.create_element("group")
+					.with_attribute(("name", group.name().as_str()))
+					.write_inner_content(|writer| -> quick_xml::Result<()> {
+						for link in group.links() {
+							writer
+								.create_element("link")
+								.with_attribute(("name", link_name(link).as_str()))
+								.write_empty()?;
+						}
+						for joint in group.joints() {
+							writer
+								.create_element("joint")
+								.with_attribute(("name", joint_name(joint).as_str()))
+								.write_empty()?;
+						}
+						for (base, tip) in group.chains() {
+							writer
+								.create_element("chain")
+								.with_attribute(("base_link", link_name(base).as_str()))
+								.with_attribute(("tip_link", link_name(tip).as_str()))
+								.write_empty()?;
+						}
+						for subgroup in group.subgroups() {
+							writer
+								.create_element("group")
+								.with_attribute(("name", subgroup.as_str()))
+								.write_empty()?;
+						}
+						Ok(())
+					})?;
+			}
+
+			for group_state in description.group_states() {
+				writer
+					.create_element("group_state")
+					.with_attribute(("name", group_state.name().as_str()))
+					.with_attribute(("group", group_state.group().as_str()))
+					.write_inner_content(|writer| -> quick_xml::Result<()> {
+						for (joint, values) in group_state.joint_values() {
+							writer
+								.create_element("joint")
+								.with_attribute(("name", joint_name(joint).as_str()))
+								.with_attribute(("value", values.iter().join(" ").as_str()))
+								.write_empty()?;
+						}
+						Ok(())
+					})?;
+			}
+
+			for end_effector in description.end_effectors() {
+				let mut element = writer
+					.create_element("end_effector")
+					.with_attribute(("name", end_effector.name().as_str()))
+					.with_attribute((
+						"parent_link",
+						link_name(&end_effector.parent_link()).as_str(),
+					))
+					.with_attribute(("group", end_effector.group().as_str()));
+				if let Some(parent_group) = end_effector.parent_group() {
+					element = element.with_attribute(("parent_group", parent_group.as_str()));
+				}
+				element.write_empty()?;
+			}
+
+			for virtual_joint in description.virtual_joints() {
+				writer
+					.create_element("virtual_joint")
+					.with_attribute(("name", virtual_joint.name().as_str()))
+					.with_attribute(("type", virtual_joint.joint_type().to_string().as_str()))
+					.with_attribute(("parent_frame", virtual_joint.parent_frame().as_str()))
+					.with_attribute((
+						"child_link",
+						link_name(&virtual_joint.child_link()).as_str(),
+					))
+					.write_empty()?;
+			}
+
+			for joint in description.passive_joints() {
+				writer
+					.create_element("passive_joint")
+					.with_attribute(("name", joint_name(joint).as_str()))
+					.write_empty()?;
+			}
+
+			for disabled_collision in description.disabled_collisions() {
+				writer
+					.create_element("disable_collisions")
+					.with_attribute(("link1", link_name(&disabled_collision.link1()).as_str()))
+					.with_attribute(("link2", link_name(&disabled_collision.link2()).as_str()))
+					.with_attribute(("reason", disabled_collision.reason().to_string().as_str()))
+					.write_empty()?;
+			}
+
+			Ok(())
+		})?;
+
+	Ok(writer)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{to_srdf, SRDFConfig};
+	use crate::{
+		prelude::*,
+		srdf::{
+			DisabledCollisionReason, EndEffectorBuilder, GroupStateBuilder, PlanningGroupBuilder,
+			SemanticDescriptionBuilder, VirtualJointBuilder, VirtualJointType,
+		},
+		to_rdf::{xml_writer_to_string, XMLMode},
+		JointBuilder, JointType, Link, Robot,
+	};
+	use test_log::test;
+
+	/// Builds a serial chain of `Link`s named `links`, connected by the continuous `Joint`s named `joints`.
+	fn serial_robot(links: &[&str], joints: &[&str]) -> Robot {
+		let robot = Link::builder(links[0]).build_tree().to_robot("robot");
+		for (index, joint) in joints.iter().enumerate() {
+			robot
+				.get_link(links[index])
+				.unwrap()
+				.write()
+				.unwrap()
+				.try_attach_child(
+					JointBuilder::new(*joint, JointType::Continuous),
+					Link::builder(links[index + 1]),
+				)
+				.unwrap();
+		}
+		robot
+	}
+
+	/// Writes the `description` as indented SRDF, without the XML declaration.
+	fn write(description: SemanticDescriptionBuilder, robot: &Robot) -> String {
+		let srdf = xml_writer_to_string(
+			to_srdf(
+				&description.build(robot).unwrap(),
+				SRDFConfig {
+					xml_mode: XMLMode::Indent(' ', 2),
+				},
+			)
+			.unwrap(),
+		);
+		// This unwrap is Ok, since the writer always writes the XML declaration.
+		srdf.split_once('\n').unwrap().1.to_string()
+	}
+
+	#[test]
+	fn full_description() {
+		let robot = Link::builder("[[a]]_base").build_tree().to_robot("arm_bot");
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("[[a]]_slide", JointType::Prismatic),
+				Link::builder("[[a]]_carriage"),
+			)
+			.unwrap();
+		robot
+			.get_link("[[a]]_carriage")
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				JointBuilder::new("[[a]]_mount", JointType::Planar),
+				Link::builder("[[a]]_tool"),
+			)
+			.unwrap();
+
+		let description = SemanticDescriptionBuilder::new("arm_bot")
+			.add_virtual_joint(VirtualJointBuilder::new(
+				"world_joint",
+				VirtualJointType::Planar,
+				"odom",
+				"[[a]]_base",
+			))
+			.add_group(
+				PlanningGroupBuilder::new("rail")
+					.add_joint("[[a]]_slide")
+					.add_link("[[a]]_carriage"),
+			)
+			.add_group(PlanningGroupBuilder::new("tool").add_link("[[a]]_tool"))
+			.add_group(
+				PlanningGroupBuilder::new("all")
+					.add_subgroup("rail")
+					.add_subgroup("tool"),
+			)
+			.add_group_state(
+				GroupStateBuilder::new("parked", "all")
+					.add_joint_value("[[a]]_slide", -0.25)
+					.add_joint_values("[[a]]_mount", vec![1., 2., 0.5]),
+			)
+			.add_end_effector(
+				EndEffectorBuilder::new("tool", "[[a]]_carriage", "tool").with_parent_group("rail"),
+			)
+			.add_passive_joint("[[a]]_mount")
+			.disable_collisions("[[a]]_base", "[[a]]_tool", DisabledCollisionReason::Never)
+			.build(&robot)
+			.unwrap();
+
+		assert_eq!(
+			xml_writer_to_string(
+				to_srdf(
+					&description,
+					SRDFConfig {
+						xml_mode: XMLMode::Indent(' ', 2)
+					}
+				)
+				.unwrap()
+			),
+			r#"﻿<?xml version="1.0"?>
+<robot name="arm_bot">
+  <group name="rail">
+    <link name="a_carriage"/>
+    <joint name="a_slide"/>
+  </group>
+  <group name="tool">
+    <link name="a_tool"/>
+  </group>
+  <group name="all">
+    <group name="rail"/>
+    <group name="tool"/>
+  </group>
+  <group_state name="parked" group="all">
+    <joint name="a_slide" value="-0.25"/>
+    <joint name="a_mount" value="1 2 0.5"/>
+  </group_state>
+  <end_effector name="tool" parent_link="a_carriage" group="tool" parent_group="rail"/>
+  <virtual_joint name="world_joint" type="planar" parent_frame="odom" child_link="a_base"/>
+  <passive_joint name="a_mount"/>
+  <disable_collisions link1="a_base" link2="a_tool" reason="Never"/>
+</robot>"#
+		);
+	}
+
+	#[test]
+	fn groups() {
+		let robot = serial_robot(
+			&["base", "shoulder_link", "elbow_link", "hand"],
+			&["shoulder", "elbow", "wrist"],
+		);
+
+		let srdf = write(
+			SemanticDescriptionBuilder::new("robot")
+				.add_group(PlanningGroupBuilder::new("arm").add_chain("base", "elbow_link"))
+				.add_group(
+					PlanningGroupBuilder::new("gripper")
+						.add_link("hand")
+						.add_joint("wrist"),
+				)
+				.add_group(
+					PlanningGroupBuilder::new("arm_with_gripper")
+						.add_chain("shoulder_link", "hand")
+						.add_subgroup("arm")
+						.add_subgroup("gripper"),
+				),
+			&robot,
+		);
+
+		assert_eq!(
+			srdf,
+			r#"<robot name="robot">
+  <group name="arm">
+    <chain base_link="base" tip_link="elbow_link"/>
+  </group>
+  <group name="gripper">
+    <link name="hand"/>
+    <joint name="wrist"/>
+  </group>
+  <group name="arm_with_gripper">
+    <chain base_link="shoulder_link" tip_link="hand"/>
+    <group name="arm"/>
+    <group name="gripper"/>
+  </group>
+</robot>"#
+		);
+	}
+
+	#[test]
+	fn end_effectors() {
+		let robot = serial_robot(&["base", "palm", "finger"], &["wrist", "knuckle"]);
+
+		let srdf = write(
+			SemanticDescriptionBuilder::new("robot")
+				.add_group(PlanningGroupBuilder::new("arm").add_chain("base", "palm"))
+				.add_group(PlanningGroupBuilder::new("hand").add_link("finger"))
+				.add_end_effector(EndEffectorBuilder::new("gripper", "palm", "hand"))
+				.add_end_effector(
+					EndEffectorBuilder::new("arm_gripper", "palm", "hand").with_parent_group("arm"),
+				),
+			&robot,
+		);
+
+		assert!(srdf.contains(
+			r#"  <end_effector name="gripper" parent_link="palm" group="hand"/>
+  <end_effector name="arm_gripper" parent_link="palm" group="hand" parent_group="arm"/>"#
+		));
+	}
+
+	#[test]
+	fn disabled_collisions() {
+		let robot = serial_robot(&["base", "upper_arm", "forearm"], &["shoulder", "elbow"]);
+
+		let srdf = write(
+			SemanticDescriptionBuilder::new("robot")
+				.disable_collisions("base", "upper_arm", DisabledCollisionReason::Adjacent)
+				.disable_collisions("upper_arm", "forearm", DisabledCollisionReason::Default)
+				.disable_collisions("base", "forearm", DisabledCollisionReason::Never)
+				.disable_collisions("forearm", "base", DisabledCollisionReason::User),
+			&robot,
+		);
+
+		assert_eq!(
+			srdf,
+			r#"<robot name="robot">
+  <disable_collisions link1="base" link2="upper_arm" reason="Adjacent"/>
+  <disable_collisions link1="upper_arm" link2="forearm" reason="Default"/>
+  <disable_collisions link1="base" link2="forearm" reason="Never"/>
+  <disable_collisions link1="forearm" link2="base" reason="User"/>
+</robot>"#
+		);
+	}
+
+	#[test]
+	fn escaped_group_id_delimiters() {
+		let robot = serial_robot(
+			&[r"[\[left]\]_base", "[[L01]]_arm"],
+			&[r"[\[left]\]_[[L01]]_joint"],
+		);
+
+		let srdf = write(
+			SemanticDescriptionBuilder::new("robot")
+				.add_group(
+					PlanningGroupBuilder::new("[[left]]")
+						.add_chain(r"[\[left]\]_base", "[[L01]]_arm")
+						.add_joint(r"[\[left]\]_[[L01]]_joint"),
+				)
+				.disable_collisions(
+					r"[\[left]\]_base",
+					"[[L01]]_arm",
+					DisabledCollisionReason::Adjacent,
+				),
+			&robot,
+		);
+
+		assert_eq!(
+			srdf,
+			r#"<robot name="robot">
+  <group name="[[left]]">
+    <joint name="[[left]]_L01_joint"/>
+    <chain base_link="[[left]]_base" tip_link="L01_arm"/>
+  </group>
+  <disable_collisions link1="[[left]]_base" link2="L01_arm" reason="Adjacent"/>
+</robot>"#
+		);
+	}
+}
@@ -1,7 +1,7 @@
 //! The infrastructure to describe a `Robot` in the Universal Robot Description Format (URDF).
 // TODO: EXPAND Module doc?, Matbe not
 
-use std::io::Cursor;
+use std::{io::Cursor, sync::Arc};
 
 use quick_xml::{
 	events::{BytesDecl, Event},
@@ -11,6 +11,13 @@ use quick_xml::{
 use super::{make_xml_writer, XMLMode};
 use crate::cluster_objects::KinematicInterface;
 
+mod consumers;
+
+pub use consumers::{
+	check_compatibility, Handling, Incompatibility, IncompatibilityKind, URDFCompatibilityError,
+	URDFConsumer,
+};
+
 // FIXME: FIX CONFIG, MAYBE MAKE AN INTERNAL CONFIG TYPE
 /// A Configuration for the exporting of the description in the [URDF](http://wiki.ros.org/urdf) format.
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
@@ -27,6 +34,11 @@ pub struct URDFConfig {
 	pub urdf_target: URDFTarget,
 	/// Determines the XML style.
 	pub xml_mode: XMLMode,
+	/// The [`URDFConsumer`]s, which have to accept the description.
+	///
+	/// [`to_urdf`] fails with a [`URDFCompatibilityError`] if one of these consumers rejects a part of the description.
+	/// Parts which are only ignored by a consumer are allowed, see [`check_compatibility`] to find them.
+	pub strict_consumers: Vec<URDFConsumer>,
 }
 
 /// Determines how Referencable/Named `Material`s should be written.
@@ -96,6 +108,9 @@ pub trait ToURDF {
 ///
 /// This function should be used to generate the descriptions.
 ///
+/// If one of the [`URDFConfig::strict_consumers`] rejects a part of the description, this function fails with a [`URDFCompatibilityError`].
+/// See [`URDFCompatibilityError::from_xml_error`] to retrieve it.
+///
 /// # Example
 /// Reads and writes are hidden for brevity.
 /// ```
@@ -175,6 +190,17 @@ pub fn to_urdf(
 	tree: &(impl KinematicInterface + ToURDF),
 	urdf_config: URDFConfig,
 ) -> Result<Writer<Cursor<Vec<u8>>>, quick_xml::Error> {
+	let incompatibilities: Vec<_> = check_compatibility(tree, &urdf_config.strict_consumers)
+		.into_iter()
+		.filter(|incompatibility| incompatibility.handling == Handling::Rejected)
+		.collect();
+	if !incompatibilities.is_empty() {
+		return Err(quick_xml::Error::Io(Arc::new(std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			URDFCompatibilityError { incompatibilities },
+		))));
+	}
+
 	let mut writer = make_xml_writer(urdf_config.xml_mode);
 
 	writer.write_bom()?;
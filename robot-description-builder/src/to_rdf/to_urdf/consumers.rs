@@ -0,0 +1,625 @@
+//! The profiles of common consumers of URDF descriptions, which do not support every part of the URDF specification.
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::JointType,
+	link_data::{geometry::GeometryShapeContainer, LinkParent},
+	validate::is_simulated_separately,
+};
+
+/// The mesh formats, which can be loaded by MuJoCo.
+const MUJOCO_MESH_FORMATS: [&str; 3] = ["stl", "obj", "msh"];
+
+/// A common consumer of URDF descriptions.
+///
+/// Most consumers do not support every part of the [URDF specification](http://wiki.ros.org/urdf/XML).
+/// Some parts are silently ignored, while others make the consumer reject the whole description.
+/// [`URDFConsumer::check`] reports the parts of a description, which are not supported by a consumer.
+///
+/// This is independent of the [`URDFTarget`](super::URDFTarget), which only changes how the description is written.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum URDFConsumer {
+	/// [RViz](http://wiki.ros.org/rviz), [`robot_state_publisher`](http://wiki.ros.org/robot_state_publisher) and [`joint_state_publisher`](http://wiki.ros.org/joint_state_publisher).
+	///
+	/// [`Floating`](JointType::Floating) and [`Planar`](JointType::Planar) `Joint`s are ignored by `joint_state_publisher`, which also requires limits on [`Continuous`](JointType::Continuous) `Joint`s.
+	RobotStatePublisher,
+	/// The [Kinematics and Dynamics Library](https://www.orocos.org/kdl.html), read with [`kdl_parser`](http://wiki.ros.org/kdl_parser).
+	///
+	/// Multi-axis `Joint`s are converted to fixed `Joint`s, mimics are ignored and the `Inertial` of the root `Link` is not supported.
+	KDL,
+	/// [MoveIt](https://moveit.ros.org/), which requires velocity limits on all moving `Joint`s for its trajectory processing.
+	MoveIt,
+	/// [Gazebo Classic](https://classic.gazebosim.org/), which converts the description to SDFormat.
+	///
+	/// `Link`s without an `Inertial` are dropped, mimics are ignored and the colors of `Material`s are ignored in favor of `<gazebo>` extensions.
+	GazeboClassic,
+	/// [Gazebo Sim](https://gazebosim.org/), which converts the description to SDFormat.
+	///
+	/// `Link`s without an `Inertial` are dropped.
+	GazeboSim,
+	/// The URDF import of [MuJoCo](https://mujoco.org/).
+	///
+	/// Visuals are discarded, mimics are ignored and only STL, OBJ and MSH meshes can be loaded.
+	/// [`Floating`](JointType::Floating) `Joint`s become free joints, which can only be attached to the root `Link`.
+	MuJoCo,
+}
+
+impl URDFConsumer {
+	/// All `URDFConsumer`s.
+	pub const ALL: [URDFConsumer; 6] = [
+		URDFConsumer::RobotStatePublisher,
+		URDFConsumer::KDL,
+		URDFConsumer::MoveIt,
+		URDFConsumer::GazeboClassic,
+		URDFConsumer::GazeboSim,
+		URDFConsumer::MuJoCo,
+	];
+
+	/// Finds the parts of the `tree`, which are rejected or ignored by this consumer.
+	///
+	/// The incompatibilities are ordered by the `Joint`s and `Link`s, which are each ordered by name.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{
+	/// #     prelude::*,
+	/// #     to_rdf::to_urdf::{Handling, IncompatibilityKind, URDFConsumer},
+	/// #     JointType, Link, SmartJointBuilder,
+	/// # };
+	/// let robot = Link::builder("world").build_tree().to_robot("robot");
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(SmartJointBuilder::new_floating("base_joint"), Link::builder("base"))
+	///     .unwrap();
+	///
+	/// let incompatibilities = URDFConsumer::KDL.check(&robot);
+	/// assert_eq!(incompatibilities.len(), 1);
+	/// assert_eq!(incompatibilities[0].path, "joint[base_joint]");
+	/// assert_eq!(incompatibilities[0].handling, Handling::Ignored);
+	/// assert_eq!(
+	///     incompatibilities[0].kind,
+	///     IncompatibilityKind::UnsupportedJointType(JointType::Floating)
+	/// );
+	///
+	/// // MuJoCo turns the floating joint into a free joint.
+	/// assert!(URDFConsumer::MuJoCo.check(&robot).is_empty());
+	/// ```
+	pub fn check(self, tree: &impl KinematicInterface) -> Vec<Incompatibility> {
+		check_compatibility(tree, &[self])
+	}
+
+	/// Gets how this consumer handles the `kind` of part, or `None` if it is supported.
+	fn handling(self, kind: &IncompatibilityKind) -> Option<Handling> {
+		use IncompatibilityKind::*;
+		use URDFConsumer::*;
+
+		match (self, kind) {
+			(RobotStatePublisher | KDL | GazeboClassic | GazeboSim, UnsupportedJointType(_)) => {
+				Some(Handling::Ignored)
+			}
+			(MuJoCo, NestedFloatingJoint) => Some(Handling::Rejected),
+			(RobotStatePublisher | MoveIt, MissingLimit(_)) => Some(Handling::Rejected),
+			(
+				KDL | GazeboClassic | GazeboSim,
+				MissingLimit(JointType::Revolute | JointType::Prismatic),
+			) => Some(Handling::Rejected),
+			(KDL | GazeboClassic | MuJoCo, Mimic) => Some(Handling::Ignored),
+			(KDL, RootInertial) => Some(Handling::Ignored),
+			(GazeboClassic | GazeboSim, MissingInertial) => Some(Handling::Ignored),
+			(GazeboClassic, Material) => Some(Handling::Ignored),
+			(MuJoCo, Visual) => Some(Handling::Ignored),
+			(MuJoCo, MeshFormat(_)) => Some(Handling::Rejected),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for URDFConsumer {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			URDFConsumer::RobotStatePublisher => write!(f, "robot_state_publisher"),
+			URDFConsumer::KDL => write!(f, "KDL"),
+			URDFConsumer::MoveIt => write!(f, "MoveIt"),
+			URDFConsumer::GazeboClassic => write!(f, "Gazebo Classic"),
+			URDFConsumer::GazeboSim => write!(f, "Gazebo Sim"),
+			URDFConsumer::MuJoCo => write!(f, "MuJoCo"),
+		}
+	}
+}
+
+/// The way a [`URDFConsumer`] handles a part of a description, which it does not support.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Handling {
+	/// The part is silently ignored or replaced, the rest of the description can be used.
+	Ignored,
+	/// The description is rejected as a whole.
+	Rejected,
+}
+
+impl fmt::Display for Handling {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Handling::Ignored => write!(f, "ignored"),
+			Handling::Rejected => write!(f, "rejected"),
+		}
+	}
+}
+
+/// The kind of part of a description, which is not supported by every [`URDFConsumer`].
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum IncompatibilityKind {
+	/// A multi-axis [`Floating`](JointType::Floating) or [`Planar`](JointType::Planar) `Joint`.
+	#[error("The {0:?} joint type is not supported")]
+	UnsupportedJointType(JointType),
+	/// A [`Floating`](JointType::Floating) `Joint`, which is not attached to the root `Link`.
+	#[error("The floating joint is not attached to the root link")]
+	NestedFloatingJoint,
+	/// A moving `Joint` without `LimitData`.
+	#[error("The {0:?} joint has no limit")]
+	MissingLimit(JointType),
+	/// A `Joint`, which mimics another `Joint`.
+	#[error("The joint mimics another joint")]
+	Mimic,
+	/// An `Inertial` on the root `Link`.
+	#[error("The root link has an inertial")]
+	RootInertial,
+	/// A moving `Link` without an `Inertial`.
+	#[error("The link has no inertial")]
+	MissingInertial,
+	/// The `Material` of a `Visual`.
+	#[error("The visual has a material")]
+	Material,
+	/// A `Visual`.
+	#[error("The link has a visual")]
+	Visual,
+	/// A collision mesh with the specified file extension.
+	#[error("The mesh format {0:?} is not supported")]
+	MeshFormat(String),
+}
+
+/// A part of a description, which is not supported by a [`URDFConsumer`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Incompatibility {
+	/// The consumer, which does not support the part.
+	pub consumer: URDFConsumer,
+	/// The way the consumer handles the part.
+	pub handling: Handling,
+	/// The path of the element of the part, like the paths of a [`Diagnostic`](crate::validate::Diagnostic).
+	pub path: String,
+	/// The kind of part.
+	pub kind: IncompatibilityKind,
+}
+
+impl fmt::Display for Incompatibility {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"{}: {} ({} by {})",
+			self.path, self.kind, self.handling, self.consumer
+		)
+	}
+}
+
+/// An error, which is returned by [`to_urdf`](super::to_urdf) when a description is rejected by one of the [`URDFConfig::strict_consumers`](super::URDFConfig::strict_consumers).
+///
+/// Since [`to_urdf`](super::to_urdf) returns a [`quick_xml::Error`], this error is wrapped in a [`quick_xml::Error::Io`] with the kind [`InvalidData`](std::io::ErrorKind::InvalidData).
+/// It can be retrieved with [`URDFCompatibilityError::from_xml_error`].
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+#[error(
+	"The description is rejected by {} part(s), the first is `{}`",
+	.incompatibilities.len(),
+	.incompatibilities[0]
+)]
+pub struct URDFCompatibilityError {
+	/// The rejected parts of the description.
+	pub incompatibilities: Vec<Incompatibility>,
+}
+
+impl URDFCompatibilityError {
+	/// Gets the `URDFCompatibilityError` wrapped in the `error`, if the `error` was caused by a rejected description.
+	pub fn from_xml_error(error: &quick_xml::Error) -> Option<&Self> {
+		match error {
+			quick_xml::Error::Io(error) => error.get_ref()?.downcast_ref(),
+			_ => None,
+		}
+	}
+}
+
+/// Finds the parts of the `tree`, which are rejected or ignored by the `consumers`.
+///
+/// The incompatibilities are ordered by the `Joint`s and `Link`s, which are each ordered by name, and then by consumer.
+pub fn check_compatibility(
+	tree: &impl KinematicInterface,
+	consumers: &[URDFConsumer],
+) -> Vec<Incompatibility> {
+	parts(tree)
+		.into_iter()
+		.flat_map(|(path, kind)| {
+			consumers.iter().filter_map(move |consumer| {
+				consumer.handling(&kind).map(|handling| Incompatibility {
+					consumer: *consumer,
+					handling,
+					path: path.clone(),
+					kind: kind.clone(),
+				})
+			})
+		})
+		.collect()
+}
+
+/// Gets the parts of the `tree`, which are not supported by every [`URDFConsumer`], with the paths of their elements.
+fn parts(tree: &impl KinematicInterface) -> Vec<(String, IncompatibilityKind)> {
+	let mut parts = Vec::new();
+	let root_name = tree.get_root_link().read().unwrap().name().clone();
+
+	let mut joints: Vec<_> = {
+		let joints = tree.get_joints();
+		let joints = joints.read().unwrap();
+		joints
+			.values()
+			.filter_map(|joint| joint.upgrade())
+			.collect()
+	};
+	joints.sort_by_key(|joint| joint.read().unwrap().name().clone());
+
+	for joint in joints {
+		let joint = joint.read().unwrap();
+		let name = joint.name();
+
+		match joint.joint_type() {
+			JointType::Floating | JointType::Planar => {
+				parts.push((
+					format!("joint[{name}]"),
+					IncompatibilityKind::UnsupportedJointType(joint.joint_type()),
+				));
+			}
+			JointType::Revolute | JointType::Continuous | JointType::Prismatic
+				if joint.limit().is_none() =>
+			{
+				parts.push((
+					format!("joint[{name}]/limit"),
+					IncompatibilityKind::MissingLimit(joint.joint_type()),
+				));
+			}
+			_ => (),
+		}
+
+		if joint.joint_type() == JointType::Floating
+			&& joint.parent_link().read().unwrap().name() != &root_name
+		{
+			parts.push((
+				format!("joint[{name}]"),
+				IncompatibilityKind::NestedFloatingJoint,
+			));
+		}
+
+		if joint.mimic().is_some() {
+			parts.push((format!("joint[{name}]/mimic"), IncompatibilityKind::Mimic));
+		}
+	}
+
+	let mut links: Vec<_> = {
+		let links = tree.get_links();
+		let links = links.read().unwrap();
+		links.values().filter_map(|link| link.upgrade()).collect()
+	};
+	links.sort_by_key(|link| link.read().unwrap().name().clone());
+
+	for link in links {
+		let link = link.read().unwrap();
+		let name = link.name();
+
+		match (link.parent(), link.inertial()) {
+			(LinkParent::KinematicTree(_), Some(_)) => parts.push((
+				format!("link[{name}]/inertial"),
+				IncompatibilityKind::RootInertial,
+			)),
+			(parent, None) if is_simulated_separately(parent) => parts.push((
+				format!("link[{name}]"),
+				IncompatibilityKind::MissingInertial,
+			)),
+			_ => (),
+		}
+
+		for (index, visual) in link.visuals().iter().enumerate() {
+			parts.push((
+				format!("link[{name}]/visual[{index}]"),
+				IncompatibilityKind::Visual,
+			));
+			if visual.material().is_some() {
+				parts.push((
+					format!("link[{name}]/visual[{index}]/material"),
+					IncompatibilityKind::Material,
+				));
+			}
+		}
+
+		for (index, collider) in link.colliders().iter().enumerate() {
+			if let GeometryShapeContainer::Mesh(mesh) = collider.geometry().shape_container() {
+				let extension = mesh
+					.path
+					.rsplit_once('.')
+					.map(|(_, extension)| extension.to_lowercase())
+					.unwrap_or_default();
+
+				if !MUJOCO_MESH_FORMATS.contains(&extension.as_str()) {
+					parts.push((
+						format!("link[{name}]/collision[{index}]/geometry/mesh"),
+						IncompatibilityKind::MeshFormat(extension),
+					));
+				}
+			}
+		}
+	}
+
+	parts
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{check_compatibility, Handling, URDFCompatibilityError, URDFConsumer};
+	use crate::{
+		joint_data::{LimitData, MimicBuilderData},
+		link_data::{
+			geometry::{BoxGeometry, MeshGeometry},
+			Collision, Inertial, Visual,
+		},
+		linkbuilding::LinkBuilder,
+		material::MaterialDescriptor,
+		prelude::*,
+		to_rdf::to_urdf::{to_urdf, URDFConfig},
+		JointBuilder, JointType, Link, Robot,
+	};
+	use test_log::test;
+
+	fn weight() -> Inertial {
+		Inertial {
+			mass: 1.,
+			ixx: 0.1,
+			iyy: 0.1,
+			izz: 0.1,
+			..Default::default()
+		}
+	}
+
+	fn attach(robot: &Robot, parent: &str, joint: JointBuilder, link: LinkBuilder) {
+		robot
+			.get_link(parent)
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(joint, link)
+			.unwrap();
+	}
+
+	/// A robot with a part, which is not supported by at least one of the consumers, on every `Joint` and `Link`.
+	fn robot() -> Robot {
+		let robot = Link::builder("world")
+			.add_intertial(weight())
+			.build_tree()
+			.to_robot("robot");
+
+		attach(
+			&robot,
+			"world",
+			JointBuilder::new("base_joint", JointType::Floating),
+			Link::builder("base").add_intertial(weight()),
+		);
+		attach(
+			&robot,
+			"base",
+			JointBuilder::new("arm_joint", JointType::Planar),
+			Link::builder("arm")
+				.add_visual(
+					Visual::builder(BoxGeometry::new(1., 0.1, 0.1))
+						.materialized(MaterialDescriptor::new_rgb(1., 0., 0.)),
+				)
+				.add_collider(Collision::builder(MeshGeometry::new(
+					"package://robot/meshes/arm.DAE",
+					(1., 0.1, 0.1),
+					None,
+				))),
+		);
+		attach(
+			&robot,
+			"arm",
+			JointBuilder::new("elbow", JointType::Continuous),
+			Link::builder("forearm").add_intertial(weight()),
+		);
+		let mut wrist = JointBuilder::new("wrist", JointType::Revolute);
+		*wrist.mimic_mut() = Some(MimicBuilderData {
+			joint_name: "elbow".to_string(),
+			multiplier: None,
+			offset: None,
+		});
+		attach(
+			&robot,
+			"forearm",
+			wrist,
+			Link::builder("hand").add_intertial(weight()),
+		);
+		attach(
+			&robot,
+			"hand",
+			JointBuilder::new("tool_joint", JointType::Floating),
+			Link::builder("tool").add_intertial(weight()),
+		);
+
+		robot
+	}
+
+	/// Gets the paths and the handling of the parts of the `robot`, which are not supported by the `consumer`.
+	fn check(robot: &Robot, consumer: URDFConsumer) -> Vec<(String, Handling)> {
+		consumer
+			.check(robot)
+			.into_iter()
+			.map(|incompatibility| (incompatibility.path, incompatibility.handling))
+			.collect()
+	}
+
+	#[test]
+	fn consumers() {
+		let robot = robot();
+		let ignored = |path: &str| (path.to_string(), Handling::Ignored);
+		let rejected = |path: &str| (path.to_string(), Handling::Rejected);
+
+		assert_eq!(
+			check(&robot, URDFConsumer::RobotStatePublisher),
+			vec![
+				ignored("joint[arm_joint]"),
+				ignored("joint[base_joint]"),
+				rejected("joint[elbow]/limit"),
+				ignored("joint[tool_joint]"),
+				rejected("joint[wrist]/limit"),
+			]
+		);
+		assert_eq!(
+			check(&robot, URDFConsumer::KDL),
+			vec![
+				ignored("joint[arm_joint]"),
+				ignored("joint[base_joint]"),
+				ignored("joint[tool_joint]"),
+				rejected("joint[wrist]/limit"),
+				ignored("joint[wrist]/mimic"),
+				ignored("link[world]/inertial"),
+			]
+		);
+		assert_eq!(
+			check(&robot, URDFConsumer::MoveIt),
+			vec![
+				rejected("joint[elbow]/limit"),
+				rejected("joint[wrist]/limit")
+			]
+		);
+		assert_eq!(
+			check(&robot, URDFConsumer::GazeboClassic),
+			vec![
+				ignored("joint[arm_joint]"),
+				ignored("joint[base_joint]"),
+				ignored("joint[tool_joint]"),
+				rejected("joint[wrist]/limit"),
+				ignored("joint[wrist]/mimic"),
+				ignored("link[arm]"),
+				ignored("link[arm]/visual[0]/material"),
+			]
+		);
+		assert_eq!(
+			check(&robot, URDFConsumer::GazeboSim),
+			vec![
+				ignored("joint[arm_joint]"),
+				ignored("joint[base_joint]"),
+				ignored("joint[tool_joint]"),
+				rejected("joint[wrist]/limit"),
+				ignored("link[arm]"),
+			]
+		);
+		assert_eq!(
+			check(&robot, URDFConsumer::MuJoCo),
+			vec![
+				rejected("joint[tool_joint]"),
+				ignored("joint[wrist]/mimic"),
+				ignored("link[arm]/visual[0]"),
+				rejected("link[arm]/collision[0]/geometry/mesh"),
+			]
+		);
+	}
+
+	#[test]
+	fn compatible() {
+		let robot = Link::builder("base").build_tree().to_robot("robot");
+		let mut shoulder = JointBuilder::new("shoulder", JointType::Revolute);
+		*shoulder.limit_mut() = Some(LimitData {
+			lower: Some(-1.),
+			upper: Some(1.),
+			effort: 10.,
+			velocity: 1.,
+		});
+		attach(
+			&robot,
+			"base",
+			shoulder,
+			Link::builder("arm")
+				.add_intertial(weight())
+				.add_collider(Collision::builder(MeshGeometry::new(
+					"package://robot/meshes/arm.stl",
+					(1., 0.1, 0.1),
+					None,
+				))),
+		);
+
+		assert_eq!(check_compatibility(&robot, &URDFConsumer::ALL), vec![]);
+		assert!(to_urdf(
+			&robot,
+			URDFConfig {
+				strict_consumers: URDFConsumer::ALL.to_vec(),
+				..Default::default()
+			}
+		)
+		.is_ok());
+	}
+
+	#[test]
+	fn strict() {
+		let robot = robot();
+
+		let error = to_urdf(
+			&robot,
+			URDFConfig {
+				strict_consumers: vec![URDFConsumer::MoveIt, URDFConsumer::GazeboSim],
+				..Default::default()
+			},
+		)
+		.err()
+		.unwrap();
+		let error = URDFCompatibilityError::from_xml_error(&error).unwrap();
+		assert_eq!(
+			error
+				.incompatibilities
+				.iter()
+				.map(|incompatibility| (incompatibility.path.as_str(), incompatibility.consumer))
+				.collect::<Vec<_>>(),
+			vec![
+				("joint[elbow]/limit", URDFConsumer::MoveIt),
+				("joint[wrist]/limit", URDFConsumer::MoveIt),
+				("joint[wrist]/limit", URDFConsumer::GazeboSim),
+			]
+		);
+		assert_eq!(
+			error.to_string(),
+			"The description is rejected by 3 part(s), the first is `joint[elbow]/limit: The Continuous joint has no limit (rejected by MoveIt)`"
+		);
+
+		assert!(to_urdf(&robot, URDFConfig::default()).is_ok());
+	}
+
+	#[test]
+	fn strict_ignored() {
+		let robot = Link::builder("world").build_tree().to_robot("robot");
+		attach(
+			&robot,
+			"world",
+			JointBuilder::new("base_joint", JointType::Floating),
+			Link::builder("base"),
+		);
+
+		// KDL only ignores the floating joint, which is allowed.
+		assert_eq!(
+			check(&robot, URDFConsumer::KDL),
+			vec![("joint[base_joint]".to_string(), Handling::Ignored)]
+		);
+		assert!(to_urdf(
+			&robot,
+			URDFConfig {
+				strict_consumers: vec![URDFConsumer::KDL],
+				..Default::default()
+			}
+		)
+		.is_ok());
+	}
+}
@@ -0,0 +1,1029 @@
+//! The infrastructure to describe a `Robot` as a [xacro](http://wiki.ros.org/xacro) URDF description.
+//!
+//! The description is generated with [`to_urdf`] and afterwards factored to make it easier to edit by hand:
+//! - Subtrees (a [`Joint`](crate::Joint) with its child [`Link`](crate::Link) and all its descendants),
+//!   which are structurally identical except for their [`GroupID`](crate::identifiers::GroupID), are written once as a `<xacro:macro>`.
+//!   Every occurence of the subtree is replaced by an instantiation of the macro.
+//!   The `prefix` parameter of the macro replaces the `GroupID` and the `parent` parameter is the name of the parent `Link`.
+//! - Subtrees, which only differ in the sign of some of their numbers (e.g. as a result of [`Chained::mirror`](crate::Chained)),
+//!   get an additional `reflect` parameter. It is `1` for the first occurence and `-1` for the mirrored occurences.
+//! - Numbers, which are used more than once in the same component of attributes with the same name, are lifted into `<xacro:property>` elements.
+//!   The properties are named after the element of their first use. The numbers `0` and `1` are never lifted.
+//!
+//! Only the outermost repeated subtrees are factored, repeated subtrees inside of a macro are not turned into nested macros.
+//! All names in a subtree need to have the same `GroupID` for it to be factored.
+use std::{
+	collections::{HashMap, HashSet},
+	io::Cursor,
+};
+
+use itertools::Itertools;
+use quick_xml::{
+	events::{BytesDecl, BytesText, Event},
+	Writer,
+};
+
+use super::{
+	make_xml_writer,
+	to_urdf::{to_urdf, ToURDF, URDFConfig},
+	xml_writer_to_string, XMLMode,
+};
+use crate::{
+	cluster_objects::KinematicInterface,
+	from_rdf::xml_element::XMLElement,
+	identifiers::{GroupID, DELIMITER_CLOSE_GROUPID, DELIMITER_OPEN_GROUPID},
+	link::Link,
+};
+
+/// The XML namespace of the xacro elements.
+pub const XACRO_NAMESPACE: &str = "http://www.ros.org/wiki/xacro";
+
+/// Identifies a number, which can be lifted to a property, by the name of its attribute, its index in the attribute value and its magnitude.
+type PropertyKey = (String, usize, String);
+
+/// The attributes of URDF elements, which contain identifiers instead of numbers.
+const IDENTIFIER_ATTRIBUTES: [&str; 4] = ["name", "link", "joint", "filename"];
+
+/// A function to represent a `KinematicInterface` implementor as a xacro URDF description.
+///
+/// The `urdf_config` is used to generate the underlying URDF description.
+/// See the [module-level documentation](self) for the applied factoring.
+///
+/// # Example
+/// ```
+/// # use robot_description_builder::{
+/// #     link_data::{geometry::*, Visual},
+/// #     prelude::*,
+/// #     to_rdf::{
+/// #         to_urdf::URDFConfig,
+/// #         to_xacro::to_xacro,
+/// #         xml_writer_to_string, XMLMode,
+/// #     },
+/// #     Link, MirrorAxis, SmartJointBuilder, Transform,
+/// # };
+/// let robot = Link::builder("base").build_tree().to_robot("biped");
+///
+/// let left_leg = Link::builder("[[left]]_leg")
+///     .add_visual(
+///         Visual::builder(BoxGeometry::new(0.1, 0.1, 0.6))
+///             .transformed(Transform::new_translation(0., 0.05, -0.3)),
+///     )
+///     .build_tree()
+///     .yank_root()
+///     .unwrap();
+///
+/// let mut right_leg = left_leg.mirror(MirrorAxis::Y);
+/// right_leg.change_group_id("right").unwrap();
+///
+/// let mut root_link = robot.get_root_link();
+/// let mut root_link = root_link.write().unwrap();
+/// root_link
+///     .try_attach_child(
+///         SmartJointBuilder::new_fixed("[[left]]_hip")
+///             .add_transform(Transform::new_translation(0., 0.2, 0.)),
+///         left_leg,
+///     )
+///     .unwrap();
+/// root_link
+///     .try_attach_child(
+///         SmartJointBuilder::new_fixed("[[right]]_hip")
+///             .add_transform(Transform::new_translation(0., -0.2, 0.)),
+///         right_leg,
+///     )
+///     .unwrap();
+/// # drop(root_link);
+///
+/// assert_eq!(
+///     xml_writer_to_string(
+///         to_xacro(
+///             &robot,
+///             URDFConfig {
+///                 xml_mode: XMLMode::Indent(' ', 2),
+///                 ..Default::default()
+///             }
+///         )
+///         .unwrap()
+///     ),
+///     r#"﻿<?xml version="1.0"?>
+/// <robot name="biped" xmlns:xacro="http://www.ros.org/wiki/xacro">
+///   <xacro:macro name="leg" params="prefix parent reflect">
+///     <joint name="${prefix}_hip" type="fixed">
+///       <origin xyz="0 ${reflect*0.2} 0"/>
+///       <parent link="${parent}"/>
+///       <child link="${prefix}_leg"/>
+///     </joint>
+///     <link name="${prefix}_leg">
+///       <visual>
+///         <origin xyz="0 ${reflect*0.05} -0.3"/>
+///         <geometry>
+///           <box size="0.1 0.1 0.6"/>
+///         </geometry>
+///       </visual>
+///     </link>
+///   </xacro:macro>
+///   <link name="base"/>
+///   <xacro:leg prefix="left" parent="base" reflect="1"/>
+///   <xacro:leg prefix="right" parent="base" reflect="-1"/>
+/// </robot>"#
+/// );
+/// ```
+pub fn to_xacro(
+	tree: &(impl KinematicInterface + ToURDF),
+	urdf_config: URDFConfig,
+) -> Result<Writer<Cursor<Vec<u8>>>, quick_xml::Error> {
+	let urdf = xml_writer_to_string(to_urdf(
+		tree,
+		URDFConfig {
+			xml_mode: XMLMode::NoIndent,
+			..urdf_config.clone()
+		},
+	)?);
+	let robot = XMLElement::parse(urdf.trim_start_matches('\u{feff}'))
+		.expect("The generated URDF should be valid XML");
+
+	let subtrees = subtrees(&tree.get_root_link().read().unwrap());
+	let description = XacroDescription::new(&robot, &subtrees);
+
+	let mut writer = make_xml_writer(urdf_config.xml_mode);
+	writer.write_bom()?;
+	writer.write_event(Event::Decl(BytesDecl::new("1.0", None, None)))?;
+	description.write(&mut writer)?;
+	Ok(writer)
+}
+
+/// A `Joint` with its child `Link` and all their descendants, as found in the `Robot`.
+///
+/// All names are the names as written in the URDF description.
+struct Subtree {
+	/// The name of the parent `Link` of the `Joint`.
+	parent: String,
+	/// The name of the child `Link` of the `Joint`.
+	child: String,
+	/// The `GroupID` shared by all `Link`s and `Joint`s in the subtree, if there is exactly one.
+	group_id: Option<String>,
+	/// The names of the `Link`s and `Joint`s in the subtree.
+	elements: HashSet<String>,
+	/// The templated names (where the `GroupID` is replaced by `${prefix}`) indexed by the name.
+	names: HashMap<String, String>,
+	/// The subtrees of the `Joint`s of the child `Link`.
+	children: Vec<Subtree>,
+}
+
+impl Subtree {
+	/// Gets an iterator over this subtree and all its descendants in depth first order.
+	fn iter(&self) -> Box<dyn Iterator<Item = &Subtree> + '_> {
+		Box::new(std::iter::once(self).chain(self.children.iter().flat_map(Subtree::iter)))
+	}
+}
+
+/// Collects the `Subtree`s of all the `Joint`s of the `link`.
+fn subtrees(link: &Link) -> Vec<Subtree> {
+	link.joints()
+		.iter()
+		.map(|joint| {
+			let joint = joint.read().unwrap();
+			let child_link = joint.child_link();
+			let child_link = child_link.read().unwrap();
+			let children = subtrees(&child_link);
+
+			let group_id = [joint.name(), child_link.name()]
+				.into_iter()
+				.map(|name| single_group_id(name))
+				.chain(children.iter().map(|child| child.group_id.as_deref()))
+				.all_equal_value()
+				.ok()
+				.flatten()
+				.map(str::to_string);
+
+			let mut elements: HashSet<String> = children
+				.iter()
+				.flat_map(|child| child.elements.iter().cloned())
+				.collect();
+			elements.insert(joint.name().display());
+			elements.insert(child_link.name().display());
+
+			let mut names = HashMap::new();
+			if let Some(group_id) = &group_id {
+				[joint.name(), child_link.name()]
+					.into_iter()
+					.chain(
+						child_link
+							.visuals()
+							.iter()
+							.filter_map(|visual| visual.name()),
+					)
+					.chain(
+						child_link
+							.colliders()
+							.iter()
+							.filter_map(|collision| collision.name()),
+					)
+					.filter(|name| single_group_id(name) == Some(group_id))
+					.for_each(|name| {
+						names.insert(name.display(), templated_name(name));
+					});
+
+				children
+					.iter()
+					.for_each(|child| names.extend(child.names.clone()));
+			}
+
+			Subtree {
+				parent: joint.parent_link().read().unwrap().name().display(),
+				child: child_link.name().display(),
+				group_id,
+				elements,
+				names,
+				children,
+			}
+		})
+		.collect()
+}
+
+/// Gets the `GroupID` of `name`, if it contains exactly one `GroupID`.
+///
+/// These are the `GroupID`s, which can be changed by a [`GroupIDChanger`](crate::identifiers::GroupIDChanger).
+fn single_group_id(name: &str) -> Option<&str> {
+	match name.matches(DELIMITER_OPEN_GROUPID).count() == 1
+		&& name.matches(DELIMITER_CLOSE_GROUPID).count() == 1
+	{
+		true => name
+			.split_once(DELIMITER_OPEN_GROUPID)
+			.and_then(|(_, remainder)| remainder.split_once(DELIMITER_CLOSE_GROUPID))
+			.map(|(group_id, _)| group_id),
+		false => None,
+	}
+}
+
+/// Replaces the `GroupID` in `name` by the `prefix` macro parameter and applies the `GroupID` delimiter transformations to the remainder.
+fn templated_name(name: &str) -> String {
+	let (pre, post) = name
+		.split_once(DELIMITER_OPEN_GROUPID)
+		.and_then(|(pre, remainder)| {
+			remainder
+				.split_once(DELIMITER_CLOSE_GROUPID)
+				.map(|(_, post)| (pre, post))
+		})
+		.expect("Only names with a single GroupID should be templated");
+
+	format!(
+		"{}${{prefix}}{}",
+		escape_substitutions(&pre.display()),
+		escape_substitutions(&post.display())
+	)
+}
+
+/// Escapes the xacro substitution syntax (`${...}` and `$(...)`) in literal text.
+fn escape_substitutions(text: &str) -> String {
+	text.replace("${", "$${").replace("$(", "$$(")
+}
+
+/// Converts `text` into a name, which only contains alphanumeric characters and single underscores.
+fn identifier(text: &str) -> String {
+	text.replace("${prefix}", "")
+		.split(|c: char| !c.is_ascii_alphanumeric())
+		.filter(|part| !part.is_empty())
+		.join("_")
+}
+
+/// Makes `name` unique in `used`, by appending a number when required.
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+	let name = (1..)
+		.map(|count| match count {
+			1 => name.clone(),
+			_ => format!("{name}_{count}"),
+		})
+		.find(|candidate| !used.contains(candidate))
+		.unwrap();
+	used.insert(name.clone());
+	name
+}
+
+/// A part of an attribute value in the xacro description.
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+	/// Text, which is written as is.
+	Text(String),
+	/// A number as written in the URDF description.
+	///
+	/// When `reflect` is `true`, the number is multiplied by the `reflect` parameter of the macro.
+	Number { value: String, reflect: bool },
+}
+
+impl Token {
+	/// Gets the magnitude of a `Number` as written in the URDF, which is used to identify shared numbers.
+	fn magnitude(value: &str) -> &str {
+		value.trim_start_matches('-')
+	}
+
+	/// Checks if a number is zero, since zero has no sign that can be reflected.
+	fn is_zero(value: &str) -> bool {
+		value.parse::<f64>().is_ok_and(|value| value == 0.)
+	}
+
+	/// Writes the token, in which the magnitude of a number is substituted by the `property` if it exists.
+	fn render<'a>(&self, property: impl FnOnce(&str) -> Option<&'a String>) -> String {
+		match self {
+			Token::Text(text) => text.clone(),
+			Token::Number { value, reflect } => {
+				let magnitude = Self::magnitude(value);
+				let sign = if value.starts_with('-') { "-" } else { "" };
+
+				match (property(magnitude), reflect) {
+					(None, false) => value.clone(),
+					(Some(property), false) => format!("${{{sign}{property}}}"),
+					(property, true) => format!(
+						"${{{sign}reflect*{}}}",
+						property.map_or(magnitude, String::as_str)
+					),
+				}
+			}
+		}
+	}
+}
+
+/// An element of the xacro description, in which the numbers can be parameterized.
+#[derive(Debug, PartialEq, Clone)]
+struct XacroElement {
+	name: String,
+	attributes: Vec<(String, Vec<Token>)>,
+	children: Vec<XacroElement>,
+	text: String,
+}
+
+impl XacroElement {
+	/// Converts an `element` of the URDF description.
+	///
+	/// Identifiers found in `names` are replaced by their templated version.
+	fn new(element: &XMLElement, names: &HashMap<String, String>) -> Self {
+		let attributes = element
+			.attributes
+			.iter()
+			.map(|(key, value)| {
+				let is_number = |part: &str| part.parse::<f64>().is_ok_and(f64::is_finite);
+
+				let tokens = if IDENTIFIER_ATTRIBUTES.contains(&key.as_str()) {
+					vec![Token::Text(
+						names
+							.get(value)
+							.cloned()
+							.unwrap_or_else(|| escape_substitutions(value)),
+					)]
+				} else if !value.trim().is_empty() && value.split_whitespace().all(is_number) {
+					value
+						.split_whitespace()
+						.map(|part| Token::Number {
+							value: part.to_string(),
+							reflect: false,
+						})
+						.collect()
+				} else {
+					vec![Token::Text(escape_substitutions(value))]
+				};
+
+				(key.clone(), tokens)
+			})
+			.collect();
+
+		Self {
+			name: element.name.clone(),
+			attributes,
+			children: element
+				.children
+				.iter()
+				.map(|child| Self::new(child, names))
+				.collect(),
+			text: escape_substitutions(&element.text),
+		}
+	}
+
+	/// Creates an element without children, with only textual attributes.
+	fn new_empty(name: String, attributes: Vec<(&str, String)>) -> Self {
+		Self {
+			name,
+			attributes: attributes
+				.into_iter()
+				.map(|(key, value)| (key.to_string(), vec![Token::Text(value)]))
+				.collect(),
+			children: Vec::new(),
+			text: String::new(),
+		}
+	}
+
+	/// Gets the textual value of the attribute with the specified `key`, if it exists.
+	fn attribute(&self, key: &str) -> Option<String> {
+		self.attributes
+			.iter()
+			.find(|(attribute_key, _)| attribute_key == key)
+			.map(|(_, tokens)| tokens.iter().map(|token| token.render(|_| None)).join(" "))
+	}
+
+	/// Compares the structure of `elements` with `others`, in which numbers are compared by magnitude.
+	///
+	/// Returns for every number (in document order) if its sign is flipped in `others`, or `None` if they do not match.
+	fn flips(elements: &[Self], others: &[Self]) -> Option<Vec<bool>> {
+		let mut flips = Vec::new();
+		Self::collect_flips(elements, others, &mut flips).then_some(flips)
+	}
+
+	fn collect_flips(elements: &[Self], others: &[Self], flips: &mut Vec<bool>) -> bool {
+		elements.len() == others.len()
+			&& elements.iter().zip(others).all(|(element, other)| {
+				element.name == other.name
+					&& element.text == other.text
+					&& element.attributes.len() == other.attributes.len()
+					&& element.attributes.iter().zip(&other.attributes).all(
+						|((key, tokens), (other_key, other_tokens))| {
+							key == other_key
+								&& tokens.len() == other_tokens.len()
+								&& tokens.iter().zip(other_tokens).all(|pair| match pair {
+									(Token::Text(text), Token::Text(other_text)) => {
+										text == other_text
+									}
+									(
+										Token::Number { value, .. },
+										Token::Number {
+											value: other_value, ..
+										},
+									) => match Token::is_zero(value) {
+										true => Token::is_zero(other_value),
+										false
+											if Token::magnitude(value)
+												== Token::magnitude(other_value) =>
+										{
+											flips.push(value != other_value);
+											true
+										}
+										false => false,
+									},
+									_ => false,
+								})
+						},
+					) && Self::collect_flips(&element.children, &other.children, flips)
+			})
+	}
+
+	/// Marks the nonzero numbers (in document order) for which `reflect` yields `true` to be reflected.
+	fn set_reflect(&mut self, reflect: &mut impl Iterator<Item = bool>) {
+		self.attributes
+			.iter_mut()
+			.flat_map(|(_, tokens)| tokens.iter_mut())
+			.for_each(|token| match token {
+				Token::Number { value, reflect: r } if !Token::is_zero(value) => {
+					*r = reflect.next().unwrap_or_default();
+				}
+				_ => (),
+			});
+
+		self.children
+			.iter_mut()
+			.for_each(|child| child.set_reflect(reflect));
+	}
+
+	/// Collects the magnitudes of all numbers, which can be lifted to a property, with a suggested property name.
+	///
+	/// The `owner` is the name of the closest named `Link`, `Joint` or `Material`.
+	fn collect_numbers(&self, owner: &str, numbers: &mut Vec<(PropertyKey, String)>) {
+		let owner = match self.name.as_str() {
+			"link" | "joint" | "material" | "xacro:macro" => self
+				.attribute("name")
+				.map(|name| identifier(&name))
+				.unwrap_or_else(|| owner.to_string()),
+			_ => owner.to_string(),
+		};
+
+		for (key, tokens) in &self.attributes {
+			let components: &[&str] = match (self.name.as_str(), key.as_str()) {
+				("origin", "xyz") => &["x", "y", "z"],
+				("origin", "rpy") => &["roll", "pitch", "yaw"],
+				(_, "xyz" | "size" | "scale") => &["x", "y", "z"],
+				(_, "rgba") => &["r", "g", "b", "a"],
+				_ => &[],
+			};
+
+			let attribute = match (self.name.as_str(), key.as_str()) {
+				("origin", _) => None,
+				(name, "value") => Some(name),
+				(_, key) => Some(key),
+			};
+
+			tokens
+				.iter()
+				.enumerate()
+				.filter_map(|(index, token)| match token {
+					Token::Number { value, .. } => Some((index, Token::magnitude(value))),
+					Token::Text(_) => None,
+				})
+				.filter(|(_, magnitude)| {
+					magnitude
+						.parse::<f64>()
+						.is_ok_and(|magnitude| magnitude != 0. && magnitude != 1.)
+				})
+				.for_each(|(index, magnitude)| {
+					let component = match (components.get(index), tokens.len()) {
+						(Some(component), _) => Some(component.to_string()),
+						(None, 1) => None,
+						(None, _) => Some(index.to_string()),
+					};
+
+					let name = [Some(owner.as_str()), attribute, component.as_deref()]
+						.into_iter()
+						.flatten()
+						.filter(|part| !part.is_empty())
+						.join("_");
+
+					numbers.push((
+						(key.clone(), index, magnitude.to_string()),
+						identifier(&name),
+					));
+				});
+		}
+
+		self.children
+			.iter()
+			.for_each(|child| child.collect_numbers(&owner, numbers));
+	}
+
+	fn write(
+		&self,
+		writer: &mut Writer<Cursor<Vec<u8>>>,
+		properties: &HashMap<PropertyKey, String>,
+	) -> Result<(), quick_xml::Error> {
+		let attributes = self
+			.attributes
+			.iter()
+			.map(|(key, tokens)| {
+				(
+					key.as_str(),
+					tokens
+						.iter()
+						.enumerate()
+						.map(|(index, token)| {
+							token.render(|magnitude| {
+								properties.get(&(key.clone(), index, magnitude.to_string()))
+							})
+						})
+						.join(" "),
+				)
+			})
+			.collect_vec();
+
+		let element = writer
+			.create_element(self.name.as_str())
+			.with_attributes(attributes.iter().map(|(key, value)| (*key, value.as_str())));
+
+		if !self.children.is_empty() {
+			element.write_inner_content(|writer| {
+				self.children
+					.iter()
+					.map(|child| child.write(writer, properties))
+					.process_results(|iter| iter.collect::<()>())
+			})?;
+		} else if !self.text.is_empty() {
+			element.write_text_content(BytesText::new(&self.text))?;
+		} else {
+			element.write_empty()?;
+		}
+
+		Ok(())
+	}
+}
+
+/// A factored version of a URDF description.
+struct XacroDescription {
+	/// The name of the `Robot`.
+	name: String,
+	/// The lifted properties as `(name, number)`.
+	properties: Vec<(String, PropertyKey)>,
+	/// The `<xacro:macro>` elements.
+	macros: Vec<XacroElement>,
+	/// The elements of the description, in which the repeated subtrees are replaced by instantiations.
+	elements: Vec<XacroElement>,
+}
+
+impl XacroDescription {
+	/// Factors the URDF description `robot` using the `subtrees` of the described `Robot`.
+	fn new(robot: &XMLElement, subtrees: &[Subtree]) -> Self {
+		let element_name = |element: &XMLElement| match element.name.as_str() {
+			"link" | "joint" => element.attribute("name").map(str::to_string),
+			_ => None,
+		};
+
+		// The templated elements of all subtrees, which could be written as a macro.
+		let candidates = subtrees
+			.iter()
+			.flat_map(Subtree::iter)
+			.filter(|subtree| subtree.group_id.is_some())
+			.map(|subtree| {
+				let mut elements = robot
+					.children
+					.iter()
+					.filter(|element| {
+						element_name(element).is_some_and(|name| subtree.elements.contains(&name))
+					})
+					.map(|element| XacroElement::new(element, &subtree.names))
+					.collect_vec();
+
+				// The `Joint` of the subtree is always the first element.
+				elements
+					.iter_mut()
+					.take(1)
+					.flat_map(|joint| joint.children.iter_mut())
+					.filter(|child| child.name == "parent")
+					.for_each(|parent| {
+						parent.attributes = vec![(
+							"link".to_string(),
+							vec![Token::Text("${parent}".to_string())],
+						)]
+					});
+
+				(subtree, elements)
+			})
+			.collect_vec();
+
+		let mut macros = Vec::new();
+		let mut macro_names = HashSet::new();
+		// The instantiations indexed by the name of the first element they replace.
+		let mut instantiations: HashMap<String, XacroElement> = HashMap::new();
+		let mut consumed: HashSet<String> = HashSet::new();
+
+		for (index, (subtree, elements)) in candidates.iter().enumerate() {
+			if subtree.elements.iter().any(|name| consumed.contains(name)) {
+				continue;
+			}
+
+			let matches = candidates
+				.iter()
+				.skip(index + 1)
+				.filter(|(other, _)| {
+					!other
+						.elements
+						.iter()
+						.any(|name| consumed.contains(name) || subtree.elements.contains(name))
+				})
+				.filter_map(|(other, other_elements)| {
+					XacroElement::flips(elements, other_elements).map(|flips| (*other, flips))
+				})
+				.collect_vec();
+
+			// The numbers with a flipped sign should be the same for all mirrored instances.
+			let reflected = matches
+				.iter()
+				.map(|(_, flips)| flips)
+				.find(|flips| flips.iter().any(|flip| *flip))
+				.cloned();
+
+			let instances = matches
+				.into_iter()
+				.filter_map(|(other, flips)| match flips.iter().any(|flip| *flip) {
+					false => Some((other, false)),
+					true if Some(&flips) == reflected.as_ref() => Some((other, true)),
+					true => None,
+				})
+				.collect_vec();
+
+			if instances.is_empty() {
+				continue;
+			}
+
+			let macro_name = unique_name(
+				Some(identifier(
+					subtree.names.get(&subtree.child).unwrap_or(&subtree.child),
+				))
+				.filter(|name| !name.is_empty())
+				.unwrap_or_else(|| "subtree".to_string()),
+				&mut macro_names,
+			);
+
+			let mut body = elements.clone();
+			if let Some(reflected) = &reflected {
+				let mut reflected = reflected.iter().copied();
+				body.iter_mut()
+					.for_each(|element| element.set_reflect(&mut reflected));
+			}
+
+			let mut params = vec!["prefix", "parent"];
+			if reflected.is_some() {
+				params.push("reflect");
+			}
+
+			macros.push(XacroElement {
+				children: body,
+				..XacroElement::new_empty(
+					"xacro:macro".to_string(),
+					vec![("name", macro_name.clone()), ("params", params.join(" "))],
+				)
+			});
+
+			for (instance, is_reflected) in std::iter::once((*subtree, false)).chain(instances) {
+				let mut attributes = vec![
+					(
+						"prefix",
+						escape_substitutions(
+							&instance.group_id.as_deref().unwrap_or_default().display(),
+						),
+					),
+					("parent", escape_substitutions(&instance.parent)),
+				];
+				if reflected.is_some() {
+					attributes.push(("reflect", if is_reflected { "-1" } else { "1" }.to_string()));
+				}
+
+				if let Some(first) = robot
+					.children
+					.iter()
+					.filter_map(element_name)
+					.find(|name| instance.elements.contains(name))
+				{
+					instantiations.insert(
+						first,
+						XacroElement::new_empty(format!("xacro:{macro_name}"), attributes),
+					);
+				}
+				consumed.extend(instance.elements.iter().cloned());
+			}
+		}
+
+		let elements = robot
+			.children
+			.iter()
+			.filter_map(|element| match element_name(element) {
+				Some(name) if instantiations.contains_key(&name) => instantiations.remove(&name),
+				Some(name) if consumed.contains(&name) => None,
+				_ => Some(XacroElement::new(element, &HashMap::new())),
+			})
+			.collect_vec();
+
+		let mut description = Self {
+			name: escape_substitutions(robot.attribute("name").unwrap_or_default()),
+			properties: Vec::new(),
+			macros,
+			elements,
+		};
+		description.lift_properties();
+		description
+	}
+
+	/// Lifts all numbers, which are used more than once, to properties.
+	fn lift_properties(&mut self) {
+		let mut numbers = Vec::new();
+		self.macros
+			.iter()
+			.chain(&self.elements)
+			.for_each(|element| element.collect_numbers("", &mut numbers));
+
+		let counts = numbers.iter().counts_by(|(number, _)| number.clone());
+		let mut used_names = HashSet::new();
+
+		self.properties = numbers
+			.into_iter()
+			.filter(|(number, _)| counts[number] > 1)
+			.unique_by(|(number, _)| number.clone())
+			.map(|(number, name)| {
+				let name = match name.is_empty() {
+					true => "value".to_string(),
+					false => name,
+				};
+				(unique_name(name, &mut used_names), number)
+			})
+			.collect();
+	}
+
+	fn write(&self, writer: &mut Writer<Cursor<Vec<u8>>>) -> Result<(), quick_xml::Error> {
+		let properties: HashMap<PropertyKey, String> = self
+			.properties
+			.iter()
+			.map(|(name, number)| (number.clone(), name.clone()))
+			.collect();
+
+		writer
+			.create_element("robot")
+			.with_attributes([
+				("name", self.name.as_str()),
+				("xmlns:xacro", XACRO_NAMESPACE),
+			])
+			.write_inner_content(|writer| -> quick_xml::Result<()> {
+				self.properties
+					.iter()
+					.map(|(name, (_, _, magnitude))| {
+						writer
+							.create_element("xacro:property")
+							.with_attributes([
+								("name", name.as_str()),
+								("value", magnitude.as_str()),
+							])
+							.write_empty()
+							.map(|_| ())
+					})
+					.process_results(|iter| iter.collect::<()>())?;
+
+				self.macros
+					.iter()
+					.chain(&self.elements)
+					.map(|element| element.write(writer, &properties))
+					.process_results(|iter| iter.collect::<()>())
+			})?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::to_xacro;
+	use crate::{
+		link_data::{geometry::*, Visual},
+		material::MaterialDescriptor,
+		prelude::*,
+		to_rdf::{to_urdf::URDFConfig, xml_writer_to_string, XMLMode},
+		Link, MirrorAxis, Robot, SmartJointBuilder, Transform,
+	};
+	use test_log::test;
+
+	fn write(robot: &Robot) -> String {
+		xml_writer_to_string(
+			to_xacro(
+				robot,
+				URDFConfig {
+					xml_mode: XMLMode::Indent(' ', 2),
+					..Default::default()
+				},
+			)
+			.unwrap(),
+		)
+	}
+
+	/// A robot with a leg, a mirrored copy of that leg and a renamed copy of that leg.
+	fn legged_robot() -> Robot {
+		let robot = Link::builder("body")
+			.add_visual(Visual::builder(BoxGeometry::new(0.6, 0.4, 0.2)))
+			.build_tree()
+			.to_robot("walker");
+
+		let leg = Link::builder("[[front_left]]_upper")
+			.add_visual(
+				Visual::builder(CylinderGeometry::new(0.05, 0.3))
+					.transformed(Transform::new_translation(0., 0.1, -0.15))
+					.materialized(MaterialDescriptor::new_rgb(0.2, 0.2, 0.2).named("grey")),
+			)
+			.build_tree();
+		leg.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_revolute("[[front_left]]_knee")
+					.add_transform(Transform::new_translation(0., 0.1, -0.3))
+					.with_axis((0., 1., 0.))
+					.with_limit(20., 5.)
+					.set_upper_limit(1.5)
+					.set_lower_limit(-1.5),
+				Link::builder("[[front_left]]_lower").add_visual(
+					Visual::builder(CylinderGeometry::new(0.05, 0.3))
+						.transformed(Transform::new_translation(0., 0., -0.15)),
+				),
+			)
+			.unwrap();
+		let leg = leg.yank_root().unwrap();
+
+		let mut mirrored_leg = leg.mirror(MirrorAxis::Y);
+		mirrored_leg.change_group_id("front_right").unwrap();
+		let mut copied_leg = leg.clone();
+		copied_leg.change_group_id("back_left").unwrap();
+
+		for (hip, y, chain) in [
+			("[[front_left]]_hip", 0.2, leg),
+			("[[front_right]]_hip", -0.2, mirrored_leg),
+			("[[back_left]]_hip", 0.2, copied_leg),
+		] {
+			robot
+				.get_root_link()
+				.write()
+				.unwrap()
+				.try_attach_child(
+					SmartJointBuilder::new_fixed(hip).add_transform(Transform::new_translation(
+						if hip.contains("front") { 0.3 } else { -0.3 },
+						y,
+						0.,
+					)),
+					chain,
+				)
+				.unwrap();
+		}
+
+		robot
+	}
+
+	#[test]
+	fn macros() {
+		let xacro = write(&legged_robot());
+
+		assert!(
+			xacro.contains(r#"<robot name="walker" xmlns:xacro="http://www.ros.org/wiki/xacro">"#)
+		);
+		assert!(xacro.contains(r#"<xacro:macro name="upper" params="prefix parent reflect">"#));
+		assert!(xacro.contains(
+			r#"<joint name="${prefix}_hip" type="fixed">
+      <origin xyz="${hip_x} ${reflect*hip_y} 0"/>
+      <parent link="${parent}"/>
+      <child link="${prefix}_upper"/>
+    </joint>"#
+		));
+		assert!(xacro.contains(
+			r#"<joint name="${prefix}_knee" type="revolute">
+      <origin xyz="0 ${reflect*upper_y} ${-knee_z}"/>
+      <parent link="${prefix}_upper"/>
+      <child link="${prefix}_lower"/>"#
+		));
+		assert!(xacro.contains(
+			r#"<xacro:upper prefix="front_left" parent="body" reflect="1"/>
+  <xacro:upper prefix="front_right" parent="body" reflect="-1"/>"#
+		));
+		// The back leg is translated differently, so it is not an instance.
+		assert!(xacro.contains(r#"<joint name="back_left_hip" type="fixed">"#));
+		assert!(xacro.contains(r#"<link name="back_left_lower">"#));
+		assert!(!xacro.contains("front_left_lower"));
+	}
+
+	#[test]
+	fn properties() {
+		let xacro = write(&legged_robot());
+
+		assert!(xacro.contains(r#"<xacro:property name="upper_radius" value="0.05"/>"#));
+		assert!(xacro.contains(r#"<cylinder radius="${upper_radius}" length="${upper_length}"/>"#));
+		assert!(xacro.contains(r#"<xacro:property name="upper_length" value="0.3"/>"#));
+		assert!(xacro.contains(r#"<xacro:property name="knee_z" value="0.3"/>"#));
+		assert!(xacro.contains(
+			r#"<limit effort="${knee_effort}" velocity="${knee_velocity}" lower="${-knee_lower}" upper="${knee_upper}"/>"#
+		));
+		// Numbers used only once are written as is.
+		assert!(xacro.contains(r#"<color rgba="0.2 0.2 0.2 1"/>"#));
+		assert!(xacro.contains(r#"<box size="0.6 0.4 0.2"/>"#));
+		// Zero and one are never lifted.
+		assert!(!xacro.contains(r#"value="0""#));
+		assert!(!xacro.contains(r#"value="1""#));
+	}
+
+	#[test]
+	fn round_trip() {
+		use crate::{
+			from_rdf::from_xacro::{from_xacro, XacroConfig},
+			to_rdf::to_urdf::to_urdf,
+		};
+
+		let robot = legged_robot();
+		let expanded = from_xacro(&write(&robot), &XacroConfig::default()).unwrap();
+
+		// Mirroring produces negative zeros, which are written as `0` in the xacro description.
+		assert_eq!(
+			xml_writer_to_string(to_urdf(&expanded, URDFConfig::default()).unwrap()),
+			xml_writer_to_string(to_urdf(&robot, URDFConfig::default()).unwrap())
+				.replace(r#"xyz="-0 1 -0""#, r#"xyz="0 1 0""#)
+		);
+	}
+
+	#[test]
+	fn no_repetition() {
+		let robot = Link::builder("base")
+			.add_visual(Visual::builder(SphereGeometry::new(0.5)))
+			.build_tree()
+			.to_robot("single");
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.try_attach_child(
+				SmartJointBuilder::new_fixed("[[a]]_joint"),
+				Link::builder("[[a]]_link"),
+			)
+			.unwrap();
+
+		assert_eq!(
+			write(&robot),
+			r#"﻿<?xml version="1.0"?>
+<robot name="single" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <link name="base">
+    <visual>
+      <geometry>
+        <sphere radius="0.5"/>
+      </geometry>
+    </visual>
+  </link>
+  <joint name="a_joint" type="fixed">
+    <parent link="base"/>
+    <child link="a_link"/>
+  </joint>
+  <link name="a_link"/>
+</robot>"#
+		);
+	}
+
+	#[test]
+	fn escaped_substitutions() {
+		let robot = Link::builder("${not_a_property}")
+			.build_tree()
+			.to_robot("$(not_a_command)");
+
+		let xacro = write(&robot);
+		assert!(xacro.contains(r#"<robot name="$$(not_a_command)""#));
+		assert!(xacro.contains(r#"<link name="$${not_a_property}"/>"#));
+	}
+}
@@ -2,10 +2,27 @@
 // User docs finished
 // TODO: MirrorDocs
 use itertools::Itertools;
-use nalgebra::{vector, Matrix3, Rotation3, Vector3};
+use nalgebra::{
+	vector, Isometry3, Matrix3, Matrix4, Quaternion, Rotation3, Translation3, UnitQuaternion,
+	Vector3,
+};
 
+#[cfg(feature = "mjcf")]
+use crate::from_rdf::from_mjcf::{optional_float_array, FromMJCF, FromMJCFContext, FromMJCFError};
+#[cfg(feature = "urdf")]
+use crate::from_rdf::from_urdf::{optional_vector3, FromURDF, FromURDFContext, FromURDFError};
+#[cfg(any(feature = "urdf", feature = "sdf", feature = "mjcf"))]
+use crate::from_rdf::xml_element::XMLElement;
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+use crate::Float;
+#[cfg(feature = "sdf")]
+use crate::{
+	from_rdf::from_sdf::{content_float_array, FromSDF, FromSDFContext, FromSDFError},
+	to_rdf::to_sdf::ToSDF,
+};
+#[cfg(feature = "sdf")]
+use quick_xml::events::BytesText;
 #[cfg(feature = "xml")]
 use quick_xml::{events::attributes::Attribute, name::QName};
 
@@ -22,21 +39,21 @@ use quick_xml::{events::attributes::Attribute, name::QName};
 /// In URDF this element is often refered to as `<origin>`.
 pub struct Transform {
 	/// The translation of origin of the new coordinate system in meters.
-	pub translation: Option<(f32, f32, f32)>,
+	pub translation: Option<(Float, Float, Float)>,
 	/// The rotation of the new coordinate system in radians.
-	pub rotation: Option<(f32, f32, f32)>,
+	pub rotation: Option<(Float, Float, Float)>,
 }
 
 impl Transform {
 	/// Creates a new `Transform`.
 	///
-	/// Creates a new `Transform` from a tuple of cartesian coordinates in meters as `f32` and a tuple of roll-pitch-yaw angles in radians as `f32`.
+	/// Creates a new `Transform` from a tuple of cartesian coordinates in meters as [`Float`] and a tuple of roll-pitch-yaw angles in radians as [`Float`].
 	///
 	/// # Example
 	///
 	/// ```
 	/// use robot_description_builder::Transform;
-	/// use std::f32::consts::PI;
+	/// use robot_description_builder::float::consts::PI;
 	/// let transform = Transform::new((1., 1000., 0.), (0., PI, 0.));
 	///
 	/// assert_eq!(
@@ -47,7 +64,7 @@ impl Transform {
 	///     }
 	/// )
 	/// ```
-	pub fn new(xyz: (f32, f32, f32), rpy: (f32, f32, f32)) -> Self {
+	pub fn new(xyz: (Float, Float, Float), rpy: (Float, Float, Float)) -> Self {
 		Self {
 			translation: Some(xyz),
 			rotation: Some(rpy),
@@ -56,7 +73,7 @@ impl Transform {
 
 	/// Creates a new `Transform` from cartesian x, y and z coordinates.
 	///
-	/// Creates a new `Transform` from a tuple of cartesian coordinates in meters as `f32` and leaves the other values at the default.
+	/// Creates a new `Transform` from a tuple of cartesian coordinates in meters as [`Float`] and leaves the other values at the default.
 	///
 	/// # Example
 	///
@@ -72,7 +89,7 @@ impl Transform {
 	///     }
 	/// )
 	/// ```
-	pub fn new_translation(x: f32, y: f32, z: f32) -> Self {
+	pub fn new_translation(x: Float, y: Float, z: Float) -> Self {
 		Self {
 			translation: Some((x, y, z)),
 			..Default::default()
@@ -81,13 +98,13 @@ impl Transform {
 
 	/// Creates a new `Transform` from roll-pitch-yaw angles.
 	///
-	/// Creates a new `Transform` from the roll-pitch-yaw angles in radians as `f32` and leaves the other values at the default.
+	/// Creates a new `Transform` from the roll-pitch-yaw angles in radians as [`Float`] and leaves the other values at the default.
 	///
 	/// # Example
 	///
 	/// ```
 	/// use robot_description_builder::Transform;
-	/// use std::f32::consts::PI;
+	/// use robot_description_builder::float::consts::PI;
 	/// let transform = Transform::new_rotation( 0., PI, 0.);
 	///
 	/// assert_eq!(
@@ -98,7 +115,7 @@ impl Transform {
 	///     }
 	/// )
 	/// ```
-	pub fn new_rotation(r: f32, p: f32, y: f32) -> Self {
+	pub fn new_rotation(r: Float, p: Float, y: Float) -> Self {
 		Self {
 			rotation: Some((r, p, y)),
 			..Default::default()
@@ -135,10 +152,314 @@ impl Transform {
 	pub fn contains_some(&self) -> bool {
 		self.translation.is_some() || self.rotation.is_some()
 	}
+
+	/// Creates a new `Transform` from the components of a quaternion.
+	///
+	/// Creates a new `Transform` from the real part `w` and the imaginary parts `x`, `y` and `z` of a quaternion and leaves the translation at the default.
+	/// The quaternion gets normalized, so it does not need to be a unit quaternion.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use robot_description_builder::Transform;
+	/// use robot_description_builder::float::consts::FRAC_1_SQRT_2;
+	/// let transform = Transform::new_quaternion(FRAC_1_SQRT_2, 0., 0., FRAC_1_SQRT_2);
+	///
+	/// let (roll, pitch, yaw) = transform.rotation.unwrap();
+	/// assert!(roll.abs() < 1e-6 && pitch.abs() < 1e-6);
+	/// assert!((yaw - robot_description_builder::float::consts::FRAC_PI_2).abs() < 1e-6);
+	/// assert_eq!(transform.translation, None);
+	/// ```
+	pub fn new_quaternion(w: Float, x: Float, y: Float, z: Float) -> Self {
+		UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z)).into()
+	}
+
+	/// Creates a new `Transform` from a rotation of `angle` radians around `axis`.
+	///
+	/// The `axis` gets normalized and the translation is left at the default.
+	/// If the `axis` has no length, the `Transform` has no rotation.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use robot_description_builder::Transform;
+	/// use robot_description_builder::float::consts::FRAC_PI_2;
+	/// let transform = Transform::new_axis_angle((0., 2., 0.), FRAC_PI_2);
+	///
+	/// let (roll, pitch, yaw) = transform.rotation.unwrap();
+	/// assert!(roll.abs() < 1e-6 && yaw.abs() < 1e-6);
+	/// assert!((pitch - FRAC_PI_2).abs() < 1e-3);
+	/// ```
+	pub fn new_axis_angle(axis: (Float, Float, Float), angle: Float) -> Self {
+		let (x, y, z) = axis;
+		let axis = vector![x, y, z]
+			.try_normalize(Float::EPSILON)
+			.unwrap_or_else(Vector3::zeros);
+		UnitQuaternion::from_scaled_axis(axis * angle).into()
+	}
+
+	/// Creates a new `Transform` at `eye`, which looks at `target`.
+	///
+	/// The x-axis of the new coordinate system points from `eye` to `target` and the z-axis points as much as possible in the direction of `up`,
+	/// which follows the ROS convention of the x-axis pointing forward and the z-axis pointing up.
+	///
+	/// Returns `None` if `eye` and `target` are the same point or if `up` is parallel to the viewing direction.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use robot_description_builder::Transform;
+	/// let camera = Transform::look_at((0., 0., 1.), (1., 0., 1.), (0., 0., 1.)).unwrap();
+	///
+	/// let (x, y, z) = camera.transform_vector((1., 0., 0.));
+	/// assert!((x - 1.).abs() < 1e-6 && y.abs() < 1e-6 && z.abs() < 1e-6);
+	/// assert_eq!(camera.translation, Some((0., 0., 1.)));
+	///
+	/// assert!(Transform::look_at((0., 0., 1.), (0., 0., 2.), (0., 0., 1.)).is_none());
+	/// ```
+	pub fn look_at(
+		eye: (Float, Float, Float),
+		target: (Float, Float, Float),
+		up: (Float, Float, Float),
+	) -> Option<Self> {
+		let eye = vector![eye.0, eye.1, eye.2];
+		let x_axis = (vector![target.0, target.1, target.2] - eye).try_normalize(Float::EPSILON)?;
+		let y_axis = vector![up.0, up.1, up.2]
+			.cross(&x_axis)
+			.try_normalize(Float::EPSILON)?;
+		let z_axis = x_axis.cross(&y_axis);
+
+		let rotation = UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(
+			Matrix3::from_columns(&[x_axis, y_axis, z_axis]),
+		));
+
+		Some(Self::from_isometry(&Isometry3::from_parts(
+			eye.into(),
+			rotation,
+		)))
+	}
+
+	/// Composes this `Transform` with the `Transform` of a child coordinate system.
+	///
+	/// The result is the transform from the parent coordinate system of `self` to the coordinate system of `child`,
+	/// such that applying it is the same as first applying `child` and then `self`.
+	/// The same result is obtained with `self * child`.
+	///
+	/// Fields which are `None` in both `Transform`s stay `None`.
+	/// If only one of the `Transform`s has a rotation, it is copied as is, which prevents rounding noise in the angles.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use robot_description_builder::Transform;
+	/// use robot_description_builder::float::consts::FRAC_PI_2;
+	/// let shoulder = Transform::new((0., 0., 1.), (0., 0., FRAC_PI_2));
+	/// let elbow = Transform::new_translation(1., 0., 0.);
+	///
+	/// let (x, y, z) = shoulder.compose(&elbow).translation.unwrap();
+	/// assert!(x.abs() < 1e-6 && (y - 1.).abs() < 1e-6 && (z - 1.).abs() < 1e-6);
+	///
+	/// assert_eq!(
+	///     Transform::new_translation(1., 2., 3.).compose(&Transform::new_translation(1., 1., 1.)),
+	///     Transform::new_translation(2., 3., 4.)
+	/// );
+	/// ```
+	pub fn compose(&self, child: &Transform) -> Self {
+		let composed = Self::from_isometry(&(self.to_isometry() * child.to_isometry()));
+
+		Self {
+			translation: match (self.translation, child.translation, self.rotation) {
+				(None, None, _) => None,
+				// Without a rotation of `self`, the translations can be added directly.
+				(_, _, None) => {
+					let (x, y, z) = self.translation.unwrap_or_default();
+					let (dx, dy, dz) = child.translation.unwrap_or_default();
+					Some((x + dx, y + dy, z + dz))
+				}
+				_ => composed.translation,
+			},
+			rotation: match (self.rotation, child.rotation) {
+				(None, None) => None,
+				(Some(rotation), None) | (None, Some(rotation)) => Some(rotation),
+				(Some(_), Some(_)) => composed.rotation,
+			},
+		}
+	}
+
+	/// Inverts the `Transform`.
+	///
+	/// The result is the transform from the new coordinate system back to the parent coordinate system.
+	/// Fields which are `None` stay `None`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use robot_description_builder::Transform;
+	/// let transform = Transform::new_translation(1., -2., 3.);
+	///
+	/// assert_eq!(transform.inverse(), Transform::new_translation(-1., 2., -3.));
+	/// assert_eq!(transform.compose(&transform.inverse()), Transform::new_translation(0., 0., 0.));
+	/// ```
+	pub fn inverse(&self) -> Self {
+		Self::from_isometry(&self.to_isometry().inverse())
+			.with_fields_of(self.translation.is_some(), self.rotation.is_some())
+	}
+
+	/// Applies the `Transform` to a point, which converts it from the new coordinate system to the parent coordinate system.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use robot_description_builder::Transform;
+	/// let transform = Transform::new_translation(1., 2., 3.);
+	///
+	/// assert_eq!(transform.transform_point((1., 0., 0.)), (2., 2., 3.));
+	/// ```
+	pub fn transform_point(&self, point: (Float, Float, Float)) -> (Float, Float, Float) {
+		let (x, y, z) = point;
+		let point = self.to_isometry() * nalgebra::Point3::new(x, y, z);
+		(point.x, point.y, point.z)
+	}
+
+	/// Applies the `Transform` to a vector, which only rotates it, since vectors are not affected by the translation.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use robot_description_builder::Transform;
+	/// use robot_description_builder::float::consts::FRAC_PI_2;
+	/// let transform = Transform::new((1., 2., 3.), (0., 0., FRAC_PI_2));
+	///
+	/// let (x, y, z) = transform.transform_vector((1., 0., 0.));
+	/// assert!(x.abs() < 1e-6 && (y - 1.).abs() < 1e-6 && z.abs() < 1e-6);
+	/// ```
+	pub fn transform_vector(&self, vector: (Float, Float, Float)) -> (Float, Float, Float) {
+		let (x, y, z) = vector;
+		let vector = self.to_isometry() * vector![x, y, z];
+		(vector.x, vector.y, vector.z)
+	}
+
+	/// Converts the `Transform` to the equivalent [`Isometry3`].
+	///
+	/// Fields which are `None` are treated as zero.
+	pub fn to_isometry(self) -> Isometry3<Float> {
+		let (x, y, z) = self.translation.unwrap_or_default();
+
+		Isometry3::from_parts(Translation3::new(x, y, z), self.to_quaternion())
+	}
+
+	/// Creates a `Transform` from an [`Isometry3`], with both the `translation` and the `rotation` set.
+	pub fn from_isometry(isometry: &Isometry3<Float>) -> Self {
+		let translation = isometry.translation.vector;
+		let (roll, pitch, yaw) = isometry.rotation.euler_angles();
+
+		// Adding `0.` turns `-0.` into `0.`, which keeps the descriptions clean.
+		Self {
+			translation: Some((translation.x + 0., translation.y + 0., translation.z + 0.)),
+			rotation: Some((roll + 0., pitch + 0., yaw + 0.)),
+		}
+	}
+
+	/// Converts the rotation of the `Transform` to the equivalent [`UnitQuaternion`].
+	pub fn to_quaternion(self) -> UnitQuaternion<Float> {
+		let (roll, pitch, yaw) = self.rotation.unwrap_or_default();
+		UnitQuaternion::from_euler_angles(roll, pitch, yaw)
+	}
+
+	/// Converts the `Transform` to the equivalent homogeneous transformation [`Matrix4`].
+	pub fn to_matrix(self) -> Matrix4<Float> {
+		self.to_isometry().to_homogeneous()
+	}
+
+	/// Creates a `Transform` from a homogeneous transformation [`Matrix4`], with both the `translation` and the `rotation` set.
+	///
+	/// The bottom row of the matrix is ignored and the rotation part is replaced by the closest rotation matrix,
+	/// so scaling and shearing are removed.
+	pub fn from_matrix(matrix: &Matrix4<Float>) -> Self {
+		let rotation = Rotation3::from_matrix(&matrix.fixed_view::<3, 3>(0, 0).into_owned());
+		let translation = Translation3::from(matrix.fixed_view::<3, 1>(0, 3).into_owned());
+
+		Self::from_isometry(&Isometry3::from_parts(
+			translation,
+			UnitQuaternion::from_rotation_matrix(&rotation),
+		))
+	}
+
+	/// Clears the `translation` and the `rotation` if they are not `used`.
+	fn with_fields_of(mut self, translation_used: bool, rotation_used: bool) -> Self {
+		if !translation_used {
+			self.translation = None;
+		}
+		if !rotation_used {
+			self.rotation = None;
+		}
+		self
+	}
+
+	/// Writes the `Transform` as a SDFormat `<pose>`, optionally `relative_to` the specified frame.
+	#[cfg(feature = "sdf")]
+	pub(crate) fn write_sdf_pose(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		relative_to: Option<&str>,
+	) -> Result<(), quick_xml::Error> {
+		let (x, y, z) = self.translation.unwrap_or_default();
+		let (roll, pitch, yaw) = self.rotation.unwrap_or_default();
+
+		let mut element = writer.create_element("pose");
+		if let Some(frame) = relative_to {
+			element = element.with_attribute(Attribute {
+				key: QName(b"relative_to"),
+				value: frame.as_bytes().into(),
+			});
+		}
+
+		element.write_text_content(BytesText::new(&format!(
+			"{} {} {} {} {} {}",
+			x, y, z, roll, pitch, yaw
+		)))?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "mjcf")]
+impl Transform {
+	/// Gets the `pos` and `quat` attributes to place a MJCF element with this `Transform`.
+	///
+	/// Only the attributes of the specified fields are returned, since MJCF defaults to the identity.
+	pub(crate) fn mjcf_attributes(&self) -> Vec<Attribute<'static>> {
+		let mut attributes = Vec::new();
+
+		if let Some((x, y, z)) = self.translation {
+			attributes.push(Attribute {
+				key: QName(b"pos"),
+				value: format!("{} {} {}", x, y, z).into_bytes().into(),
+			});
+		}
+
+		if let Some((roll, pitch, yaw)) = self.rotation {
+			let quaternion = UnitQuaternion::from_euler_angles(roll, pitch, yaw);
+			// Adding `0.` turns `-0.` into `0.`, which keeps the descriptions clean.
+			attributes.push(Attribute {
+				key: QName(b"quat"),
+				value: format!(
+					"{} {} {} {}",
+					quaternion.w + 0.,
+					quaternion.i + 0.,
+					quaternion.j + 0.,
+					quaternion.k + 0.
+				)
+				.into_bytes()
+				.into(),
+			});
+		}
+
+		attributes
+	}
 }
 
 impl Mirror for Transform {
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self {
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self {
 		Transform {
 			translation: self.translation.as_ref().map(|(x, y, z)| {
 				let old_translation = vector![*x, *y, *z];
@@ -161,7 +482,7 @@ impl Mirror for Transform {
 }
 
 impl MirrorUpdater for Transform {
-	fn update_mirror_matrix(&self, mirror_matrix: &Matrix3<f32>) -> Matrix3<f32> {
+	fn update_mirror_matrix(&self, mirror_matrix: &Matrix3<Float>) -> Matrix3<Float> {
 		match self.rotation.as_ref() {
 			Some(rpy) => {
 				Rotation3::from_euler_angles(rpy.0, rpy.1, rpy.2)
@@ -203,6 +524,217 @@ impl ToURDF for Transform {
 	}
 }
 
+#[cfg(feature = "sdf")]
+impl ToSDF for Transform {
+	fn to_sdf(
+		&self,
+		writer: &mut quick_xml::Writer<std::io::Cursor<Vec<u8>>>,
+		_sdf_config: &crate::to_rdf::to_sdf::SDFConfig,
+	) -> Result<(), quick_xml::Error> {
+		self.write_sdf_pose(writer, None)
+	}
+}
+
+#[cfg(feature = "urdf")]
+impl FromURDF for Transform {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self {
+			translation: optional_vector3(element, "xyz")?,
+			rotation: optional_vector3(element, "rpy")?,
+		})
+	}
+}
+
+#[cfg(feature = "sdf")]
+/// Reads the content of a `<pose>` element, without resolving its `relative_to` frame.
+///
+/// Both the `euler_rpy` and the `quat_xyzw` rotation formats are supported.
+impl FromSDF for Transform {
+	fn from_sdf(
+		element: &XMLElement,
+		_sdf_context: &mut FromSDFContext,
+	) -> Result<Self, FromSDFError> {
+		// An empty pose is the identity.
+		if element.text.is_empty() {
+			return Ok(Self::default());
+		}
+
+		let (translation, rotation) = match element.attribute("rotation_format") {
+			None | Some("euler_rpy") => {
+				let [x, y, z, roll, pitch, yaw] = content_float_array(element)?;
+				match element.attribute("degrees") {
+					Some("true") | Some("1") => (
+						(x, y, z),
+						(roll.to_radians(), pitch.to_radians(), yaw.to_radians()),
+					),
+					_ => ((x, y, z), (roll, pitch, yaw)),
+				}
+			}
+			Some("quat_xyzw") => {
+				let [x, y, z, qx, qy, qz, qw] = content_float_array(element)?;
+				(
+					(x, y, z),
+					UnitQuaternion::from_quaternion(Quaternion::new(qw, qx, qy, qz)).euler_angles(),
+				)
+			}
+			Some(other) => {
+				return Err(FromSDFError::InvalidValue {
+					element: element.name.clone(),
+					attribute: String::from("rotation_format"),
+					value: other.to_string(),
+					line: element.line,
+				})
+			}
+		};
+
+		Ok(Self {
+			translation: Some(translation),
+			rotation: Some(rotation),
+		})
+	}
+}
+
+#[cfg(feature = "mjcf")]
+/// Reads the `pos` and the orientation attributes of an element.
+///
+/// The orientation can be specified by one of `quat`, `axisangle`, `euler`, `xyaxes` or `zaxis`.
+/// The `angle` and `eulerseq` settings of the `<compiler>` are taken into account.
+impl FromMJCF for Transform {
+	fn from_mjcf(
+		element: &XMLElement,
+		mjcf_context: &mut FromMJCFContext,
+	) -> Result<Self, FromMJCFError> {
+		use crate::from_rdf::{from_mjcf::invalid_value, to_transform};
+		use nalgebra::{Isometry3, Quaternion, Translation3, Unit};
+
+		if ["quat", "axisangle", "euler", "xyaxes", "zaxis"]
+			.into_iter()
+			.filter(|attribute| element.attribute(attribute).is_some())
+			.count() > 1
+		{
+			return Err(FromMJCFError::MultipleOrientations {
+				element: element.name.clone(),
+				line: element.line,
+			});
+		}
+
+		let to_vector = |[x, y, z]: [Float; 3]| Vector3::<f64>::new(x.into(), y.into(), z.into());
+		let normalized = |vector: Vector3<f64>, attribute: &str| {
+			Unit::try_new(vector, f64::EPSILON).ok_or_else(|| invalid_value(element, attribute))
+		};
+
+		let rotation = if let Some([w, x, y, z]) = optional_float_array(element, "quat")? {
+			UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z).cast())
+		} else if let Some([x, y, z, angle]) = optional_float_array(element, "axisangle")? {
+			UnitQuaternion::from_axis_angle(
+				&normalized(to_vector([x, y, z]), "axisangle")?,
+				mjcf_context.angle(angle),
+			)
+		} else if let Some(angles) = optional_float_array::<3>(element, "euler")? {
+			mjcf_context.eulerseq.into_iter().zip(angles).fold(
+				UnitQuaternion::identity(),
+				|rotation, (axis, angle)| {
+					let step = UnitQuaternion::from_axis_angle(
+						&match axis.to_ascii_lowercase() {
+							'x' => Vector3::x_axis(),
+							'y' => Vector3::y_axis(),
+							_ => Vector3::z_axis(),
+						},
+						mjcf_context.angle(angle),
+					);
+					// Lower case axes rotate with the frame, upper case axes are fixed.
+					match axis.is_ascii_lowercase() {
+						true => rotation * step,
+						false => step * rotation,
+					}
+				},
+			)
+		} else if let Some([x1, y1, z1, x2, y2, z2]) = optional_float_array(element, "xyaxes")? {
+			let x_axis = normalized(to_vector([x1, y1, z1]), "xyaxes")?;
+			let y_axis = to_vector([x2, y2, z2]);
+			let y_axis = normalized(y_axis - x_axis.into_inner() * x_axis.dot(&y_axis), "xyaxes")?;
+
+			UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(
+				Matrix3::from_columns(&[
+					x_axis.into_inner(),
+					y_axis.into_inner(),
+					x_axis.cross(&y_axis),
+				]),
+			))
+		} else if let Some(z_axis) = optional_float_array(element, "zaxis")? {
+			let z_axis = normalized(to_vector(z_axis), "zaxis")?;
+			// The Z-axis is rotated to the opposite direction around the X-axis.
+			UnitQuaternion::rotation_between_axis(&Vector3::z_axis(), &z_axis).unwrap_or_else(
+				|| UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f64::consts::PI),
+			)
+		} else {
+			UnitQuaternion::identity()
+		};
+
+		let translation =
+			optional_float_array(element, "pos")?.map_or_else(Vector3::zeros, to_vector);
+
+		Ok(to_transform(&Isometry3::from_parts(
+			Translation3::from(translation),
+			rotation,
+		))
+		.unwrap_or_default())
+	}
+}
+
+impl std::ops::Mul for Transform {
+	type Output = Transform;
+
+	/// Composes two `Transform`s, see [`Transform::compose`].
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.compose(&rhs)
+	}
+}
+
+impl From<Isometry3<Float>> for Transform {
+	fn from(value: Isometry3<Float>) -> Self {
+		Self::from_isometry(&value)
+	}
+}
+
+impl From<Transform> for Isometry3<Float> {
+	fn from(value: Transform) -> Self {
+		value.to_isometry()
+	}
+}
+
+impl From<UnitQuaternion<Float>> for Transform {
+	/// Creates a `Transform` with only the `rotation` set.
+	fn from(value: UnitQuaternion<Float>) -> Self {
+		let (roll, pitch, yaw) = value.euler_angles();
+
+		// Adding `0.` turns `-0.` into `0.`, which keeps the descriptions clean.
+		Self::new_rotation(roll + 0., pitch + 0., yaw + 0.)
+	}
+}
+
+impl From<Transform> for UnitQuaternion<Float> {
+	/// Gets the rotation of the `Transform`, the translation is dropped.
+	fn from(value: Transform) -> Self {
+		value.to_quaternion()
+	}
+}
+
+impl From<Matrix4<Float>> for Transform {
+	fn from(value: Matrix4<Float>) -> Self {
+		Self::from_matrix(&value)
+	}
+}
+
+impl From<Transform> for Matrix4<Float> {
+	fn from(value: Transform) -> Self {
+		value.to_matrix()
+	}
+}
+
 impl From<Transform> for crate::joint::JointTransformMode {
 	fn from(value: Transform) -> Self {
 		Self::Direct(value)
@@ -220,7 +752,7 @@ pub enum MirrorAxis {
 	Z,
 }
 
-impl From<MirrorAxis> for Matrix3<f32> {
+impl From<MirrorAxis> for Matrix3<Float> {
 	fn from(value: MirrorAxis) -> Self {
 		let diag = match value {
 			MirrorAxis::X => (-1., 1., 1.),
@@ -239,7 +771,7 @@ pub(crate) trait Mirror {
 	/// Returns a mirrored clone of itself.
 	///
 	/// TODO: EXAMPLE
-	fn mirrored(&self, mirror_matrix: &Matrix3<f32>) -> Self;
+	fn mirrored(&self, mirror_matrix: &Matrix3<Float>) -> Self;
 }
 
 /// A type which can change the `mirror_matrix` for its children.
@@ -249,10 +781,10 @@ pub(crate) trait Mirror {
 /// Types implementing `MirrorUpdater` can be [`mirrored`](Mirror::mirrored). As a result of this mirror the `mirror_matrix` changes.
 pub(crate) trait MirrorUpdater: Sized + Mirror {
 	/// Get the updated `mirror_matrix` which should be used for all children.
-	fn update_mirror_matrix(&self, mirror_matrix: &Matrix3<f32>) -> Matrix3<f32>;
+	fn update_mirror_matrix(&self, mirror_matrix: &Matrix3<Float>) -> Matrix3<Float>;
 
 	/// Return a mirrored clone of itself and the updated `mirror_matrix`.
-	fn mirrored_update_matrix(&self, mirror_matrix: &Matrix3<f32>) -> (Self, Matrix3<f32>) {
+	fn mirrored_update_matrix(&self, mirror_matrix: &Matrix3<Float>) -> (Self, Matrix3<Float>) {
 		(
 			self.mirrored(mirror_matrix),
 			self.update_mirror_matrix(mirror_matrix),
@@ -262,8 +794,8 @@ pub(crate) trait MirrorUpdater: Sized + Mirror {
 
 #[cfg(test)]
 mod tests {
-	use super::Transform;
-	use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+	use super::{Float, Transform};
+	use crate::float::consts::{FRAC_PI_2, FRAC_PI_4};
 	use test_log::test;
 
 	mod mirror {
@@ -274,7 +806,7 @@ mod tests {
 		fn test_mirror(
 			transform: Transform,
 			mirror_axis: MirrorAxis,
-			result: (Transform, Matrix3<f32>),
+			result: (Transform, Matrix3<Float>),
 		) {
 			assert_eq!(
 				transform.mirrored_update_matrix(&mirror_axis.into()),
@@ -282,7 +814,7 @@ mod tests {
 			)
 		}
 
-		fn test_all_mirrors(transform: Transform, results: [(Transform, Matrix3<f32>); 3]) {
+		fn test_all_mirrors(transform: Transform, results: [(Transform, Matrix3<Float>); 3]) {
 			results
 				.into_iter()
 				.enumerate()
@@ -302,8 +834,8 @@ mod tests {
 
 		fn test_all_mirrors_angle_var(
 			transform: Transform,
-			angle: f32,
-			results: [(Transform, [Matrix3<f32>; 3]); 3],
+			angle: Float,
+			results: [(Transform, [Matrix3<Float>; 3]); 3],
 		) {
 			for i in 0..2 {
 				let rotation = match i {
@@ -729,4 +1261,228 @@ mod tests {
 			);
 		}
 	}
+
+	#[cfg(feature = "sdf")]
+	mod to_sdf {
+		use super::{test, Transform};
+		use crate::to_rdf::to_sdf::{SDFConfig, ToSDF};
+		use std::io::Seek;
+
+		fn test_to_sdf_transform(transform: Transform, result: String, sdf_config: &SDFConfig) {
+			let mut writer = quick_xml::Writer::new(std::io::Cursor::new(Vec::new()));
+			assert!(transform.to_sdf(&mut writer, sdf_config).is_ok());
+
+			writer.get_mut().rewind().unwrap();
+			assert_eq!(
+				std::io::read_to_string(writer.into_inner()).unwrap(),
+				result
+			)
+		}
+
+		#[test]
+		fn empty() {
+			test_to_sdf_transform(
+				Transform::default(),
+				String::from("<pose>0 0 0 0 0 0</pose>"),
+				&SDFConfig::default(),
+			);
+		}
+
+		#[test]
+		fn translation_rotatation() {
+			test_to_sdf_transform(
+				Transform {
+					translation: Some((1.23, 2.34, 3.45)),
+					rotation: Some((4.56, 5.67, 6.78)),
+				},
+				String::from("<pose>1.23 2.34 3.45 4.56 5.67 6.78</pose>"),
+				&SDFConfig::default(),
+			);
+		}
+
+		#[test]
+		fn relative_to() {
+			let mut writer = quick_xml::Writer::new(std::io::Cursor::new(Vec::new()));
+			assert!(Transform::new_translation(1., 2., 3.)
+				.write_sdf_pose(&mut writer, Some("base_link"))
+				.is_ok());
+
+			writer.get_mut().rewind().unwrap();
+			assert_eq!(
+				std::io::read_to_string(writer.into_inner()).unwrap(),
+				r#"<pose relative_to="base_link">1 2 3 0 0 0</pose>"#
+			)
+		}
+	}
+
+	mod algebra {
+		use super::{test, Float, Transform, FRAC_PI_2, FRAC_PI_4};
+		use nalgebra::{Isometry3, Matrix4, UnitQuaternion};
+
+		fn assert_close(left: Transform, right: Transform) {
+			let (left, right) = (left.to_matrix(), right.to_matrix());
+			assert!(
+				(left - right).abs().max() < 1e-5,
+				"{} is not close to {}",
+				left,
+				right
+			);
+		}
+
+		#[test]
+		fn isometry() {
+			let transform = Transform::new((1., 2., 3.), (0.1, 0.2, 0.3));
+			let result = Transform::from_isometry(&transform.to_isometry());
+
+			let (x, y, z) = result.translation.unwrap();
+			let (roll, pitch, yaw) = result.rotation.unwrap();
+			assert!((x - 1.).abs() < 1e-6 && (y - 2.).abs() < 1e-6 && (z - 3.).abs() < 1e-6);
+			assert!(
+				(roll - 0.1).abs() < 1e-6 && (pitch - 0.2).abs() < 1e-6 && (yaw - 0.3).abs() < 1e-6
+			);
+
+			let isometry: Isometry3<Float> = transform.into();
+			assert_close(isometry.into(), transform);
+		}
+
+		#[test]
+		fn compose() {
+			let first = Transform::new((1., 0., 0.), (0., 0., FRAC_PI_2));
+			let second = Transform::new((0., 2., 0.), (FRAC_PI_4, 0., 0.));
+
+			assert_close(
+				first.compose(&second),
+				Transform::from_isometry(&(first.to_isometry() * second.to_isometry())),
+			);
+			assert_eq!(first * second, first.compose(&second));
+			assert_close(
+				first * second,
+				Transform::new((-1., 0., 0.), (FRAC_PI_4, 0., FRAC_PI_2)),
+			);
+
+			// Composing is associative.
+			let third = Transform::new((0.5, -0.5, 3.), (0.3, -0.2, 0.1));
+			assert_close((first * second) * third, first * (second * third));
+
+			// Fields which are `None` in both stay `None`.
+			let rotation =
+				Transform::new_rotation(0., 0., 0.5) * Transform::new_rotation(0., 0., 0.25);
+			assert_eq!(rotation.translation, None);
+			assert_close(rotation, Transform::new_rotation(0., 0., 0.75));
+			assert_eq!(
+				Transform::default() * Transform::default(),
+				Transform::default()
+			);
+		}
+
+		#[test]
+		fn inverse() {
+			let transform = Transform::new((1., 2., 3.), (0.4, -0.5, 0.6));
+
+			assert_close(transform * transform.inverse(), Transform::default());
+			assert_close(transform.inverse() * transform, Transform::default());
+			assert_close(transform.inverse().inverse(), transform);
+
+			assert_eq!(Transform::default().inverse(), Transform::default());
+			assert_eq!(
+				Transform::new_rotation(0., 0., FRAC_PI_2)
+					.inverse()
+					.translation,
+				None
+			);
+		}
+
+		#[test]
+		fn apply() {
+			let transform = Transform::new((1., 2., 3.), (0., 0., FRAC_PI_2));
+
+			let (x, y, z) = transform.transform_point((1., 0., 0.));
+			assert!((x - 1.).abs() < 1e-6 && (y - 3.).abs() < 1e-6 && (z - 3.).abs() < 1e-6);
+
+			let (x, y, z) = transform.transform_vector((1., 0., 0.));
+			assert!(x.abs() < 1e-6 && (y - 1.).abs() < 1e-6 && z.abs() < 1e-6);
+
+			let (x, y, z) = transform
+				.inverse()
+				.transform_point(transform.transform_point((0.3, -0.7, 1.1)));
+			assert!((x - 0.3).abs() < 1e-6 && (y + 0.7).abs() < 1e-6 && (z - 1.1).abs() < 1e-6);
+		}
+
+		#[test]
+		fn quaternion() {
+			let quaternion = UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3);
+			let transform: Transform = quaternion.into();
+			assert_eq!(transform.translation, None);
+			assert!(transform.to_quaternion().angle_to(&quaternion) < 1e-6);
+
+			let transform =
+				Transform::new_quaternion(quaternion.w, quaternion.i, quaternion.j, quaternion.k);
+			assert!(UnitQuaternion::from(transform).angle_to(&quaternion) < 1e-6);
+
+			// Not normalized
+			assert_close(
+				Transform::new_quaternion(2., 0., 0., 2.),
+				Transform::new_rotation(0., 0., FRAC_PI_2),
+			);
+		}
+
+		#[test]
+		fn axis_angle() {
+			assert_close(
+				Transform::new_axis_angle((0., 0., 3.), FRAC_PI_4),
+				Transform::new_rotation(0., 0., FRAC_PI_4),
+			);
+			assert_close(
+				Transform::new_axis_angle((-1., 0., 0.), FRAC_PI_2),
+				Transform::new_rotation(-FRAC_PI_2, 0., 0.),
+			);
+			assert_close(
+				Transform::new_axis_angle((0., 0., 0.), FRAC_PI_2),
+				Transform::default(),
+			);
+		}
+
+		#[test]
+		fn matrix() {
+			let transform = Transform::new((1., 2., 3.), (0.4, -0.5, 0.6));
+			let matrix: Matrix4<Float> = transform.into();
+
+			assert_eq!(
+				matrix.row(3).into_owned(),
+				nalgebra::RowVector4::new(0., 0., 0., 1.)
+			);
+			assert_eq!(matrix.column(3).xyz(), nalgebra::vector![1., 2., 3.]);
+			assert_close(matrix.into(), transform);
+
+			// Scaling gets removed.
+			let mut scaled = matrix;
+			scaled.fixed_view_mut::<3, 3>(0, 0).scale_mut(2.);
+			assert_close(Transform::from_matrix(&scaled), transform);
+		}
+
+		#[test]
+		fn look_at() {
+			let transform = Transform::look_at((1., 1., 0.), (1., 3., 0.), (0., 0., 1.)).unwrap();
+			assert_close(transform, Transform::new((1., 1., 0.), (0., 0., FRAC_PI_2)));
+
+			let transform = Transform::look_at((0., 0., 2.), (1., 0., 1.), (0., 0., 1.)).unwrap();
+			let (x, y, z) = transform.transform_vector((1., 0., 0.));
+			assert!(
+				(x - Float::sqrt(0.5)).abs() < 1e-6
+					&& y.abs() < 1e-6
+					&& (z + Float::sqrt(0.5)).abs() < 1e-6
+			);
+			let (_, _, z) = transform.transform_vector((0., 0., 1.));
+			assert!(z > 0.);
+
+			assert_eq!(
+				Transform::look_at((1., 2., 3.), (1., 2., 3.), (0., 0., 1.)),
+				None
+			);
+			assert_eq!(
+				Transform::look_at((0., 0., 0.), (0., 0., -1.), (0., 0., 1.)),
+				None
+			);
+		}
+	}
 }
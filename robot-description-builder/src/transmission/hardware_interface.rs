@@ -1,4 +1,9 @@
 #[cfg(feature = "urdf")]
+use crate::from_rdf::{
+	from_urdf::{FromURDF, FromURDFContext, FromURDFError},
+	xml_element::XMLElement,
+};
+#[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::{ToURDF, URDFTarget};
 #[cfg(feature = "xml")]
 use quick_xml::events::BytesText;
@@ -98,3 +103,38 @@ impl ToURDF for TransmissionHardwareInterface {
 		Ok(())
 	}
 }
+
+#[cfg(feature = "urdf")]
+/// Reads a `<hardwareInterface>` element, with or without the `hardware_interface/` prefix.
+impl FromURDF for TransmissionHardwareInterface {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let content = element.text.trim();
+
+		match content
+			.strip_prefix("hardware_interface/")
+			.unwrap_or(content)
+		{
+			"JointCommandInterface" => Ok(Self::JointCommandInterface),
+			"EffortJointInterface" => Ok(Self::EffortJointInterface),
+			"VelocityJointInterface" => Ok(Self::VelocityJointInterface),
+			"PositionJointInterface" => Ok(Self::PositionJointInterface),
+			"JointStateInterface" => Ok(Self::JointStateInterface),
+			"ActuatorStateInterface" => Ok(Self::ActuatorStateInterface),
+			"EffortActuatorInterface" => Ok(Self::EffortActuatorInterface),
+			"VelocityActuatorInterface" => Ok(Self::VelocityActuatorInterface),
+			"PositionActuatorInterface" => Ok(Self::PositionActuatorInterface),
+			"PosVelJointInterface" => Ok(Self::PosVelJointInterface),
+			"PosVelAccJointInterface" => Ok(Self::PosVelAccJointInterface),
+			"ForceTorqueSensorInterface" => Ok(Self::ForceTorqueSensorInterface),
+			"IMUSensorInterface" => Ok(Self::IMUSensorInterface),
+			_ => Err(FromURDFError::InvalidContent {
+				element: element.name.clone(),
+				content: element.text.clone(),
+				line: element.line,
+			}),
+		}
+	}
+}
@@ -19,6 +19,11 @@ use crate::{cluster_objects::kinematic_data_tree::KinematicDataTree, identifiers
 
 use itertools::Itertools;
 
+#[cfg(feature = "urdf")]
+use crate::from_rdf::{
+	from_urdf::{required_child, required_name, FromURDF, FromURDFContext, FromURDFError},
+	xml_element::XMLElement,
+};
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 #[cfg(feature = "xml")]
@@ -302,6 +307,43 @@ impl ToURDF for Transmission {
 	}
 }
 
+#[cfg(feature = "urdf")]
+/// Reads a `<transmission>` element, which requires at least one `<joint>` and one `<actuator>`.
+impl FromURDF for TransmissionBuilder<WithJoints, WithActuator> {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let name = required_name(element)?;
+		let transmission_type =
+			TransmissionType::from_urdf(required_child(element, "type")?, urdf_context)?;
+
+		let joints = element
+			.children_named("joint")
+			.map(|joint| TransmissionJointBuilder::from_urdf(joint, urdf_context))
+			.collect::<Result<Vec<_>, _>>()?;
+		let actuators = element
+			.children_named("actuator")
+			.map(|actuator| TransmissionActuator::from_urdf(actuator, urdf_context))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		// `required_child` gives the correct error for the missing elements.
+		if joints.is_empty() {
+			required_child(element, "joint")?;
+		}
+		if actuators.is_empty() {
+			required_child(element, "actuator")?;
+		}
+
+		Ok(Self {
+			name,
+			transmission_type,
+			joints: WithJoints(joints),
+			actuators: WithActuator(actuators),
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{
@@ -1,6 +1,11 @@
-use crate::identifiers::GroupID;
+#[cfg(feature = "urdf")]
+use crate::from_rdf::{
+	from_urdf::{content_float, required_name, FromURDF, FromURDFContext, FromURDFError},
+	xml_element::XMLElement,
+};
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
+use crate::{identifiers::GroupID, Float};
 #[cfg(feature = "xml")]
 use quick_xml::{
 	events::{attributes::Attribute, BytesText},
@@ -11,7 +16,7 @@ use quick_xml::{
 pub struct TransmissionActuator {
 	name: String,
 	/// Specifies a mechanical reduction at the joint/actuator transmission. This tag may not be needed for all transmissions.
-	mechanical_reduction: Option<f32>,
+	mechanical_reduction: Option<Float>,
 }
 
 impl TransmissionActuator {
@@ -22,7 +27,7 @@ impl TransmissionActuator {
 		}
 	}
 
-	pub fn new_with_reduction(name: impl Into<String>, mechanical_reduction: f32) -> Self {
+	pub fn new_with_reduction(name: impl Into<String>, mechanical_reduction: Float) -> Self {
 		Self {
 			name: name.into(),
 			mechanical_reduction: Some(mechanical_reduction),
@@ -33,18 +38,18 @@ impl TransmissionActuator {
 		&self.name
 	}
 
-	pub fn mechanically_reduced(mut self, mechanical_reduction: f32) -> Self {
+	pub fn mechanically_reduced(mut self, mechanical_reduction: Float) -> Self {
 		self.mechanical_reduction = Some(mechanical_reduction);
 		self
 	}
 
-	pub fn mechanical_reduction(&self) -> Option<&f32> {
+	pub fn mechanical_reduction(&self) -> Option<&Float> {
 		self.mechanical_reduction.as_ref()
 	}
 
 	/// TODO: Maybe remove because of immutability
 	#[deprecated]
-	pub fn set_mechanical_reduction(&mut self, mechanical_reduction: f32) {
+	pub fn set_mechanical_reduction(&mut self, mechanical_reduction: Float) {
 		self.mechanical_reduction = Some(mechanical_reduction);
 	}
 }
@@ -74,3 +79,19 @@ impl ToURDF for TransmissionActuator {
 		Ok(())
 	}
 }
+
+#[cfg(feature = "urdf")]
+impl FromURDF for TransmissionActuator {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		Ok(Self {
+			name: required_name(element)?,
+			mechanical_reduction: element
+				.child("mechanicalReduction")
+				.map(content_float)
+				.transpose()?,
+		})
+	}
+}
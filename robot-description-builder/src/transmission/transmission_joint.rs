@@ -7,6 +7,11 @@ use crate::{
 	utils::{ArcRW, WeakLock},
 };
 
+#[cfg(feature = "urdf")]
+use crate::from_rdf::{
+	from_urdf::{required_name, FromURDF, FromURDFContext, FromURDFError},
+	xml_element::XMLElement,
+};
 #[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 #[cfg(feature = "xml")]
@@ -181,3 +186,31 @@ impl Clone for TransmissionJoint {
 		}
 	}
 }
+
+#[cfg(feature = "urdf")]
+impl FromURDF for TransmissionJointBuilder {
+	fn from_urdf(
+		element: &XMLElement,
+		urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let hardware_interfaces = element
+			.children_named("hardwareInterface")
+			.map(|hardware_interface| {
+				TransmissionHardwareInterface::from_urdf(hardware_interface, urdf_context)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		if hardware_interfaces.is_empty() {
+			return Err(FromURDFError::MissingElement {
+				element: element.name.clone(),
+				child: String::from("hardwareInterface"),
+				line: element.line,
+			});
+		}
+
+		Ok(Self {
+			joint_name: required_name(element)?,
+			hardware_interfaces,
+		})
+	}
+}
@@ -1,4 +1,9 @@
 #[cfg(feature = "urdf")]
+use crate::from_rdf::{
+	from_urdf::{FromURDF, FromURDFContext, FromURDFError},
+	xml_element::XMLElement,
+};
+#[cfg(feature = "urdf")]
 use crate::to_rdf::to_urdf::ToURDF;
 #[cfg(feature = "xml")]
 use quick_xml::events::BytesText;
@@ -55,3 +60,28 @@ impl ToURDF for TransmissionType {
 		Ok(())
 	}
 }
+
+#[cfg(feature = "urdf")]
+/// Reads the `<type>` element of a `<transmission>`.
+impl FromURDF for TransmissionType {
+	fn from_urdf(
+		element: &XMLElement,
+		_urdf_context: &mut FromURDFContext,
+	) -> Result<Self, FromURDFError> {
+		let content = element.text.trim();
+
+		match content
+			.strip_prefix("transmission_interface/")
+			.unwrap_or(content)
+		{
+			"SimpleTransmission" => Ok(Self::SimpleTransmission),
+			"DifferentialTransmission" => Ok(Self::DifferentialTransmission),
+			"FourBarLinkageTransmission" => Ok(Self::FourBarLinkageTransmission),
+			_ => Err(FromURDFError::InvalidContent {
+				element: element.name.clone(),
+				content: element.text.clone(),
+				line: element.line,
+			}),
+		}
+	}
+}
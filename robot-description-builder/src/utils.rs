@@ -1,6 +1,12 @@
 //! A module containing utilities for simplifing the use of `Arc<RwLock<T>>`.
 use std::sync::{Arc, PoisonError, RwLockReadGuard, RwLockWriteGuard, Weak};
 
+/// A shared and lockable reference, which is used for all elements of a kinematic tree.
+///
+/// A lock is only poisoned when a thread panicked while holding it, which leaves the kinematic tree in an unknown state.
+/// Therefore operations which only read the tree, like the kinematics, the validation and the exports,
+/// unwrap the read guards of the locks instead of reporting the poisoning as an error of the operation.
+/// Operations which modify the tree report the poisoning with [`ArcRW::mread`] and [`ArcRW::mwrite`].
 pub(crate) type ArcLock<T> = std::sync::Arc<std::sync::RwLock<T>>;
 pub(crate) type WeakLock<T> = std::sync::Weak<std::sync::RwLock<T>>;
 
@@ -0,0 +1,756 @@
+//! Validation of a [`Robot`], which finds the problems that would otherwise only surface in downstream tools.
+//!
+//! [`Robot::validate`] walks the kinematic tree and reports every problem as a [`Diagnostic`],
+//! with its [`Severity`] and the path of the element it was found in.
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use thiserror::Error;
+
+use crate::{
+	cluster_objects::KinematicInterface,
+	joint::{Joint, JointType},
+	link::InertialError,
+	link_data::{geometry::GeometryShapeContainer, LinkParent},
+	material::{data::MaterialData, Material},
+	utils::ArcLock,
+	Float, Robot,
+};
+
+/// The tolerance on the length of an axis, before it is reported as [`DiagnosticKind::UnnormalizedAxis`].
+const AXIS_LENGTH_TOLERANCE: Float = 1e-4;
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Severity {
+	/// The description is usable, but it probably does not behave as intended.
+	Warning,
+	/// The description is invalid and is rejected or misinterpreted by most tools.
+	Error,
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Severity::Warning => write!(f, "warning"),
+			Severity::Error => write!(f, "error"),
+		}
+	}
+}
+
+/// The kind of problem reported by a [`Diagnostic`].
+#[derive(Debug, PartialEq, Clone, Error)]
+pub enum DiagnosticKind {
+	/// A [`Revolute`](JointType::Revolute) or [`Prismatic`](JointType::Prismatic) `Joint` has no `LimitData`, which is required in URDF.
+	#[error("The {0:?} joint has no limit")]
+	MissingLimit(JointType),
+	/// The lower limit of a `Joint` is larger than its upper limit.
+	#[error("The lower limit {lower} is larger than the upper limit {upper}")]
+	InvertedLimit { lower: Float, upper: Float },
+	/// The soft limit of the `SafetyControllerData` of a `Joint` is outside of the limits of its `LimitData`.
+	#[error("The soft limit {soft_limit} is outside of the joint limits")]
+	SafetyLimitOutsideLimit { soft_limit: Float },
+	/// The axis of a `Joint` has a length of zero.
+	#[error("The axis has a length of zero")]
+	ZeroAxis,
+	/// The axis of a `Joint` is not normalized.
+	///
+	/// Most tools normalize the axis, but the values in the description are misleading.
+	#[error("The axis is not normalized, its length is {length}")]
+	UnnormalizedAxis { length: Float },
+	/// The mimic of a `Joint` is a part of a cycle of mimicking `Joint`s.
+	#[error("The joint mimics itself through a cycle of mimics")]
+	MimicCycle,
+	/// A `Joint` mimics a `Joint`, which is not a part of the same tree.
+	#[error("The mimicked joint is not a part of this tree")]
+	MimicOutsideTree,
+	/// A `Link` has no `Inertial` in a tree, in which other `Link`s do have an `Inertial`.
+	#[error("The link has no inertial, while other links do")]
+	MissingInertial,
+	/// The mass of the `Inertial` of a `Link` is zero.
+	#[error("The mass of the inertial is zero")]
+	ZeroMass,
+	/// The `Inertial` of a `Link` is physically invalid, see [`Inertial::validate`](crate::link_data::Inertial::validate).
+	#[error("The inertial is invalid: {0}")]
+	InvalidInertial(#[from] InertialError),
+	/// A component of a [`MaterialData::Color`] is outside of the range `0..=1`.
+	#[error("The color {0:?} has a component outside of the range 0..=1")]
+	ColorOutOfRange((Float, Float, Float, Float)),
+	/// The path of a mesh is empty.
+	#[error("The mesh has an empty path")]
+	EmptyMeshPath,
+}
+
+impl DiagnosticKind {
+	/// Gets the [`Severity`] of this kind of problem.
+	pub fn severity(&self) -> Severity {
+		match self {
+			DiagnosticKind::MissingLimit(_)
+			| DiagnosticKind::InvertedLimit { .. }
+			| DiagnosticKind::ZeroAxis
+			| DiagnosticKind::MimicCycle
+			| DiagnosticKind::MimicOutsideTree
+			| DiagnosticKind::InvalidInertial(_)
+			| DiagnosticKind::EmptyMeshPath => Severity::Error,
+			DiagnosticKind::SafetyLimitOutsideLimit { .. }
+			| DiagnosticKind::UnnormalizedAxis { .. }
+			| DiagnosticKind::MissingInertial
+			| DiagnosticKind::ZeroMass
+			| DiagnosticKind::ColorOutOfRange(_) => Severity::Warning,
+		}
+	}
+}
+
+/// A problem found by [`Robot::validate`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+	/// The path of the element, in which the problem was found.
+	///
+	/// The path starts at the `Link`, `Joint` or named `Material` and follows the URDF element names,
+	/// with the name or the index of the element in brackets. For example: `joint[shoulder]/limit` or `link[arm]/visual[0]/geometry/mesh`.
+	pub path: String,
+	/// The kind of problem.
+	pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+	fn new(path: impl Into<String>, kind: DiagnosticKind) -> Self {
+		Self {
+			path: path.into(),
+			kind,
+		}
+	}
+
+	/// Gets the [`Severity`] of the problem.
+	pub fn severity(&self) -> Severity {
+		self.kind.severity()
+	}
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}: {}", self.severity(), self.path, self.kind)
+	}
+}
+
+impl Robot {
+	/// Validates the `Robot` and returns the problems which were found.
+	///
+	/// The following problems are reported:
+	/// - `Joint`s:
+	///   - [`Revolute`](JointType::Revolute) and [`Prismatic`](JointType::Prismatic) `Joint`s without `LimitData`.
+	///   - A lower limit which is larger than the upper limit.
+	///   - Soft limits of the `SafetyControllerData`, which are outside of the limits.
+	///   - Axes with a length of zero or which are not normalized.
+	///   - Mimics, which form a cycle or which refer to a `Joint` outside of this tree.
+	/// - `Link`s:
+	///   - `Link`s without an `Inertial` in a dynamic tree, in which some `Link`s have an `Inertial`.
+	///     The root `Link` and `Link`s attached with a [`Fixed`](JointType::Fixed) `Joint` are not reported, since they are not simulated separately.
+	///   - `Inertial`s with a mass of zero or which are invalid according to [`Inertial::validate`](crate::link_data::Inertial::validate).
+	///   - Meshes with an empty path.
+	/// - `Material`s with a [`Color`](MaterialData::Color), which has components outside of the range `0..=1`.
+	///
+	/// The diagnostics are ordered by the `Joint`s, `Link`s and `Material`s, which are each ordered by name.
+	///
+	/// # Example
+	/// ```
+	/// # use robot_description_builder::{
+	/// #     prelude::*,
+	/// #     validate::Severity,
+	/// #     JointBuilder, JointType, Link,
+	/// # };
+	/// let robot = Link::builder("base").build_tree().to_robot("robot");
+	/// assert!(robot.validate().is_empty());
+	///
+	/// let mut shoulder = JointBuilder::new("shoulder", JointType::Revolute);
+	/// shoulder.with_axis((0., 0., 2.));
+	/// robot
+	///     .get_root_link()
+	///     .write()
+	///     .unwrap()
+	///     .try_attach_child(shoulder, Link::builder("arm"))
+	///     .unwrap();
+	///
+	/// let diagnostics = robot.validate();
+	/// assert_eq!(diagnostics.len(), 2);
+	/// assert_eq!(diagnostics[0].path, "joint[shoulder]/limit");
+	/// assert_eq!(diagnostics[0].severity(), Severity::Error);
+	/// assert_eq!(
+	///     diagnostics[1].to_string(),
+	///     "warning: joint[shoulder]/axis: The axis is not normalized, its length is 2"
+	/// );
+	/// ```
+	pub fn validate(&self) -> Vec<Diagnostic> {
+		let mut diagnostics = Vec::new();
+
+		let joints: BTreeMap<String, ArcLock<Joint>> = {
+			let joints = self.get_joints();
+			let joints = joints.read().unwrap();
+			joints
+				.iter()
+				.filter_map(|(name, joint)| Some((name.clone(), joint.upgrade()?)))
+				.collect()
+		};
+		let mimics = mimicked_joints(&joints);
+
+		for (name, joint) in joints.iter() {
+			let joint = joint.read().unwrap();
+			validate_joint(&joint, &mut diagnostics);
+
+			if let Some(mimicked_joint) = mimics.get(name) {
+				match mimicked_joint {
+					None => diagnostics.push(Diagnostic::new(
+						format!("joint[{name}]/mimic"),
+						DiagnosticKind::MimicOutsideTree,
+					)),
+					Some(_) if is_mimic_cycle(name, &mimics) => diagnostics.push(Diagnostic::new(
+						format!("joint[{name}]/mimic"),
+						DiagnosticKind::MimicCycle,
+					)),
+					Some(_) => (),
+				}
+			}
+		}
+
+		let links = {
+			let links = self.get_links();
+			let links = links.read().unwrap();
+			links
+				.iter()
+				.filter_map(|(name, link)| Some((name.clone(), link.upgrade()?)))
+				.collect::<BTreeMap<_, _>>()
+		};
+		let is_dynamic = links
+			.values()
+			.any(|link| link.read().unwrap().inertial().is_some());
+
+		for (name, link) in links.iter() {
+			let link = link.read().unwrap();
+
+			match link.inertial() {
+				Some(inertial) => match inertial.validate() {
+					Err(error) => diagnostics.push(Diagnostic::new(
+						format!("link[{name}]/inertial"),
+						error.into(),
+					)),
+					Ok(()) if inertial.mass == 0. => diagnostics.push(Diagnostic::new(
+						format!("link[{name}]/inertial/mass"),
+						DiagnosticKind::ZeroMass,
+					)),
+					Ok(()) => (),
+				},
+				None if is_dynamic && is_simulated_separately(link.parent()) => diagnostics.push(
+					Diagnostic::new(format!("link[{name}]"), DiagnosticKind::MissingInertial),
+				),
+				None => (),
+			}
+
+			for (index, visual) in link.visuals().iter().enumerate() {
+				if is_empty_mesh(&visual.geometry().shape_container()) {
+					diagnostics.push(Diagnostic::new(
+						format!("link[{name}]/visual[{index}]/geometry/mesh"),
+						DiagnosticKind::EmptyMeshPath,
+					));
+				}
+
+				// Named `Material`s are validated once, from the material index.
+				if let Some(material) = visual
+					.material()
+					.filter(|material| material.name().is_none())
+				{
+					validate_material(
+						material,
+						format!("link[{name}]/visual[{index}]/material"),
+						&mut diagnostics,
+					);
+				}
+			}
+
+			for (index, collider) in link.colliders().iter().enumerate() {
+				if is_empty_mesh(&collider.geometry().shape_container()) {
+					diagnostics.push(Diagnostic::new(
+						format!("link[{name}]/collision[{index}]/geometry/mesh"),
+						DiagnosticKind::EmptyMeshPath,
+					));
+				}
+			}
+		}
+
+		let material_names = {
+			let materials = self.get_materials();
+			let materials = materials.read().unwrap();
+			let mut names: Vec<_> = materials.keys().cloned().collect();
+			names.sort();
+			names
+		};
+		for name in material_names {
+			if let Some(material) = self.get_material(&name) {
+				validate_material(&material, format!("material[{name}]"), &mut diagnostics);
+			}
+		}
+
+		diagnostics
+	}
+}
+
+/// Validates the limits and the axis of the `joint`.
+fn validate_joint(joint: &Joint, diagnostics: &mut Vec<Diagnostic>) {
+	let name = joint.name();
+
+	match (joint.joint_type(), joint.limit()) {
+		(JointType::Revolute | JointType::Prismatic, None) => diagnostics.push(Diagnostic::new(
+			format!("joint[{name}]/limit"),
+			DiagnosticKind::MissingLimit(joint.joint_type()),
+		)),
+		(_, Some(limit)) => {
+			if let (Some(lower), Some(upper)) = (limit.lower, limit.upper) {
+				if lower > upper {
+					diagnostics.push(Diagnostic::new(
+						format!("joint[{name}]/limit"),
+						DiagnosticKind::InvertedLimit { lower, upper },
+					));
+				}
+			}
+
+			if let Some(safety_controller) = joint.safety_controller() {
+				let is_outside = |soft_limit: Float| {
+					limit.lower.is_some_and(|lower| soft_limit < lower)
+						|| limit.upper.is_some_and(|upper| soft_limit > upper)
+				};
+
+				safety_controller
+					.soft_lower_limit
+					.into_iter()
+					.chain(safety_controller.soft_upper_limit)
+					.filter(|soft_limit| is_outside(*soft_limit))
+					.for_each(|soft_limit| {
+						diagnostics.push(Diagnostic::new(
+							format!("joint[{name}]/safety_controller"),
+							DiagnosticKind::SafetyLimitOutsideLimit { soft_limit },
+						))
+					});
+			}
+		}
+		(_, None) => (),
+	}
+
+	let has_axis = matches!(
+		joint.joint_type(),
+		JointType::Revolute | JointType::Continuous | JointType::Prismatic | JointType::Planar
+	);
+	if let Some((x, y, z)) = joint.axis().filter(|_| has_axis) {
+		let length = (x * x + y * y + z * z).sqrt();
+		if length == 0. {
+			diagnostics.push(Diagnostic::new(
+				format!("joint[{name}]/axis"),
+				DiagnosticKind::ZeroAxis,
+			));
+		} else if (length - 1.).abs() > AXIS_LENGTH_TOLERANCE {
+			diagnostics.push(Diagnostic::new(
+				format!("joint[{name}]/axis"),
+				DiagnosticKind::UnnormalizedAxis { length },
+			));
+		}
+	}
+}
+
+/// Gets the name of the mimicked `Joint` of each mimicking `Joint` in the `joints`.
+///
+/// The name is `None` if the mimicked `Joint` is not a part of the tree.
+fn mimicked_joints(joints: &BTreeMap<String, ArcLock<Joint>>) -> BTreeMap<String, Option<String>> {
+	joints
+		.iter()
+		.filter_map(|(name, joint)| {
+			// The lock is released before the mimicked `Joint` is read, since a `Joint` could mimic itself.
+			let mimicked_joint = joint.read().unwrap().mimic()?.joint.upgrade();
+
+			let mimicked_name = mimicked_joint.and_then(|mimicked_joint| {
+				let mimicked_name = mimicked_joint.read().unwrap().name().clone();
+				// The mimicked `Joint` has to be the `Joint` which is registered under its name in this tree.
+				joints
+					.get(&mimicked_name)
+					.filter(|registered_joint| Arc::ptr_eq(registered_joint, &mimicked_joint))
+					.map(|_| mimicked_name)
+			});
+
+			Some((name.clone(), mimicked_name))
+		})
+		.collect()
+}
+
+/// Checks if following the mimics from the `Joint` named `name` leads back to the same `Joint`.
+fn is_mimic_cycle(name: &String, mimics: &BTreeMap<String, Option<String>>) -> bool {
+	let mut current = name;
+	// A cycle can not be longer than the number of mimicking `Joint`s.
+	for _ in 0..mimics.len() {
+		match mimics.get(current) {
+			Some(Some(mimicked_name)) if mimicked_name == name => return true,
+			Some(Some(mimicked_name)) => current = mimicked_name,
+			_ => return false,
+		}
+	}
+	false
+}
+
+/// Checks if a `Link` with the specified `parent` is simulated as a separate body.
+///
+/// The root `Link` is the fixed base and `Link`s attached with a [`Fixed`](JointType::Fixed) `Joint` are merged into their parent.
+pub(crate) fn is_simulated_separately(parent: &LinkParent) -> bool {
+	match parent {
+		LinkParent::Joint(joint) => joint
+			.upgrade()
+			.is_some_and(|joint| joint.read().unwrap().joint_type() != JointType::Fixed),
+		LinkParent::KinematicTree(_) => false,
+	}
+}
+
+/// Checks if the `shape` is a mesh with an empty path.
+fn is_empty_mesh(shape: &GeometryShapeContainer) -> bool {
+	matches!(shape, GeometryShapeContainer::Mesh(mesh) if mesh.path.trim().is_empty())
+}
+
+/// Validates the color of the `material`, found at `path`.
+fn validate_material(material: &Material, path: String, diagnostics: &mut Vec<Diagnostic>) {
+	if let Ok(MaterialData::Color(red, green, blue, alpha)) =
+		MaterialData::try_from(material.material_data())
+	{
+		if ![red, green, blue, alpha]
+			.iter()
+			.all(|component| (0. ..=1.).contains(component))
+		{
+			diagnostics.push(Diagnostic::new(
+				path + "/color",
+				DiagnosticKind::ColorOutOfRange((red, green, blue, alpha)),
+			));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Diagnostic, DiagnosticKind, Severity};
+	use crate::{
+		errors::InertialError,
+		joint_data::{LimitData, MimicBuilderData, SafetyControllerData},
+		link_data::{
+			geometry::{BoxGeometry, MeshGeometry},
+			Collision, Inertial, Visual,
+		},
+		material::MaterialDescriptor,
+		prelude::*,
+		Float, JointBuilder, JointType, Link, Robot,
+	};
+	use test_log::test;
+
+	fn weight(mass: Float) -> Inertial {
+		Inertial {
+			mass,
+			ixx: 0.1,
+			iyy: 0.1,
+			izz: 0.1,
+			..Default::default()
+		}
+	}
+
+	fn joint(name: &str, joint_type: JointType, limit: Option<(Float, Float)>) -> JointBuilder {
+		let mut joint = JointBuilder::new(name, joint_type);
+		*joint.limit_mut() = limit.map(|(lower, upper)| LimitData {
+			lower: Some(lower),
+			upper: Some(upper),
+			effort: 10.,
+			velocity: 1.,
+		});
+		joint
+	}
+
+	/// Attaches a chain of `Link`s to the `Link` named `parent`.
+	fn attach(
+		robot: &Robot,
+		parent: &str,
+		joint: JointBuilder,
+		link: crate::linkbuilding::LinkBuilder,
+	) {
+		robot
+			.get_link(parent)
+			.unwrap()
+			.write()
+			.unwrap()
+			.try_attach_child(joint, link)
+			.unwrap();
+	}
+
+	#[test]
+	fn valid() {
+		let robot = Link::builder("base")
+			.add_visual(
+				Visual::builder(BoxGeometry::new(1., 1., 1.))
+					.materialized(MaterialDescriptor::new_color(1., 0., 0., 1.).named("red")),
+			)
+			.build_tree()
+			.to_robot("robot");
+
+		let mut shoulder = joint("shoulder", JointType::Revolute, Some((-1., 1.)));
+		shoulder.with_axis((0., 0., 1.));
+		attach(
+			&robot,
+			"base",
+			shoulder,
+			Link::builder("arm").add_intertial(weight(1.)),
+		);
+		// A sensor frame does not need an `Inertial`.
+		attach(
+			&robot,
+			"arm",
+			JointBuilder::new("sensor_joint", JointType::Fixed),
+			Link::builder("sensor"),
+		);
+
+		assert_eq!(robot.validate(), vec![]);
+	}
+
+	#[test]
+	fn joints() {
+		let robot = Link::builder("base")
+			.add_intertial(weight(1.))
+			.build_tree()
+			.to_robot("robot");
+
+		let mut shoulder = joint("shoulder", JointType::Revolute, Some((-1., 1.)));
+		shoulder.with_axis((0., 0., 1.));
+		*shoulder.safety_controller_mut() = Some(SafetyControllerData {
+			soft_lower_limit: Some(-1.2),
+			soft_upper_limit: Some(0.9),
+			k_position: Some(10.),
+			k_velocity: 10.,
+		});
+		attach(
+			&robot,
+			"base",
+			shoulder,
+			Link::builder("arm").add_intertial(weight(1.)),
+		);
+
+		let mut elbow = joint("elbow", JointType::Prismatic, None);
+		elbow.with_axis((0., 0., 0.));
+		attach(
+			&robot,
+			"arm",
+			elbow,
+			Link::builder("forearm").add_intertial(weight(1.)),
+		);
+
+		let mut wrist = joint("wrist", JointType::Continuous, Some((1., -1.)));
+		wrist.with_axis((0., 0.6, 0.6));
+		attach(
+			&robot,
+			"forearm",
+			wrist,
+			Link::builder("hand").add_intertial(weight(1.)),
+		);
+
+		let diagnostics = robot.validate();
+		assert_eq!(
+			diagnostics,
+			vec![
+				Diagnostic::new(
+					"joint[elbow]/limit",
+					DiagnosticKind::MissingLimit(JointType::Prismatic)
+				),
+				Diagnostic::new("joint[elbow]/axis", DiagnosticKind::ZeroAxis),
+				Diagnostic::new(
+					"joint[shoulder]/safety_controller",
+					DiagnosticKind::SafetyLimitOutsideLimit { soft_limit: -1.2 }
+				),
+				Diagnostic::new(
+					"joint[wrist]/limit",
+					DiagnosticKind::InvertedLimit {
+						lower: 1.,
+						upper: -1.
+					}
+				),
+				Diagnostic::new(
+					"joint[wrist]/axis",
+					DiagnosticKind::UnnormalizedAxis {
+						length: (0.72 as Float).sqrt()
+					}
+				),
+			]
+		);
+		assert_eq!(
+			diagnostics
+				.iter()
+				.map(Diagnostic::severity)
+				.collect::<Vec<_>>(),
+			vec![
+				Severity::Error,
+				Severity::Error,
+				Severity::Warning,
+				Severity::Error,
+				Severity::Warning
+			]
+		);
+	}
+
+	#[test]
+	fn links() {
+		let robot = Link::builder("base")
+			.add_visual(
+				Visual::builder(BoxGeometry::new(1., 1., 1.))
+					.materialized(MaterialDescriptor::new_color(1., 0., 0., 2.).named("red")),
+			)
+			.build_tree()
+			.to_robot("robot");
+
+		attach(
+			&robot,
+			"base",
+			joint("shoulder", JointType::Continuous, None),
+			Link::builder("arm")
+				.add_intertial(weight(1.))
+				.add_visual(
+					Visual::builder(MeshGeometry::new("", (1., 1., 1.), None))
+						.materialized(MaterialDescriptor::new_color(-0.5, 0., 0., 1.)),
+				)
+				.add_collider(Collision::builder(MeshGeometry::new(
+					" ",
+					(1., 1., 1.),
+					None,
+				))),
+		);
+		attach(
+			&robot,
+			"arm",
+			joint("elbow", JointType::Continuous, None),
+			Link::builder("forearm"),
+		);
+		attach(
+			&robot,
+			"forearm",
+			joint("wrist", JointType::Continuous, None),
+			Link::builder("hand").add_intertial(Inertial::default()),
+		);
+		attach(
+			&robot,
+			"hand",
+			joint("tool_joint", JointType::Continuous, None),
+			Link::builder("tool").add_intertial(Inertial {
+				izz: 1.,
+				..weight(1.)
+			}),
+		);
+
+		assert_eq!(
+			robot.validate(),
+			vec![
+				Diagnostic::new(
+					"link[arm]/visual[0]/geometry/mesh",
+					DiagnosticKind::EmptyMeshPath
+				),
+				Diagnostic::new(
+					"link[arm]/visual[0]/material/color",
+					DiagnosticKind::ColorOutOfRange((-0.5, 0., 0., 1.))
+				),
+				Diagnostic::new(
+					"link[arm]/collision[0]/geometry/mesh",
+					DiagnosticKind::EmptyMeshPath
+				),
+				Diagnostic::new("link[forearm]", DiagnosticKind::MissingInertial),
+				Diagnostic::new("link[hand]/inertial/mass", DiagnosticKind::ZeroMass),
+				Diagnostic::new(
+					"link[tool]/inertial",
+					DiagnosticKind::InvalidInertial(InertialError::TriangleInequality {
+						principal_moments: (0.1, 0.1, 1.)
+					})
+				),
+				Diagnostic::new(
+					"material[red]/color",
+					DiagnosticKind::ColorOutOfRange((1., 0., 0., 2.))
+				),
+			]
+		);
+	}
+
+	#[test]
+	fn mimic_cycle() {
+		let robot = Link::builder("base").build_tree().to_robot("robot");
+		attach(
+			&robot,
+			"base",
+			joint("shoulder", JointType::Continuous, None),
+			Link::builder("arm"),
+		);
+		let mut elbow = joint("elbow", JointType::Continuous, None);
+		*elbow.mimic_mut() = Some(MimicBuilderData {
+			joint_name: "shoulder".to_string(),
+			multiplier: None,
+			offset: None,
+		});
+		attach(&robot, "arm", elbow, Link::builder("forearm"));
+		let mut wrist = joint("wrist", JointType::Continuous, None);
+		*wrist.mimic_mut() = Some(MimicBuilderData {
+			joint_name: "elbow".to_string(),
+			multiplier: None,
+			offset: None,
+		});
+		attach(&robot, "forearm", wrist, Link::builder("hand"));
+		assert_eq!(robot.validate(), vec![]);
+
+		// Reattaching the shoulder, which mimics the elbow in its own chain, closes the cycle.
+		let mut shoulder = robot.yank_joint("shoulder").unwrap();
+		*shoulder.mimic_mut() = Some(MimicBuilderData {
+			joint_name: "elbow".to_string(),
+			multiplier: None,
+			offset: None,
+		});
+		robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.attach_joint_chain(shoulder)
+			.unwrap();
+
+		// The wrist follows the cycle, but it is not a part of it.
+		assert_eq!(
+			robot.validate(),
+			vec![
+				Diagnostic::new("joint[elbow]/mimic", DiagnosticKind::MimicCycle),
+				Diagnostic::new("joint[shoulder]/mimic", DiagnosticKind::MimicCycle),
+			]
+		);
+	}
+
+	#[test]
+	fn mimic_outside_tree() {
+		let robot = Link::builder("base").build_tree().to_robot("robot");
+		attach(
+			&robot,
+			"base",
+			joint("left", JointType::Continuous, None),
+			Link::builder("left_finger"),
+		);
+		let mut right = joint("right", JointType::Continuous, None);
+		*right.mimic_mut() = Some(MimicBuilderData {
+			joint_name: "left".to_string(),
+			multiplier: Some(-1.),
+			offset: None,
+		});
+		attach(&robot, "base", right, Link::builder("right_finger"));
+
+		// The left finger is moved to another robot.
+		let other_robot = Link::builder("other_base").build_tree().to_robot("other");
+		other_robot
+			.get_root_link()
+			.write()
+			.unwrap()
+			.attach_joint_chain(robot.yank_joint("left").unwrap())
+			.unwrap();
+
+		assert_eq!(
+			robot.validate(),
+			vec![Diagnostic::new(
+				"joint[right]/mimic",
+				DiagnosticKind::MimicOutsideTree
+			)]
+		);
+		assert_eq!(other_robot.validate(), vec![]);
+	}
+}